@@ -0,0 +1,109 @@
+use std::{
+    fs,
+    io::Write as _,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use error_stack::{Report, Result, ResultExt};
+use type_codegen::Generator;
+use type_system::schema::{DataType, EntityType, PropertyType};
+
+use crate::error::GraphError;
+
+#[derive(Debug, Parser)]
+#[clap(version, author, about, long_about = None)]
+pub struct CodegenArgs {
+    /// The ontology type schemas to generate code for.
+    ///
+    /// Directories are searched recursively for `.json` files.
+    #[clap(required = true)]
+    pub paths: Vec<PathBuf>,
+
+    /// The file to write the generated code to. If not specified, the code is written to stdout.
+    #[clap(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), GraphError> {
+    if path.is_dir() {
+        let mut entries = fs::read_dir(path)
+            .change_context(GraphError)
+            .attach_printable_lazy(|| path.display().to_string())?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<core::result::Result<Vec<_>, _>>()
+            .change_context(GraphError)?;
+        entries.sort();
+
+        for entry in entries {
+            if entry.is_dir()
+                || entry
+                    .extension()
+                    .is_some_and(|extension| extension == "json")
+            {
+                collect_files(&entry, files)?;
+            }
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    Ok(())
+}
+
+fn add_schema(generator: &mut Generator, path: &Path) -> Result<(), GraphError> {
+    let content = fs::read(path)
+        .change_context(GraphError)
+        .attach_printable_lazy(|| path.display().to_string())?;
+    let schema = serde_json::from_slice::<serde_json::Value>(&content)
+        .change_context(GraphError)
+        .attach_printable_lazy(|| path.display().to_string())?;
+
+    match schema.get("kind").and_then(serde_json::Value::as_str) {
+        Some("dataType") => generator.add_data_type(
+            serde_json::from_value::<DataType>(schema)
+                .change_context(GraphError)
+                .attach_printable_lazy(|| path.display().to_string())?,
+        ),
+        Some("propertyType") => generator.add_property_type(
+            serde_json::from_value::<PropertyType>(schema)
+                .change_context(GraphError)
+                .attach_printable_lazy(|| path.display().to_string())?,
+        ),
+        Some("entityType") => generator.add_entity_type(
+            serde_json::from_value::<EntityType>(schema)
+                .change_context(GraphError)
+                .attach_printable_lazy(|| path.display().to_string())?,
+        ),
+        _ => {
+            return Err(Report::new(GraphError)
+                .attach_printable("the schema is not an ontology type")
+                .attach_printable(path.display().to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn codegen(args: &CodegenArgs) -> Result<(), GraphError> {
+    let mut files = Vec::new();
+    for path in &args.paths {
+        collect_files(path, &mut files)?;
+    }
+
+    let mut generator = Generator::new();
+    for file in &files {
+        add_schema(&mut generator, file)?;
+    }
+
+    let code = generator.generate().change_context(GraphError)?;
+
+    match &args.output {
+        Some(output) => fs::write(output, code)
+            .change_context(GraphError)
+            .attach_printable_lazy(|| output.display().to_string()),
+        None => std::io::stdout()
+            .write_all(code.as_bytes())
+            .change_context(GraphError),
+    }
+}
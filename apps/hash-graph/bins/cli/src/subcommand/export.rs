@@ -0,0 +1,167 @@
+use std::{
+    fs,
+    io::{self, BufWriter, Write as _},
+    path::PathBuf,
+};
+
+use authorization::NoAuthorization;
+use clap::Parser;
+use error_stack::{Report, ResultExt};
+use graph::{
+    knowledge::EntityQueryPath,
+    store::{
+        knowledge::GetEntitySubgraphParams,
+        query::{Filter, FilterExpression, Parameter},
+        DatabaseConnectionInfo, DatabasePoolConfig, EntityQuerySorting, EntityStore,
+        PostgresStorePool, StorePool,
+    },
+    subgraph::{
+        edges::{EdgeResolveDepths, GraphResolveDepths, OutgoingEdgeResolveDepth},
+        rdf::RdfFormat,
+        temporal_axes::{
+            PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
+            VariableTemporalAxisUnresolved,
+        },
+    },
+};
+use graph_types::{account::AccountId, knowledge::entity::Entity};
+use uuid::Uuid;
+
+use crate::error::GraphError;
+
+#[derive(Debug, Parser)]
+#[clap(version, author, about, long_about = None)]
+pub struct ExportArgs {
+    #[clap(flatten)]
+    pub db_info: DatabaseConnectionInfo,
+
+    #[clap(flatten)]
+    pub pool_config: DatabasePoolConfig,
+
+    /// The linked data format to export the graph as.
+    #[clap(long, value_enum, default_value_t = RdfFormat::Turtle)]
+    pub format: RdfFormat,
+
+    /// The file to write the export to. If not specified, the export is written to stdout.
+    #[clap(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Only export the entities owned by the specified webs.
+    ///
+    /// The ontology types the entities depend on are exported regardless of the webs. Can be
+    /// specified multiple times.
+    #[clap(long = "web", value_name = "WEB_ID")]
+    pub webs: Vec<Uuid>,
+
+    /// Whether to include draft entities.
+    #[clap(long)]
+    pub include_drafts: bool,
+}
+
+/// Resolves every ontology type the entities depend on, but no other entities.
+const fn ontology_depths() -> GraphResolveDepths {
+    let all = OutgoingEdgeResolveDepth {
+        outgoing: 255,
+        incoming: 0,
+    };
+    GraphResolveDepths {
+        inherits_from: all,
+        constrains_values_on: all,
+        constrains_properties_on: all,
+        constrains_links_on: all,
+        constrains_link_destinations_on: all,
+        is_of_type: OutgoingEdgeResolveDepth {
+            outgoing: 1,
+            incoming: 0,
+        },
+        has_left_entity: EdgeResolveDepths {
+            incoming: 0,
+            outgoing: 0,
+        },
+        has_right_entity: EdgeResolveDepths {
+            incoming: 0,
+            outgoing: 0,
+        },
+    }
+}
+
+fn web_filter(webs: &[Uuid]) -> Filter<'static, Entity> {
+    Filter::Any(
+        webs.iter()
+            .map(|web| {
+                Filter::Equal(
+                    Some(FilterExpression::Path(EntityQueryPath::OwnedById)),
+                    Some(FilterExpression::Parameter(Parameter::Uuid(*web))),
+                )
+            })
+            .collect(),
+    )
+}
+
+pub async fn export(args: ExportArgs) -> Result<(), Report<GraphError>> {
+    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config)
+        .await
+        .change_context(GraphError)
+        .map_err(|report| {
+            tracing::error!(error = ?report, "Failed to connect to database");
+            report
+        })?;
+
+    let store = pool
+        .acquire(NoAuthorization, None)
+        .await
+        .change_context(GraphError)?;
+
+    let filter = if args.webs.is_empty() {
+        Filter::All(Vec::new())
+    } else {
+        web_filter(&args.webs)
+    };
+
+    // Authorization is not checked, so the actor is irrelevant
+    let response = store
+        .get_entity_subgraph(
+            AccountId::new(Uuid::nil()),
+            GetEntitySubgraphParams {
+                filter,
+                temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(None),
+                    variable: VariableTemporalAxisUnresolved::new(None, None),
+                },
+                graph_resolve_depths: ontology_depths(),
+                sorting: EntityQuerySorting {
+                    paths: Vec::new(),
+                    cursor: None,
+                },
+                limit: None,
+                include_drafts: args.include_drafts,
+                include_count: false,
+            },
+        )
+        .await
+        .change_context(GraphError)?;
+
+    match &args.output {
+        Some(output) => {
+            let mut writer = BufWriter::new(
+                fs::File::create(output)
+                    .change_context(GraphError)
+                    .attach_printable_lazy(|| output.display().to_string())?,
+            );
+            response
+                .subgraph
+                .write_rdf(args.format, &mut writer)
+                .and_then(|()| writer.flush())
+                .change_context(GraphError)
+                .attach_printable_lazy(|| output.display().to_string())
+        }
+        None => {
+            let mut writer = BufWriter::new(io::stdout().lock());
+            response
+                .subgraph
+                .write_rdf(args.format, &mut writer)
+                .and_then(|()| writer.flush())
+                .change_context(GraphError)
+        }
+    }
+}
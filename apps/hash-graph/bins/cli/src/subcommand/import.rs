@@ -0,0 +1,166 @@
+use std::{io::Write as _, path::PathBuf};
+
+use authorization::{
+    backend::SpiceDbOpenApi, zanzibar::ZanzibarClient, AuthorizationApi, NoAuthorization,
+};
+use clap::Parser;
+use error_stack::{Report, ResultExt};
+use graph::{
+    knowledge::import::{
+        import_entities, EntityImportMapping, ImportFormat, ImportReport, ImportRowDecoder,
+    },
+    store::{DatabaseConnectionInfo, DatabasePoolConfig, PostgresStorePool, StorePool},
+};
+use graph_types::account::AccountId;
+use tokio::{
+    fs,
+    io::{self, AsyncRead},
+};
+use tokio_util::codec::FramedRead;
+use uuid::Uuid;
+
+use crate::error::GraphError;
+
+#[derive(Debug, Parser)]
+#[clap(version, author, about, long_about = None)]
+pub struct ImportArgs {
+    #[clap(flatten)]
+    pub db_info: DatabaseConnectionInfo,
+
+    #[clap(flatten)]
+    pub pool_config: DatabasePoolConfig,
+
+    /// The rows to import, the rows are read from stdin if omitted.
+    pub file: Option<PathBuf>,
+
+    /// The format of the rows.
+    #[clap(long, value_enum, default_value_t = ImportFormat::Csv)]
+    pub format: ImportFormat,
+
+    /// A JSON file describing the entity types of the created entities, the web they are created
+    /// in and which column is imported as which property.
+    #[clap(long, value_name = "FILE")]
+    pub mapping: PathBuf,
+
+    /// The account the entities are created by.
+    #[clap(long, value_name = "ACCOUNT_ID")]
+    pub actor_id: Uuid,
+
+    /// The number of entities which are inserted at once.
+    #[clap(long, default_value_t = 1000)]
+    pub batch_size: usize,
+
+    /// The host the Spice DB server is listening at.
+    ///
+    /// If omitted, the relationships of the mapping are not written and permissions are not
+    /// checked.
+    #[clap(long, env = "HASH_SPICEDB_HOST")]
+    pub spicedb_host: Option<String>,
+
+    /// The port the Spice DB server is listening at.
+    #[clap(long, env = "HASH_SPICEDB_HTTP_PORT")]
+    pub spicedb_http_port: Option<u16>,
+
+    /// The secret key used to authenticate with the Spice DB server.
+    #[clap(long, env = "HASH_SPICEDB_GRPC_PRESHARED_KEY")]
+    pub spicedb_grpc_preshared_key: Option<String>,
+}
+
+async fn import_rows<A>(
+    pool: &PostgresStorePool,
+    authorization_api: A,
+    args: &ImportArgs,
+    mapping: &EntityImportMapping,
+) -> Result<ImportReport, Report<GraphError>>
+where
+    A: AuthorizationApi,
+{
+    let mut store = pool
+        .acquire(authorization_api, None)
+        .await
+        .change_context(GraphError)
+        .map_err(|report| {
+            tracing::error!(error = ?report, "Failed to acquire database connection");
+            report
+        })?;
+
+    let input: Box<dyn AsyncRead + Send + Unpin> = if let Some(file) = &args.file {
+        Box::new(
+            fs::File::open(file)
+                .await
+                .change_context(GraphError)
+                .attach_printable_lazy(|| file.display().to_string())?,
+        )
+    } else {
+        Box::new(io::stdin())
+    };
+    let rows = FramedRead::new(
+        io::BufReader::new(input),
+        ImportRowDecoder::new(args.format),
+    );
+
+    import_entities(
+        &mut store,
+        AccountId::new(args.actor_id),
+        mapping,
+        rows,
+        args.batch_size.max(1),
+    )
+    .await
+    .change_context(GraphError)
+}
+
+pub async fn import(args: ImportArgs) -> Result<(), Report<GraphError>> {
+    let mapping: EntityImportMapping = serde_json::from_slice(
+        &fs::read(&args.mapping)
+            .await
+            .change_context(GraphError)
+            .attach_printable_lazy(|| args.mapping.display().to_string())?,
+    )
+    .change_context(GraphError)
+    .attach_printable("The mapping is invalid")?;
+
+    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config)
+        .await
+        .change_context(GraphError)
+        .map_err(|report| {
+            tracing::error!(error = ?report, "Failed to connect to database");
+            report
+        })?;
+
+    let report = if let (Some(spicedb_host), Some(spicedb_http_port)) =
+        (&args.spicedb_host, args.spicedb_http_port)
+    {
+        let spicedb_client = SpiceDbOpenApi::new(
+            format!("{spicedb_host}:{spicedb_http_port}"),
+            args.spicedb_grpc_preshared_key.as_deref(),
+        )
+        .change_context(GraphError)?;
+        import_rows(&pool, ZanzibarClient::new(spicedb_client), &args, &mapping).await?
+    } else {
+        tracing::warn!(
+            "No Spice DB server specified, the relationships of the imported entities are not \
+             written"
+        );
+        import_rows(&pool, NoAuthorization, &args, &mapping).await?
+    };
+
+    if let Some(reason) = &report.aborted {
+        tracing::error!(
+            created = report.created.len(),
+            failed = report.errors.len(),
+            reason,
+            "Import aborted"
+        );
+    } else {
+        tracing::info!(
+            created = report.created.len(),
+            failed = report.errors.len(),
+            "Import finished"
+        );
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &report).change_context(GraphError)?;
+    writeln!(stdout).change_context(GraphError)
+}
@@ -4,7 +4,6 @@ use error_stack::{Result, ResultExt};
 use graph::store::{
     DatabaseConnectionInfo, DatabasePoolConfig, PostgresStorePool, StoreMigration, StorePool,
 };
-use tokio_postgres::NoTls;
 
 use crate::error::GraphError;
 
@@ -19,7 +18,7 @@ pub struct MigrateArgs {
 }
 
 pub async fn migrate(args: MigrateArgs) -> Result<(), GraphError> {
-    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config, NoTls)
+    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config)
         .await
         .change_context(GraphError)
         .map_err(|report| {
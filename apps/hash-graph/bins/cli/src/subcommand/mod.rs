@@ -1,4 +1,7 @@
+mod codegen;
 mod completions;
+mod export;
+mod import;
 mod migrate;
 mod server;
 mod snapshot;
@@ -15,7 +18,10 @@ use tokio::{runtime::Handle, time::sleep};
 #[cfg(feature = "test-server")]
 pub use self::test_server::{test_server, TestServerArgs};
 pub use self::{
+    codegen::{codegen, CodegenArgs},
     completions::{completions, CompletionsArgs},
+    export::{export, ExportArgs},
+    import::{import, ImportArgs},
     migrate::{migrate, MigrateArgs},
     server::{server, ServerArgs},
     snapshot::{snapshot, SnapshotArgs},
@@ -27,7 +33,7 @@ use crate::error::{GraphError, HealthcheckError};
 #[derive(Debug, clap::Subcommand)]
 pub enum Subcommand {
     /// Run the Graph webserver.
-    Server(ServerArgs),
+    Server(Box<ServerArgs>),
     /// Run database migrations required by the Graph.
     Migrate(MigrateArgs),
     /// Run the type fetcher to request external types.
@@ -36,6 +42,12 @@ pub enum Subcommand {
     Completions(CompletionsArgs),
     /// Snapshot API for the database.
     Snapshot(SnapshotArgs),
+    /// Generate Rust types from ontology types.
+    Codegen(CodegenArgs),
+    /// Export the entities and ontology types of the graph as linked data.
+    Export(ExportArgs),
+    /// Import entities from CSV or JSON-lines rows.
+    Import(ImportArgs),
     /// Test server
     #[cfg(feature = "test-server")]
     TestServer(TestServerArgs),
@@ -61,7 +73,7 @@ fn block_on(
 impl Subcommand {
     pub(crate) fn execute(self, tracing_config: TracingConfig) -> Result<(), GraphError> {
         match self {
-            Self::Server(args) => block_on(server(args), tracing_config),
+            Self::Server(args) => block_on(server(*args), tracing_config),
             Self::Migrate(args) => block_on(migrate(args), tracing_config),
             Self::TypeFetcher(args) => block_on(type_fetcher(args), tracing_config),
             Self::Completions(ref args) => {
@@ -69,6 +81,9 @@ impl Subcommand {
                 Ok(())
             }
             Self::Snapshot(args) => block_on(snapshot(args), tracing_config),
+            Self::Codegen(ref args) => codegen(args),
+            Self::Export(args) => block_on(export(args), tracing_config),
+            Self::Import(args) => block_on(import(args), tracing_config),
             #[cfg(feature = "test-server")]
             Self::TestServer(args) => block_on(test_server(args), tracing_config),
         }
@@ -5,7 +5,7 @@ use core::{
     str::FromStr,
     time::Duration,
 };
-use std::fs;
+use std::{fs, path::PathBuf};
 
 use authorization::{
     backend::{SpiceDbOpenApi, ZanzibarBackend},
@@ -19,13 +19,17 @@ use graph::{
     store::{
         DatabaseConnectionInfo, DatabasePoolConfig, FetchingPool, PostgresStorePool, StorePool,
     },
+    webhook::{WebhookConfig, WebhookDispatcher},
+};
+use graph_api::rest::{
+    authentication::AuthenticationConfig, rest_api_router, OpenApiDocumentation,
+    RestRouterDependencies,
 };
-use graph_api::rest::{rest_api_router, OpenApiDocumentation, RestRouterDependencies};
 use regex::Regex;
 use reqwest::{Client, Url};
 use temporal_client::TemporalClientConfig;
 use tokio::{net::TcpListener, time::timeout};
-use tokio_postgres::NoTls;
+use type_fetcher::cache::OntologyTypeCache;
 
 use crate::{
     error::{GraphError, HealthcheckError},
@@ -117,6 +121,11 @@ pub struct ServerArgs {
     #[clap(long, default_value_t = false)]
     pub offline: bool,
 
+    /// Directory of the type fetcher cache, from which external ontology types are loaded in
+    /// offline mode.
+    #[clap(long, requires = "offline", env = "HASH_GRAPH_TYPE_FETCHER_CACHE_DIR")]
+    pub type_fetcher_cache_dir: Option<PathBuf>,
+
     /// The host the Spice DB server is listening at.
     #[clap(long, env = "HASH_SPICEDB_HOST")]
     pub spicedb_host: String,
@@ -138,6 +147,17 @@ pub struct ServerArgs {
     /// The URL of the Temporal server.
     #[clap(long, env = "HASH_TEMPORAL_SERVER_PORT", default_value_t = 7233)]
     pub temporal_port: u16,
+
+    /// Path to a JSON file configuring the API keys and JWT secrets accepted by the REST API.
+    ///
+    /// If not set, no bearer tokens are accepted and the `X-Authenticated-User-Actor-Id` header is
+    /// trusted.
+    #[clap(long, env = "HASH_GRAPH_AUTHENTICATION_CONFIG")]
+    pub authentication_config: Option<PathBuf>,
+
+    /// Configuration for delivering entity changes to subscribed webhooks.
+    #[clap(flatten)]
+    pub webhook_config: WebhookConfig,
 }
 
 #[expect(clippy::too_many_lines)]
@@ -178,7 +198,21 @@ pub async fn server(args: ServerArgs) -> Result<(), GraphError> {
         return Ok(());
     }
 
-    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config, NoTls)
+    let authentication = if let Some(path) = &args.authentication_config {
+        serde_json::from_slice::<AuthenticationConfig>(
+            &fs::read(path)
+                .change_context(GraphError)
+                .attach_printable("could not read authentication config")
+                .attach_printable_lazy(|| path.display().to_string())?,
+        )
+        .change_context(GraphError)
+        .attach_printable("could not parse authentication config")?
+    } else {
+        AuthenticationConfig::default()
+    };
+    authentication.validate().change_context(GraphError)?;
+
+    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config)
         .await
         .change_context(GraphError)
         .map_err(|report| {
@@ -192,7 +226,15 @@ pub async fn server(args: ServerArgs) -> Result<(), GraphError> {
         .attach_printable("Connection to database failed")?;
 
     let pool = if args.offline {
-        FetchingPool::new_offline(pool)
+        let cache = match &args.type_fetcher_cache_dir {
+            Some(cache_dir) => Some(
+                OntologyTypeCache::open(cache_dir)
+                    .await
+                    .change_context(GraphError)?,
+            ),
+            None => None,
+        };
+        FetchingPool::new_offline(pool, cache)
     } else {
         FetchingPool::new(
             pool,
@@ -219,10 +261,30 @@ pub async fn server(args: ServerArgs) -> Result<(), GraphError> {
     let mut zanzibar_client = ZanzibarClient::new(spicedb_client);
     zanzibar_client.seed().await.change_context(GraphError)?;
 
+    let store = Arc::new(pool);
+    let authorization_api = Arc::new(zanzibar_client);
+    let subscription_secret_key = args.webhook_config.secret_key.clone();
+    if let Some(secret_key) = subscription_secret_key.clone() {
+        tokio::spawn(
+            WebhookDispatcher::new(
+                Arc::clone(&store),
+                Arc::clone(&authorization_api),
+                args.webhook_config,
+                secret_key,
+            )
+            .change_context(GraphError)?
+            .run(),
+        );
+    } else {
+        tracing::warn!("No webhook secret key configured, webhooks are disabled");
+    }
+
     let router = rest_api_router(RestRouterDependencies {
-        store: Arc::new(pool),
-        authorization_api: Arc::new(zanzibar_client),
+        store,
+        authorization_api,
         domain_regex: DomainValidator::new(args.allowed_url_domain),
+        authentication,
+        subscription_secret_key,
         temporal_client: if let Some(host) = args.temporal_host {
             Some(
                 TemporalClientConfig::new(
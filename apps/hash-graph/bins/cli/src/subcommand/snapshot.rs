@@ -1,17 +1,33 @@
+use std::{
+    io::Write as _,
+    path::{Path, PathBuf},
+};
+
+use async_compression::tokio::{bufread::ZstdDecoder, write::ZstdEncoder};
 use authorization::{
     backend::{SpiceDbOpenApi, ZanzibarBackend},
     zanzibar::ZanzibarClient,
     AuthorizationApi, NoAuthorization,
 };
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use codec::bytes::JsonLinesEncoder;
 use error_stack::{Report, ResultExt};
+use futures::{Stream, TryStreamExt};
 use graph::{
-    snapshot::{SnapshotDumpSettings, SnapshotEntry, SnapshotStore},
-    store::{DatabaseConnectionInfo, DatabasePoolConfig, PostgresStorePool, StorePool},
+    snapshot::{
+        SnapshotDecoder, SnapshotDigest, SnapshotDumpSettings, SnapshotEntry, SnapshotInspector,
+        SnapshotRestoreError, SnapshotScope, SnapshotStore,
+    },
+    store::{AsClient, DatabaseConnectionInfo, DatabasePoolConfig, PostgresStorePool, StorePool},
+};
+use graph_types::owned_by_id::OwnedById;
+use temporal_versioning::{Timestamp, TransactionTime};
+use tokio::{
+    fs,
+    io::{self, AsyncBufReadExt, AsyncRead, AsyncWrite},
 };
-use tokio::io;
-use tokio_postgres::NoTls;
 use tokio_util::codec::{FramedRead, FramedWrite};
+use uuid::Uuid;
 
 use crate::error::GraphError;
 
@@ -56,8 +72,37 @@ pub struct SnapshotDumpArgs {
     /// Whether to skip dumping the relations.
     #[clap(long)]
     pub no_relations: bool,
+
+    /// Only dump the records owned by the specified webs.
+    ///
+    /// External ontology types are dumped regardless of the webs. Can be specified multiple
+    /// times.
+    #[clap(long = "web", value_name = "WEB_ID")]
+    pub webs: Vec<Uuid>,
+
+    /// Only dump the records which were created at or after the specified transaction
+    /// time, e.g. `2024-08-01T00:00:00Z`.
+    ///
+    /// The resulting snapshot is merged into the existing graph when it is restored.
+    #[clap(long, value_name = "TIMESTAMP")]
+    pub since: Option<Timestamp<TransactionTime>>,
+
+    /// The compression to apply to the snapshot.
+    #[clap(long, value_enum, default_value_t = SnapshotCompression::None)]
+    pub compression: SnapshotCompression,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum SnapshotCompression {
+    /// Writes the snapshot as plain JSON lines.
+    None,
+    /// Compresses the snapshot using zstd.
+    Zstd,
 }
 
+/// The magic number every zstd frame starts with.
+const ZSTD_MAGIC_NUMBER: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
 #[derive(Debug, Parser)]
 pub struct SnapshotRestoreArgs {
     /// Whether to skip the validation checks.
@@ -67,12 +112,78 @@ pub struct SnapshotRestoreArgs {
     /// Whether to skip the authorization restoring.
     #[clap(long)]
     pub skip_authorization: bool,
+
+    /// Whether to accept snapshots without a manifest.
+    ///
+    /// Snapshots created by older versions of the Graph don't contain a manifest. If a manifest is
+    /// present, it is still verified.
+    #[clap(long)]
+    pub allow_missing_manifest: bool,
+
+    /// Stages the snapshot batch by batch in the database schema with the specified name.
+    ///
+    /// If the restore fails, running it again with the same snapshot and checkpoint resumes the
+    /// restore after the last staged batch. The staged records are committed after the snapshot
+    /// has been read and verified completely. Dropping the schema discards the staged records.
+    #[clap(long, value_name = "NAME")]
+    pub checkpoint: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+pub struct SnapshotInspectArgs {
+    /// The snapshot to inspect, the snapshot is read from stdin if omitted.
+    pub file: Option<PathBuf>,
+
+    /// Whether to accept snapshots without a manifest.
+    #[clap(long)]
+    pub allow_missing_manifest: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct SnapshotDiffArgs {
+    /// The snapshot to compare against.
+    pub old: PathBuf,
+
+    /// The snapshot to compare.
+    pub new: PathBuf,
+
+    /// Whether to accept snapshots without a manifest.
+    #[clap(long)]
+    pub allow_missing_manifest: bool,
+}
+
+async fn restore_snapshot<C, A>(
+    mut store: SnapshotStore<C, A>,
+    snapshot: impl Stream<Item = Result<SnapshotEntry, Report<io::Error>>> + Send + 'static,
+    args: &SnapshotRestoreArgs,
+) -> Result<(), Report<SnapshotRestoreError>>
+where
+    C: AsClient,
+    A: ZanzibarBackend + AuthorizationApi,
+{
+    if let Some(checkpoint) = &args.checkpoint {
+        store
+            .restore_snapshot_with_checkpoint(snapshot, 10_000, !args.skip_validation, checkpoint)
+            .await
+    } else {
+        store
+            .restore_snapshot(snapshot, 10_000, !args.skip_validation)
+            .await
+    }
 }
 
 #[derive(Debug, Parser)]
 pub enum SnapshotCommand {
     Dump(SnapshotDumpArgs),
     Restore(SnapshotRestoreArgs),
+    /// Reports the contents of a snapshot without connecting to the database.
+    ///
+    /// The report contains the number of records per section and web, the versions of the
+    /// ontology types and references to records which are not contained in the snapshot.
+    Inspect(SnapshotInspectArgs),
+    /// Lists the entities and ontology types which were added, removed or changed between two
+    /// snapshots without connecting to the database.
+    Diff(SnapshotDiffArgs),
 }
 
 #[derive(Debug, Parser)]
@@ -88,22 +199,147 @@ pub struct SnapshotArgs {
     pub pool_config: DatabasePoolConfig,
 
     /// The host the Spice DB server is listening at.
+    ///
+    /// Required to dump or restore the authorization relations.
     #[clap(long, env = "HASH_SPICEDB_HOST")]
-    pub spicedb_host: String,
+    pub spicedb_host: Option<String>,
 
     /// The port the Spice DB server is listening at.
+    ///
+    /// Required to dump or restore the authorization relations.
     #[clap(long, env = "HASH_SPICEDB_HTTP_PORT")]
-    pub spicedb_http_port: u16,
+    pub spicedb_http_port: Option<u16>,
 
     /// The secret key used to authenticate with the Spice DB server.
     #[clap(long, env = "HASH_SPICEDB_GRPC_PRESHARED_KEY")]
     pub spicedb_grpc_preshared_key: Option<String>,
 }
 
+/// Writes the snapshot to stdout and compresses it if requested.
+fn write_snapshot(
+    compression: SnapshotCompression,
+) -> FramedWrite<Box<dyn AsyncWrite + Send + Unpin>, JsonLinesEncoder<SnapshotEntry>> {
+    let output: Box<dyn AsyncWrite + Send + Unpin> = match compression {
+        SnapshotCompression::None => Box::new(io::BufWriter::new(io::stdout())),
+        SnapshotCompression::Zstd => Box::new(ZstdEncoder::new(io::BufWriter::new(io::stdout()))),
+    };
+
+    FramedWrite::new(output, JsonLinesEncoder::default())
+}
+
+/// Reads the snapshot from `file`, or stdin if omitted, and decompresses it if it's compressed.
+async fn read_snapshot(
+    file: Option<&Path>,
+    allow_missing_manifest: bool,
+) -> Result<FramedRead<Box<dyn AsyncRead + Send + Unpin>, SnapshotDecoder>, Report<GraphError>> {
+    let input: Box<dyn AsyncRead + Send + Unpin> = if let Some(file) = file {
+        Box::new(
+            fs::File::open(file)
+                .await
+                .change_context(GraphError)
+                .attach_printable_lazy(|| file.display().to_string())?,
+        )
+    } else {
+        Box::new(io::stdin())
+    };
+    let mut input = io::BufReader::new(input);
+    let is_compressed = input
+        .fill_buf()
+        .await
+        .change_context(GraphError)?
+        .starts_with(&ZSTD_MAGIC_NUMBER);
+    let input: Box<dyn AsyncRead + Send + Unpin> = if is_compressed {
+        let mut decoder = ZstdDecoder::new(input);
+        decoder.multiple_members(true);
+        Box::new(decoder)
+    } else {
+        Box::new(input)
+    };
+
+    let decoder = if allow_missing_manifest {
+        SnapshotDecoder::new().allow_missing_manifest()
+    } else {
+        SnapshotDecoder::new()
+    };
+
+    Ok(FramedRead::new(input, decoder))
+}
+
+async fn inspect_snapshot(args: &SnapshotInspectArgs) -> Result<(), Report<GraphError>> {
+    let mut snapshot = read_snapshot(args.file.as_deref(), args.allow_missing_manifest).await?;
+    let mut inspector = SnapshotInspector::default();
+    while let Some(entry) = snapshot.try_next().await.change_context(GraphError)? {
+        inspector.update(&entry);
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &inspector.finish()).change_context(GraphError)?;
+    writeln!(stdout).change_context(GraphError)
+}
+
+async fn digest_snapshot(
+    file: &Path,
+    allow_missing_manifest: bool,
+) -> Result<SnapshotDigest, Report<GraphError>> {
+    let mut snapshot = read_snapshot(Some(file), allow_missing_manifest).await?;
+    let mut digest = SnapshotDigest::default();
+    while let Some(entry) = snapshot.try_next().await.change_context(GraphError)? {
+        digest
+            .update(&entry)
+            .change_context(GraphError)
+            .attach_printable_lazy(|| file.display().to_string())?;
+    }
+
+    Ok(digest)
+}
+
+async fn diff_snapshots(args: &SnapshotDiffArgs) -> Result<(), Report<GraphError>> {
+    let old = digest_snapshot(&args.old, args.allow_missing_manifest).await?;
+    let new = digest_snapshot(&args.new, args.allow_missing_manifest).await?;
+
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &old.diff(new)).change_context(GraphError)?;
+    writeln!(stdout).change_context(GraphError)
+}
+
+async fn connect_authorization(
+    args: &SnapshotArgs,
+) -> Result<ZanzibarClient<SpiceDbOpenApi>, Report<GraphError>> {
+    let (Some(spicedb_host), Some(spicedb_http_port)) =
+        (&args.spicedb_host, args.spicedb_http_port)
+    else {
+        return Err(Report::new(GraphError).attach_printable(
+            "The Spice DB host and port are required to dump or restore the authorization \
+             relations",
+        ));
+    };
+    let mut spicedb_client = SpiceDbOpenApi::new(
+        format!("{spicedb_host}:{spicedb_http_port}"),
+        args.spicedb_grpc_preshared_key.as_deref(),
+    )
+    .change_context(GraphError)?;
+    spicedb_client
+        .import_schema(include_str!(
+            "../../../../../../libs/@local/hash-authorization/schemas/v1__initial_schema.zed"
+        ))
+        .await
+        .change_context(GraphError)?;
+
+    let mut zanzibar_client = ZanzibarClient::new(spicedb_client);
+    zanzibar_client.seed().await.change_context(GraphError)?;
+    Ok(zanzibar_client)
+}
+
 pub async fn snapshot(args: SnapshotArgs) -> Result<(), Report<GraphError>> {
     SnapshotEntry::install_error_stack_hook();
 
-    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config, NoTls)
+    match &args.command {
+        SnapshotCommand::Inspect(args) => return inspect_snapshot(args).await,
+        SnapshotCommand::Diff(args) => return diff_snapshots(args).await,
+        SnapshotCommand::Dump(_) | SnapshotCommand::Restore(_) => {}
+    }
+
+    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config)
         .await
         .change_context(GraphError)
         .map_err(|report| {
@@ -114,34 +350,18 @@ pub async fn snapshot(args: SnapshotArgs) -> Result<(), Report<GraphError>> {
     let skip_authorization = match &args.command {
         SnapshotCommand::Dump(args) => args.no_relations,
         SnapshotCommand::Restore(args) => args.skip_authorization,
+        SnapshotCommand::Inspect(_) | SnapshotCommand::Diff(_) => true,
     };
 
     let authorization = if skip_authorization {
         None
     } else {
-        let mut spicedb_client = SpiceDbOpenApi::new(
-            format!("{}:{}", args.spicedb_host, args.spicedb_http_port),
-            args.spicedb_grpc_preshared_key.as_deref(),
-        )
-        .change_context(GraphError)?;
-        spicedb_client
-            .import_schema(include_str!(
-                "../../../../../../libs/@local/hash-authorization/schemas/v1__initial_schema.zed"
-            ))
-            .await
-            .change_context(GraphError)?;
-
-        let mut zanzibar_client = ZanzibarClient::new(spicedb_client);
-        zanzibar_client.seed().await.change_context(GraphError)?;
-        Some(zanzibar_client)
+        Some(connect_authorization(&args).await?)
     };
 
     match args.command {
         SnapshotCommand::Dump(args) => {
-            let write = FramedWrite::new(
-                io::BufWriter::new(io::stdout()),
-                codec::bytes::JsonLinesEncoder::default(),
-            );
+            let write = write_snapshot(args.compression);
             let settings = SnapshotDumpSettings {
                 chunk_size: 10_000,
                 dump_webs: !args.no_webs,
@@ -153,12 +373,16 @@ pub async fn snapshot(args: SnapshotArgs) -> Result<(), Report<GraphError>> {
                 dump_data_types: !args.no_data_types,
                 dump_embeddings: !args.no_embeddings,
                 dump_relations: !args.no_relations,
+                scope: SnapshotScope {
+                    webs: args.webs.into_iter().map(OwnedById::new).collect(),
+                    transaction_time_since: args.since,
+                },
             };
 
             if let Some(authorization) = authorization {
-                pool.dump_snapshot(write, &authorization, settings)
+                pool.dump_snapshot(write, &authorization, &settings)
             } else {
-                pool.dump_snapshot(write, &NoAuthorization, settings)
+                pool.dump_snapshot(write, &NoAuthorization, &settings)
             }
             .change_context(GraphError)
             .attach_printable("Failed to produce snapshot dump")?;
@@ -166,37 +390,47 @@ pub async fn snapshot(args: SnapshotArgs) -> Result<(), Report<GraphError>> {
             tracing::info!("Snapshot dumped successfully");
         }
         SnapshotCommand::Restore(args) => {
-            let read = FramedRead::new(
-                io::BufReader::new(io::stdin()),
-                codec::bytes::JsonLinesDecoder::default(),
-            );
+            let read = read_snapshot(None, args.allow_missing_manifest).await?;
+
             if let Some(authorization) = authorization {
-                SnapshotStore::new(
-                    pool.acquire(authorization, None)
-                        .await
-                        .change_context(GraphError)
-                        .map_err(|report| {
-                            tracing::error!(error = ?report, "Failed to acquire database connection");
-                            report
-                        })?,
+                restore_snapshot(
+                    SnapshotStore::new(
+                        pool.acquire(authorization, None)
+                            .await
+                            .change_context(GraphError)
+                            .map_err(|report| {
+                                tracing::error!(error = ?report, "Failed to acquire database connection");
+                                report
+                            })?,
+                    ),
+                    read,
+                    &args,
                 )
-                    .restore_snapshot(read, 10_000, !args.skip_validation)
-                    .await
+                .await
             } else {
-                SnapshotStore::new(pool.acquire(NoAuthorization, None).await
-                    .change_context(GraphError)
-                    .map_err(|report| {
-                        tracing::error!(error = ?report, "Failed to acquire database connection");
-                        report
-                    })?)
-                    .restore_snapshot(read, 10_000, !args.skip_validation)
-                    .await
+                restore_snapshot(
+                    SnapshotStore::new(
+                        pool.acquire(NoAuthorization, None)
+                            .await
+                            .change_context(GraphError)
+                            .map_err(|report| {
+                                tracing::error!(error = ?report, "Failed to acquire database connection");
+                                report
+                            })?,
+                    ),
+                    read,
+                    &args,
+                )
+                .await
             }
             .change_context(GraphError)
             .attach_printable("Failed to restore snapshot")?;
 
             tracing::info!("Snapshot restored successfully");
         }
+        SnapshotCommand::Inspect(_) | SnapshotCommand::Diff(_) => {
+            unreachable!("snapshots are inspected without connecting to the database")
+        }
     }
 
     Ok(())
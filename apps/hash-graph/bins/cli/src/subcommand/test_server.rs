@@ -13,7 +13,6 @@ use graph::{
 };
 use reqwest::Client;
 use tokio::{net::TcpListener, time::timeout};
-use tokio_postgres::NoTls;
 
 use crate::{
     error::{GraphError, HealthcheckError},
@@ -71,7 +70,7 @@ pub async fn test_server(args: TestServerArgs) -> Result<(), GraphError> {
         .change_context(GraphError);
     }
 
-    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config, NoTls)
+    let pool = PostgresStorePool::new(&args.db_info, &args.pool_config)
         .await
         .change_context(GraphError)
         .map_err(|report| {
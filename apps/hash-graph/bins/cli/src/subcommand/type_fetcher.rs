@@ -1,7 +1,11 @@
 use core::time::Duration;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use clap::Parser;
-use error_stack::{Result, ResultExt};
+use error_stack::{Report, Result, ResultExt};
 use futures::{future, StreamExt};
 use tarpc::{
     serde_transport::Transport,
@@ -10,9 +14,11 @@ use tarpc::{
 use tokio::time::timeout;
 use tokio_serde::formats::Json;
 use type_fetcher::{
+    cache::OntologyTypeCache,
     fetcher::{Fetcher, FetcherRequest, FetcherResponse},
     fetcher_server::FetchServer,
 };
+use type_system::url::VersionedUrl;
 
 use crate::{
     error::{GraphError, HealthcheckError},
@@ -50,6 +56,43 @@ pub struct TypeFetcherArgs {
     /// Timeout for the wait flag in seconds
     #[clap(long, requires = "wait")]
     pub timeout: Option<u64>,
+
+    /// Directory in which fetched ontology types are cached.
+    #[clap(long, env = "HASH_GRAPH_TYPE_FETCHER_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Only serves ontology types from the cache directory without accessing the network.
+    #[clap(
+        long,
+        default_value_t = false,
+        env = "HASH_GRAPH_TYPE_FETCHER_OFFLINE",
+        requires = "cache_dir"
+    )]
+    pub offline: bool,
+
+    /// Populates the cache directory with the provided ontology types, including all ontology
+    /// types they refer to, and exits.
+    #[clap(long, num_args = 1.., requires = "cache_dir", conflicts_with = "offline")]
+    pub mirror: Vec<VersionedUrl>,
+
+    /// Same as `--mirror` but reads the ontology type URLs from a file, one URL per line.
+    #[clap(long, requires = "cache_dir", conflicts_with = "offline")]
+    pub mirror_file: Option<PathBuf>,
+}
+
+fn read_mirror_file(path: &Path) -> Result<Vec<VersionedUrl>, GraphError> {
+    fs::read_to_string(path)
+        .change_context(GraphError)
+        .attach_printable_lazy(|| path.display().to_string())?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.parse::<VersionedUrl>()
+                .change_context(GraphError)
+                .attach_printable_lazy(|| line.to_owned())
+        })
+        .collect()
 }
 
 pub async fn type_fetcher(args: TypeFetcherArgs) -> Result<(), GraphError> {
@@ -63,6 +106,35 @@ pub async fn type_fetcher(args: TypeFetcherArgs) -> Result<(), GraphError> {
         .change_context(GraphError);
     }
 
+    let cache = match &args.cache_dir {
+        Some(cache_dir) => Some(
+            OntologyTypeCache::open(cache_dir)
+                .await
+                .change_context(GraphError)?,
+        ),
+        None => None,
+    };
+    let server = FetchServer {
+        buffer_size: 10,
+        cache,
+        offline: args.offline,
+    };
+
+    if !args.mirror.is_empty() || args.mirror_file.is_some() {
+        let mut urls = args.mirror;
+        if let Some(mirror_file) = &args.mirror_file {
+            urls.extend(read_mirror_file(mirror_file)?);
+        }
+
+        let num_types = server
+            .mirror_ontology_types(urls)
+            .await
+            .map_err(|error| Report::new(error).change_context(GraphError))?;
+        tracing::info!(cache_dir = ?args.cache_dir, "Mirrored {num_types} ontology types");
+
+        return Ok(());
+    }
+
     let mut listener = tarpc::serde_transport::tcp::listen(
         (
             args.address.type_fetcher_host,
@@ -83,10 +155,7 @@ pub async fn type_fetcher(args: TypeFetcherArgs) -> Result<(), GraphError> {
     listener
         .filter_map(|r| future::ready(r.ok()))
         .map(server::BaseChannel::with_defaults)
-        .map(|channel| {
-            let server = FetchServer { buffer_size: 10 };
-            channel.execute(server.serve())
-        })
+        .map(|channel| channel.execute(server.clone().serve()))
         .buffer_unordered(255)
         .for_each(|()| async {})
         .await;
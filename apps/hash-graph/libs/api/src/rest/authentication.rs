@@ -0,0 +1,644 @@
+//! Authentication of requests to the REST API.
+//!
+//! Requests can authenticate with an `Authorization: Bearer <token>` header, where the token is
+//! either a configured API key or a JWT signed with one of the configured secrets. Trusted
+//! internal callers may instead pass the acting account in the `X-Authenticated-User-Actor-Id`
+//! header if [`AuthenticationConfig::allow_actor_header`] is set.
+//!
+//! Authenticated actors are only allowed to call a route if they were granted its [`Scope`].
+//! Routes require [`Scope::Write`] unless they are `GET` routes or declared [`read_only`].
+
+use alloc::sync::Arc;
+use core::fmt;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderMap, Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::MethodRouter,
+    Extension,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use error_stack::{Context, Report, ResultExt};
+use graph_types::account::AccountId;
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AuthenticationError {
+    MalformedToken,
+    UnknownApiKey,
+    UnsupportedAlgorithm,
+    InvalidSignature,
+    Expired,
+    NotYetValid,
+    InvalidIssuer,
+    InvalidAudience,
+}
+
+impl fmt::Display for AuthenticationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedToken => fmt.write_str("The bearer token is malformed"),
+            Self::UnknownApiKey => fmt.write_str("The API key is not known"),
+            Self::UnsupportedAlgorithm => fmt.write_str("The JWT algorithm is not supported"),
+            Self::InvalidSignature => fmt.write_str("The JWT signature is invalid"),
+            Self::Expired => fmt.write_str("The JWT has expired"),
+            Self::NotYetValid => fmt.write_str("The JWT is not yet valid"),
+            Self::InvalidIssuer => fmt.write_str("The JWT was not issued by the expected issuer"),
+            Self::InvalidAudience => fmt.write_str("The JWT is not intended for the graph"),
+        }
+    }
+}
+
+impl Context for AuthenticationError {}
+
+/// The permissions granted to an authenticated actor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+    /// Allows requests which only read from the graph.
+    Read,
+    /// Allows requests which modify the graph.
+    Write,
+}
+
+impl Scope {
+    fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "read" => Some(Self::Read),
+            "write" => Some(Self::Write),
+            _ => None,
+        }
+    }
+
+    /// Returns the scope a route requires if it does not declare one.
+    ///
+    /// `GET` requests only require [`Scope::Read`], all other requests require [`Scope::Write`].
+    #[must_use]
+    pub fn default_for(method: &Method) -> Self {
+        if method.is_safe() {
+            Self::Read
+        } else {
+            Self::Write
+        }
+    }
+}
+
+/// Declares that a route only reads from the graph, so it only requires [`Scope::Read`].
+///
+/// This is used for query-like routes, which are not `GET` routes as they take a request body.
+pub(crate) fn read_only<S>(route: MethodRouter<S>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    route.layer(Extension(Scope::Read))
+}
+
+/// An API key which authenticates requests as the specified account.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ApiKey {
+    pub key: String,
+    pub account_id: AccountId,
+    pub scopes: Vec<Scope>,
+}
+
+/// The account and scopes a request was authenticated with.
+///
+/// The authentication layer stores this in the request extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedActor {
+    pub actor_id: AccountId,
+    pub scopes: Vec<Scope>,
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+/// The `aud` claim, which is either a single audience or a list of audiences.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JwtAudience {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl JwtAudience {
+    fn contains(&self, audience: &str) -> bool {
+        match self {
+            Self::Single(value) => value == audience,
+            Self::Multiple(values) => values.iter().any(|value| value == audience),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct JwtClaims {
+    sub: AccountId,
+    exp: i64,
+    #[serde(default)]
+    nbf: Option<i64>,
+    #[serde(default)]
+    iss: Option<String>,
+    #[serde(default)]
+    aud: Option<JwtAudience>,
+    /// Space-separated list of scopes, scopes unknown to the graph are ignored.
+    #[serde(default)]
+    scope: String,
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, Report<AuthenticationError>> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .change_context(AuthenticationError::MalformedToken)?;
+    serde_json::from_slice(&bytes).change_context(AuthenticationError::MalformedToken)
+}
+
+#[derive(Debug)]
+pub struct InvalidAuthenticationConfig;
+
+impl fmt::Display for InvalidAuthenticationConfig {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("The authentication config is invalid")
+    }
+}
+
+impl Context for InvalidAuthenticationConfig {}
+
+/// Configures how requests to the REST API are authenticated.
+///
+/// The default configuration does not accept any tokens and trusts the actor header, which is the
+/// behavior for deployments where the graph is only reachable by internal callers.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AuthenticationConfig {
+    /// API keys which are accepted as bearer tokens.
+    #[serde(default)]
+    pub api_keys: Vec<ApiKey>,
+    /// Secrets used to verify `HS256` signed JWTs.
+    ///
+    /// Multiple secrets can be specified to rotate them without downtime.
+    #[serde(default)]
+    pub jwt_secrets: Vec<String>,
+    /// The `iss` claim JWTs are required to have, required if [`jwt_secrets`] are configured.
+    ///
+    /// [`jwt_secrets`]: Self::jwt_secrets
+    #[serde(default)]
+    pub jwt_issuer: Option<String>,
+    /// The audience JWTs are required to list in their `aud` claim, required if [`jwt_secrets`]
+    /// are configured.
+    ///
+    /// [`jwt_secrets`]: Self::jwt_secrets
+    #[serde(default)]
+    pub jwt_audience: Option<String>,
+    /// Accepts the `X-Authenticated-User-Actor-Id` header for requests without a bearer token.
+    ///
+    /// If not set, the header is only accepted if neither API keys nor JWT secrets are
+    /// configured, so configuring credentials cannot be bypassed by passing the header.
+    #[serde(default)]
+    pub allow_actor_header: Option<bool>,
+}
+
+impl AuthenticationConfig {
+    /// Returns if requests without a bearer token may specify the actor in the header.
+    #[must_use]
+    pub fn allows_actor_header(&self) -> bool {
+        self.allow_actor_header
+            .unwrap_or(self.api_keys.is_empty() && self.jwt_secrets.is_empty())
+    }
+
+    /// Checks that the config can be used to authenticate requests.
+    ///
+    /// # Errors
+    ///
+    /// - if JWT secrets are configured without the expected issuer or audience
+    pub fn validate(&self) -> Result<(), Report<InvalidAuthenticationConfig>> {
+        if !self.jwt_secrets.is_empty()
+            && (self.jwt_issuer.is_none() || self.jwt_audience.is_none())
+        {
+            return Err(Report::new(InvalidAuthenticationConfig)
+                .attach_printable("`jwtIssuer` and `jwtAudience` are required to verify JWTs"));
+        }
+        Ok(())
+    }
+
+    /// Authenticates a bearer token.
+    ///
+    /// Tokens containing a `.` are verified as JWTs, all other tokens are looked up as API keys.
+    ///
+    /// # Errors
+    ///
+    /// - [`UnknownApiKey`] if the token is not a configured API key
+    /// - [`MalformedToken`] if the token is not a valid JWT
+    /// - [`UnsupportedAlgorithm`] if the JWT is not signed with `HS256`
+    /// - [`InvalidSignature`] if the JWT is not signed with any of the configured secrets
+    /// - [`Expired`] or [`NotYetValid`] if the JWT is not valid at `now`
+    /// - [`InvalidIssuer`] or [`InvalidAudience`] if the JWT was not issued for the graph
+    ///
+    /// [`UnknownApiKey`]: AuthenticationError::UnknownApiKey
+    /// [`MalformedToken`]: AuthenticationError::MalformedToken
+    /// [`UnsupportedAlgorithm`]: AuthenticationError::UnsupportedAlgorithm
+    /// [`InvalidSignature`]: AuthenticationError::InvalidSignature
+    /// [`Expired`]: AuthenticationError::Expired
+    /// [`NotYetValid`]: AuthenticationError::NotYetValid
+    /// [`InvalidIssuer`]: AuthenticationError::InvalidIssuer
+    /// [`InvalidAudience`]: AuthenticationError::InvalidAudience
+    pub fn authenticate(
+        &self,
+        token: &str,
+        now: OffsetDateTime,
+    ) -> Result<AuthenticatedActor, Report<AuthenticationError>> {
+        if token.contains('.') {
+            self.verify_jwt(token, now.unix_timestamp())
+        } else {
+            self.verify_api_key(token)
+        }
+    }
+
+    fn verify_api_key(
+        &self,
+        token: &str,
+    ) -> Result<AuthenticatedActor, Report<AuthenticationError>> {
+        // Comparing digests avoids leaking the key through the timing of the comparison.
+        let digest = Sha256::digest(token);
+        self.api_keys
+            .iter()
+            .find(|api_key| Sha256::digest(&api_key.key) == digest)
+            .map(|api_key| AuthenticatedActor {
+                actor_id: api_key.account_id,
+                scopes: api_key.scopes.clone(),
+            })
+            .ok_or_else(|| Report::new(AuthenticationError::UnknownApiKey))
+    }
+
+    fn verify_jwt(
+        &self,
+        token: &str,
+        now: i64,
+    ) -> Result<AuthenticatedActor, Report<AuthenticationError>> {
+        let (signing_input, signature) = token
+            .rsplit_once('.')
+            .ok_or(AuthenticationError::MalformedToken)?;
+        let (header, claims) = signing_input
+            .split_once('.')
+            .ok_or(AuthenticationError::MalformedToken)?;
+
+        let header: JwtHeader = decode_segment(header)?;
+        if header.alg != "HS256" {
+            return Err(
+                Report::new(AuthenticationError::UnsupportedAlgorithm).attach_printable(header.alg)
+            );
+        }
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .change_context(AuthenticationError::MalformedToken)?;
+        let signed_by_known_secret = self.jwt_secrets.iter().any(|secret| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                .expect("HMAC should accept keys of any size");
+            mac.update(signing_input.as_bytes());
+            mac.verify_slice(&signature).is_ok()
+        });
+        if !signed_by_known_secret {
+            return Err(Report::new(AuthenticationError::InvalidSignature));
+        }
+
+        let claims: JwtClaims = decode_segment(claims)?;
+        if claims.exp <= now {
+            return Err(Report::new(AuthenticationError::Expired));
+        }
+        if claims.nbf.is_some_and(|nbf| nbf > now) {
+            return Err(Report::new(AuthenticationError::NotYetValid));
+        }
+        // Unconfigured values are rejected by `validate`, a missing value never matches.
+        if self.jwt_issuer.is_none() || claims.iss != self.jwt_issuer {
+            return Err(Report::new(AuthenticationError::InvalidIssuer));
+        }
+        if !self.jwt_audience.as_deref().is_some_and(|audience| {
+            claims
+                .aud
+                .as_ref()
+                .is_some_and(|aud| aud.contains(audience))
+        }) {
+            return Err(Report::new(AuthenticationError::InvalidAudience));
+        }
+
+        Ok(AuthenticatedActor {
+            actor_id: claims.sub,
+            scopes: claims
+                .scope
+                .split_whitespace()
+                .filter_map(Scope::parse)
+                .collect(),
+        })
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<Option<&str>, Report<AuthenticationError>> {
+    let Some(value) = headers.get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(Some)
+        .ok_or_else(|| Report::new(AuthenticationError::MalformedToken))
+}
+
+fn unauthorized(message: String) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        message,
+    )
+        .into_response()
+}
+
+/// Middleware which authenticates requests according to the [`AuthenticationConfig`].
+///
+/// Requests with a valid bearer token have the [`AuthenticatedActor`] added to their extensions.
+/// Requests with an invalid token are rejected, as are requests without a token if the actor
+/// header is not allowed.
+///
+/// The scopes of the actor are checked against the [`Scope`] of the route when the actor is
+/// extracted by the handler, as the route is only known after the request was routed.
+pub async fn authenticate(
+    State(config): State<Arc<AuthenticationConfig>>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let token = match bearer_token(request.headers()) {
+        Ok(token) => token,
+        Err(report) => return unauthorized(report.current_context().to_string()),
+    };
+
+    if let Some(token) = token {
+        let actor = match config.authenticate(token, OffsetDateTime::now_utc()) {
+            Ok(actor) => actor,
+            Err(report) => {
+                tracing::debug!(?report, "Rejected bearer token");
+                return unauthorized(report.current_context().to_string());
+            }
+        };
+        request.extensions_mut().insert(actor);
+    } else if !config.allows_actor_header() {
+        return unauthorized("A bearer token is required".to_owned());
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{routing::post, Router};
+    use serde_json::json;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::rest::AuthenticatedUserHeader;
+
+    const SECRET: &str = "secret";
+
+    fn sign_jwt(secret: &str, claims: &serde_json::Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+        let claims = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC should accept keys of any size");
+        mac.update(format!("{header}.{claims}").as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{header}.{claims}.{signature}")
+    }
+
+    fn config() -> AuthenticationConfig {
+        AuthenticationConfig {
+            api_keys: vec![ApiKey {
+                key: "api-key".to_owned(),
+                account_id: AccountId::new(Uuid::nil()),
+                scopes: vec![Scope::Read],
+            }],
+            jwt_secrets: vec!["old-secret".to_owned(), SECRET.to_owned()],
+            jwt_issuer: Some("https://auth.example.com".to_owned()),
+            jwt_audience: Some("graph".to_owned()),
+            allow_actor_header: None,
+        }
+    }
+
+    fn claims(actor_id: Uuid, exp: i64) -> serde_json::Value {
+        json!({
+            "sub": actor_id,
+            "exp": exp,
+            "iss": "https://auth.example.com",
+            "aud": ["graph", "other"],
+        })
+    }
+
+    fn now() -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(1_000).expect("timestamp should be valid")
+    }
+
+    #[test]
+    fn api_key() {
+        let config = config();
+        assert_eq!(
+            config
+                .authenticate("api-key", now())
+                .expect("API key should be accepted"),
+            AuthenticatedActor {
+                actor_id: AccountId::new(Uuid::nil()),
+                scopes: vec![Scope::Read],
+            }
+        );
+
+        let report = config
+            .authenticate("unknown-key", now())
+            .expect_err("unknown API key should be rejected");
+        assert_eq!(
+            *report.current_context(),
+            AuthenticationError::UnknownApiKey
+        );
+    }
+
+    #[test]
+    fn jwt() {
+        let actor_id = Uuid::new_v4();
+        let mut claims = claims(actor_id, 2_000);
+        claims["scope"] = json!("read write openid");
+        let token = sign_jwt(SECRET, &claims);
+
+        assert_eq!(
+            config()
+                .authenticate(&token, now())
+                .expect("JWT should be accepted"),
+            AuthenticatedActor {
+                actor_id: AccountId::new(actor_id),
+                scopes: vec![Scope::Read, Scope::Write],
+            }
+        );
+    }
+
+    #[test]
+    fn reject_jwt() {
+        let config = config();
+        let valid = claims(Uuid::new_v4(), 2_000);
+
+        let report = config
+            .authenticate(&sign_jwt("wrong-secret", &valid), now())
+            .expect_err("JWT with unknown secret should be rejected");
+        assert_eq!(
+            *report.current_context(),
+            AuthenticationError::InvalidSignature
+        );
+
+        let report = config
+            .authenticate(&sign_jwt(SECRET, &claims(Uuid::new_v4(), 1_000)), now())
+            .expect_err("expired JWT should be rejected");
+        assert_eq!(*report.current_context(), AuthenticationError::Expired);
+
+        let mut not_yet_valid = valid.clone();
+        not_yet_valid["nbf"] = json!(1_500);
+        let report = config
+            .authenticate(&sign_jwt(SECRET, &not_yet_valid), now())
+            .expect_err("JWT which is not yet valid should be rejected");
+        assert_eq!(*report.current_context(), AuthenticationError::NotYetValid);
+
+        let mut other_issuer = valid.clone();
+        other_issuer["iss"] = json!("https://evil.example.com");
+        let report = config
+            .authenticate(&sign_jwt(SECRET, &other_issuer), now())
+            .expect_err("JWT of another issuer should be rejected");
+        assert_eq!(
+            *report.current_context(),
+            AuthenticationError::InvalidIssuer
+        );
+
+        let mut other_audience = valid.clone();
+        other_audience["aud"] = json!("other");
+        let report = config
+            .authenticate(&sign_jwt(SECRET, &other_audience), now())
+            .expect_err("JWT for another audience should be rejected");
+        assert_eq!(
+            *report.current_context(),
+            AuthenticationError::InvalidAudience
+        );
+
+        let token = sign_jwt(SECRET, &valid);
+        let unsigned = format!(
+            "{}.{}.",
+            URL_SAFE_NO_PAD.encode(br#"{"alg":"none"}"#),
+            token.split('.').nth(1).expect("JWT should have claims")
+        );
+        let report = config
+            .authenticate(&unsigned, now())
+            .expect_err("unsigned JWT should be rejected");
+        assert_eq!(
+            *report.current_context(),
+            AuthenticationError::UnsupportedAlgorithm
+        );
+    }
+
+    #[test]
+    fn actor_header_default() {
+        assert!(AuthenticationConfig::default().allows_actor_header());
+        assert!(!config().allows_actor_header());
+        assert!(
+            AuthenticationConfig {
+                allow_actor_header: Some(true),
+                ..config()
+            }
+            .allows_actor_header()
+        );
+    }
+
+    #[test]
+    fn validate_config() {
+        config().validate().expect("config should be valid");
+        _ = AuthenticationConfig {
+            jwt_audience: None,
+            ..config()
+        }
+        .validate()
+        .expect_err("JWT secrets without an audience should be rejected");
+    }
+
+    /// Routes the request through the authentication middleware to a handler extracting the
+    /// actor.
+    async fn request(config: AuthenticationConfig, request: Request<Body>) -> StatusCode {
+        async fn handler(AuthenticatedUserHeader(_): AuthenticatedUserHeader) -> StatusCode {
+            StatusCode::OK
+        }
+
+        Router::new()
+            .route("/write", post(handler))
+            .route("/read", read_only(post(handler)))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(config),
+                authenticate,
+            ))
+            .oneshot(request)
+            .await
+            .expect("router should be infallible")
+            .status()
+    }
+
+    fn post_request(path: &str, token: Option<&str>) -> Request<Body> {
+        let mut request =
+            Request::post(path).header("X-Authenticated-User-Actor-Id", Uuid::new_v4().to_string());
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        request
+            .body(Body::empty())
+            .expect("request should be valid")
+    }
+
+    #[tokio::test]
+    async fn reject_actor_header() {
+        assert_eq!(
+            request(config(), post_request("/read", None)).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            request(AuthenticationConfig::default(), post_request("/read", None)).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn require_route_scope() {
+        // The API key is only granted the read scope
+        assert_eq!(
+            request(config(), post_request("/read", Some("api-key"))).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            request(config(), post_request("/write", Some("api-key"))).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_expired_jwt() {
+        // The middleware verifies tokens at the current time
+        let token = sign_jwt(SECRET, &claims(Uuid::new_v4(), 1_000));
+        assert_eq!(
+            request(config(), post_request("/read", Some(&token))).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[test]
+    fn default_scope() {
+        assert_eq!(Scope::default_for(&Method::GET), Scope::Read);
+        assert_eq!(Scope::default_for(&Method::POST), Scope::Write);
+        assert_eq!(Scope::default_for(&Method::PUT), Scope::Write);
+    }
+}
@@ -0,0 +1,148 @@
+//! Web routes for reading the change feed of graph mutations.
+
+use alloc::sync::Arc;
+use core::time::Duration;
+
+use authorization::AuthorizationApiPool;
+use axum::{response::Response, routing::post, Extension, Router};
+use graph::store::{
+    change_feed::{
+        ChangeEvent, ChangeFeedStore, ChangeKind, ChangedRecord, ReadChangesParams,
+        ReadChangesResponse,
+    },
+    StorePool,
+};
+use serde::Deserialize;
+use temporal_client::TemporalClient;
+use tokio::time::{sleep, Instant};
+use utoipa::{OpenApi, ToSchema};
+
+use super::api_resource::RoutedResource;
+use crate::rest::{
+    authentication::read_only, json::Json, status::report_to_response, AuthenticatedUserHeader,
+};
+
+/// The upper bound for the time a request waits for new changes.
+const MAX_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The interval in which the change feed is read again while waiting for new changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        read_changes,
+    ),
+    components(
+        schemas(
+            ReadChangesRequest,
+            ReadChangesResponse,
+            ChangeEvent,
+            ChangeKind,
+            ChangedRecord,
+        ),
+    ),
+    tags(
+        (name = "Change Feed", description = "Change feed API")
+    )
+)]
+pub(crate) struct ChangeFeedResource;
+
+impl RoutedResource for ChangeFeedResource {
+    /// Create routes for reading the change feed.
+    fn routes<S, A>() -> Router
+    where
+        S: StorePool + Send + Sync + 'static,
+        A: AuthorizationApiPool + Send + Sync + 'static,
+    {
+        Router::new().route("/changes", read_only(post(read_changes::<S, A>)))
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ReadChangesRequest {
+    /// Only changes after this sequence ID are returned.
+    #[serde(default)]
+    #[schema(nullable = false)]
+    after: Option<i64>,
+    /// The maximum number of changes which are returned, defaults to 1000.
+    #[serde(default)]
+    #[schema(nullable = false)]
+    limit: Option<usize>,
+    /// The number of milliseconds to wait for new changes if there are none, at most 30 seconds.
+    ///
+    /// If omitted, the request returns immediately.
+    #[serde(default)]
+    #[schema(nullable = false)]
+    timeout_ms: Option<u64>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/changes",
+    tag = "Change Feed",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The changes after the cursor and the cursor to continue reading from", body = ReadChangesResponse),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = ReadChangesRequest,
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client)
+)]
+async fn read_changes<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    Json(request): Json<ReadChangesRequest>,
+) -> Result<Json<ReadChangesResponse>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let deadline = Instant::now()
+        + request
+            .timeout_ms
+            .map_or(Duration::ZERO, Duration::from_millis)
+            .min(MAX_POLL_TIMEOUT);
+    let mut after = request.after;
+
+    loop {
+        // The connections are released between polls so waiting requests don't exhaust the pools.
+        let authorization_api = authorization_api_pool
+            .acquire()
+            .await
+            .map_err(report_to_response)?;
+        let store = store_pool
+            .acquire(authorization_api, temporal_client.0.clone())
+            .await
+            .map_err(report_to_response)?;
+
+        let response = store
+            .read_changes(
+                actor_id,
+                ReadChangesParams {
+                    after,
+                    limit: request.limit,
+                },
+            )
+            .await
+            .map_err(report_to_response)?;
+        drop(store);
+
+        if !response.changes.is_empty() || Instant::now() + POLL_INTERVAL > deadline {
+            return Ok(Json(response));
+        }
+
+        after = response.cursor;
+        sleep(POLL_INTERVAL).await;
+    }
+}
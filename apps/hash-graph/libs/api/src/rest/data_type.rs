@@ -12,8 +12,8 @@ use authorization::{
 };
 use axum::{
     extract::Path,
-    http::StatusCode,
-    response::Response,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post, put},
     Extension, Router,
 };
@@ -26,9 +26,10 @@ use graph::{
     store::{
         error::VersionedUrlAlreadyExists,
         ontology::{
-            ArchiveDataTypeParams, CreateDataTypeParams, GetDataTypeSubgraphParams,
-            GetDataTypesParams, GetDataTypesResponse, UnarchiveDataTypeParams,
-            UpdateDataTypeEmbeddingParams, UpdateDataTypesParams,
+            ArchiveDataTypeParams, CheckDataTypeUpdateParams, CheckDataTypeUpdateResponse,
+            CreateDataTypeParams, GetDataTypeSubgraphParams, GetDataTypesParams,
+            GetDataTypesResponse, UnarchiveDataTypeParams, UpdateDataTypeEmbeddingParams,
+            UpdateDataTypesParams,
         },
         BaseUrlAlreadyExists, ConflictBehavior, DataTypeStore, OntologyVersionDoesNotExist,
         StorePool,
@@ -49,14 +50,16 @@ use serde::{Deserialize, Serialize};
 use temporal_client::TemporalClient;
 use time::OffsetDateTime;
 use type_system::{
-    schema::DataType,
+    schema::{DataType, DataTypeIncompatibility},
     url::{OntologyTypeVersion, VersionedUrl},
 };
 use utoipa::{OpenApi, ToSchema};
 
 use super::api_resource::RoutedResource;
 use crate::rest::{
+    authentication::read_only,
     json::Json,
+    rdf,
     status::{report_to_response, status_to_response},
     utoipa_typedef::{subgraph::Subgraph, ListOrValue, MaybeListOfDataType},
     AuthenticatedUserHeader, PermissionResponse, RestApiStore,
@@ -74,6 +77,7 @@ use crate::rest::{
         get_data_types,
         get_data_type_subgraph,
         update_data_type,
+        check_data_type_update,
         update_data_type_embeddings,
         archive_data_type,
         unarchive_data_type,
@@ -91,6 +95,9 @@ use crate::rest::{
             CreateDataTypeRequest,
             LoadExternalDataTypeRequest,
             UpdateDataTypeRequest,
+            CheckDataTypeUpdateRequest,
+            CheckDataTypeUpdateResponse,
+            DataTypeIncompatibility,
             UpdateDataTypeEmbeddingParams,
             DataTypeQueryToken,
             GetDataTypesParams,
@@ -144,10 +151,14 @@ impl RoutedResource for DataTypeResource {
                 .nest(
                     "/query",
                     Router::new()
-                        .route("/", post(get_data_types::<S, A>))
-                        .route("/subgraph", post(get_data_type_subgraph::<S, A>)),
+                        .route("/", read_only(post(get_data_types::<S, A>)))
+                        .route("/subgraph", read_only(post(get_data_type_subgraph::<S, A>))),
                 )
                 .route("/load", post(load_external_data_type::<S, A>))
+                .route(
+                    "/check-update",
+                    read_only(post(check_data_type_update::<S, A>)),
+                )
                 .route("/archive", put(archive_data_type::<S, A>))
                 .route("/unarchive", put(unarchive_data_type::<S, A>))
                 .route("/embeddings", post(update_data_type_embeddings::<S, A>)),
@@ -456,8 +467,15 @@ struct GetDataTypeSubgraphResponse {
     responses(
         (
             status = 200,
-            content_type = "application/json",
-            body = GetDataTypeSubgraphResponse,
+            content(
+                ("application/json" = GetDataTypeSubgraphResponse),
+                ("application/ld+json" = String),
+                ("application/n-triples" = String),
+                ("text/turtle" = String),
+            ),
+            headers(
+                ("X-Hash-Cursor" = String, description = "The JSON encoded cursor to read the next page, only returned for linked data"),
+            ),
             description = "Gets a subgraph rooted at all data types that satisfy the given query, each resolved to the requested depth.",
         ),
 
@@ -467,19 +485,22 @@ struct GetDataTypeSubgraphResponse {
 )]
 #[tracing::instrument(
     level = "info",
-    skip(store_pool, authorization_api_pool, temporal_client, request)
+    skip(store_pool, authorization_api_pool, temporal_client, headers, request)
 )]
 async fn get_data_type_subgraph<S, A>(
     AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
     store_pool: Extension<Arc<S>>,
     authorization_api_pool: Extension<Arc<A>>,
     temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    headers: HeaderMap,
     Json(request): Json<serde_json::Value>,
-) -> Result<Json<GetDataTypeSubgraphResponse>, Response>
+) -> Result<Response, Response>
 where
     S: StorePool + Send + Sync,
     A: AuthorizationApiPool + Send + Sync,
 {
+    let rdf_format = rdf::requested_format(&headers);
+
     let authorization_api = authorization_api_pool
         .acquire()
         .await
@@ -502,11 +523,20 @@ where
         .get_data_type_subgraph(actor_id, request)
         .await
         .map_err(report_to_response)
-        .map(|response| {
-            Json(GetDataTypeSubgraphResponse {
+        .and_then(|response| {
+            if let Some(format) = rdf_format {
+                return rdf::subgraph_response(
+                    &response.subgraph,
+                    format,
+                    response.cursor.as_ref(),
+                    None,
+                );
+            }
+            Ok(Json(GetDataTypeSubgraphResponse {
                 subgraph: Subgraph::from(response.subgraph),
                 cursor: response.cursor,
             })
+            .into_response())
         })
 }
 
@@ -664,6 +694,73 @@ where
         .map_err(report_to_response)
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CheckDataTypeUpdateRequest {
+    #[schema(value_type = VAR_UPDATE_DATA_TYPE)]
+    schema: serde_json::Value,
+    type_to_update: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/data-types/check-update",
+    tag = "DataType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The incompatible changes of the update and the number of entities which would become invalid", body = CheckDataTypeUpdateResponse),
+        (status = 400, content_type = "application/json", description = "Provided request body is invalid", body = Status),
+
+        (status = 403, description = "Insufficient permissions to view the data type"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = CheckDataTypeUpdateRequest,
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client)
+)]
+async fn check_data_type_update<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    body: Json<CheckDataTypeUpdateRequest>,
+) -> Result<Json<CheckDataTypeUpdateResponse>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(CheckDataTypeUpdateRequest {
+        schema,
+        mut type_to_update,
+    }) = body;
+
+    type_to_update.version = OntologyTypeVersion::new(type_to_update.version.inner() + 1);
+
+    let schema = patch_id_and_parse(&type_to_update, schema)
+        .attach(hash_status::StatusCode::InvalidArgument)
+        .map_err(report_to_response)?;
+
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .check_data_type_update(actor_id, CheckDataTypeUpdateParams { schema })
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
+
 #[utoipa::path(
     put,
     path = "/data-types/archive",
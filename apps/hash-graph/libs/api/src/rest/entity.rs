@@ -1,6 +1,7 @@
 //! Web routes for CRU operations on entities.
 
 use alloc::sync::Arc;
+use std::io;
 
 use authorization::{
     backend::{ModifyRelationshipOperation, PermissionAssertion},
@@ -13,21 +14,30 @@ use authorization::{
     AuthorizationApi, AuthorizationApiPool,
 };
 use axum::{
-    extract::Path,
-    http::StatusCode,
-    response::Response,
+    body::Body,
+    extract::{Path, Query},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Extension, Router,
 };
 use error_stack::{Report, ResultExt};
+use futures::TryStreamExt;
 use graph::{
-    knowledge::{EntityQueryPath, EntityQuerySortingToken, EntityQueryToken},
+    knowledge::{
+        import::{
+            import_entities, EntityImportError, EntityImportMapping, ImportFormat, ImportReport,
+            ImportRowDecoder, ImportRowError,
+        },
+        EntityQueryPath, EntityQuerySortingToken, EntityQueryToken,
+    },
     store::{
-        error::{EntityDoesNotExist, RaceConditionOnUpdate},
+        error::{EntityDoesNotExist, EntityEditionConflict, RaceConditionOnUpdate},
         knowledge::{
             CountEntitiesParams, CreateEntityRequest, DiffEntityParams, DiffEntityResult,
-            GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams, PatchEntityParams,
-            UpdateEntityEmbeddingsParams, ValidateEntityParams,
+            EntityMigrationFailure, GetEntitiesParams, GetEntitiesResponse,
+            GetEntitySubgraphParams, MigrateEntitiesParams, MigrateEntitiesResponse,
+            PatchEntityParams, PropertyMapping, UpdateEntityEmbeddingsParams, ValidateEntityParams,
         },
         query::Filter,
         AccountStore, EntityQueryCursor, EntityQuerySorting, EntityQuerySortingRecord, EntityStore,
@@ -44,22 +54,24 @@ use graph_types::{
             SourceProvenance, SourceType,
         },
         link::LinkData,
-        ArrayMetadata, Confidence, EntityTypeIdDiff, ObjectMetadata, Property, PropertyDiff,
-        PropertyMetadata, PropertyMetadataObject, PropertyObject, PropertyPatchOperation,
-        PropertyPath, PropertyPathElement, PropertyProvenance, PropertyWithMetadata,
-        PropertyWithMetadataObject, ValueMetadata,
+        ArrayMetadata, Confidence, EntityTypeIdDiff, ObjectMetadata, PatchTestFailed, Property,
+        PropertyDiff, PropertyMetadata, PropertyMetadataObject, PropertyObject,
+        PropertyPatchOperation, PropertyPath, PropertyPathElement, PropertyProvenance,
+        PropertyWithMetadata, PropertyWithMetadataObject, ValueMetadata,
     },
     owned_by_id::OwnedById,
     Embedding,
 };
 use serde::{Deserialize, Serialize};
 use temporal_client::TemporalClient;
-use utoipa::{OpenApi, ToSchema};
+use tokio_util::{codec::FramedRead, io::StreamReader};
+use utoipa::{IntoParams, OpenApi, ToSchema};
 use validation::ValidateEntityComponents;
 
 use crate::rest::{
-    api_resource::RoutedResource, json::Json, status::report_to_response,
-    utoipa_typedef::subgraph::Subgraph, AuthenticatedUserHeader, PermissionResponse,
+    api_resource::RoutedResource, authentication::read_only, json::Json, rdf,
+    status::report_to_response, utoipa_typedef::subgraph::Subgraph, AuthenticatedUserHeader,
+    PermissionResponse,
 };
 
 #[derive(OpenApi)]
@@ -67,6 +79,7 @@ use crate::rest::{
     paths(
         create_entity,
         create_entities,
+        import_entities_from_rows,
         validate_entity,
         check_entity_permission,
         get_entities,
@@ -75,6 +88,7 @@ use crate::rest::{
         patch_entity,
         update_entity_embeddings,
         diff_entity,
+        migrate_entities,
 
         get_entity_authorization_relationships,
         modify_entity_authorization_relationships,
@@ -157,6 +171,16 @@ use crate::rest::{
             PropertyPath,
             PropertyPathElement,
             Confidence,
+
+            MigrateEntitiesParams,
+            MigrateEntitiesResponse,
+            PropertyMapping,
+            EntityMigrationFailure,
+
+            EntityImportMapping,
+            ImportFormat,
+            ImportReport,
+            ImportRowError,
         )
     ),
     tags(
@@ -178,12 +202,14 @@ impl RoutedResource for EntityResource {
             Router::new()
                 .route("/", post(create_entity::<S, A>).patch(patch_entity::<S, A>))
                 .route("/bulk", post(create_entities::<S, A>))
+                .route("/import", post(import_entities_from_rows::<S, A>))
                 .route(
                     "/relationships",
                     post(modify_entity_authorization_relationships::<A>),
                 )
-                .route("/diff", post(diff_entity::<S, A>))
-                .route("/validate", post(validate_entity::<S, A>))
+                .route("/diff", read_only(post(diff_entity::<S, A>)))
+                .route("/migrate", post(migrate_entities::<S, A>))
+                .route("/validate", read_only(post(validate_entity::<S, A>)))
                 .route("/embeddings", post(update_entity_embeddings::<S, A>))
                 .nest(
                     "/:entity_id",
@@ -209,9 +235,9 @@ impl RoutedResource for EntityResource {
                 .nest(
                     "/query",
                     Router::new()
-                        .route("/", post(get_entities::<S, A>))
-                        .route("/subgraph", post(get_entity_subgraph::<S, A>))
-                        .route("/count", post(count_entities::<S, A>)),
+                        .route("/", read_only(post(get_entities::<S, A>)))
+                        .route("/subgraph", read_only(post(get_entity_subgraph::<S, A>)))
+                        .route("/count", read_only(post(count_entities::<S, A>))),
                 ),
         )
     }
@@ -317,6 +343,109 @@ where
         .map(Json)
 }
 
+const fn default_import_batch_size() -> usize {
+    1000
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+struct ImportEntitiesQuery {
+    /// The JSON encoded `EntityImportMapping` describing how rows are turned into entities.
+    mapping: String,
+    /// The number of entities which are inserted at once.
+    #[serde(default = "default_import_batch_size")]
+    batch_size: usize,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entities/import",
+    request_body(content = String, description = "The rows to import", content_type = "text/csv"),
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+        ImportEntitiesQuery,
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The created entities and the rows which could not be imported", body = ImportReport),
+        (status = 400, content_type = "application/json", description = "The mapping or the format of the rows is invalid"),
+
+        (status = 404, description = "A type of the mapping was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client, headers, body)
+)]
+async fn import_entities_from_rows<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    Query(query): Query<ImportEntitiesQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Json<ImportReport>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let mapping = serde_json::from_str::<EntityImportMapping>(&query.mapping)
+        .attach(hash_status::StatusCode::InvalidArgument)
+        .map_err(report_to_response)?;
+
+    let media_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split(';').next())
+        .unwrap_or_default()
+        .trim();
+    let format = ImportFormat::from_media_type(media_type).ok_or_else(|| {
+        report_to_response(
+            Report::new(EntityImportError::Read)
+                .attach_printable(format!("unsupported content type `{media_type}`"))
+                .attach(hash_status::StatusCode::InvalidArgument),
+        )
+    })?;
+
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let mut store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    let rows = FramedRead::new(
+        StreamReader::new(body.into_data_stream().map_err(io::Error::other)),
+        ImportRowDecoder::new(format),
+    );
+
+    import_entities(
+        &mut store,
+        actor_id,
+        &mapping,
+        rows,
+        query.batch_size.max(1),
+    )
+    .await
+    .map_err(|report| {
+        let status_code = match report.current_context() {
+            EntityImportError::MissingType => hash_status::StatusCode::NotFound,
+            EntityImportError::UnmappedProperty | EntityImportError::Read => {
+                hash_status::StatusCode::InvalidArgument
+            }
+            EntityImportError::Query => hash_status::StatusCode::Internal,
+        };
+        report_to_response(report.attach(status_code))
+    })
+    .map(Json)
+}
+
 #[utoipa::path(
     post,
     path = "/entities/validate",
@@ -610,8 +739,16 @@ struct GetEntitySubgraphResponse<'r> {
     responses(
         (
             status = 200,
-            content_type = "application/json",
-            body = GetEntitySubgraphResponse,
+            content(
+                ("application/json" = GetEntitySubgraphResponse),
+                ("application/ld+json" = String),
+                ("application/n-triples" = String),
+                ("text/turtle" = String),
+            ),
+            headers(
+                ("X-Hash-Cursor" = String, description = "The JSON encoded cursor to read the next page, only returned for linked data"),
+                ("X-Hash-Count" = usize, description = "The number of entities matching the query, only returned for linked data"),
+            ),
             description = "A subgraph rooted at entities that satisfy the given query, each resolved to the requested depth.",
         ),
         (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
@@ -620,19 +757,22 @@ struct GetEntitySubgraphResponse<'r> {
 )]
 #[tracing::instrument(
     level = "info",
-    skip(store_pool, authorization_api_pool, temporal_client, request)
+    skip(store_pool, authorization_api_pool, temporal_client, headers, request)
 )]
 async fn get_entity_subgraph<S, A>(
     AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
     store_pool: Extension<Arc<S>>,
     authorization_api_pool: Extension<Arc<A>>,
     temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    headers: HeaderMap,
     Json(request): Json<serde_json::Value>,
-) -> Result<Json<GetEntitySubgraphResponse<'static>>, Response>
+) -> Result<Response, Response>
 where
     S: StorePool + Send + Sync,
     A: AuthorizationApiPool + Send + Sync,
 {
+    let rdf_format = rdf::requested_format(&headers);
+
     let authorization_api = authorization_api_pool
         .acquire()
         .await
@@ -669,14 +809,23 @@ where
             },
         )
         .await
-        .map(|response| {
-            Json(GetEntitySubgraphResponse {
+        .map_err(report_to_response)
+        .and_then(|response| {
+            if let Some(format) = rdf_format {
+                return rdf::subgraph_response(
+                    &response.subgraph,
+                    format,
+                    response.cursor.as_ref(),
+                    response.count,
+                );
+            }
+            Ok(Json(GetEntitySubgraphResponse {
                 subgraph: response.subgraph.into(),
                 cursor: response.cursor.map(EntityQueryCursor::into_owned),
                 count: response.count,
             })
+            .into_response())
         })
-        .map_err(report_to_response)
 }
 
 #[utoipa::path(
@@ -747,6 +896,7 @@ where
         (status = 200, content_type = "application/json", description = "The updated entity", body = Entity),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
         (status = 423, content_type = "text/plain", description = "The entity that should be updated was unexpectedly updated at the same time"),
+        (status = 409, content_type = "text/plain", description = "The entity is not at the expected edition or a `test` operation of the patch did not match the current value of the entity"),
 
         (status = 404, description = "Entity ID or Entity Type URL was not found"),
         (status = 500, description = "Store error occurred"),
@@ -781,19 +931,24 @@ where
     store
         .patch_entity(actor_id, params)
         .await
-        .map_err(|report| {
-            if report.contains::<EntityDoesNotExist>() {
-                report.attach(hash_status::StatusCode::NotFound)
-            } else if report.contains::<RaceConditionOnUpdate>() {
-                report.attach(hash_status::StatusCode::Cancelled)
-            } else {
-                report
-            }
-        })
+        .map_err(patch_entity_status)
         .map_err(report_to_response)
         .map(Json)
 }
 
+/// Attaches the status code matching the reason why patching an entity failed.
+fn patch_entity_status<C>(report: Report<C>) -> Report<C> {
+    if report.contains::<EntityDoesNotExist>() {
+        report.attach(hash_status::StatusCode::NotFound)
+    } else if report.contains::<RaceConditionOnUpdate>() {
+        report.attach(hash_status::StatusCode::Cancelled)
+    } else if report.contains::<PatchTestFailed>() || report.contains::<EntityEditionConflict>() {
+        report.attach(hash_status::StatusCode::Aborted)
+    } else {
+        report
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/entities/embeddings",
@@ -901,6 +1056,53 @@ where
         .map(Json)
 }
 
+#[utoipa::path(
+    post,
+    path = "/entities/migrate",
+    tag = "Entity",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The number of migrated entities and the entities which could not be migrated", body = MigrateEntitiesResponse),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = MigrateEntitiesParams,
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client)
+)]
+async fn migrate_entities<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    Json(params): Json<MigrateEntitiesParams>,
+) -> Result<Json<MigrateEntitiesResponse>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let mut store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .migrate_entities(actor_id, params)
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
+
 #[utoipa::path(
     get,
     path = "/entities/{entity_id}/relationships",
@@ -1398,3 +1600,23 @@ where
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[cfg(test)]
+mod tests {
+    use graph::store::UpdateError;
+    use graph_types::knowledge::PatchError;
+
+    use super::*;
+
+    #[test]
+    fn failed_patch_test_is_a_conflict() {
+        let report = Report::new(PatchTestFailed)
+            .change_context(PatchError)
+            .change_context(UpdateError);
+
+        assert_eq!(
+            report_to_response(patch_entity_status(report)).status(),
+            StatusCode::CONFLICT
+        );
+    }
+}
@@ -15,8 +15,8 @@ use authorization::{
 };
 use axum::{
     extract::Path,
-    http::StatusCode,
-    response::Response,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post, put},
     Extension, Router,
 };
@@ -29,9 +29,10 @@ use graph::{
     store::{
         error::{BaseUrlAlreadyExists, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
         ontology::{
-            ArchiveEntityTypeParams, CreateEntityTypeParams, GetEntityTypeSubgraphParams,
-            GetEntityTypesParams, GetEntityTypesResponse, UnarchiveEntityTypeParams,
-            UpdateEntityTypeEmbeddingParams, UpdateEntityTypesParams,
+            ArchiveEntityTypeParams, CheckEntityTypeUpdateParams, CheckEntityTypeUpdateResponse,
+            CreateEntityTypeParams, GetEntityTypeSubgraphParams, GetEntityTypesParams,
+            GetEntityTypesResponse, UnarchiveEntityTypeParams, UpdateEntityTypeEmbeddingParams,
+            UpdateEntityTypesParams,
         },
         ConflictBehavior, EntityTypeStore, StorePool,
     },
@@ -50,7 +51,7 @@ use serde::{Deserialize, Serialize};
 use temporal_client::TemporalClient;
 use time::OffsetDateTime;
 use type_system::{
-    schema::EntityType,
+    schema::{EntityType, EntityTypeIncompatibility},
     url::{BaseUrl, OntologyTypeVersion, VersionedUrl},
 };
 use utoipa::{OpenApi, ToSchema};
@@ -59,7 +60,9 @@ use crate::{
     error::{ErrorInfo, Status, StatusPayloads},
     rest::{
         api_resource::RoutedResource,
+        authentication::read_only,
         json::Json,
+        rdf,
         status::{report_to_response, status_to_response},
         utoipa_typedef::{subgraph::Subgraph, ListOrValue, MaybeListOfEntityType},
         AuthenticatedUserHeader, PermissionResponse, RestApiStore,
@@ -78,6 +81,7 @@ use crate::{
         get_entity_types,
         get_entity_type_subgraph,
         update_entity_type,
+        check_entity_type_update,
         update_entity_type_embeddings,
         archive_entity_type,
         unarchive_entity_type,
@@ -100,6 +104,9 @@ use crate::{
             CreateEntityTypeRequest,
             LoadExternalEntityTypeRequest,
             UpdateEntityTypeRequest,
+            CheckEntityTypeUpdateRequest,
+            CheckEntityTypeUpdateResponse,
+            EntityTypeIncompatibility,
             UpdateEntityTypeEmbeddingParams,
             EntityTypeQueryToken,
             GetEntityTypesParams,
@@ -151,10 +158,17 @@ impl RoutedResource for EntityTypeResource {
                 .nest(
                     "/query",
                     Router::new()
-                        .route("/", post(get_entity_types::<S, A>))
-                        .route("/subgraph", post(get_entity_type_subgraph::<S, A>)),
+                        .route("/", read_only(post(get_entity_types::<S, A>)))
+                        .route(
+                            "/subgraph",
+                            read_only(post(get_entity_type_subgraph::<S, A>)),
+                        ),
                 )
                 .route("/load", post(load_external_entity_type::<S, A>))
+                .route(
+                    "/check-update",
+                    read_only(post(check_entity_type_update::<S, A>)),
+                )
                 .route("/archive", put(archive_entity_type::<S, A>))
                 .route("/unarchive", put(unarchive_entity_type::<S, A>))
                 .route("/embeddings", post(update_entity_type_embeddings::<S, A>)),
@@ -762,8 +776,15 @@ struct GetEntityTypeSubgraphResponse {
     responses(
         (
             status = 200,
-            content_type = "application/json",
-            body = GetEntityTypeSubgraphResponse,
+            content(
+                ("application/json" = GetEntityTypeSubgraphResponse),
+                ("application/ld+json" = String),
+                ("application/n-triples" = String),
+                ("text/turtle" = String),
+            ),
+            headers(
+                ("X-Hash-Cursor" = String, description = "The JSON encoded cursor to read the next page, only returned for linked data"),
+            ),
             description = "A subgraph rooted at entity types that satisfy the given query, each resolved to the requested depth.",
         ),
         (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
@@ -772,19 +793,22 @@ struct GetEntityTypeSubgraphResponse {
 )]
 #[tracing::instrument(
     level = "info",
-    skip(store_pool, authorization_api_pool, temporal_client, request)
+    skip(store_pool, authorization_api_pool, temporal_client, headers, request)
 )]
 async fn get_entity_type_subgraph<S, A>(
     AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
     store_pool: Extension<Arc<S>>,
     authorization_api_pool: Extension<Arc<A>>,
     temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    headers: HeaderMap,
     Json(request): Json<serde_json::Value>,
-) -> Result<Json<GetEntityTypeSubgraphResponse>, Response>
+) -> Result<Response, Response>
 where
     S: StorePool + Send + Sync,
     A: AuthorizationApiPool + Send + Sync,
 {
+    let rdf_format = rdf::requested_format(&headers);
+
     let authorization_api = authorization_api_pool
         .acquire()
         .await
@@ -805,11 +829,20 @@ where
         .get_entity_type_subgraph(actor_id, request)
         .await
         .map_err(report_to_response)
-        .map(|response| {
-            Json(GetEntityTypeSubgraphResponse {
+        .and_then(|response| {
+            if let Some(format) = rdf_format {
+                return rdf::subgraph_response(
+                    &response.subgraph,
+                    format,
+                    response.cursor.as_ref(),
+                    None,
+                );
+            }
+            Ok(Json(GetEntityTypeSubgraphResponse {
                 subgraph: Subgraph::from(response.subgraph),
                 cursor: response.cursor,
             })
+            .into_response())
         })
 }
 
@@ -976,6 +1009,73 @@ where
         .map_err(report_to_response)
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CheckEntityTypeUpdateRequest {
+    #[schema(value_type = VAR_UPDATE_ENTITY_TYPE)]
+    schema: serde_json::Value,
+    type_to_update: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-types/check-update",
+    tag = "EntityType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The incompatible changes of the update and the number of entities which would become invalid", body = CheckEntityTypeUpdateResponse),
+        (status = 400, content_type = "application/json", description = "Provided request body is invalid", body = Status),
+
+        (status = 403, description = "Insufficient permissions to view the entity type"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = CheckEntityTypeUpdateRequest,
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client)
+)]
+async fn check_entity_type_update<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    body: Json<CheckEntityTypeUpdateRequest>,
+) -> Result<Json<CheckEntityTypeUpdateResponse>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(CheckEntityTypeUpdateRequest {
+        schema,
+        mut type_to_update,
+    }) = body;
+
+    type_to_update.version = OntologyTypeVersion::new(type_to_update.version.inner() + 1);
+
+    let schema = patch_id_and_parse(&type_to_update, schema)
+        .attach(hash_status::StatusCode::InvalidArgument)
+        .map_err(report_to_response)?;
+
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .check_entity_type_update(actor_id, CheckEntityTypeUpdateParams { schema })
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
+
 #[utoipa::path(
     put,
     path = "/entity-types/archive",
@@ -3,16 +3,20 @@
 //! Handler methods are grouped by routes that make up the REST API.
 
 mod api_resource;
+pub mod authentication;
 mod json;
 pub mod middleware;
 pub mod status;
 mod utoipa_typedef;
 
 mod account;
+mod change_feed;
 mod data_type;
 mod entity;
 mod entity_type;
 mod property_type;
+mod rdf;
+mod subscription;
 mod web;
 
 use alloc::{borrow::Cow, sync::Arc};
@@ -31,7 +35,10 @@ use axum::{
 use error_stack::{Report, ResultExt};
 use graph::{
     ontology::{domain_validator::DomainValidator, Selector},
-    store::{error::VersionedUrlAlreadyExists, Store, StorePool, TypeFetcher},
+    store::{
+        error::VersionedUrlAlreadyExists, subscription::SubscriptionSecretKey, Store, StorePool,
+        TypeFetcher,
+    },
     subgraph::{
         edges::{
             EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphEdgeKind, OntologyEdgeKind,
@@ -77,6 +84,7 @@ use uuid::Uuid;
 
 use self::{
     api_resource::RoutedResource,
+    authentication::{authenticate, AuthenticatedActor, AuthenticationConfig, Scope},
     middleware::span_trace_layer,
     status::{report_to_response, status_to_response},
     utoipa_typedef::{
@@ -90,6 +98,14 @@ use self::{
     },
 };
 
+/// The actor a request is made by.
+///
+/// This is the actor authenticated by a bearer token or, if no token was provided, the actor
+/// specified in the `X-Authenticated-User-Actor-Id` header. Whether the header is accepted is
+/// decided by the [`AuthenticationConfig`].
+///
+/// Extracting the actor fails with `403 Forbidden` if the bearer token was not granted the
+/// [`Scope`] of the route.
 pub struct AuthenticatedUserHeader(pub AccountId);
 
 #[async_trait]
@@ -97,7 +113,22 @@ impl<S> FromRequestParts<S> for AuthenticatedUserHeader {
     type Rejection = (StatusCode, Cow<'static, str>);
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        if let Some(header_value) = parts.headers.get("X-Authenticated-User-Actor-Id") {
+        if let Some(actor) = parts.extensions.get::<AuthenticatedActor>() {
+            let required_scope = parts
+                .extensions
+                .get::<Scope>()
+                .copied()
+                .unwrap_or_else(|| Scope::default_for(&parts.method));
+            if !actor.scopes.contains(&required_scope) {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    Cow::Owned(format!(
+                        "The token is missing the `{required_scope:?}` scope"
+                    )),
+                ));
+            }
+            Ok(Self(actor.actor_id))
+        } else if let Some(header_value) = parts.headers.get("X-Authenticated-User-Actor-Id") {
             let header_string = header_value
                 .to_str()
                 .map_err(|error| (StatusCode::BAD_REQUEST, Cow::Owned(error.to_string())))?;
@@ -172,10 +203,12 @@ where
 {
     vec![
         account::AccountResource::routes::<S, A>(),
+        change_feed::ChangeFeedResource::routes::<S, A>(),
         data_type::DataTypeResource::routes::<S, A>(),
         property_type::PropertyTypeResource::routes::<S, A>(),
         entity_type::EntityTypeResource::routes::<S, A>(),
         entity::EntityResource::routes::<S, A>(),
+        subscription::SubscriptionResource::routes::<S, A>(),
         web::WebResource::routes::<S, A>(),
     ]
 }
@@ -183,10 +216,12 @@ where
 fn api_documentation() -> Vec<openapi::OpenApi> {
     vec![
         account::AccountResource::documentation(),
+        change_feed::ChangeFeedResource::documentation(),
         data_type::DataTypeResource::documentation(),
         property_type::PropertyTypeResource::documentation(),
         entity_type::EntityTypeResource::documentation(),
         entity::EntityResource::documentation(),
+        subscription::SubscriptionResource::documentation(),
         web::WebResource::documentation(),
     ]
 }
@@ -200,6 +235,8 @@ where
     pub authorization_api: Arc<A>,
     pub temporal_client: Option<TemporalClient>,
     pub domain_regex: DomainValidator,
+    pub authentication: AuthenticationConfig,
+    pub subscription_secret_key: Option<SubscriptionSecretKey>,
 }
 
 /// A [`Router`] that only serves the `OpenAPI` specification (JSON, and necessary subschemas) for
@@ -240,7 +277,14 @@ where
         .layer(Extension(dependencies.store))
         .layer(Extension(dependencies.authorization_api))
         .layer(Extension(dependencies.temporal_client.map(Arc::new)))
+        .layer(Extension(
+            dependencies.subscription_secret_key.map(Arc::new),
+        ))
         .layer(Extension(dependencies.domain_regex))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(dependencies.authentication),
+            authenticate,
+        ))
         .layer(span_trace_layer())
         .merge(openapi_only_router())
 }
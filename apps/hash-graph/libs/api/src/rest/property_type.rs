@@ -14,8 +14,8 @@ use authorization::{
 };
 use axum::{
     extract::Path,
-    http::StatusCode,
-    response::Response,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post, put},
     Extension, Router,
 };
@@ -28,9 +28,11 @@ use graph::{
     store::{
         error::VersionedUrlAlreadyExists,
         ontology::{
-            ArchivePropertyTypeParams, CreatePropertyTypeParams, GetPropertyTypeSubgraphParams,
-            GetPropertyTypesParams, GetPropertyTypesResponse, UnarchivePropertyTypeParams,
-            UpdatePropertyTypeEmbeddingParams, UpdatePropertyTypesParams,
+            ArchivePropertyTypeParams, CheckPropertyTypeUpdateParams,
+            CheckPropertyTypeUpdateResponse, CreatePropertyTypeParams,
+            GetPropertyTypeSubgraphParams, GetPropertyTypesParams, GetPropertyTypesResponse,
+            UnarchivePropertyTypeParams, UpdatePropertyTypeEmbeddingParams,
+            UpdatePropertyTypesParams,
         },
         BaseUrlAlreadyExists, ConflictBehavior, OntologyVersionDoesNotExist, PropertyTypeStore,
         StorePool,
@@ -50,14 +52,16 @@ use serde::{Deserialize, Serialize};
 use temporal_client::TemporalClient;
 use time::OffsetDateTime;
 use type_system::{
-    schema::PropertyType,
+    schema::{PropertyType, PropertyTypeIncompatibility},
     url::{OntologyTypeVersion, VersionedUrl},
 };
 use utoipa::{OpenApi, ToSchema};
 
 use super::api_resource::RoutedResource;
 use crate::rest::{
+    authentication::read_only,
     json::Json,
+    rdf,
     status::{report_to_response, status_to_response},
     utoipa_typedef::{subgraph::Subgraph, ListOrValue, MaybeListOfPropertyType},
     AuthenticatedUserHeader, PermissionResponse, RestApiStore,
@@ -75,6 +79,7 @@ use crate::rest::{
         get_property_types,
         get_property_type_subgraph,
         update_property_type,
+        check_property_type_update,
         update_property_type_embeddings,
         archive_property_type,
         unarchive_property_type,
@@ -96,6 +101,9 @@ use crate::rest::{
             CreatePropertyTypeRequest,
             LoadExternalPropertyTypeRequest,
             UpdatePropertyTypeRequest,
+            CheckPropertyTypeUpdateRequest,
+            CheckPropertyTypeUpdateResponse,
+            PropertyTypeIncompatibility,
             UpdatePropertyTypeEmbeddingParams,
             PropertyTypeQueryToken,
             GetPropertyTypesParams,
@@ -147,10 +155,17 @@ impl RoutedResource for PropertyTypeResource {
                 .nest(
                     "/query",
                     Router::new()
-                        .route("/", post(get_property_types::<S, A>))
-                        .route("/subgraph", post(get_property_type_subgraph::<S, A>)),
+                        .route("/", read_only(post(get_property_types::<S, A>)))
+                        .route(
+                            "/subgraph",
+                            read_only(post(get_property_type_subgraph::<S, A>)),
+                        ),
                 )
                 .route("/load", post(load_external_property_type::<S, A>))
+                .route(
+                    "/check-update",
+                    read_only(post(check_property_type_update::<S, A>)),
+                )
                 .route("/archive", put(archive_property_type::<S, A>))
                 .route("/unarchive", put(unarchive_property_type::<S, A>))
                 .route("/embeddings", post(update_property_type_embeddings::<S, A>)),
@@ -458,8 +473,15 @@ struct GetPropertyTypeSubgraphResponse {
     responses(
         (
             status = 200,
-            content_type = "application/json",
-            body = GetPropertyTypeSubgraphResponse,
+            content(
+                ("application/json" = GetPropertyTypeSubgraphResponse),
+                ("application/ld+json" = String),
+                ("application/n-triples" = String),
+                ("text/turtle" = String),
+            ),
+            headers(
+                ("X-Hash-Cursor" = String, description = "The JSON encoded cursor to read the next page, only returned for linked data"),
+            ),
             description = "A subgraph rooted at property types that satisfy the given query, each resolved to the requested depth.",
             headers(
                 ("Link" = String, description = "The link to be used to query the next page of property types"),
@@ -473,19 +495,22 @@ struct GetPropertyTypeSubgraphResponse {
 )]
 #[tracing::instrument(
     level = "info",
-    skip(store_pool, authorization_api_pool, temporal_client, request)
+    skip(store_pool, authorization_api_pool, temporal_client, headers, request)
 )]
 async fn get_property_type_subgraph<S, A>(
     AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
     store_pool: Extension<Arc<S>>,
     authorization_api_pool: Extension<Arc<A>>,
     temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    headers: HeaderMap,
     Json(request): Json<serde_json::Value>,
-) -> Result<Json<GetPropertyTypeSubgraphResponse>, Response>
+) -> Result<Response, Response>
 where
     S: StorePool + Send + Sync,
     A: AuthorizationApiPool + Send + Sync,
 {
+    let rdf_format = rdf::requested_format(&headers);
+
     let authorization_api = authorization_api_pool
         .acquire()
         .await
@@ -506,11 +531,20 @@ where
         .get_property_type_subgraph(actor_id, request)
         .await
         .map_err(report_to_response)
-        .map(|response| {
-            Json(GetPropertyTypeSubgraphResponse {
+        .and_then(|response| {
+            if let Some(format) = rdf_format {
+                return rdf::subgraph_response(
+                    &response.subgraph,
+                    format,
+                    response.cursor.as_ref(),
+                    None,
+                );
+            }
+            Ok(Json(GetPropertyTypeSubgraphResponse {
                 subgraph: Subgraph::from(response.subgraph),
                 cursor: response.cursor,
             })
+            .into_response())
         })
 }
 
@@ -668,6 +702,73 @@ where
         .map_err(report_to_response)
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CheckPropertyTypeUpdateRequest {
+    #[schema(value_type = VAR_UPDATE_PROPERTY_TYPE)]
+    schema: serde_json::Value,
+    type_to_update: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/property-types/check-update",
+    tag = "PropertyType",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The incompatible changes of the update and the number of entities which would become invalid", body = CheckPropertyTypeUpdateResponse),
+        (status = 400, content_type = "application/json", description = "Provided request body is invalid", body = Status),
+
+        (status = 403, description = "Insufficient permissions to view the property type"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = CheckPropertyTypeUpdateRequest,
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client)
+)]
+async fn check_property_type_update<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    body: Json<CheckPropertyTypeUpdateRequest>,
+) -> Result<Json<CheckPropertyTypeUpdateResponse>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Json(CheckPropertyTypeUpdateRequest {
+        schema,
+        mut type_to_update,
+    }) = body;
+
+    type_to_update.version = OntologyTypeVersion::new(type_to_update.version.inner() + 1);
+
+    let schema = patch_id_and_parse(&type_to_update, schema)
+        .attach(hash_status::StatusCode::InvalidArgument)
+        .map_err(report_to_response)?;
+
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .check_property_type_update(actor_id, CheckPropertyTypeUpdateParams { schema })
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
+
 #[utoipa::path(
     put,
     path = "/property-types/archive",
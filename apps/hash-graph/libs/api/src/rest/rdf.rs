@@ -0,0 +1,105 @@
+//! Linked data responses for subgraph queries.
+//!
+//! Subgraph queries respond with JSON by default. If the `Accept` header prefers one of the formats
+//! in [`RdfFormat`], the vertices of the subgraph are returned as linked data instead. As the
+//! linked data only describes the subgraph, the cursor and the count of the query are returned in
+//! the [`CURSOR_HEADER`] and [`COUNT_HEADER`].
+
+use core::fmt::Write as _;
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use error_stack::Report;
+use graph::subgraph::{rdf::RdfFormat, Subgraph};
+use serde::Serialize;
+
+use crate::rest::status::report_to_response;
+
+/// The header containing the JSON encoded cursor to read the next page of a linked data response.
+pub(crate) const CURSOR_HEADER: &str = "X-Hash-Cursor";
+
+/// The header containing the number of roots matching the query of a linked data response.
+pub(crate) const COUNT_HEADER: &str = "X-Hash-Count";
+
+/// Returns the linked data format requested by the `Accept` header, if any.
+pub(crate) fn requested_format(headers: &HeaderMap) -> Option<RdfFormat> {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|accept| accept.to_str().ok())
+        .find_map(RdfFormat::negotiate)
+}
+
+/// Encodes `value` as JSON which only contains ASCII characters, so it is a valid header value.
+fn ascii_json(value: &impl Serialize) -> Result<String, Report<serde_json::Error>> {
+    let json = serde_json::to_string(value)?;
+    let mut escaped = String::with_capacity(json.len());
+    for character in json.chars() {
+        if character.is_ascii() {
+            escaped.push(character);
+        } else {
+            // Non-ASCII characters only occur in strings, where they can be escaped
+            for unit in character.encode_utf16(&mut [0; 2]) {
+                let _ = write!(escaped, "\\u{unit:04x}");
+            }
+        }
+    }
+    Ok(escaped)
+}
+
+/// Writes the subgraph in the requested linked data format.
+///
+/// The cursor and the count are returned in the [`CURSOR_HEADER`] and [`COUNT_HEADER`].
+pub(crate) fn subgraph_response(
+    subgraph: &Subgraph,
+    format: RdfFormat,
+    cursor: Option<&impl Serialize>,
+    count: Option<usize>,
+) -> Result<Response, Response> {
+    let mut body = Vec::new();
+    subgraph
+        .write_rdf(format, &mut body)
+        .map_err(|error| report_to_response(Report::new(error)))?;
+
+    let mut response = (
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(format.media_type()),
+        )],
+        body,
+    )
+        .into_response();
+    if let Some(cursor) = cursor {
+        let cursor = ascii_json(cursor).map_err(report_to_response)?;
+        let cursor = HeaderValue::try_from(cursor)
+            .map_err(|error| report_to_response(Report::new(error)))?;
+        response.headers_mut().insert(CURSOR_HEADER, cursor);
+    }
+    if let Some(count) = count {
+        response
+            .headers_mut()
+            .insert(COUNT_HEADER, HeaderValue::from(count));
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn cursor_header_is_ascii() {
+        let cursor = json!(["Zo\u{eb} \u{1f980}", 1]);
+        let encoded = ascii_json(&cursor).expect("could not encode cursor");
+        assert_eq!(encoded, r#"["Zo\u00eb \ud83e\udd80",1]"#);
+        HeaderValue::try_from(encoded.as_str()).expect("cursor should be a valid header value");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&encoded).expect("invalid JSON"),
+            cursor
+        );
+    }
+}
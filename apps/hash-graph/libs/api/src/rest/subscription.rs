@@ -0,0 +1,274 @@
+//! Web routes for managing subscriptions to entity changes.
+
+use alloc::sync::Arc;
+
+use authorization::AuthorizationApiPool;
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::Response,
+    routing::{delete, get, post},
+    Extension, Router,
+};
+use error_stack::Report;
+use graph::store::{
+    error::InsertionError,
+    subscription::{
+        CreateEntitySubscriptionParams, EntitySubscription, ParkedChange, SubscriptionId,
+        SubscriptionSecretKey, SubscriptionStore,
+    },
+    StorePool,
+};
+use temporal_client::TemporalClient;
+use utoipa::OpenApi;
+
+use super::api_resource::RoutedResource;
+use crate::rest::{json::Json, status::report_to_response, AuthenticatedUserHeader};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        create_entity_subscription,
+        get_entity_subscriptions,
+        delete_entity_subscription,
+        get_parked_changes,
+    ),
+    components(
+        schemas(
+            SubscriptionId,
+            EntitySubscription,
+            CreateEntitySubscriptionParams,
+            ParkedChange,
+        ),
+    ),
+    tags(
+        (name = "Subscription", description = "Webhook subscription management API")
+    )
+)]
+pub(crate) struct SubscriptionResource;
+
+impl RoutedResource for SubscriptionResource {
+    /// Create routes for interacting with subscriptions.
+    fn routes<S, A>() -> Router
+    where
+        S: StorePool + Send + Sync + 'static,
+        A: AuthorizationApiPool + Send + Sync + 'static,
+    {
+        Router::new().nest(
+            "/subscriptions/entities",
+            Router::new()
+                .route(
+                    "/",
+                    post(create_entity_subscription::<S, A>).get(get_entity_subscriptions::<S, A>),
+                )
+                .route(
+                    "/:subscription_id",
+                    delete(delete_entity_subscription::<S, A>),
+                )
+                .route("/:subscription_id/parked", get(get_parked_changes::<S, A>)),
+        )
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/subscriptions/entities",
+    tag = "Subscription",
+    request_body = CreateEntitySubscriptionParams,
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The created subscription", body = EntitySubscription),
+        (status = 400, content_type = "application/json", description = "The URL or the filter is invalid"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 500, description = "Store error occurred"),
+        (status = 501, description = "No key to encrypt the secrets of subscriptions is configured"),
+    )
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(
+        store_pool,
+        authorization_api_pool,
+        temporal_client,
+        secret_key,
+        params
+    )
+)]
+async fn create_entity_subscription<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+    secret_key: Extension<Option<Arc<SubscriptionSecretKey>>>,
+    Json(params): Json<CreateEntitySubscriptionParams>,
+) -> Result<Json<EntitySubscription>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let Some(secret_key) = secret_key.0 else {
+        return Err(report_to_response(
+            Report::new(InsertionError)
+                .attach(hash_status::StatusCode::Unimplemented)
+                .attach_printable("webhooks are not configured"),
+        ));
+    };
+
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let mut store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .create_entity_subscription(actor_id, params, &secret_key)
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/subscriptions/entities",
+    tag = "Subscription",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The subscriptions created by the actor", body = [EntitySubscription]),
+
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client)
+)]
+async fn get_entity_subscriptions<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+) -> Result<Json<Vec<EntitySubscription>>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .get_entity_subscriptions(actor_id)
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/subscriptions/entities/{subscription_id}",
+    tag = "Subscription",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+        ("subscription_id" = SubscriptionId, Path, description = "The ID of the subscription to remove"),
+    ),
+    responses(
+        (status = 204, description = "The subscription was removed"),
+        (status = 404, description = "The actor has no subscription with the ID"),
+
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client)
+)]
+async fn delete_entity_subscription<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    Path(subscription_id): Path<SubscriptionId>,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+) -> Result<StatusCode, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let mut store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .delete_entity_subscription(actor_id, subscription_id)
+        .await
+        .map_err(report_to_response)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/subscriptions/entities/{subscription_id}/parked",
+    tag = "Subscription",
+    params(
+        ("X-Authenticated-User-Actor-Id" = AccountId, Header, description = "The ID of the actor which is used to authorize the request"),
+        ("subscription_id" = SubscriptionId, Path, description = "The ID of the subscription to read the parked changes of"),
+    ),
+    responses(
+        (status = 200, content_type = "application/json", description = "The changes which could not be delivered", body = [ParkedChange]),
+        (status = 404, description = "The actor has no subscription with the ID"),
+
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(
+    level = "info",
+    skip(store_pool, authorization_api_pool, temporal_client)
+)]
+async fn get_parked_changes<S, A>(
+    AuthenticatedUserHeader(actor_id): AuthenticatedUserHeader,
+    Path(subscription_id): Path<SubscriptionId>,
+    store_pool: Extension<Arc<S>>,
+    authorization_api_pool: Extension<Arc<A>>,
+    temporal_client: Extension<Option<Arc<TemporalClient>>>,
+) -> Result<Json<Vec<ParkedChange>>, Response>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    let authorization_api = authorization_api_pool
+        .acquire()
+        .await
+        .map_err(report_to_response)?;
+
+    let store = store_pool
+        .acquire(authorization_api, temporal_client.0)
+        .await
+        .map_err(report_to_response)?;
+
+    store
+        .get_parked_changes(actor_id, subscription_id)
+        .await
+        .map_err(report_to_response)
+        .map(Json)
+}
@@ -0,0 +1,401 @@
+use std::io;
+
+use csv_core::ReadRecordResult;
+use error_stack::Report;
+use serde_json::Value as JsonValue;
+use tokio_util::{
+    bytes::{Buf as _, BytesMut},
+    codec::{Decoder, LinesCodec, LinesCodecError},
+};
+
+use crate::knowledge::import::{ImportFormat, ImportRow, ImportRowError};
+
+/// The number of bytes a row may span by default.
+pub const DEFAULT_MAX_ROW_LENGTH: usize = 1024 * 1024;
+
+fn row_too_long(max_length: usize) -> String {
+    format!("the row exceeds the maximum length of {max_length} bytes")
+}
+
+/// Incrementally parses CSV records, the first record is used as header.
+#[derive(Debug)]
+struct CsvRows {
+    reader: csv_core::Reader,
+    output: Vec<u8>,
+    output_len: usize,
+    ends: Vec<usize>,
+    ends_len: usize,
+    headers: Option<Vec<String>>,
+    max_length: usize,
+    /// The number of bytes of the current record read so far.
+    record_len: usize,
+}
+
+impl CsvRows {
+    fn new(max_length: usize) -> Self {
+        Self {
+            reader: csv_core::Reader::new(),
+            output: vec![0; 1024],
+            output_len: 0,
+            ends: vec![0; 16],
+            ends_len: 0,
+            headers: None,
+            max_length,
+            record_len: 0,
+        }
+    }
+
+    /// Reads the next record from `input`.
+    ///
+    /// If `eof` is set, a partially read record is returned as well. Records longer than the
+    /// maximum length are skipped and returned as error.
+    fn read_record(
+        &mut self,
+        input: &mut BytesMut,
+        eof: bool,
+    ) -> Option<Result<Vec<String>, String>> {
+        loop {
+            let (result, read, written, ends) = self.reader.read_record(
+                input,
+                &mut self.output[self.output_len..],
+                &mut self.ends[self.ends_len..],
+            );
+            input.advance(read);
+            self.output_len += written;
+            self.ends_len += ends;
+            self.record_len += read;
+            // The fields of an oversized record are not needed, so the buffers are reused instead
+            // of being grown further
+            let discarding = self.record_len > self.max_length;
+
+            match result {
+                // An empty input marks the end of the input for the CSV reader
+                ReadRecordResult::InputEmpty if eof => {}
+                ReadRecordResult::InputEmpty | ReadRecordResult::End => return None,
+                ReadRecordResult::OutputFull if discarding => self.output_len = 0,
+                ReadRecordResult::OutputFull => self.output.resize(self.output.len() * 2, 0),
+                ReadRecordResult::OutputEndsFull if discarding => self.ends_len = 0,
+                ReadRecordResult::OutputEndsFull => self.ends.resize(self.ends.len() * 2, 0),
+                ReadRecordResult::Record if discarding => {
+                    self.output_len = 0;
+                    self.ends_len = 0;
+                    self.record_len = 0;
+                    return Some(Err(row_too_long(self.max_length)));
+                }
+                ReadRecordResult::Record => {
+                    let mut start = 0;
+                    let fields = self.ends[..self.ends_len]
+                        .iter()
+                        .map(|&end| {
+                            let field = String::from_utf8(self.output[start..end].to_vec())
+                                .map_err(|_error| "the row is not valid UTF-8".to_owned());
+                            start = end;
+                            field
+                        })
+                        .collect();
+                    self.output_len = 0;
+                    self.ends_len = 0;
+                    self.record_len = 0;
+                    return Some(fields);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Rows {
+    Csv(Box<CsvRows>),
+    JsonLines {
+        lines: LinesCodec,
+        max_length: usize,
+    },
+}
+
+/// Decodes the rows of an entity import.
+///
+/// Rows which cannot be parsed, are not valid UTF-8 or are longer than the maximum row length are
+/// returned as [`ImportRowError`], so the remaining rows can still be imported. Only I/O errors
+/// abort the decoding.
+#[derive(Debug)]
+pub struct ImportRowDecoder {
+    rows: Rows,
+    current_row: usize,
+}
+
+impl ImportRowDecoder {
+    /// Creates a decoder which accepts rows of up to [`DEFAULT_MAX_ROW_LENGTH`] bytes.
+    #[must_use]
+    pub fn new(format: ImportFormat) -> Self {
+        Self::new_with_max_length(format, DEFAULT_MAX_ROW_LENGTH)
+    }
+
+    /// Creates a decoder which accepts rows of up to `max_length` bytes.
+    #[must_use]
+    pub fn new_with_max_length(format: ImportFormat, max_length: usize) -> Self {
+        Self {
+            rows: match format {
+                ImportFormat::Csv => Rows::Csv(Box::new(CsvRows::new(max_length))),
+                ImportFormat::JsonLines => Rows::JsonLines {
+                    lines: LinesCodec::new_with_max_length(max_length),
+                    max_length,
+                },
+            },
+            current_row: 0,
+        }
+    }
+
+    fn csv_row(&mut self, fields: Result<Vec<String>, String>) -> Option<<Self as Decoder>::Item> {
+        let Rows::Csv(csv) = &mut self.rows else {
+            unreachable!("CSV rows are only decoded for CSV imports")
+        };
+        self.current_row += 1;
+        let row = self.current_row;
+
+        let fields = match fields {
+            Ok(fields) => fields,
+            Err(message) => return Some(Err(ImportRowError { row, message })),
+        };
+
+        let Some(headers) = &csv.headers else {
+            csv.headers = Some(
+                fields
+                    .into_iter()
+                    .map(|header| header.trim_start_matches('\u{feff}').trim().to_owned())
+                    .collect(),
+            );
+            return None;
+        };
+
+        if fields.len() != headers.len() {
+            return Some(Err(ImportRowError {
+                row,
+                message: format!("expected {} columns, found {}", headers.len(), fields.len()),
+            }));
+        }
+
+        Some(Ok(ImportRow {
+            row,
+            values: headers
+                .iter()
+                .cloned()
+                .zip(fields.into_iter().map(JsonValue::String))
+                .collect(),
+        }))
+    }
+
+    fn json_row(&self, line: &str) -> <Self as Decoder>::Item {
+        let row = self.current_row;
+        match serde_json::from_str::<JsonValue>(line) {
+            Ok(JsonValue::Object(values)) => Ok(ImportRow { row, values }),
+            Ok(_) => Err(ImportRowError {
+                row,
+                message: "the row is not a JSON object".to_owned(),
+            }),
+            Err(error) => Err(ImportRowError {
+                row,
+                message: error.to_string(),
+            }),
+        }
+    }
+
+    fn decode_input(
+        &mut self,
+        src: &mut BytesMut,
+        eof: bool,
+    ) -> Result<Option<<Self as Decoder>::Item>, Report<io::Error>> {
+        loop {
+            match &mut self.rows {
+                Rows::Csv(csv) => {
+                    if src.is_empty() && !eof {
+                        return Ok(None);
+                    }
+                    let Some(fields) = csv.read_record(src, eof) else {
+                        return Ok(None);
+                    };
+                    if let Some(row) = self.csv_row(fields) {
+                        return Ok(Some(row));
+                    }
+                }
+                Rows::JsonLines { lines, max_length } => {
+                    let line = if eof {
+                        lines.decode_eof(src)
+                    } else {
+                        lines.decode(src)
+                    };
+
+                    // Both the UTF-8 check and the length check consume the offending line, so
+                    // decoding can continue with the next line.
+                    let message = match line {
+                        Ok(None) => return Ok(None),
+                        Ok(Some(line)) => {
+                            self.current_row += 1;
+                            if line.trim().is_empty() {
+                                continue;
+                            }
+                            return Ok(Some(self.json_row(&line)));
+                        }
+                        Err(LinesCodecError::MaxLineLengthExceeded) => row_too_long(*max_length),
+                        Err(LinesCodecError::Io(error))
+                            if error.kind() == io::ErrorKind::InvalidData =>
+                        {
+                            "the row is not valid UTF-8".to_owned()
+                        }
+                        Err(LinesCodecError::Io(error)) => return Err(Report::new(error)),
+                    };
+                    self.current_row += 1;
+                    return Ok(Some(Err(ImportRowError {
+                        row: self.current_row,
+                        message,
+                    })));
+                }
+            }
+        }
+    }
+}
+
+impl Decoder for ImportRowDecoder {
+    // `Decoder::Error` requires `From<io::Error>` so we need to use `Report<io::Error>` here.
+    type Error = Report<io::Error>;
+    type Item = Result<ImportRow, ImportRowError>;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_input(src, false)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_input(buf, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn decode(format: ImportFormat, input: &str) -> Vec<Result<ImportRow, ImportRowError>> {
+        decode_with(ImportRowDecoder::new(format), input.as_bytes())
+    }
+
+    fn decode_with(
+        mut decoder: ImportRowDecoder,
+        input: &[u8],
+    ) -> Vec<Result<ImportRow, ImportRowError>> {
+        let mut rows = Vec::new();
+        let mut buffer = BytesMut::new();
+        // Feed the input in small chunks to cover rows split across reads
+        for chunk in input.chunks(3) {
+            buffer.extend_from_slice(chunk);
+            while let Some(row) = decoder.decode(&mut buffer).expect("could not decode rows") {
+                rows.push(row);
+            }
+        }
+        while let Some(row) = decoder
+            .decode_eof(&mut buffer)
+            .expect("could not decode rows")
+        {
+            rows.push(row);
+        }
+        rows
+    }
+
+    fn row(row: usize, values: JsonValue) -> ImportRow {
+        let JsonValue::Object(values) = values else {
+            panic!("row values must be an object")
+        };
+        ImportRow { row, values }
+    }
+
+    #[test]
+    fn decodes_csv() {
+        let rows = decode(
+            ImportFormat::Csv,
+            "\u{feff}name, age\nAlice,42\n\"Bob, Jr.\",\"7\"\nEve\nMallory,1",
+        );
+        assert_eq!(
+            rows,
+            [
+                Ok(row(2, json!({ "name": "Alice", "age": "42" }))),
+                Ok(row(3, json!({ "name": "Bob, Jr.", "age": "7" }))),
+                Err(ImportRowError {
+                    row: 4,
+                    message: "expected 2 columns, found 1".to_owned(),
+                }),
+                Ok(row(5, json!({ "name": "Mallory", "age": "1" }))),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_json_lines() {
+        let rows = decode(
+            ImportFormat::JsonLines,
+            "{\"name\": \"Alice\", \"age\": 42}\n\n[1, 2]\n{\"name\": \"Bob\"}",
+        );
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], Ok(row(1, json!({ "name": "Alice", "age": 42 }))));
+        assert_eq!(
+            rows[1],
+            Err(ImportRowError {
+                row: 3,
+                message: "the row is not a JSON object".to_owned(),
+            })
+        );
+        assert_eq!(rows[2], Ok(row(4, json!({ "name": "Bob" }))));
+    }
+
+    #[test]
+    fn reports_invalid_utf8() {
+        for format in [ImportFormat::Csv, ImportFormat::JsonLines] {
+            let input: &[u8] = match format {
+                ImportFormat::Csv => b"name\nAlice\nB\xffb\nEve",
+                ImportFormat::JsonLines => {
+                    b"{\"name\": \"Alice\"}\n{\"name\": \"B\xffb\"}\n{\"name\": \"Eve\"}"
+                }
+            };
+            let first = usize::from(format == ImportFormat::Csv) + 1;
+            assert_eq!(
+                decode_with(ImportRowDecoder::new(format), input),
+                [
+                    Ok(row(first, json!({ "name": "Alice" }))),
+                    Err(ImportRowError {
+                        row: first + 1,
+                        message: "the row is not valid UTF-8".to_owned(),
+                    }),
+                    Ok(row(first + 2, json!({ "name": "Eve" }))),
+                ],
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_oversized_rows() {
+        let long_name = "a".repeat(64);
+        for format in [ImportFormat::Csv, ImportFormat::JsonLines] {
+            let input = match format {
+                ImportFormat::Csv => format!("name\nAlice\n{long_name}\nEve"),
+                ImportFormat::JsonLines => format!(
+                    "{{\"name\": \"Alice\"}}\n{{\"name\": \"{long_name}\"}}\n{{\"name\": \"Eve\"}}"
+                ),
+            };
+            let first = usize::from(format == ImportFormat::Csv) + 1;
+            assert_eq!(
+                decode_with(
+                    ImportRowDecoder::new_with_max_length(format, 32),
+                    input.as_bytes()
+                ),
+                [
+                    Ok(row(first, json!({ "name": "Alice" }))),
+                    Err(ImportRowError {
+                        row: first + 1,
+                        message: "the row exceeds the maximum length of 32 bytes".to_owned(),
+                    }),
+                    Ok(row(first + 2, json!({ "name": "Eve" }))),
+                ],
+                "{format:?}"
+            );
+        }
+    }
+}
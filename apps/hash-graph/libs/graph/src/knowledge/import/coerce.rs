@@ -0,0 +1,270 @@
+use serde_json::{Map as JsonMap, Number as JsonNumber, Value as JsonValue};
+use type_system::{schema::JsonSchemaValueType, url::BaseUrl};
+
+/// Describes how the values of a column are converted into the values of a property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PropertyPlan {
+    pub base_url: BaseUrl,
+    /// Whether the property expects a list of values.
+    pub array: bool,
+    /// The JSON types of the data types and the nested values the property type allows, in the
+    /// order the coercion is attempted.
+    pub value_types: Vec<JsonSchemaValueType>,
+}
+
+fn has_type(value: &JsonValue, value_type: JsonSchemaValueType) -> bool {
+    match (value, value_type) {
+        (JsonValue::Null, JsonSchemaValueType::Null)
+        | (JsonValue::Bool(_), JsonSchemaValueType::Boolean)
+        | (JsonValue::Number(_), JsonSchemaValueType::Number)
+        | (JsonValue::String(_), JsonSchemaValueType::String)
+        | (JsonValue::Array(_), JsonSchemaValueType::Array)
+        | (JsonValue::Object(_), JsonSchemaValueType::Object) => true,
+        (JsonValue::Number(number), JsonSchemaValueType::Integer) => {
+            number.is_i64() || number.is_u64()
+        }
+        _ => false,
+    }
+}
+
+/// Parses a textual value, e.g. a CSV cell, into the specified JSON type.
+fn parse(text: &str, value_type: JsonSchemaValueType) -> Option<JsonValue> {
+    let text = text.trim();
+    match value_type {
+        JsonSchemaValueType::Null => (text.is_empty() || text == "null").then_some(JsonValue::Null),
+        JsonSchemaValueType::Boolean => match text.to_lowercase().as_str() {
+            "true" => Some(JsonValue::Bool(true)),
+            "false" => Some(JsonValue::Bool(false)),
+            _ => None,
+        },
+        JsonSchemaValueType::Integer => text.parse::<i64>().ok().map(JsonValue::from),
+        JsonSchemaValueType::Number => {
+            text.parse::<i64>().ok().map(JsonValue::from).or_else(|| {
+                text.parse::<f64>()
+                    .ok()
+                    .and_then(JsonNumber::from_f64)
+                    .map(JsonValue::Number)
+            })
+        }
+        JsonSchemaValueType::String => Some(JsonValue::String(text.to_owned())),
+        JsonSchemaValueType::Array | JsonSchemaValueType::Object => {
+            serde_json::from_str::<JsonValue>(text)
+                .ok()
+                .filter(|value| has_type(value, value_type))
+        }
+    }
+}
+
+fn expected_types(value_types: &[JsonSchemaValueType]) -> String {
+    value_types
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+impl PropertyPlan {
+    fn coerce_value(&self, value: JsonValue) -> Result<JsonValue, String> {
+        if self
+            .value_types
+            .iter()
+            .any(|value_type| has_type(&value, *value_type))
+        {
+            return Ok(value);
+        }
+
+        let coerced = match &value {
+            JsonValue::String(text) => self
+                .value_types
+                .iter()
+                .find_map(|value_type| parse(text, *value_type)),
+            JsonValue::Bool(_) | JsonValue::Number(_)
+                if self.value_types.contains(&JsonSchemaValueType::String) =>
+            {
+                Some(JsonValue::String(value.to_string()))
+            }
+            _ => None,
+        };
+
+        coerced.ok_or_else(|| {
+            format!(
+                "expected {}, found `{value}`",
+                expected_types(&self.value_types)
+            )
+        })
+    }
+
+    /// Converts a value of a row into the value of the property.
+    ///
+    /// Returns `None` if the value is empty and the property should not be set.
+    pub(crate) fn coerce(
+        &self,
+        value: JsonValue,
+        array_delimiter: char,
+    ) -> Result<Option<JsonValue>, String> {
+        let is_empty = match &value {
+            JsonValue::Null => !self.value_types.contains(&JsonSchemaValueType::Null),
+            JsonValue::String(text) => text.trim().is_empty(),
+            _ => false,
+        };
+        if is_empty {
+            return Ok(None);
+        }
+
+        if !self.array {
+            return self.coerce_value(value).map(Some);
+        }
+
+        let values = match value {
+            JsonValue::Array(values) => values,
+            JsonValue::String(text) => text
+                .split(array_delimiter)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| JsonValue::String(value.to_owned()))
+                .collect(),
+            value => vec![value],
+        };
+        values
+            .into_iter()
+            .map(|value| self.coerce_value(value))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|values| Some(JsonValue::Array(values)))
+    }
+}
+
+/// Converts the values of a row into the properties of an entity.
+///
+/// Values of columns without a plan are ignored. All values which cannot be converted are
+/// reported.
+pub(crate) fn row_properties<'p>(
+    plans: impl IntoIterator<Item = (&'p str, &'p PropertyPlan)>,
+    mut values: JsonMap<String, JsonValue>,
+    array_delimiter: char,
+) -> Result<JsonMap<String, JsonValue>, String> {
+    let mut properties = JsonMap::new();
+    let mut errors = Vec::new();
+
+    for (column, plan) in plans {
+        let Some(value) = values.remove(column) else {
+            continue;
+        };
+        match plan.coerce(value, array_delimiter) {
+            Ok(Some(value)) => {
+                properties.insert(plan.base_url.to_string(), value);
+            }
+            Ok(None) => {}
+            Err(error) => errors.push(format!("column `{column}`: {error}")),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(properties)
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn plan(array: bool, value_types: impl Into<Vec<JsonSchemaValueType>>) -> PropertyPlan {
+        PropertyPlan {
+            base_url: BaseUrl::new(
+                "https://example.com/@alice/types/property-type/value/".to_owned(),
+            )
+            .expect("invalid base URL"),
+            array,
+            value_types: value_types.into(),
+        }
+    }
+
+    #[test]
+    fn coerces_text() {
+        let number = plan(false, [JsonSchemaValueType::Number]);
+        assert_eq!(number.coerce(json!("42"), ';'), Ok(Some(json!(42))));
+        assert_eq!(number.coerce(json!(" 1.5 "), ';'), Ok(Some(json!(1.5))));
+        assert_eq!(number.coerce(json!(7), ';'), Ok(Some(json!(7))));
+        assert_eq!(number.coerce(json!(""), ';'), Ok(None));
+        number
+            .coerce(json!("abc"), ';')
+            .expect_err("`abc` is not a number");
+
+        let boolean = plan(false, [JsonSchemaValueType::Boolean]);
+        assert_eq!(boolean.coerce(json!("TRUE"), ';'), Ok(Some(json!(true))));
+        boolean
+            .coerce(json!("yes please"), ';')
+            .expect_err("`yes please` is not a boolean");
+
+        let text = plan(false, [JsonSchemaValueType::String]);
+        assert_eq!(text.coerce(json!(42), ';'), Ok(Some(json!("42"))));
+        assert_eq!(text.coerce(json!("Alice"), ';'), Ok(Some(json!("Alice"))));
+
+        let object = plan(false, [JsonSchemaValueType::Object]);
+        assert_eq!(
+            object.coerce(json!(r#"{"a": 1}"#), ';'),
+            Ok(Some(json!({ "a": 1 })))
+        );
+    }
+
+    #[test]
+    fn coerces_in_order() {
+        let number_or_text = plan(
+            false,
+            [JsonSchemaValueType::Number, JsonSchemaValueType::String],
+        );
+        assert_eq!(
+            number_or_text.coerce(json!("42"), ';'),
+            Ok(Some(json!("42")))
+        );
+
+        let number_or_null = plan(
+            false,
+            [JsonSchemaValueType::Number, JsonSchemaValueType::Null],
+        );
+        assert_eq!(
+            number_or_null.coerce(json!(null), ';'),
+            Ok(Some(json!(null)))
+        );
+        assert_eq!(
+            number_or_null.coerce(json!("null"), ';'),
+            Ok(Some(json!(null)))
+        );
+    }
+
+    #[test]
+    fn coerces_arrays() {
+        let numbers = plan(true, [JsonSchemaValueType::Number]);
+        assert_eq!(
+            numbers.coerce(json!("1; 2;3"), ';'),
+            Ok(Some(json!([1, 2, 3])))
+        );
+        assert_eq!(numbers.coerce(json!("1|2"), '|'), Ok(Some(json!([1, 2]))));
+        assert_eq!(
+            numbers.coerce(json!(["4", 5]), ';'),
+            Ok(Some(json!([4, 5])))
+        );
+        assert_eq!(numbers.coerce(json!(6), ';'), Ok(Some(json!([6]))));
+        numbers
+            .coerce(json!("1;x"), ';')
+            .expect_err("`x` is not a number");
+    }
+
+    #[test]
+    fn reports_all_columns() {
+        let number = plan(false, [JsonSchemaValueType::Number]);
+        let boolean = plan(false, [JsonSchemaValueType::Boolean]);
+        let values = json!({ "age": "old", "active": "maybe", "unmapped": "x" });
+        let JsonValue::Object(values) = values else {
+            unreachable!()
+        };
+
+        let error = row_properties([("age", &number), ("active", &boolean)], values, ';')
+            .expect_err("the row should be invalid");
+        assert!(error.contains("column `age`"), "{error}");
+        assert!(error.contains("column `active`"), "{error}");
+    }
+}
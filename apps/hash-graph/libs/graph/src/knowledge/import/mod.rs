@@ -0,0 +1,484 @@
+//! Bulk import of entities from tabular data.
+//!
+//! Every row of a CSV file or a JSON-lines stream is turned into an entity of the mapped entity
+//! types. The values of the mapped columns are coerced according to the data types the
+//! corresponding property types expect, e.g. the CSV cell `42` becomes a number if the property
+//! expects a number. Rows which cannot be converted or inserted are collected in an
+//! [`ImportReport`] instead of failing the whole import.
+
+mod codec;
+mod coerce;
+
+use core::{error::Error, fmt};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
+
+use authorization::schema::EntityRelationAndSubject;
+use error_stack::{Report, ResultExt};
+use futures::{Stream, StreamExt};
+use graph_types::{
+    account::AccountId,
+    knowledge::{
+        entity::{EntityId, ProvidedEntityEditionProvenance},
+        PropertyObject, PropertyWithMetadataObject,
+    },
+    owned_by_id::OwnedById,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use type_system::{
+    schema::{JsonSchemaValueType, PropertyTypeReference, PropertyValues, ValueOrArray},
+    url::{BaseUrl, VersionedUrl},
+};
+
+pub use self::codec::{ImportRowDecoder, DEFAULT_MAX_ROW_LENGTH};
+use self::coerce::{row_properties, PropertyPlan};
+use crate::{
+    store::{
+        knowledge::CreateEntityParams,
+        ontology::{GetDataTypesParams, GetEntityTypesParams, GetPropertyTypesParams},
+        query::Filter,
+        DataTypeStore, EntityStore, EntityTypeStore, PropertyTypeStore,
+    },
+    subgraph::temporal_axes::{
+        PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
+    },
+};
+
+/// The format of the rows to import.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportFormat {
+    /// Comma separated values, the first row contains the column names.
+    Csv,
+    /// One JSON object per line, the keys are used as column names.
+    JsonLines,
+}
+
+impl ImportFormat {
+    #[must_use]
+    pub const fn media_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::JsonLines => "application/x-ndjson",
+        }
+    }
+
+    #[must_use]
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "text/csv" => Some(Self::Csv),
+            "application/x-ndjson" | "application/jsonl" | "application/jsonlines" => {
+                Some(Self::JsonLines)
+            }
+            _ => None,
+        }
+    }
+}
+
+const fn default_array_delimiter() -> char {
+    ';'
+}
+
+/// Describes how the rows of an import are turned into entities.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EntityImportMapping {
+    /// The web the entities are created in.
+    pub owned_by_id: OwnedById,
+    /// The entity types of the created entities.
+    #[cfg_attr(feature = "utoipa", schema(value_type = Vec<VersionedUrl>))]
+    pub entity_type_ids: HashSet<VersionedUrl>,
+    /// Maps the column names to the properties they are imported as. Columns which are not mapped
+    /// are ignored.
+    #[cfg_attr(feature = "utoipa", schema(value_type = HashMap<String, String>))]
+    pub columns: HashMap<String, BaseUrl>,
+    /// Separates the values of a cell if the property expects a list of values.
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    #[serde(default = "default_array_delimiter")]
+    pub array_delimiter: char,
+    #[serde(default)]
+    pub draft: bool,
+    /// The relationships every created entity is assigned.
+    #[serde(default)]
+    pub relationships: Vec<EntityRelationAndSubject>,
+}
+
+/// A row to import, identified by its position in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRow {
+    /// The 1-based row in the input, for CSV input the header is row 1.
+    pub row: usize,
+    pub values: JsonMap<String, JsonValue>,
+}
+
+/// A row which could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowError {
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// The entities which were created.
+    pub created: Vec<EntityId>,
+    /// The rows which could not be imported, ordered by row.
+    pub errors: Vec<ImportRowError>,
+    /// The reason the input could not be read to its end, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aborted: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum EntityImportError {
+    Query,
+    MissingType,
+    UnmappedProperty,
+    Read,
+}
+
+impl fmt::Display for EntityImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Query => write!(f, "could not read the types of the import from the store"),
+            Self::MissingType => write!(f, "a type of the import does not exist"),
+            Self::UnmappedProperty => {
+                write!(
+                    f,
+                    "a column is mapped to a property the entity types do not have"
+                )
+            }
+            Self::Read => write!(f, "could not read the rows of the import"),
+        }
+    }
+}
+
+impl Error for EntityImportError {}
+
+fn latest_temporal_axes() -> QueryTemporalAxesUnresolved {
+    QueryTemporalAxesUnresolved::DecisionTime {
+        pinned: PinnedTemporalAxisUnresolved::new(None),
+        variable: VariableTemporalAxisUnresolved::new(None, None),
+    }
+}
+
+/// Collects the properties of the entity types including the properties of their parents.
+async fn entity_type_properties<S>(
+    store: &S,
+    actor_id: AccountId,
+    entity_type_ids: &HashSet<VersionedUrl>,
+) -> Result<HashMap<BaseUrl, ValueOrArray<PropertyTypeReference>>, Report<EntityImportError>>
+where
+    S: EntityTypeStore + Sync,
+{
+    let mut properties = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut queue = entity_type_ids.iter().cloned().collect::<Vec<_>>();
+
+    while let Some(entity_type_id) = queue.pop() {
+        if !visited.insert(entity_type_id.clone()) {
+            continue;
+        }
+        let entity_type = store
+            .get_entity_types(
+                actor_id,
+                GetEntityTypesParams {
+                    filter: Filter::for_versioned_url(&entity_type_id),
+                    temporal_axes: latest_temporal_axes(),
+                    include_drafts: false,
+                    after: None,
+                    limit: None,
+                    include_count: false,
+                },
+            )
+            .await
+            .change_context(EntityImportError::Query)?
+            .entity_types
+            .pop()
+            .ok_or_else(|| {
+                Report::new(EntityImportError::MissingType)
+                    .attach_printable(entity_type_id.to_string())
+            })?
+            .schema;
+
+        properties.extend(entity_type.properties);
+        queue.extend(
+            entity_type
+                .all_of
+                .into_iter()
+                .map(|reference| reference.url),
+        );
+    }
+
+    Ok(properties)
+}
+
+/// Resolves the JSON types the values of a property type are coerced to.
+async fn property_value_types<S>(
+    store: &S,
+    actor_id: AccountId,
+    property_type_id: &VersionedUrl,
+    data_types: &mut HashMap<VersionedUrl, JsonSchemaValueType>,
+) -> Result<Vec<JsonSchemaValueType>, Report<EntityImportError>>
+where
+    S: PropertyTypeStore + DataTypeStore + Sync,
+{
+    let property_type = store
+        .get_property_types(
+            actor_id,
+            GetPropertyTypesParams {
+                filter: Filter::for_versioned_url(property_type_id),
+                temporal_axes: latest_temporal_axes(),
+                include_drafts: false,
+                after: None,
+                limit: None,
+                include_count: false,
+            },
+        )
+        .await
+        .change_context(EntityImportError::Query)?
+        .property_types
+        .pop()
+        .ok_or_else(|| {
+            Report::new(EntityImportError::MissingType)
+                .attach_printable(property_type_id.to_string())
+        })?
+        .schema;
+
+    let mut value_types = Vec::with_capacity(property_type.one_of.len());
+    for values in &property_type.one_of {
+        let value_type = match values {
+            PropertyValues::DataTypeReference(reference) => {
+                if let Some(value_type) = data_types.get(&reference.url) {
+                    *value_type
+                } else {
+                    let value_type = store
+                        .get_data_types(
+                            actor_id,
+                            GetDataTypesParams {
+                                filter: Filter::for_versioned_url(&reference.url),
+                                temporal_axes: latest_temporal_axes(),
+                                include_drafts: false,
+                                after: None,
+                                limit: None,
+                                include_count: false,
+                            },
+                        )
+                        .await
+                        .change_context(EntityImportError::Query)?
+                        .data_types
+                        .pop()
+                        .ok_or_else(|| {
+                            Report::new(EntityImportError::MissingType)
+                                .attach_printable(reference.url.to_string())
+                        })?
+                        .schema
+                        .json_type;
+                    data_types.insert(reference.url.clone(), value_type);
+                    value_type
+                }
+            }
+            PropertyValues::PropertyTypeObject(_) => JsonSchemaValueType::Object,
+            PropertyValues::ArrayOfPropertyValues(_) => JsonSchemaValueType::Array,
+        };
+        if !value_types.contains(&value_type) {
+            value_types.push(value_type);
+        }
+    }
+
+    Ok(value_types)
+}
+
+/// Resolves how the mapped columns are converted, ordered by column name.
+async fn property_plans<S>(
+    store: &S,
+    actor_id: AccountId,
+    mapping: &EntityImportMapping,
+) -> Result<Vec<(String, PropertyPlan)>, Report<EntityImportError>>
+where
+    S: EntityTypeStore + PropertyTypeStore + DataTypeStore + Sync,
+{
+    let properties = entity_type_properties(store, actor_id, &mapping.entity_type_ids).await?;
+
+    let mut columns = mapping.columns.iter().collect::<Vec<_>>();
+    columns.sort();
+
+    let mut data_types = HashMap::new();
+    let mut plans = Vec::with_capacity(columns.len());
+    for (column, base_url) in columns {
+        let (reference, array) = match properties.get(base_url) {
+            Some(ValueOrArray::Value(reference)) => (reference, false),
+            Some(ValueOrArray::Array(array)) => (&array.items, true),
+            None => {
+                return Err(Report::new(EntityImportError::UnmappedProperty)
+                    .attach_printable(format!("column `{column}` is mapped to `{base_url}`")));
+            }
+        };
+
+        plans.push((
+            column.clone(),
+            PropertyPlan {
+                base_url: base_url.clone(),
+                array,
+                value_types: property_value_types(store, actor_id, &reference.url, &mut data_types)
+                    .await?,
+            },
+        ));
+    }
+
+    Ok(plans)
+}
+
+fn create_params(
+    mapping: &EntityImportMapping,
+    properties: PropertyObject,
+) -> CreateEntityParams<Vec<EntityRelationAndSubject>> {
+    CreateEntityParams {
+        owned_by_id: mapping.owned_by_id,
+        entity_uuid: None,
+        decision_time: None,
+        entity_type_ids: mapping.entity_type_ids.clone(),
+        properties: PropertyWithMetadataObject::from_parts(properties, None)
+            .expect("properties without metadata are always valid"),
+        confidence: None,
+        link_data: None,
+        draft: mapping.draft,
+        relationships: mapping.relationships.clone(),
+        provenance: ProvidedEntityEditionProvenance::default(),
+    }
+}
+
+/// Inserts a batch of rows into the store.
+///
+/// If the batch cannot be inserted, the rows are inserted one by one to find the failing rows.
+async fn insert_batch<S>(
+    store: &mut S,
+    actor_id: AccountId,
+    mapping: &EntityImportMapping,
+    batch: &mut Vec<(usize, PropertyObject)>,
+    report: &mut ImportReport,
+) where
+    S: EntityStore + Send,
+{
+    if batch.is_empty() {
+        return;
+    }
+
+    let params = batch
+        .iter()
+        .map(|(_, properties)| create_params(mapping, properties.clone()))
+        .collect();
+    match store.create_entities(actor_id, params).await {
+        Ok(entities) => {
+            report.created.extend(
+                entities
+                    .into_iter()
+                    .map(|entity| entity.metadata.record_id.entity_id),
+            );
+            batch.clear();
+        }
+        Err(_) if batch.len() > 1 => {
+            for (row, properties) in batch.drain(..) {
+                match store
+                    .create_entity(actor_id, create_params(mapping, properties))
+                    .await
+                {
+                    Ok(entity) => report.created.push(entity.metadata.record_id.entity_id),
+                    Err(error) => report.errors.push(ImportRowError {
+                        row,
+                        message: format!("{error:#}"),
+                    }),
+                }
+            }
+        }
+        Err(error) => {
+            for (row, _) in batch.drain(..) {
+                report.errors.push(ImportRowError {
+                    row,
+                    message: format!("{error:#}"),
+                });
+            }
+        }
+    }
+}
+
+/// Creates an entity for every row according to the `mapping`.
+///
+/// Entities are inserted in batches of `batch_size`. Rows which cannot be converted or inserted
+/// are added to the returned [`ImportReport`]. If reading the rows fails, the rows read so far are
+/// inserted and the failure is returned as [`ImportReport::aborted`].
+///
+/// # Errors
+///
+/// - [`Query`] or [`MissingType`] if the types of the mapping cannot be read
+/// - [`UnmappedProperty`] if a column is mapped to a property the entity types don't have
+///
+/// [`Query`]: EntityImportError::Query
+/// [`MissingType`]: EntityImportError::MissingType
+/// [`UnmappedProperty`]: EntityImportError::UnmappedProperty
+pub async fn import_entities<S>(
+    store: &mut S,
+    actor_id: AccountId,
+    mapping: &EntityImportMapping,
+    rows: impl Stream<Item = Result<Result<ImportRow, ImportRowError>, Report<io::Error>>> + Send,
+    batch_size: usize,
+) -> Result<ImportReport, Report<EntityImportError>>
+where
+    S: EntityStore + EntityTypeStore + PropertyTypeStore + DataTypeStore + Send + Sync,
+{
+    let plans = property_plans(store, actor_id, mapping).await?;
+
+    let mut report = ImportReport::default();
+    let mut batch = Vec::with_capacity(batch_size);
+
+    let mut rows = core::pin::pin!(rows);
+    loop {
+        let row = match rows.next().await {
+            Some(Ok(row)) => row,
+            Some(Err(error)) => {
+                report.aborted = Some(format!("{error:#}"));
+                break;
+            }
+            None => break,
+        };
+        let properties = row.and_then(|row| {
+            row_properties(
+                plans.iter().map(|(column, plan)| (column.as_str(), plan)),
+                row.values,
+                mapping.array_delimiter,
+            )
+            .and_then(|properties| {
+                serde_json::from_value::<PropertyObject>(JsonValue::Object(properties))
+                    .map_err(|error| error.to_string())
+            })
+            .map(|properties| (row.row, properties))
+            .map_err(|message| ImportRowError {
+                row: row.row,
+                message,
+            })
+        });
+
+        match properties {
+            Ok(row) => batch.push(row),
+            Err(error) => report.errors.push(error),
+        }
+
+        if batch.len() >= batch_size {
+            insert_batch(store, actor_id, mapping, &mut batch, &mut report).await;
+        }
+    }
+    insert_batch(store, actor_id, mapping, &mut batch, &mut report).await;
+
+    report.errors.sort_by_key(|error| error.row);
+    Ok(report)
+}
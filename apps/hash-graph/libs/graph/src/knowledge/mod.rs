@@ -1,6 +1,7 @@
 //! TODO: This module will encapsulate logic for Entities and Links, it's a parallel to the
 //!       `ontology` module, i.e you have Ontologies and Knowledge-Graphs
 
+pub mod import;
 mod query;
 
 pub use self::query::{
@@ -32,6 +32,8 @@ pub mod store;
 
 pub mod snapshot;
 
+pub mod webhook;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Environment {
     Development,
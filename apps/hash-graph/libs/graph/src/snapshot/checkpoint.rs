@@ -0,0 +1,212 @@
+use error_stack::{Result, ResultExt};
+use postgres_types::Json;
+use serde::{Deserialize, Serialize};
+use tokio_postgres::GenericClient;
+
+use crate::snapshot::{
+    restore::RelationBatch, SnapshotManifest, SnapshotMetadata, SnapshotRestoreError,
+};
+
+/// Progress of a snapshot restore, which stages the snapshot batch by batch.
+///
+/// The records of the snapshot are staged in a database schema named after the checkpoint. The
+/// checkpoint itself is stored in the same schema and is updated in the transaction, which stages
+/// a batch, so it always describes exactly the staged records. Authorization relations are staged
+/// in the same schema, as they cannot be written to the authorization backend before the snapshot
+/// has been verified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub(crate) struct SnapshotCheckpoint {
+    pub metadata: SnapshotMetadata,
+    /// The number of entries following the metadata, which have been staged.
+    pub records: u64,
+    /// The manifest of the staged entries.
+    ///
+    /// When resuming a restore, the skipped entries have to match this manifest, otherwise the
+    /// checkpoint belongs to a different snapshot.
+    pub staged: SnapshotManifest,
+}
+
+/// Quotes the name of the checkpoint, so it can be used as schema name.
+fn schema(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+impl SnapshotCheckpoint {
+    /// Reads the checkpoint with the provided name.
+    ///
+    /// Returns `None` if no checkpoint exists.
+    pub(crate) async fn load(
+        client: &(impl GenericClient + Sync),
+        name: &str,
+    ) -> Result<Option<Self>, SnapshotRestoreError> {
+        let exists: bool = client
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM pg_namespace WHERE nspname = $1)",
+                &[&name],
+            )
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)?
+            .get(0);
+        if !exists {
+            return Ok(None);
+        }
+
+        let Json(checkpoint): Json<Self> = client
+            .query_one(
+                &format!(
+                    "SELECT checkpoint FROM {}.snapshot_checkpoint",
+                    schema(name)
+                ),
+                &[],
+            )
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)
+            .attach_printable_lazy(|| format!("checkpoint: {name}"))?
+            .get(0);
+        Ok(Some(checkpoint))
+    }
+
+    /// Creates the schema of the checkpoint and stores the checkpoint in it.
+    ///
+    /// The staging tables are created as copies of the temporary tables of the current
+    /// transaction, so they have to be created before.
+    pub(crate) async fn create(
+        &self,
+        client: &(impl GenericClient + Sync),
+        name: &str,
+    ) -> Result<(), SnapshotRestoreError> {
+        client
+            .batch_execute(&format!(
+                "
+                    CREATE SCHEMA {schema};
+                    CREATE TABLE {schema}.snapshot_checkpoint (checkpoint JSONB NOT NULL);
+                    INSERT INTO {schema}.snapshot_checkpoint VALUES ('null');
+                    CREATE TABLE {schema}.snapshot_relations (
+                        batch_id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+                        relations JSONB NOT NULL
+                    );
+                ",
+                schema = schema(name)
+            ))
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)
+            .attach_printable_lazy(|| format!("checkpoint: {name}"))?;
+
+        let statements = client
+            .query(
+                "
+                    SELECT format('CREATE TABLE %I.%I (LIKE pg_temp.%I INCLUDING ALL)', $1::TEXT, \
+                 relname, relname)
+                    FROM pg_class
+                    WHERE relnamespace = pg_my_temp_schema() AND relkind = 'r'
+                ",
+                &[&name],
+            )
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)?;
+        for statement in statements {
+            client
+                .batch_execute(statement.get(0))
+                .await
+                .change_context(SnapshotRestoreError::Checkpoint)?;
+        }
+
+        self.store(client, name).await
+    }
+
+    /// Updates the stored checkpoint.
+    pub(crate) async fn store(
+        &self,
+        client: &(impl GenericClient + Sync),
+        name: &str,
+    ) -> Result<(), SnapshotRestoreError> {
+        client
+            .execute(
+                &format!(
+                    "UPDATE {}.snapshot_checkpoint SET checkpoint = $1",
+                    schema(name)
+                ),
+                &[&Json(self)],
+            )
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)
+            .attach_printable_lazy(|| format!("checkpoint: {name}"))?;
+        Ok(())
+    }
+
+    /// Stages a batch of authorization relations.
+    pub(crate) async fn stage_relations(
+        client: &(impl GenericClient + Sync),
+        name: &str,
+        relations: &RelationBatch,
+    ) -> Result<(), SnapshotRestoreError> {
+        client
+            .execute(
+                &format!(
+                    "INSERT INTO {}.snapshot_relations (relations) VALUES ($1)",
+                    schema(name)
+                ),
+                &[&Json(relations)],
+            )
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)
+            .attach_printable_lazy(|| format!("checkpoint: {name}"))?;
+        Ok(())
+    }
+
+    /// Reads the staged batch of authorization relations following the batch with the id `after`.
+    ///
+    /// Returns `None` if all staged batches have been read.
+    pub(crate) async fn staged_relations(
+        client: &(impl GenericClient + Sync),
+        name: &str,
+        after: i64,
+    ) -> Result<Option<(i64, RelationBatch)>, SnapshotRestoreError> {
+        Ok(client
+            .query_opt(
+                &format!(
+                    "SELECT batch_id, relations FROM {}.snapshot_relations WHERE batch_id > $1 \
+                     ORDER BY batch_id LIMIT 1",
+                    schema(name)
+                ),
+                &[&after],
+            )
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)
+            .attach_printable_lazy(|| format!("checkpoint: {name}"))?
+            .map(|row| {
+                let Json(relations) = row.get(1);
+                (row.get(0), relations)
+            }))
+    }
+
+    /// Resolves the staging tables of the checkpoint until the end of the current transaction.
+    pub(crate) async fn stage(
+        client: &(impl GenericClient + Sync),
+        name: &str,
+    ) -> Result<(), SnapshotRestoreError> {
+        client
+            .execute(
+                "SELECT set_config('search_path', quote_ident($1) || ', ' || \
+                 current_setting('search_path'), true)",
+                &[&name],
+            )
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)
+            .attach_printable_lazy(|| format!("checkpoint: {name}"))?;
+        Ok(())
+    }
+
+    /// Removes the checkpoint including all staged records.
+    pub(crate) async fn remove(
+        client: &(impl GenericClient + Sync),
+        name: &str,
+    ) -> Result<(), SnapshotRestoreError> {
+        client
+            .batch_execute(&format!("DROP SCHEMA {} CASCADE", schema(name)))
+            .await
+            .change_context(SnapshotRestoreError::Checkpoint)
+            .attach_printable_lazy(|| format!("checkpoint: {name}"))
+    }
+}
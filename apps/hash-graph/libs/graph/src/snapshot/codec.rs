@@ -0,0 +1,234 @@
+use std::io;
+
+use error_stack::{Report, ResultExt};
+use tokio_util::{
+    bytes::BytesMut,
+    codec::{Decoder, LinesCodec},
+};
+
+use crate::snapshot::{SnapshotEntry, SnapshotManifestBuilder};
+
+/// Decodes a snapshot from JSON lines and verifies it against its trailing manifest.
+///
+/// If the manifest does not match the decoded entries, if entries follow the manifest, or if the
+/// input ends without a manifest, an error is returned.
+#[derive(Debug, Clone)]
+pub struct SnapshotDecoder {
+    lines: LinesCodec,
+    current_line: usize,
+    manifest: SnapshotManifestBuilder,
+    found_manifest: bool,
+    require_manifest: bool,
+}
+
+impl Default for SnapshotDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapshotDecoder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            lines: LinesCodec::new(),
+            current_line: 0,
+            manifest: SnapshotManifestBuilder::default(),
+            found_manifest: false,
+            require_manifest: true,
+        }
+    }
+
+    /// Accepts snapshots without a manifest, e.g. snapshots which were created before manifests
+    /// were introduced.
+    ///
+    /// If the snapshot contains a manifest, it is still verified.
+    #[must_use]
+    pub const fn allow_missing_manifest(mut self) -> Self {
+        self.require_manifest = false;
+        self
+    }
+
+    fn decode_line(&mut self, line: &str) -> Result<SnapshotEntry, Report<io::Error>> {
+        let entry = serde_json::from_str::<SnapshotEntry>(line)
+            .map_err(io::Error::from)
+            .attach_printable_lazy(|| format!("line in input: {}", self.current_line))
+            .attach_printable_lazy(|| line.to_owned())?;
+
+        if self.found_manifest {
+            return Err(Report::new(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the snapshot contains entries after its manifest",
+            ))
+            .attach_printable(format!("line in input: {}", self.current_line)));
+        }
+
+        match &entry {
+            SnapshotEntry::Manifest(manifest) => {
+                self.found_manifest = true;
+                let actual = self.manifest.finish();
+                if actual != *manifest {
+                    let mut report = Report::new(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "the snapshot does not match its manifest",
+                    ));
+                    for (section, expected) in &manifest.sections {
+                        if actual.sections.get(section) != Some(expected) {
+                            report = report.attach_printable(format!(
+                                "section {section:?} does not match, expected {} records",
+                                expected.records
+                            ));
+                        }
+                    }
+                    for section in actual.sections.keys() {
+                        if !manifest.sections.contains_key(section) {
+                            report = report.attach_printable(format!(
+                                "section {section:?} is not contained in the manifest"
+                            ));
+                        }
+                    }
+                    return Err(report);
+                }
+            }
+            entry => {
+                if let Some(section) = entry.section() {
+                    self.manifest.update(section, line.as_bytes());
+                }
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+impl Decoder for SnapshotDecoder {
+    // `Decoder::Error` requires `From<io::Error>` so we need to use `Report<io::Error>` here.
+    type Error = Report<io::Error>;
+    type Item = SnapshotEntry;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        while let Some(line) = self
+            .lines
+            .decode(src)
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
+        {
+            self.current_line += 1;
+            if !line.is_empty() {
+                return self.decode_line(&line).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        while let Some(line) = self
+            .lines
+            .decode_eof(buf)
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
+        {
+            self.current_line += 1;
+            if !line.is_empty() {
+                return self.decode_line(&line).map(Some);
+            }
+        }
+
+        if self.require_manifest && !self.found_manifest {
+            return Err(Report::new(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the snapshot ended without a manifest, it might be truncated",
+            )));
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{SnapshotManifest, SnapshotSection};
+
+    const METADATA: &str = r#"{"type":"snapshot","blockProtocolModuleVersions":{"graph":"0.3.0"}}"#;
+    const ACCOUNTS: [&str; 2] = [
+        r#"{"type":"account","id":"00000000-0000-0000-0000-000000000001"}"#,
+        r#"{"type":"account","id":"00000000-0000-0000-0000-000000000002"}"#,
+    ];
+
+    fn manifest(accounts: &[&str]) -> String {
+        let mut manifest = SnapshotManifestBuilder::default();
+        for account in accounts {
+            manifest.update(SnapshotSection::Account, account.as_bytes());
+        }
+        serde_json::to_string(&SnapshotEntry::Manifest(manifest.finish()))
+            .expect("manifest should be serializable")
+    }
+
+    fn decode(decoder: &mut SnapshotDecoder, lines: &[&str]) -> Result<usize, Report<io::Error>> {
+        let mut buffer = BytesMut::from(lines.join("\n").as_str());
+        let mut entries = 0;
+        while decoder.decode(&mut buffer)?.is_some() {
+            entries += 1;
+        }
+        while decoder.decode_eof(&mut buffer)?.is_some() {
+            entries += 1;
+        }
+        Ok(entries)
+    }
+
+    #[test]
+    fn verifies_manifest() {
+        let manifest = manifest(&ACCOUNTS);
+
+        assert_eq!(
+            decode(
+                &mut SnapshotDecoder::new(),
+                &[METADATA, ACCOUNTS[0], ACCOUNTS[1], &manifest]
+            )
+            .expect("snapshot should match its manifest"),
+            4
+        );
+
+        _ = decode(
+            &mut SnapshotDecoder::new(),
+            &[METADATA, ACCOUNTS[0], &manifest],
+        )
+        .expect_err("missing entry should be detected");
+
+        _ = decode(
+            &mut SnapshotDecoder::new(),
+            &[METADATA, ACCOUNTS[1], ACCOUNTS[0], &manifest],
+        )
+        .expect_err("reordered entries should be detected");
+
+        _ = decode(
+            &mut SnapshotDecoder::new(),
+            &[METADATA, ACCOUNTS[0], ACCOUNTS[1], &manifest, ACCOUNTS[0]],
+        )
+        .expect_err("entries after the manifest should be detected");
+    }
+
+    #[test]
+    fn requires_manifest() {
+        _ = decode(
+            &mut SnapshotDecoder::new(),
+            &[METADATA, ACCOUNTS[0], ACCOUNTS[1]],
+        )
+        .expect_err("truncated snapshot should be detected");
+
+        assert_eq!(
+            decode(
+                &mut SnapshotDecoder::new().allow_missing_manifest(),
+                &[METADATA, ACCOUNTS[0], ACCOUNTS[1]],
+            )
+            .expect("manifest should be optional"),
+            3
+        );
+
+        let entry = serde_json::from_str::<SnapshotEntry>(r#"{"type":"manifest","sections":{}}"#)
+            .expect("empty manifest should be deserializable");
+        assert!(
+            matches!(entry, SnapshotEntry::Manifest(SnapshotManifest { sections }) if sections.is_empty())
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use alloc::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::snapshot::{SnapshotEntry, SnapshotRecordId};
+
+/// Entities and ontology types which differ between two snapshots.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    /// Records which are only contained in the new snapshot.
+    pub added: Vec<SnapshotRecordId>,
+    /// Records which are only contained in the old snapshot.
+    pub removed: Vec<SnapshotRecordId>,
+    /// Records which are contained in both snapshots but differ in their contents.
+    pub changed: Vec<SnapshotRecordId>,
+}
+
+impl SnapshotDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Hashes of the entities and ontology types of a snapshot, used to compute a [`SnapshotDiff`].
+///
+/// Only the hashes are kept in memory, so large snapshots can be compared. All editions of an
+/// entity are combined into one record, so a changed edition marks the entity as changed.
+#[derive(Debug, Default)]
+pub struct SnapshotDigest {
+    records: BTreeMap<SnapshotRecordId, Vec<[u8; 32]>>,
+}
+
+/// Sorts the keys of all objects contained in the value.
+///
+/// The keys of a [`serde_json::Map`] are only sorted if the `preserve_order` feature of
+/// `serde_json` is disabled, which might be enabled by any crate in the dependency graph.
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Array(values) => Value::Array(values.into_iter().map(canonicalize).collect()),
+        Value::Object(object) => {
+            let mut entries = object.into_iter().collect::<Vec<_>>();
+            entries.sort_unstable_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect(),
+            )
+        }
+        value => value,
+    }
+}
+
+impl SnapshotDigest {
+    fn insert(
+        &mut self,
+        id: SnapshotRecordId,
+        record: &impl Serialize,
+    ) -> Result<(), serde_json::Error> {
+        // The hash must not depend on the iteration order of the maps contained in the record
+        let value = canonicalize(serde_json::to_value(record)?);
+        let hash = Sha256::digest(serde_json::to_vec(&value)?);
+        self.records.entry(id).or_default().push(hash.into());
+        Ok(())
+    }
+
+    /// Adds the entry to the digest.
+    ///
+    /// Entries other than entities and ontology types are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry could not be serialized.
+    pub fn update(&mut self, entry: &SnapshotEntry) -> Result<(), serde_json::Error> {
+        match entry {
+            SnapshotEntry::DataType(data_type) => self.insert(
+                SnapshotRecordId::DataType(data_type.schema.id.clone()),
+                data_type,
+            ),
+            SnapshotEntry::PropertyType(property_type) => self.insert(
+                SnapshotRecordId::PropertyType(property_type.schema.id.clone()),
+                property_type,
+            ),
+            SnapshotEntry::EntityType(entity_type) => self.insert(
+                SnapshotRecordId::EntityType(entity_type.schema.id.clone()),
+                entity_type,
+            ),
+            SnapshotEntry::Entity(entity) => self.insert(
+                SnapshotRecordId::Entity(entity.metadata.record_id.entity_id),
+                entity,
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Compares this digest of the old snapshot with the digest of the `new` snapshot.
+    #[must_use]
+    pub fn diff(self, new: Self) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+        let mut old = self.records;
+
+        for (id, mut new_hashes) in new.records {
+            match old.remove(&id) {
+                Some(mut old_hashes) => {
+                    // The editions of an entity are not necessarily dumped in the same order
+                    old_hashes.sort_unstable();
+                    new_hashes.sort_unstable();
+                    if old_hashes != new_hashes {
+                        diff.changed.push(id);
+                    }
+                }
+                None => diff.added.push(id),
+            }
+        }
+        diff.removed.extend(old.into_keys());
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::snapshot::inspect::tests::{entity, object_type, WEB};
+
+    const ALICE: &str = "0000000a-0001-0000-0000-000000000000";
+    const BOB: &str = "0000000b-0001-0000-0000-000000000000";
+    const FRIENDSHIP: &str = "000000ab-0001-0000-0000-000000000000";
+    const NAME: &str = "http://localhost:3000/@snapshot/types/property-type/name/";
+    const AGE: &str = "http://localhost:3000/@snapshot/types/property-type/age/";
+
+    fn digest(entries: &[SnapshotEntry]) -> SnapshotDigest {
+        let mut digest = SnapshotDigest::default();
+        for entry in entries {
+            digest.update(entry).expect("entry should be serializable");
+        }
+        digest
+    }
+
+    fn entity_id(entity_uuid: &str) -> SnapshotRecordId {
+        SnapshotRecordId::Entity(
+            serde_json::from_value(json!(format!("{WEB}~{entity_uuid}")))
+                .expect("entity ID should be valid"),
+        )
+    }
+
+    #[test]
+    fn diff_snapshots() {
+        let old = digest(&[
+            object_type(),
+            entity(WEB, ALICE, &json!({ NAME: "Alice", AGE: 30 }), None),
+            entity(WEB, FRIENDSHIP, &json!({}), Some((ALICE, BOB))),
+        ]);
+        let new = digest(&[
+            entity(WEB, BOB, &json!({ NAME: "Bob" }), None),
+            entity(WEB, ALICE, &json!({ NAME: "Alice", AGE: 31 }), None),
+            object_type(),
+        ]);
+
+        assert_eq!(
+            old.diff(new),
+            SnapshotDiff {
+                added: vec![entity_id(BOB)],
+                removed: vec![entity_id(FRIENDSHIP)],
+                changed: vec![entity_id(ALICE)],
+            }
+        );
+    }
+
+    #[test]
+    fn diff_ignores_key_order() {
+        let old = digest(&[entity(WEB, ALICE, &json!({ NAME: "Alice", AGE: 30 }), None)]);
+        let new = digest(&[entity(WEB, ALICE, &json!({ AGE: 30, NAME: "Alice" }), None)]);
+
+        assert!(old.diff(new).is_empty());
+    }
+
+    #[test]
+    fn canonicalize_sorts_nested_keys() {
+        assert_eq!(
+            serde_json::to_string(&canonicalize(json!({ "b": [{ "d": 1, "c": 2 }], "a": 3 })))
+                .expect("value should be serializable"),
+            r#"{"a":3,"b":[{"c":2,"d":1}]}"#
+        );
+    }
+}
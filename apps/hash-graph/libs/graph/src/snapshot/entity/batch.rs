@@ -240,6 +240,59 @@ where
         Ok(())
     }
 
+    async fn merge(
+        postgres_client: &mut PostgresStore<C, A>,
+    ) -> error_stack::Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                "
+                    DELETE FROM entity_ids_tmp USING entity_ids
+                    WHERE entity_ids_tmp.web_id = entity_ids.web_id
+                      AND entity_ids_tmp.entity_uuid = entity_ids.entity_uuid;
+
+                    DELETE FROM entity_drafts_tmp USING entity_drafts
+                    WHERE entity_drafts_tmp.draft_id = entity_drafts.draft_id;
+
+                    DELETE FROM entity_is_of_type_tmp USING entity_is_of_type
+                    WHERE entity_is_of_type_tmp.entity_edition_id = \
+                 entity_is_of_type.entity_edition_id;
+
+                    DELETE FROM entity_editions_tmp USING entity_editions
+                    WHERE entity_editions_tmp.entity_edition_id = \
+                 entity_editions.entity_edition_id;
+
+                    DELETE FROM entity_has_left_entity_tmp USING entity_has_left_entity
+                    WHERE entity_has_left_entity_tmp.web_id = entity_has_left_entity.web_id
+                      AND entity_has_left_entity_tmp.entity_uuid = \
+                 entity_has_left_entity.entity_uuid;
+
+                    DELETE FROM entity_has_right_entity_tmp USING entity_has_right_entity
+                    WHERE entity_has_right_entity_tmp.web_id = entity_has_right_entity.web_id
+                      AND entity_has_right_entity_tmp.entity_uuid = \
+                 entity_has_right_entity.entity_uuid;
+
+                    DELETE FROM entity_temporal_metadata USING entity_temporal_metadata_tmp
+                    WHERE entity_temporal_metadata.entity_edition_id = \
+                 entity_temporal_metadata_tmp.entity_edition_id
+                      AND entity_temporal_metadata.transaction_time && \
+                 entity_temporal_metadata_tmp.transaction_time;
+
+                    DELETE FROM entity_embeddings USING entity_embeddings_tmp
+                    WHERE entity_embeddings.web_id = entity_embeddings_tmp.web_id
+                      AND entity_embeddings.entity_uuid = entity_embeddings_tmp.entity_uuid
+                      AND entity_embeddings.draft_id IS NOT DISTINCT FROM \
+                 entity_embeddings_tmp.draft_id
+                      AND entity_embeddings.property IS NOT DISTINCT FROM \
+                 entity_embeddings_tmp.property;
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(
         postgres_client: &mut PostgresStore<C, A>,
         validation: bool,
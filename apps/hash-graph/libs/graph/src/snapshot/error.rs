@@ -27,6 +27,7 @@ pub enum SnapshotRestoreError {
     Buffer,
     Write,
     Validation,
+    Checkpoint,
 }
 
 impl fmt::Display for SnapshotRestoreError {
@@ -38,6 +39,7 @@ impl fmt::Display for SnapshotRestoreError {
             Self::Buffer => write!(f, "could not buffer a snapshot entry"),
             Self::Write => write!(f, "could not write a snapshot entry into the store"),
             Self::Validation => write!(f, "could not validate a snapshot entry"),
+            Self::Checkpoint => write!(f, "could not use the snapshot restore checkpoint"),
         }
     }
 }
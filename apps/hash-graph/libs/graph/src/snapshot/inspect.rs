@@ -0,0 +1,428 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::iter::once;
+use std::collections::HashSet;
+
+use graph_types::{
+    knowledge::entity::EntityId, ontology::OntologyTypeClassificationMetadata,
+    owned_by_id::OwnedById,
+};
+use serde::Serialize;
+use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
+
+use crate::snapshot::{
+    entity::EntitySnapshotRecord, SnapshotEntry, SnapshotMetadata, SnapshotSection,
+};
+
+/// Identifies a record of a snapshot which can be referred to by other records.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "id")]
+pub enum SnapshotRecordId {
+    Web(OwnedById),
+    DataType(VersionedUrl),
+    PropertyType(VersionedUrl),
+    EntityType(VersionedUrl),
+    Entity(EntityId),
+}
+
+impl SnapshotRecordId {
+    /// Links refer to entities without their draft, so drafts are mapped to their live entity.
+    const fn entity(entity_id: EntityId) -> Self {
+        Self::Entity(EntityId {
+            owned_by_id: entity_id.owned_by_id,
+            entity_uuid: entity_id.entity_uuid,
+            draft_id: None,
+        })
+    }
+}
+
+const fn ontology_type_owner(
+    classification: &OntologyTypeClassificationMetadata,
+) -> Option<OwnedById> {
+    match classification {
+        OntologyTypeClassificationMetadata::Owned { owned_by_id } => Some(*owned_by_id),
+        OntologyTypeClassificationMetadata::External { .. } => None,
+    }
+}
+
+/// A reference from one record of a snapshot to a record which is not contained in the snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DanglingReference {
+    pub source: SnapshotRecordId,
+    pub target: SnapshotRecordId,
+}
+
+/// Overview of the contents of a snapshot as created by [`SnapshotInspector`].
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSummary {
+    pub metadata: Option<SnapshotMetadata>,
+    /// The number of records per section.
+    pub records: BTreeMap<SnapshotSection, u64>,
+    /// The number of records per section owned by each web.
+    pub webs: BTreeMap<OwnedById, BTreeMap<SnapshotSection, u64>>,
+    /// The versions present for every ontology type.
+    pub ontology_type_versions: BTreeMap<BaseUrl, BTreeSet<OntologyTypeVersion>>,
+    pub dangling_references: Vec<DanglingReference>,
+}
+
+/// Collects a [`SnapshotSummary`] from the entries of a snapshot.
+///
+/// References between records are only resolved in [`finish`], so the entries can be passed in
+/// any order.
+///
+/// [`finish`]: Self::finish
+#[derive(Debug, Default)]
+pub struct SnapshotInspector {
+    summary: SnapshotSummary,
+    records: HashSet<SnapshotRecordId>,
+    references: Vec<(SnapshotRecordId, SnapshotRecordId)>,
+}
+
+impl SnapshotInspector {
+    fn count(&mut self, section: SnapshotSection, web: Option<OwnedById>) {
+        *self.summary.records.entry(section).or_default() += 1;
+        if let Some(web) = web {
+            *self
+                .summary
+                .webs
+                .entry(web)
+                .or_default()
+                .entry(section)
+                .or_default() += 1;
+        }
+    }
+
+    fn add_ontology_type(
+        &mut self,
+        id: SnapshotRecordId,
+        url: &VersionedUrl,
+        classification: &OntologyTypeClassificationMetadata,
+        references: impl IntoIterator<Item = SnapshotRecordId>,
+    ) {
+        self.summary
+            .ontology_type_versions
+            .entry(url.base_url.clone())
+            .or_default()
+            .insert(url.version);
+
+        self.references.extend(
+            ontology_type_owner(classification)
+                .map(SnapshotRecordId::Web)
+                .into_iter()
+                .chain(references)
+                .map(|target| (id.clone(), target)),
+        );
+        self.records.insert(id);
+    }
+
+    fn add_entity(&mut self, entity: &EntitySnapshotRecord) {
+        let entity_id = entity.metadata.record_id.entity_id;
+        let id = SnapshotRecordId::Entity(entity_id);
+        self.references.extend(
+            once(SnapshotRecordId::Web(entity_id.owned_by_id))
+                .chain(
+                    entity
+                        .metadata
+                        .entity_type_ids
+                        .iter()
+                        .cloned()
+                        .map(SnapshotRecordId::EntityType),
+                )
+                .chain(entity.link_data.iter().flat_map(|link_data| {
+                    [
+                        SnapshotRecordId::entity(link_data.left_entity_id),
+                        SnapshotRecordId::entity(link_data.right_entity_id),
+                    ]
+                }))
+                .map(|target| (id.clone(), target)),
+        );
+        self.records.insert(SnapshotRecordId::entity(entity_id));
+    }
+
+    pub fn update(&mut self, entry: &SnapshotEntry) {
+        let section = entry.section();
+        match entry {
+            SnapshotEntry::Snapshot(metadata) => {
+                self.summary.metadata = Some(metadata.clone());
+            }
+            SnapshotEntry::Account(_)
+            | SnapshotEntry::AccountGroup(_)
+            | SnapshotEntry::Relation(_)
+            | SnapshotEntry::Manifest(_) => {}
+            SnapshotEntry::Web(web) => {
+                self.records.insert(SnapshotRecordId::Web(web.id));
+            }
+            SnapshotEntry::DataType(data_type) => self.add_ontology_type(
+                SnapshotRecordId::DataType(data_type.schema.id.clone()),
+                &data_type.schema.id,
+                &data_type.metadata.classification,
+                data_type
+                    .schema
+                    .data_type_references()
+                    .map(|(reference, _)| SnapshotRecordId::DataType(reference.url.clone())),
+            ),
+            SnapshotEntry::PropertyType(property_type) => self.add_ontology_type(
+                SnapshotRecordId::PropertyType(property_type.schema.id.clone()),
+                &property_type.schema.id,
+                &property_type.metadata.classification,
+                property_type
+                    .schema
+                    .data_type_references()
+                    .into_iter()
+                    .map(|reference| SnapshotRecordId::DataType(reference.url.clone()))
+                    .chain(
+                        property_type
+                            .schema
+                            .property_type_references()
+                            .into_iter()
+                            .map(|reference| SnapshotRecordId::PropertyType(reference.url.clone())),
+                    ),
+            ),
+            SnapshotEntry::EntityType(entity_type) => self.add_ontology_type(
+                SnapshotRecordId::EntityType(entity_type.schema.id.clone()),
+                &entity_type.schema.id,
+                &entity_type.metadata.classification,
+                entity_type
+                    .schema
+                    .property_type_references()
+                    .into_iter()
+                    .map(|reference| SnapshotRecordId::PropertyType(reference.url.clone()))
+                    .chain(
+                        entity_type
+                            .schema
+                            .all_of
+                            .iter()
+                            .chain(entity_type.schema.link_mappings().into_iter().flat_map(
+                                |(link_entity_type, destinations)| {
+                                    once(link_entity_type).chain(destinations.unwrap_or_default())
+                                },
+                            ))
+                            .map(|reference| SnapshotRecordId::EntityType(reference.url.clone())),
+                    ),
+            ),
+            SnapshotEntry::Entity(entity) => self.add_entity(entity),
+            SnapshotEntry::DataTypeEmbedding(embedding) => {
+                let id = SnapshotRecordId::DataType(embedding.data_type_id.clone());
+                self.references.push((id.clone(), id));
+            }
+            SnapshotEntry::PropertyTypeEmbedding(embedding) => {
+                let id = SnapshotRecordId::PropertyType(embedding.property_type_id.clone());
+                self.references.push((id.clone(), id));
+            }
+            SnapshotEntry::EntityTypeEmbedding(embedding) => {
+                let id = SnapshotRecordId::EntityType(embedding.entity_type_id.clone());
+                self.references.push((id.clone(), id));
+            }
+            SnapshotEntry::EntityEmbedding(embedding) => {
+                self.references.push((
+                    SnapshotRecordId::Entity(embedding.entity_id),
+                    SnapshotRecordId::entity(embedding.entity_id),
+                ));
+            }
+        }
+
+        if let Some(section) = section {
+            self.count(section, Self::owner(entry));
+        }
+    }
+
+    /// Returns the web owning the entry, if any.
+    fn owner(entry: &SnapshotEntry) -> Option<OwnedById> {
+        match entry {
+            SnapshotEntry::Web(web) => Some(web.id),
+            SnapshotEntry::Entity(entity) => Some(entity.metadata.record_id.entity_id.owned_by_id),
+            SnapshotEntry::EntityEmbedding(embedding) => Some(embedding.entity_id.owned_by_id),
+            SnapshotEntry::DataType(data_type) => {
+                ontology_type_owner(&data_type.metadata.classification)
+            }
+            SnapshotEntry::PropertyType(property_type) => {
+                ontology_type_owner(&property_type.metadata.classification)
+            }
+            SnapshotEntry::EntityType(entity_type) => {
+                ontology_type_owner(&entity_type.metadata.classification)
+            }
+            _ => None,
+        }
+    }
+
+    /// Resolves the references between the inspected records and returns the summary.
+    #[must_use]
+    pub fn finish(self) -> SnapshotSummary {
+        let mut summary = self.summary;
+        summary.dangling_references = self
+            .references
+            .into_iter()
+            .filter(|(_, target)| !self.records.contains(target))
+            .map(|(source, target)| DanglingReference { source, target })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        summary
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use core::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+
+    pub(crate) const WEB: &str = "00000000-0001-0000-0000-000000000000";
+    pub(crate) const OBJECT: &str = "http://localhost:3000/@snapshot/types/entity-type/object/v/1";
+
+    pub(crate) fn entry(value: serde_json::Value) -> SnapshotEntry {
+        serde_json::from_value(value).expect("entry should be valid")
+    }
+
+    pub(crate) fn object_type() -> SnapshotEntry {
+        entry(json!({
+            "type": "entityType",
+            "metadata": {
+                "ownedById": WEB,
+                "provenance": { "edition": { "createdById": WEB } },
+                "temporalVersioning": { "transactionTime": {
+                    "start": { "kind": "inclusive", "limit": "2000-01-01T00:00:00Z" },
+                    "end": { "kind": "unbounded" },
+                } },
+                "recordId": {
+                    "baseUrl": "http://localhost:3000/@snapshot/types/entity-type/object/",
+                    "version": 1,
+                },
+            },
+            "schema": {
+                "$id": OBJECT,
+                "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+                "kind": "entityType",
+                "properties": {},
+                "title": "Object",
+                "type": "object",
+            },
+        }))
+    }
+
+    pub(crate) fn entity(
+        web: &str,
+        entity_uuid: &str,
+        properties: &serde_json::Value,
+        link: Option<(&str, &str)>,
+    ) -> SnapshotEntry {
+        let mut entity = json!({
+            "type": "entity",
+            "metadata": {
+                "archived": false,
+                "provenance": {
+                    "createdById": WEB,
+                    "createdAtTransactionTime": "2001-01-01T00:00:00Z",
+                    "createdAtDecisionTime": "2001-01-01T00:00:00Z",
+                    "edition": { "createdById": WEB },
+                },
+                "entityTypeIds": [OBJECT],
+                "recordId": {
+                    "editionId": entity_uuid,
+                    "entityId": format!("{web}~{entity_uuid}"),
+                },
+                "temporalVersioning": {
+                    "decisionTime": {
+                        "start": { "kind": "inclusive", "limit": "2001-01-01T00:00:00Z" },
+                        "end": { "kind": "unbounded" },
+                    },
+                    "transactionTime": {
+                        "start": { "kind": "inclusive", "limit": "2001-01-01T00:00:00Z" },
+                        "end": { "kind": "unbounded" },
+                    },
+                },
+            },
+            "properties": properties,
+        });
+        if let Some((left, right)) = link {
+            entity["linkData"] = json!({
+                "leftEntityId": format!("{WEB}~{left}"),
+                "rightEntityId": format!("{WEB}~{right}"),
+            });
+        }
+        entry(entity)
+    }
+
+    #[test]
+    fn summary() {
+        const OTHER_WEB: &str = "00000000-0002-0000-0000-000000000000";
+        const ALICE: &str = "0000000a-0001-0000-0000-000000000000";
+        const BOB: &str = "0000000b-0001-0000-0000-000000000000";
+        const FRIENDSHIP: &str = "000000ab-0001-0000-0000-000000000000";
+        const CAROL: &str = "0000000c-0002-0000-0000-000000000000";
+
+        let mut inspector = SnapshotInspector::default();
+        for entry in [
+            entry(
+                json!({ "type": "snapshot", "blockProtocolModuleVersions": { "graph": "0.3.0" } }),
+            ),
+            entry(json!({ "type": "web", "id": WEB })),
+            object_type(),
+            entity(WEB, ALICE, &json!({}), None),
+            // Bob is not contained in the snapshot
+            entity(WEB, FRIENDSHIP, &json!({}), Some((ALICE, BOB))),
+            // The web of Carol is not contained in the snapshot
+            entity(OTHER_WEB, CAROL, &json!({}), None),
+        ] {
+            inspector.update(&entry);
+        }
+        let summary = inspector.finish();
+
+        assert!(summary.metadata.is_some());
+        assert_eq!(
+            summary.records,
+            BTreeMap::from([
+                (SnapshotSection::Web, 1),
+                (SnapshotSection::EntityType, 1),
+                (SnapshotSection::Entity, 3),
+            ])
+        );
+
+        let web: OwnedById = serde_json::from_value(json!(WEB)).expect("web should be valid");
+        let other_web: OwnedById =
+            serde_json::from_value(json!(OTHER_WEB)).expect("web should be valid");
+        assert_eq!(
+            summary.webs,
+            BTreeMap::from([
+                (
+                    web,
+                    BTreeMap::from([
+                        (SnapshotSection::Web, 1),
+                        (SnapshotSection::EntityType, 1),
+                        (SnapshotSection::Entity, 2),
+                    ])
+                ),
+                (other_web, BTreeMap::from([(SnapshotSection::Entity, 1)])),
+            ])
+        );
+
+        let object = VersionedUrl::from_str(OBJECT).expect("URL should be valid");
+        assert_eq!(
+            summary.ontology_type_versions,
+            BTreeMap::from([(object.base_url, BTreeSet::from([object.version]))])
+        );
+
+        let entity_id = |web: &str, entity_uuid: &str| {
+            SnapshotRecordId::Entity(
+                serde_json::from_value(json!(format!("{web}~{entity_uuid}")))
+                    .expect("entity ID should be valid"),
+            )
+        };
+        assert_eq!(
+            summary.dangling_references,
+            [
+                DanglingReference {
+                    source: entity_id(WEB, FRIENDSHIP),
+                    target: entity_id(WEB, BOB),
+                },
+                DanglingReference {
+                    source: entity_id(OTHER_WEB, CAROL),
+                    target: SnapshotRecordId::Web(other_web),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use alloc::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A group of snapshot entries of the same kind.
+///
+/// Sections are ordered by their dependencies. The entries of the sections are interleaved in a
+/// snapshot dump.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SnapshotSection {
+    Account,
+    AccountGroup,
+    Web,
+    DataType,
+    PropertyType,
+    EntityType,
+    Entity,
+    DataTypeEmbedding,
+    PropertyTypeEmbedding,
+    EntityTypeEmbedding,
+    EntityEmbedding,
+    Relation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SectionManifest {
+    /// The number of entries in the section.
+    pub records: u64,
+    /// The hex-encoded SHA-256 hash over the JSON lines of all entries in the section.
+    pub checksum: String,
+}
+
+/// Trailing entry of a snapshot, which allows to detect truncated or corrupted snapshots.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SnapshotManifest {
+    pub sections: BTreeMap<SnapshotSection, SectionManifest>,
+}
+
+/// Calculates the [`SnapshotManifest`] of the entries of a snapshot.
+#[derive(Debug, Default, Clone)]
+pub struct SnapshotManifestBuilder {
+    sections: BTreeMap<SnapshotSection, (u64, Sha256)>,
+}
+
+impl SnapshotManifestBuilder {
+    /// Adds the JSON line of an entry to the section.
+    ///
+    /// The line is expected to not contain the trailing newline.
+    pub fn update(&mut self, section: SnapshotSection, line: &[u8]) {
+        let (records, hasher) = self.sections.entry(section).or_default();
+        *records += 1;
+        hasher.update(line);
+        hasher.update(b"\n");
+    }
+
+    #[must_use]
+    pub fn finish(&self) -> SnapshotManifest {
+        SnapshotManifest {
+            sections: self
+                .sections
+                .iter()
+                .map(|(section, (records, hasher))| {
+                    (
+                        *section,
+                        SectionManifest {
+                            records: *records,
+                            checksum: format!("{:x}", hasher.clone().finalize()),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_detects_changes() {
+        let mut builder = SnapshotManifestBuilder::default();
+        builder.update(SnapshotSection::Web, br#"{"type":"web","id":"a"}"#);
+        builder.update(SnapshotSection::Web, br#"{"type":"web","id":"b"}"#);
+        builder.update(SnapshotSection::Account, br#"{"type":"account","id":"a"}"#);
+        let manifest = builder.finish();
+
+        assert_eq!(manifest.sections[&SnapshotSection::Web].records, 2);
+        assert_eq!(manifest.sections[&SnapshotSection::Account].records, 1);
+        assert_eq!(
+            serde_json::to_value(&manifest).expect("manifest should be serializable")["sections"]
+                .as_object()
+                .expect("sections should be an object")
+                .keys()
+                .collect::<Vec<_>>(),
+            ["account", "web"]
+        );
+
+        let mut reordered = SnapshotManifestBuilder::default();
+        reordered.update(SnapshotSection::Account, br#"{"type":"account","id":"a"}"#);
+        reordered.update(SnapshotSection::Web, br#"{"type":"web","id":"a"}"#);
+        reordered.update(SnapshotSection::Web, br#"{"type":"web","id":"b"}"#);
+        assert_eq!(reordered.finish(), manifest);
+
+        let mut corrupted = SnapshotManifestBuilder::default();
+        corrupted.update(SnapshotSection::Account, br#"{"type":"account","id":"a"}"#);
+        corrupted.update(SnapshotSection::Web, br#"{"type":"web","id":"a"}"#);
+        corrupted.update(SnapshotSection::Web, br#"{"type":"web","id":"c"}"#);
+        assert_ne!(corrupted.finish(), manifest);
+    }
+}
@@ -1,9 +1,15 @@
+use graph_types::{ontology::OntologyTypeClassificationMetadata, owned_by_id::OwnedById};
 use serde::{Deserialize, Serialize};
+use temporal_versioning::{
+    ClosedTemporalBound, LeftClosedTemporalInterval, Timestamp, TransactionTime,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotMetadata {
     pub block_protocol_module_versions: BlockProtocolModuleVersions,
+    #[serde(default, skip_serializing_if = "SnapshotScope::is_full")]
+    pub scope: SnapshotScope,
     #[serde(default, skip_serializing_if = "CustomGlobalMetadata::is_empty")]
     pub custom: CustomGlobalMetadata,
 }
@@ -14,6 +20,74 @@ pub struct BlockProtocolModuleVersions {
     pub graph: semver::Version,
 }
 
+/// Restricts which records are contained in a snapshot.
+///
+/// A snapshot with a non-full scope only contains a part of the graph and is expected to be merged
+/// into an existing database.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SnapshotScope {
+    /// Only records owned by these webs are contained. If empty, records of all webs are
+    /// contained.
+    ///
+    /// External ontology types are not owned by any web and are always contained.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webs: Vec<OwnedById>,
+    /// Only records, which were created at or after this transaction time, are contained.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_time_since: Option<Timestamp<TransactionTime>>,
+}
+
+impl SnapshotScope {
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.webs.is_empty() && self.transaction_time_since.is_none()
+    }
+
+    /// Returns `true` if records owned by the web are contained in the scope.
+    #[must_use]
+    pub fn contains_web(&self, web_id: OwnedById) -> bool {
+        self.webs.is_empty() || self.webs.contains(&web_id)
+    }
+
+    /// Returns `true` if a record with the provided transaction time is contained in the scope.
+    #[must_use]
+    pub fn contains_transaction_time(
+        &self,
+        transaction_time: &LeftClosedTemporalInterval<TransactionTime>,
+    ) -> bool {
+        self.transaction_time_since.map_or(true, |since| {
+            let ClosedTemporalBound::Inclusive(start) = transaction_time.start();
+            *start >= since
+        })
+    }
+
+    /// Returns `true` if an ontology type with the provided metadata is contained in the scope.
+    #[must_use]
+    pub fn contains_ontology_type(
+        &self,
+        classification: &OntologyTypeClassificationMetadata,
+        transaction_time: &LeftClosedTemporalInterval<TransactionTime>,
+    ) -> bool {
+        let contains_owner = match classification {
+            OntologyTypeClassificationMetadata::Owned { owned_by_id } => {
+                self.contains_web(*owned_by_id)
+            }
+            OntologyTypeClassificationMetadata::External { .. } => true,
+        };
+
+        contains_owner && self.contains_transaction_time(transaction_time)
+    }
+
+    /// Returns `true` if a record, which was updated at the provided transaction time, is
+    /// contained in the scope.
+    #[must_use]
+    pub fn contains_update(&self, updated_at_transaction_time: Timestamp<TransactionTime>) -> bool {
+        self.transaction_time_since
+            .map_or(true, |since| updated_at_transaction_time >= since)
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CustomGlobalMetadata;
@@ -2,8 +2,12 @@ pub mod entity;
 pub mod owner;
 
 pub use self::{
+    codec::SnapshotDecoder,
+    diff::{SnapshotDiff, SnapshotDigest},
     error::{SnapshotDumpError, SnapshotRestoreError},
-    metadata::{BlockProtocolModuleVersions, CustomGlobalMetadata},
+    inspect::{DanglingReference, SnapshotInspector, SnapshotRecordId, SnapshotSummary},
+    manifest::{SectionManifest, SnapshotManifest, SnapshotManifestBuilder, SnapshotSection},
+    metadata::{BlockProtocolModuleVersions, CustomGlobalMetadata, SnapshotScope},
     ontology::{
         DataTypeSnapshotRecord, EntityTypeSnapshotRecord, OntologyTypeSnapshotRecord,
         PropertyTypeSnapshotRecord,
@@ -11,13 +15,19 @@ pub use self::{
 };
 pub use crate::snapshot::metadata::SnapshotMetadata;
 
+mod checkpoint;
+mod codec;
+mod diff;
 mod error;
+mod inspect;
+mod manifest;
 mod metadata;
 mod ontology;
 mod restore;
 mod web;
 
-use core::future::ready;
+use core::{future::ready, pin::pin};
+use std::collections::HashSet;
 
 use async_scoped::TokioScope;
 use async_trait::async_trait;
@@ -36,7 +46,7 @@ use authorization::{
 };
 use error_stack::{ensure, Context, Report, Result, ResultExt};
 use futures::{
-    channel::mpsc, stream, Sink, SinkExt, Stream, StreamExt, TryFutureExt, TryStreamExt,
+    channel::mpsc, future, stream, Sink, SinkExt, Stream, StreamExt, TryFutureExt, TryStreamExt,
 };
 use graph_types::{
     account::{AccountGroupId, AccountId},
@@ -55,11 +65,12 @@ use type_system::url::VersionedUrl;
 
 use crate::{
     snapshot::{
+        checkpoint::SnapshotCheckpoint,
         entity::{EntityEmbeddingRecord, EntitySnapshotRecord},
         ontology::{
             DataTypeEmbeddingRecord, EntityTypeEmbeddingRecord, PropertyTypeEmbeddingRecord,
         },
-        restore::SnapshotRecordBatch,
+        restore::{RelationBatch, SnapshotRecordBatch},
     },
     store::{
         crud::Read, query::Filter, AsClient, InsertionError, PostgresStore, PostgresStorePool,
@@ -111,9 +122,32 @@ pub enum SnapshotEntry {
     Entity(Box<EntitySnapshotRecord>),
     EntityEmbedding(EntityEmbeddingRecord),
     Relation(AuthorizationRelation),
+    Manifest(SnapshotManifest),
 }
 
 impl SnapshotEntry {
+    /// Returns the section the entry belongs to.
+    ///
+    /// The metadata and the manifest of a snapshot are not part of any section.
+    #[must_use]
+    pub const fn section(&self) -> Option<SnapshotSection> {
+        match self {
+            Self::Snapshot(_) | Self::Manifest(_) => None,
+            Self::Account(_) => Some(SnapshotSection::Account),
+            Self::AccountGroup(_) => Some(SnapshotSection::AccountGroup),
+            Self::Web(_) => Some(SnapshotSection::Web),
+            Self::DataType(_) => Some(SnapshotSection::DataType),
+            Self::DataTypeEmbedding(_) => Some(SnapshotSection::DataTypeEmbedding),
+            Self::PropertyType(_) => Some(SnapshotSection::PropertyType),
+            Self::PropertyTypeEmbedding(_) => Some(SnapshotSection::PropertyTypeEmbedding),
+            Self::EntityType(_) => Some(SnapshotSection::EntityType),
+            Self::EntityTypeEmbedding(_) => Some(SnapshotSection::EntityTypeEmbedding),
+            Self::Entity(_) => Some(SnapshotSection::Entity),
+            Self::EntityEmbedding(_) => Some(SnapshotSection::EntityEmbedding),
+            Self::Relation(_) => Some(SnapshotSection::Relation),
+        }
+    }
+
     #[expect(clippy::too_many_lines)]
     pub fn install_error_stack_hook() {
         error_stack::Report::install_debug_hook::<Self>(|entry, context| match entry {
@@ -126,6 +160,14 @@ impl SnapshotEntry {
             Self::Account(account) => {
                 context.push_body(format!("account: {}", account.id));
             }
+            Self::Manifest(manifest) => {
+                for (section, manifest) in &manifest.sections {
+                    context.push_body(format!(
+                        "manifest: {section:?} with {} records",
+                        manifest.records
+                    ));
+                }
+            }
             Self::AccountGroup(account_group) => {
                 context.push_body(format!("account group: {}", account_group.id));
             }
@@ -235,6 +277,10 @@ impl SnapshotEntry {
 trait WriteBatch<C, A> {
     async fn begin(postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError>;
     async fn write(self, postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError>;
+    /// Removes records from the temporary tables, which are already present in the store.
+    ///
+    /// This is executed before `commit` when a snapshot is merged into an existing store.
+    async fn merge(postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError>;
     async fn commit(
         postgres_client: &mut PostgresStore<C, A>,
         validation: bool,
@@ -253,7 +299,7 @@ impl<C, A> SnapshotStore<C, A> {
     clippy::struct_excessive_bools,
     reason = "This is a configuration struct"
 )]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct SnapshotDumpSettings {
     pub chunk_size: usize,
     pub dump_webs: bool,
@@ -265,12 +311,15 @@ pub struct SnapshotDumpSettings {
     pub dump_data_types: bool,
     pub dump_embeddings: bool,
     pub dump_relations: bool,
+    /// Restricts the dumped records to the webs and the transaction time of the scope.
+    pub scope: SnapshotScope,
 }
 
 impl PostgresStorePool {
-    async fn read_accounts(
-        &self,
-    ) -> Result<impl Stream<Item = Result<Account, SnapshotDumpError>> + Send, SnapshotDumpError>
+    async fn read_accounts<'a>(
+        &'a self,
+        scope: &'a SnapshotScope,
+    ) -> Result<impl Stream<Item = Result<Account, SnapshotDumpError>> + Send + 'a, SnapshotDumpError>
     {
         // TODO: Make accounts a first-class `Record` type
         //   see https://linear.app/hash/issue/H-752
@@ -286,12 +335,14 @@ impl PostgresStorePool {
             .await
             .map_err(|error| Report::new(error).change_context(SnapshotDumpError::Query))?
             .map_ok(|row| Account { id: row.get(0) })
-            .map_err(|error| Report::new(error).change_context(SnapshotDumpError::Read)))
+            .map_err(|error| Report::new(error).change_context(SnapshotDumpError::Read))
+            .try_filter(|account| ready(scope.contains_web(OwnedById::from(account.id)))))
     }
 
     async fn read_account_groups<'a>(
         &'a self,
         authorization_api: &'a (impl ZanzibarBackend + Sync),
+        scope: &'a SnapshotScope,
     ) -> Result<
         impl Stream<Item = Result<AccountGroup, SnapshotDumpError>> + Send + 'a,
         SnapshotDumpError,
@@ -310,6 +361,9 @@ impl PostgresStorePool {
             .await
             .map_err(|error| Report::new(error).change_context(SnapshotDumpError::Query))?
             .map_err(|error| Report::new(error).change_context(SnapshotDumpError::Read))
+            .try_filter(|row| {
+                ready(scope.contains_web(OwnedById::from(row.get::<_, AccountGroupId>(0))))
+            })
             .and_then(move |row| async move {
                 let id: AccountGroupId = row.get(0);
                 Ok(AccountGroup {
@@ -332,6 +386,7 @@ impl PostgresStorePool {
     async fn read_webs<'a>(
         &'a self,
         authorization_api: &'a (impl ZanzibarBackend + Sync),
+        scope: &'a SnapshotScope,
     ) -> Result<impl Stream<Item = Result<Web, SnapshotDumpError>> + Send + 'a, SnapshotDumpError>
     {
         Ok(self
@@ -343,6 +398,7 @@ impl PostgresStorePool {
             .await
             .map_err(|error| Report::new(error).change_context(SnapshotDumpError::Query))?
             .map_err(|error| Report::new(error).change_context(SnapshotDumpError::Read))
+            .try_filter(|row| ready(scope.contains_web(OwnedById::new(row.get(0)))))
             .and_then(move |row| async move {
                 let id = OwnedById::new(row.get(0));
                 Ok(Web {
@@ -384,10 +440,11 @@ impl PostgresStorePool {
         .map_err(|stream_error| stream_error.change_context(SnapshotDumpError::Read)))
     }
 
-    async fn create_data_type_embedding_stream(
-        &self,
+    async fn create_data_type_embedding_stream<'a>(
+        &'a self,
+        scope: &'a SnapshotScope,
     ) -> Result<
-        impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + Send,
+        impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + Send + 'a,
         SnapshotDumpError,
     > {
         Ok(self
@@ -396,14 +453,22 @@ impl PostgresStorePool {
             .change_context(SnapshotDumpError::Query)?
             .as_client()
             .query_raw(
-                "SELECT base_url, version, embedding, updated_at_transaction_time
+                "SELECT base_url, version, embedding, updated_at_transaction_time, web_id
                  FROM data_type_embeddings
-                 JOIN ontology_ids USING (ontology_id)",
+                 JOIN ontology_ids USING (ontology_id)
+                 LEFT JOIN ontology_owned_metadata USING (ontology_id)",
                 [] as [&(dyn ToSql + Sync); 0],
             )
             .await
             .change_context(SnapshotDumpError::Query)?
             .map(|result| result.change_context(SnapshotDumpError::Query))
+            .try_filter(|row| {
+                ready(
+                    row.get::<_, Option<OwnedById>>(4)
+                        .map_or(true, |web_id| scope.contains_web(web_id))
+                        && scope.contains_update(row.get(3)),
+                )
+            })
             .map_ok(|row| {
                 SnapshotEntry::DataTypeEmbedding(DataTypeEmbeddingRecord {
                     data_type_id: VersionedUrl {
@@ -416,10 +481,11 @@ impl PostgresStorePool {
             }))
     }
 
-    async fn create_property_type_embedding_stream(
-        &self,
+    async fn create_property_type_embedding_stream<'a>(
+        &'a self,
+        scope: &'a SnapshotScope,
     ) -> Result<
-        impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + Send,
+        impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + Send + 'a,
         SnapshotDumpError,
     > {
         Ok(self
@@ -428,14 +494,22 @@ impl PostgresStorePool {
             .change_context(SnapshotDumpError::Query)?
             .as_client()
             .query_raw(
-                "SELECT base_url, version, embedding, updated_at_transaction_time
+                "SELECT base_url, version, embedding, updated_at_transaction_time, web_id
                  FROM property_type_embeddings
-                 JOIN ontology_ids USING (ontology_id)",
+                 JOIN ontology_ids USING (ontology_id)
+                 LEFT JOIN ontology_owned_metadata USING (ontology_id)",
                 [] as [&(dyn ToSql + Sync); 0],
             )
             .await
             .change_context(SnapshotDumpError::Query)?
             .map(|result| result.change_context(SnapshotDumpError::Query))
+            .try_filter(|row| {
+                ready(
+                    row.get::<_, Option<OwnedById>>(4)
+                        .map_or(true, |web_id| scope.contains_web(web_id))
+                        && scope.contains_update(row.get(3)),
+                )
+            })
             .map_ok(|row| {
                 SnapshotEntry::PropertyTypeEmbedding(PropertyTypeEmbeddingRecord {
                     property_type_id: VersionedUrl {
@@ -448,10 +522,11 @@ impl PostgresStorePool {
             }))
     }
 
-    async fn create_entity_type_embedding_stream(
-        &self,
+    async fn create_entity_type_embedding_stream<'a>(
+        &'a self,
+        scope: &'a SnapshotScope,
     ) -> Result<
-        impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + Send,
+        impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + Send + 'a,
         SnapshotDumpError,
     > {
         Ok(self
@@ -460,14 +535,22 @@ impl PostgresStorePool {
             .change_context(SnapshotDumpError::Query)?
             .as_client()
             .query_raw(
-                "SELECT base_url, version, embedding, updated_at_transaction_time
+                "SELECT base_url, version, embedding, updated_at_transaction_time, web_id
                  FROM entity_type_embeddings
-                 JOIN ontology_ids USING (ontology_id)",
+                 JOIN ontology_ids USING (ontology_id)
+                 LEFT JOIN ontology_owned_metadata USING (ontology_id)",
                 [] as [&(dyn ToSql + Sync); 0],
             )
             .await
             .change_context(SnapshotDumpError::Query)?
             .map(|result| result.change_context(SnapshotDumpError::Query))
+            .try_filter(|row| {
+                ready(
+                    row.get::<_, Option<OwnedById>>(4)
+                        .map_or(true, |web_id| scope.contains_web(web_id))
+                        && scope.contains_update(row.get(3)),
+                )
+            })
             .map_ok(|row| {
                 SnapshotEntry::EntityTypeEmbedding(EntityTypeEmbeddingRecord {
                     entity_type_id: VersionedUrl {
@@ -480,10 +563,11 @@ impl PostgresStorePool {
             }))
     }
 
-    async fn create_entity_embedding_stream(
-        &self,
+    async fn create_entity_embedding_stream<'a>(
+        &'a self,
+        scope: &'a SnapshotScope,
     ) -> Result<
-        impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + Send,
+        impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + Send + 'a,
         SnapshotDumpError,
     > {
         Ok(self
@@ -506,6 +590,9 @@ impl PostgresStorePool {
             .await
             .change_context(SnapshotDumpError::Query)?
             .map(|result| result.change_context(SnapshotDumpError::Query))
+            .try_filter(|row| {
+                ready(scope.contains_web(row.get(0)) && scope.contains_update(row.get(6)))
+            })
             .map_ok(|row| {
                 SnapshotEntry::EntityEmbedding(EntityEmbeddingRecord {
                     entity_id: EntityId {
@@ -521,6 +608,35 @@ impl PostgresStorePool {
             }))
     }
 
+    /// Returns the UUIDs of all entities contained in the scope.
+    ///
+    /// An entity is contained if it is owned by one of the webs in scope and at least one of its
+    /// editions was created at or after the transaction time of the scope.
+    async fn read_entity_uuids(
+        &self,
+        scope: &SnapshotScope,
+    ) -> Result<HashSet<EntityUuid>, SnapshotDumpError> {
+        Ok(self
+            .acquire(NoAuthorization, None)
+            .await
+            .change_context(SnapshotDumpError::Query)?
+            .as_client()
+            .query(
+                "
+                    SELECT DISTINCT entity_uuid
+                    FROM entity_temporal_metadata
+                    WHERE (cardinality($1::UUID[]) = 0 OR web_id = ANY($1))
+                      AND ($2::TIMESTAMPTZ IS NULL OR lower(transaction_time) >= $2)
+                ",
+                &[&scope.webs, &scope.transaction_time_since],
+            )
+            .await
+            .change_context(SnapshotDumpError::Query)?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect())
+    }
+
     /// Reads the snapshot from the store into the given sink.
     ///
     /// The sink is expected to be a `futures::Sink` that can be used to write the snapshot entries
@@ -535,50 +651,75 @@ impl PostgresStorePool {
         &self,
         sink: impl Sink<SnapshotEntry, Error = Report<impl Context>> + Send + 'static,
         authorization_api: &(impl ZanzibarBackend + Sync),
-        settings: SnapshotDumpSettings,
+        settings: &SnapshotDumpSettings,
     ) -> Result<(), SnapshotDumpError> {
-        let (snapshot_record_tx, snapshot_record_rx) = mpsc::channel(settings.chunk_size);
+        let (snapshot_record_tx, snapshot_record_rx) =
+            mpsc::channel::<SnapshotEntry>(settings.chunk_size);
+        let snapshot_scope = &settings.scope;
         let snapshot_record_tx = snapshot_record_tx
             .sink_map_err(|error| Report::new(error).change_context(SnapshotDumpError::Write));
 
+        let metadata = SnapshotMetadata {
+            block_protocol_module_versions: BlockProtocolModuleVersions {
+                graph: semver::Version::new(0, 3, 0),
+            },
+            scope: snapshot_scope.clone(),
+            custom: CustomGlobalMetadata,
+        };
+
         let ((), results) = TokioScope::scope_and_block(|scope| {
-            scope.spawn(snapshot_record_rx.map(Ok).forward(
-                sink.sink_map_err(|report| report.change_context(SnapshotDumpError::Write)),
-            ));
-
-            scope.spawn(
-                stream::once(ready(Ok(SnapshotEntry::Snapshot(SnapshotMetadata {
-                    block_protocol_module_versions: BlockProtocolModuleVersions {
-                        graph: semver::Version::new(0, 3, 0),
-                    },
-                    custom: CustomGlobalMetadata,
-                }))))
-                .forward(snapshot_record_tx.clone()),
-            );
+            scope.spawn(async move {
+                let mut sink = pin!(
+                    sink.sink_map_err(|report| report.change_context(SnapshotDumpError::Write))
+                );
+                let mut snapshot_record_rx = snapshot_record_rx;
+                let mut manifest = SnapshotManifestBuilder::default();
 
-            if settings.dump_webs {
+                // The sections are dumped concurrently, but the metadata is always written first,
+                // so a checkpointed restore is able to verify it before staging any records.
+                sink.as_mut()
+                    .feed(SnapshotEntry::Snapshot(metadata))
+                    .await?;
+
+                while let Some(entry) = snapshot_record_rx.next().await {
+                    if let Some(section) = entry.section() {
+                        manifest.update(
+                            section,
+                            &serde_json::to_vec(&entry).change_context(SnapshotDumpError::Write)?,
+                        );
+                    }
+                    sink.as_mut().feed(entry).await?;
+                }
+
+                sink.as_mut()
+                    .send(SnapshotEntry::Manifest(manifest.finish()))
+                    .await?;
+                sink.close().await
+            });
+
+            if settings.dump_accounts {
                 scope.spawn(
-                    self.read_webs(authorization_api)
+                    self.read_accounts(snapshot_scope)
                         .try_flatten_stream()
-                        .map_ok(SnapshotEntry::Web)
+                        .map_ok(SnapshotEntry::Account)
                         .forward(snapshot_record_tx.clone()),
                 );
             }
 
-            if settings.dump_accounts {
+            if settings.dump_account_groups {
                 scope.spawn(
-                    self.read_accounts()
+                    self.read_account_groups(authorization_api, snapshot_scope)
                         .try_flatten_stream()
-                        .map_ok(SnapshotEntry::Account)
+                        .map_ok(SnapshotEntry::AccountGroup)
                         .forward(snapshot_record_tx.clone()),
                 );
             }
 
-            if settings.dump_account_groups {
+            if settings.dump_webs {
                 scope.spawn(
-                    self.read_account_groups(authorization_api)
+                    self.read_webs(authorization_api, snapshot_scope)
                         .try_flatten_stream()
-                        .map_ok(SnapshotEntry::AccountGroup)
+                        .map_ok(SnapshotEntry::Web)
                         .forward(snapshot_record_tx.clone()),
                 );
             }
@@ -587,6 +728,12 @@ impl PostgresStorePool {
                 scope.spawn(
                     self.create_dump_stream::<DataTypeWithMetadata>()
                         .try_flatten_stream()
+                        .try_filter(|record| {
+                            ready(snapshot_scope.contains_ontology_type(
+                                &record.metadata.classification,
+                                &record.metadata.temporal_versioning.transaction_time,
+                            ))
+                        })
                         .and_then(move |record| async move {
                             Ok(SnapshotEntry::DataType(Box::new(DataTypeSnapshotRecord {
                                 schema: record.schema,
@@ -614,6 +761,12 @@ impl PostgresStorePool {
                 scope.spawn(
                         self.create_dump_stream::<PropertyTypeWithMetadata>()
                             .try_flatten_stream()
+                            .try_filter(|record| {
+                                ready(snapshot_scope.contains_ontology_type(
+                                    &record.metadata.classification,
+                                    &record.metadata.temporal_versioning.transaction_time,
+                                ))
+                            })
                             .and_then(move |record| async move {
                                 Ok(SnapshotEntry::PropertyType(Box::new(PropertyTypeSnapshotRecord {
                                     schema: record.schema,
@@ -641,6 +794,12 @@ impl PostgresStorePool {
                 scope.spawn(
                         self.create_dump_stream::<EntityTypeWithMetadata>()
                             .try_flatten_stream()
+                            .try_filter(|record| {
+                                ready(snapshot_scope.contains_ontology_type(
+                                    &record.metadata.classification,
+                                    &record.metadata.temporal_versioning.transaction_time,
+                                ))
+                            })
                             .and_then(move |record| async move {
                                 Ok(SnapshotEntry::EntityType(Box::new(EntityTypeSnapshotRecord {
                                     schema: record.schema,
@@ -668,6 +827,15 @@ impl PostgresStorePool {
                 scope.spawn(
                     self.create_dump_stream::<Entity>()
                         .try_flatten_stream()
+                        .try_filter(|entity| {
+                            ready(
+                                snapshot_scope
+                                    .contains_web(entity.metadata.record_id.entity_id.owned_by_id)
+                                    && snapshot_scope.contains_transaction_time(
+                                        &entity.metadata.temporal_versioning.transaction_time,
+                                    ),
+                            )
+                        })
                         .and_then(move |entity| async move {
                             Ok(SnapshotEntry::Entity(Box::new(EntitySnapshotRecord {
                                 properties: entity.properties,
@@ -681,7 +849,7 @@ impl PostgresStorePool {
 
             if settings.dump_data_types && settings.dump_embeddings {
                 scope.spawn(
-                    self.create_data_type_embedding_stream()
+                    self.create_data_type_embedding_stream(snapshot_scope)
                         .try_flatten_stream()
                         .forward(snapshot_record_tx.clone()),
                 );
@@ -689,7 +857,7 @@ impl PostgresStorePool {
 
             if settings.dump_property_types && settings.dump_embeddings {
                 scope.spawn(
-                    self.create_property_type_embedding_stream()
+                    self.create_property_type_embedding_stream(snapshot_scope)
                         .try_flatten_stream()
                         .forward(snapshot_record_tx.clone()),
                 );
@@ -697,7 +865,7 @@ impl PostgresStorePool {
 
             if settings.dump_entity_types && settings.dump_embeddings {
                 scope.spawn(
-                    self.create_entity_type_embedding_stream()
+                    self.create_entity_type_embedding_stream(snapshot_scope)
                         .try_flatten_stream()
                         .forward(snapshot_record_tx.clone()),
                 );
@@ -705,14 +873,22 @@ impl PostgresStorePool {
 
             if settings.dump_entities && settings.dump_embeddings {
                 scope.spawn(
-                    self.create_entity_embedding_stream()
+                    self.create_entity_embedding_stream(snapshot_scope)
                         .try_flatten_stream()
                         .forward(snapshot_record_tx.clone()),
                 );
             }
 
             if settings.dump_entities && settings.dump_relations {
-                scope.spawn(
+                // Relations are not versioned, so all relations of the entities in scope are
+                // dumped.
+                scope.spawn(async move {
+                    let entity_uuids = if snapshot_scope.is_full() {
+                        None
+                    } else {
+                        Some(self.read_entity_uuids(snapshot_scope).await?)
+                    };
+
                     authorization_api
                         .read_relations::<(EntityUuid, EntityRelationAndSubject)>(
                             RelationshipFilter::from_resource(ResourceFilter::from_kind(
@@ -722,14 +898,22 @@ impl PostgresStorePool {
                         )
                         .try_flatten_stream()
                         .map(|result| result.change_context(SnapshotDumpError::Query))
+                        .try_filter(|(id, _)| {
+                            ready(
+                                entity_uuids
+                                    .as_ref()
+                                    .map_or(true, |entity_uuids| entity_uuids.contains(id)),
+                            )
+                        })
                         .map_ok(|(id, relation)| {
                             SnapshotEntry::Relation(AuthorizationRelation::Entity {
                                 object: id,
                                 relationship: relation,
                             })
                         })
-                        .forward(snapshot_record_tx),
-                );
+                        .forward(snapshot_record_tx)
+                        .await
+                });
             }
         });
 
@@ -770,6 +954,11 @@ where
     ///      this stage might fail. In this case, the transaction is rolled back and the error is
     ///      returned.
     ///
+    /// If the snapshot only contains a part of the graph, as recorded in the [`SnapshotScope`] of
+    /// its metadata, it is merged into the existing store: Before the `commit` stage, records which
+    /// are already present in the store are removed from the temporary tables, and temporal
+    /// metadata and embeddings contained in the snapshot replace the ones in the store.
+    ///
     /// If the input stream contains an `Err` value, the snapshot restore is aborted and the error
     /// is returned.
     ///
@@ -826,7 +1015,48 @@ where
             .await
             .change_context(SnapshotRestoreError::Read)??;
 
-        SnapshotRecordBatch::commit(&mut client, validation)
+        let mut metadata = None;
+        for record in metadata_rx.collect::<Vec<SnapshotMetadata>>().await {
+            if metadata.is_some() {
+                tracing::warn!("found more than one metadata record in the snapshot");
+            }
+
+            ensure!(
+                record.block_protocol_module_versions.graph == semver::Version::new(0, 3, 0),
+                SnapshotRestoreError::Unsupported
+            );
+            metadata = Some(record);
+        }
+
+        let metadata = metadata.ok_or(SnapshotRestoreError::MissingMetadata)?;
+
+        Self::commit_restored(&mut client, &metadata, validation).await?;
+
+        client
+            .commit()
+            .await
+            .change_context(SnapshotRestoreError::Write)
+            .attach_printable("unable to commit snapshot to the store")?;
+
+        tracing::info!("snapshot restore finished");
+
+        Ok(())
+    }
+
+    /// Merges and commits the restored records of the transaction into the store.
+    async fn commit_restored(
+        client: &mut PostgresStore<tokio_postgres::Transaction<'_>, &mut A>,
+        metadata: &SnapshotMetadata,
+        validation: bool,
+    ) -> Result<(), SnapshotRestoreError> {
+        if !metadata.scope.is_full() {
+            tracing::info!(scope = ?metadata.scope, "merging partial snapshot into the store");
+            SnapshotRecordBatch::merge(client)
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+        }
+
+        SnapshotRecordBatch::commit(client, validation)
             .await
             .change_context(SnapshotRestoreError::Write)
             .map_err(|report| {
@@ -846,28 +1076,202 @@ where
                 } else {
                     report
                 }
-            })?;
+            })
+    }
 
-        client
-            .commit()
-            .await
-            .change_context(SnapshotRestoreError::Write)
-            .attach_printable("unable to commit snapshot to the store")?;
+    /// Reads the snapshot from the stream into the store, staging it batch by batch.
+    ///
+    /// In contrast to [`restore_snapshot`], the records are not written into the temporary tables
+    /// of a single transaction, but into staging tables in the database schema named
+    /// `checkpoint`. Every batch of `chunk_size` entries is staged in its own transaction, which
+    /// also records the progress in the checkpoint. If the restore fails, it can be resumed by
+    /// passing the same snapshot and checkpoint again, which skips all staged entries.
+    ///
+    /// The staged records are moved into the store in a single transaction after the snapshot has
+    /// been read completely, so a snapshot, which does not match its manifest, is never committed.
+    /// The schema is dropped in the same transaction. An interrupted restore is discarded by
+    /// dropping the schema. Authorization relations are staged in the schema as well and are only
+    /// written to the authorization backend after the snapshot has been verified, so a corrupt
+    /// snapshot does not leave any relations behind.
+    ///
+    /// The snapshot is required to start with its metadata, as it is written by
+    /// [`PostgresStorePool::dump_snapshot`].
+    ///
+    /// [`restore_snapshot`]: Self::restore_snapshot
+    ///
+    /// # Errors
+    ///
+    /// - If reading a record from the provided stream fails
+    /// - If the snapshot does not belong to the checkpoint
+    /// - If writing a record into the datastore fails
+    /// - If the checkpoint could not be read or written
+    #[expect(clippy::too_many_lines)]
+    pub async fn restore_snapshot_with_checkpoint(
+        &mut self,
+        snapshot: impl Stream<Item = Result<SnapshotEntry, impl Context>> + Send,
+        chunk_size: usize,
+        validation: bool,
+        checkpoint: &str,
+    ) -> Result<(), SnapshotRestoreError> {
+        let mut snapshot =
+            pin!(snapshot.map_err(|report| report.change_context(SnapshotRestoreError::Read)));
 
-        let mut found_metadata = false;
-        for metadata in metadata_rx.collect::<Vec<SnapshotMetadata>>().await {
-            if found_metadata {
-                tracing::warn!("found more than one metadata record in the snapshot");
+        let Some(SnapshotEntry::Snapshot(metadata)) = snapshot.try_next().await? else {
+            return Err(Report::new(SnapshotRestoreError::MissingMetadata)
+                .attach_printable("the snapshot has to start with its metadata"));
+        };
+        ensure!(
+            metadata.block_protocol_module_versions.graph == semver::Version::new(0, 3, 0),
+            SnapshotRestoreError::Unsupported
+        );
+
+        // The staged entries are hashed to verify that a resumed restore reads the same snapshot
+        let mut staged = SnapshotManifestBuilder::default();
+        let stage_entry = |staged: &mut SnapshotManifestBuilder, entry: &SnapshotEntry| {
+            if let Some(section) = entry.section() {
+                staged.update(
+                    section,
+                    &serde_json::to_vec(entry).change_context(SnapshotRestoreError::Read)?,
+                );
             }
-            found_metadata = true;
+            Ok::<_, Report<SnapshotRestoreError>>(())
+        };
 
-            ensure!(
-                metadata.block_protocol_module_versions.graph == semver::Version::new(0, 3, 0),
-                SnapshotRestoreError::Unsupported
-            );
+        let mut progress = if let Some(progress) =
+            SnapshotCheckpoint::load(self.0.as_client(), checkpoint).await?
+        {
+            let mut skipped = 0;
+            if progress.metadata == metadata {
+                while skipped < progress.records {
+                    let Some(entry) = snapshot.try_next().await? else {
+                        break;
+                    };
+                    stage_entry(&mut staged, &entry)?;
+                    skipped += 1;
+                }
+            }
+
+            if skipped != progress.records || staged.finish() != progress.staged {
+                return Err(
+                    Report::new(SnapshotRestoreError::Checkpoint).attach_printable(format!(
+                        "the checkpoint `{checkpoint}` belongs to a different snapshot, drop the \
+                         schema `{checkpoint}` to discard it"
+                    )),
+                );
+            }
+            tracing::info!(records = progress.records, "resuming snapshot restore");
+            progress
+        } else {
+            let progress = SnapshotCheckpoint {
+                metadata: metadata.clone(),
+                records: 0,
+                staged: SnapshotManifest::default(),
+            };
+
+            let mut client = self
+                .0
+                .transaction()
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+            SnapshotRecordBatch::begin(&mut client)
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+            progress.create(client.as_client(), checkpoint).await?;
+            client
+                .commit()
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+            progress
+        };
+
+        loop {
+            let mut entries = Vec::with_capacity(chunk_size);
+            while entries.len() < chunk_size {
+                let Some(entry) = snapshot.try_next().await? else {
+                    break;
+                };
+                stage_entry(&mut staged, &entry)?;
+                progress.records += 1;
+                entries.push(entry);
+            }
+            if entries.is_empty() {
+                break;
+            }
+            progress.staged = staged.finish();
+
+            let client = self
+                .0
+                .transaction()
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+            SnapshotCheckpoint::stage(client.as_client(), checkpoint).await?;
+
+            let (entry_tx, entry_rx, _metadata_rx) = restore::channel(chunk_size);
+            let ((), client) = future::try_join(
+                stream::iter(entries.into_iter().map(Ok)).forward(entry_tx),
+                entry_rx.map(Ok).try_fold(
+                    client,
+                    |mut client, records: SnapshotRecordBatch| async move {
+                        match RelationBatch::try_from(records) {
+                            Ok(relations) => {
+                                SnapshotCheckpoint::stage_relations(
+                                    client.as_client(),
+                                    checkpoint,
+                                    &relations,
+                                )
+                                .await?;
+                            }
+                            Err(records) => records
+                                .write(&mut client)
+                                .await
+                                .change_context(SnapshotRestoreError::Write)?,
+                        }
+                        Ok(client)
+                    },
+                ),
+            )
+            .await?;
+
+            // The checkpoint is updated in the same transaction, so it always matches the staged
+            // records
+            progress.store(client.as_client(), checkpoint).await?;
+            client
+                .commit()
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+            tracing::info!(records = progress.records, "staged snapshot batch");
         }
 
-        ensure!(found_metadata, SnapshotRestoreError::MissingMetadata);
+        // The stream only ends after the manifest has been verified, so the staged records can
+        // be committed now.
+        tracing::info!("snapshot reading finished, committing...");
+
+        let mut client = self
+            .0
+            .transaction()
+            .await
+            .change_context(SnapshotRestoreError::Write)?;
+        SnapshotCheckpoint::stage(client.as_client(), checkpoint).await?;
+        Self::commit_restored(&mut client, &metadata, validation).await?;
+
+        // The relations are written last, so a failure keeps the checkpoint and the restore can be
+        // resumed. Touching a relation, which has been written already, has no effect.
+        let mut written = 0;
+        while let Some((batch_id, relations)) =
+            SnapshotCheckpoint::staged_relations(client.as_client(), checkpoint, written).await?
+        {
+            SnapshotRecordBatch::from(relations)
+                .write(&mut client)
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+            written = batch_id;
+        }
+        SnapshotCheckpoint::remove(client.as_client(), checkpoint).await?;
+        client
+            .commit()
+            .await
+            .change_context(SnapshotRestoreError::Write)
+            .attach_printable("unable to commit snapshot to the store")?;
 
         tracing::info!("snapshot restore finished");
 
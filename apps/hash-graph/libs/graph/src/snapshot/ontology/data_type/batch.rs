@@ -104,6 +104,24 @@ where
         Ok(())
     }
 
+    async fn merge(postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                "
+                    DELETE FROM data_types_tmp USING data_types
+                    WHERE data_types_tmp.ontology_id = data_types.ontology_id;
+
+                    DELETE FROM data_type_embeddings USING data_type_embeddings_tmp
+                    WHERE data_type_embeddings.ontology_id = data_type_embeddings_tmp.ontology_id;
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(
         postgres_client: &mut PostgresStore<C, A>,
         _validation: bool,
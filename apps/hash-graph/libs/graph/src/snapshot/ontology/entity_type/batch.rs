@@ -203,6 +203,45 @@ where
         Ok(())
     }
 
+    async fn merge(postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                "
+                    DELETE FROM entity_type_constrains_properties_on_tmp USING \
+                 entity_type_constrains_properties_on
+                    WHERE entity_type_constrains_properties_on_tmp.source_entity_type_ontology_id \
+                 = entity_type_constrains_properties_on.source_entity_type_ontology_id;
+
+                    DELETE FROM entity_type_inherits_from_tmp USING entity_type_inherits_from
+                    WHERE entity_type_inherits_from_tmp.source_entity_type_ontology_id = \
+                 entity_type_inherits_from.source_entity_type_ontology_id;
+
+                    DELETE FROM entity_type_constrains_links_on_tmp USING \
+                 entity_type_constrains_links_on
+                    WHERE entity_type_constrains_links_on_tmp.source_entity_type_ontology_id = \
+                 entity_type_constrains_links_on.source_entity_type_ontology_id;
+
+                    DELETE FROM entity_type_constrains_link_destinations_on_tmp USING \
+                 entity_type_constrains_link_destinations_on
+                    WHERE \
+                 entity_type_constrains_link_destinations_on_tmp.source_entity_type_ontology_id = \
+                 entity_type_constrains_link_destinations_on.source_entity_type_ontology_id;
+
+                    DELETE FROM entity_types_tmp USING entity_types
+                    WHERE entity_types_tmp.ontology_id = entity_types.ontology_id;
+
+                    DELETE FROM entity_type_embeddings USING entity_type_embeddings_tmp
+                    WHERE entity_type_embeddings.ontology_id = \
+                 entity_type_embeddings_tmp.ontology_id;
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(
         postgres_client: &mut PostgresStore<C, A>,
         _validation: bool,
@@ -129,6 +129,35 @@ where
         Ok(())
     }
 
+    async fn merge(postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                "
+                    DELETE FROM ontology_ids_tmp USING ontology_ids
+                    WHERE ontology_ids_tmp.ontology_id = ontology_ids.ontology_id;
+
+                    DELETE FROM ontology_owned_metadata_tmp USING ontology_owned_metadata
+                    WHERE ontology_owned_metadata_tmp.ontology_id = \
+                 ontology_owned_metadata.ontology_id;
+
+                    DELETE FROM ontology_external_metadata_tmp USING ontology_external_metadata
+                    WHERE ontology_external_metadata_tmp.ontology_id = \
+                 ontology_external_metadata.ontology_id;
+
+                    DELETE FROM ontology_temporal_metadata USING ontology_temporal_metadata_tmp
+                    WHERE ontology_temporal_metadata.ontology_id = \
+                 ontology_temporal_metadata_tmp.ontology_id
+                      AND ontology_temporal_metadata.transaction_time && \
+                 ontology_temporal_metadata_tmp.transaction_time;
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(
         postgres_client: &mut PostgresStore<C, A>,
         _validation: bool,
@@ -139,7 +168,8 @@ where
             .simple_query(
                 "
                     INSERT INTO base_urls
-                        SELECT DISTINCT base_url FROM ontology_ids_tmp;
+                        SELECT DISTINCT base_url FROM ontology_ids_tmp
+                        ON CONFLICT DO NOTHING;
                     INSERT INTO ontology_ids
                         SELECT * FROM ontology_ids_tmp;
                     INSERT INTO ontology_temporal_metadata
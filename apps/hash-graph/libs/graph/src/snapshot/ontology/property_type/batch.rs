@@ -151,6 +151,36 @@ where
         Ok(())
     }
 
+    async fn merge(postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                "
+                    DELETE FROM property_type_constrains_values_on_tmp USING \
+                 property_type_constrains_values_on
+                    WHERE property_type_constrains_values_on_tmp.source_property_type_ontology_id \
+                 = property_type_constrains_values_on.source_property_type_ontology_id;
+
+                    DELETE FROM property_type_constrains_properties_on_tmp USING \
+                 property_type_constrains_properties_on
+                    WHERE \
+                 property_type_constrains_properties_on_tmp.source_property_type_ontology_id = \
+                 property_type_constrains_properties_on.source_property_type_ontology_id;
+
+                    DELETE FROM property_types_tmp USING property_types
+                    WHERE property_types_tmp.ontology_id = property_types.ontology_id;
+
+                    DELETE FROM property_type_embeddings USING property_type_embeddings_tmp
+                    WHERE property_type_embeddings.ontology_id = \
+                 property_type_embeddings_tmp.ontology_id;
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(
         postgres_client: &mut PostgresStore<C, A>,
         _validation: bool,
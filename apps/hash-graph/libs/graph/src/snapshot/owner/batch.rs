@@ -92,6 +92,11 @@ where
         Ok(())
     }
 
+    async fn merge(_postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError> {
+        // Accounts and account groups are inserted with `ON CONFLICT DO NOTHING` already
+        Ok(())
+    }
+
     async fn commit(
         postgres_client: &mut PostgresStore<C, A>,
         _validation: bool,
@@ -1,6 +1,22 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use authorization::{backend::ZanzibarBackend, AuthorizationApi};
+use authorization::{
+    backend::ZanzibarBackend,
+    schema::{
+        AccountGroupRelationAndSubject, DataTypeRelationAndSubject, EntityRelationAndSubject,
+        EntityTypeRelationAndSubject, PropertyTypeRelationAndSubject, WebRelationAndSubject,
+    },
+    AuthorizationApi,
+};
 use error_stack::Result;
+use graph_types::{
+    account::AccountGroupId,
+    knowledge::entity::EntityUuid,
+    ontology::{DataTypeId, EntityTypeId, PropertyTypeId},
+    owned_by_id::OwnedById,
+};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     snapshot::{
@@ -55,6 +71,17 @@ where
         }
     }
 
+    async fn merge(postgres_client: &mut PostgresStore<C, A>) -> Result<(), InsertionError> {
+        AccountRowBatch::merge(postgres_client).await?;
+        WebBatch::merge(postgres_client).await?;
+        OntologyTypeMetadataRowBatch::merge(postgres_client).await?;
+        DataTypeRowBatch::merge(postgres_client).await?;
+        PropertyTypeRowBatch::merge(postgres_client).await?;
+        EntityTypeRowBatch::merge(postgres_client).await?;
+        EntityRowBatch::merge(postgres_client).await?;
+        Ok(())
+    }
+
     async fn commit(
         postgres_client: &mut PostgresStore<C, A>,
         validation: bool,
@@ -69,3 +96,68 @@ where
         Ok(())
     }
 }
+
+/// The authorization relations of a [`SnapshotRecordBatch`].
+///
+/// Relations are written to the authorization backend instead of the staging tables, so they
+/// cannot be rolled back with a transaction. A restore, which stages its records over multiple
+/// transactions, stores them in this form until the snapshot has been verified.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "relations")]
+pub enum RelationBatch {
+    AccountGroups(Vec<(AccountGroupId, AccountGroupRelationAndSubject)>),
+    Webs(Vec<(OwnedById, WebRelationAndSubject)>),
+    DataTypes(HashMap<DataTypeId, Vec<DataTypeRelationAndSubject>>),
+    PropertyTypes(HashMap<PropertyTypeId, Vec<PropertyTypeRelationAndSubject>>),
+    EntityTypes(HashMap<EntityTypeId, Vec<EntityTypeRelationAndSubject>>),
+    Entities(Vec<(EntityUuid, EntityRelationAndSubject)>),
+}
+
+impl TryFrom<SnapshotRecordBatch> for RelationBatch {
+    type Error = SnapshotRecordBatch;
+
+    fn try_from(batch: SnapshotRecordBatch) -> core::result::Result<Self, Self::Error> {
+        match batch {
+            SnapshotRecordBatch::Accounts(AccountRowBatch::AccountGroupAccountRelations(
+                relations,
+            )) => Ok(Self::AccountGroups(relations)),
+            SnapshotRecordBatch::Webs(WebBatch::Relations(relations)) => Ok(Self::Webs(relations)),
+            SnapshotRecordBatch::DataTypes(DataTypeRowBatch::Relations(relations)) => {
+                Ok(Self::DataTypes(relations))
+            }
+            SnapshotRecordBatch::PropertyTypes(PropertyTypeRowBatch::Relations(relations)) => {
+                Ok(Self::PropertyTypes(relations))
+            }
+            SnapshotRecordBatch::EntityTypes(EntityTypeRowBatch::Relations(relations)) => {
+                Ok(Self::EntityTypes(relations))
+            }
+            SnapshotRecordBatch::Entities(EntityRowBatch::Relations(relations)) => {
+                Ok(Self::Entities(relations))
+            }
+            batch => Err(batch),
+        }
+    }
+}
+
+impl From<RelationBatch> for SnapshotRecordBatch {
+    fn from(relations: RelationBatch) -> Self {
+        match relations {
+            RelationBatch::AccountGroups(relations) => {
+                Self::Accounts(AccountRowBatch::AccountGroupAccountRelations(relations))
+            }
+            RelationBatch::Webs(relations) => Self::Webs(WebBatch::Relations(relations)),
+            RelationBatch::DataTypes(relations) => {
+                Self::DataTypes(DataTypeRowBatch::Relations(relations))
+            }
+            RelationBatch::PropertyTypes(relations) => {
+                Self::PropertyTypes(PropertyTypeRowBatch::Relations(relations))
+            }
+            RelationBatch::EntityTypes(relations) => {
+                Self::EntityTypes(EntityTypeRowBatch::Relations(relations))
+            }
+            RelationBatch::Entities(relations) => {
+                Self::Entities(EntityRowBatch::Relations(relations))
+            }
+        }
+    }
+}
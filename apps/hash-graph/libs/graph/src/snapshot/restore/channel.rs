@@ -96,6 +96,8 @@ impl Sink<SnapshotEntry> for SnapshotRecordSender {
                 .start_send_unpin(snapshot)
                 .change_context(SnapshotRestoreError::Read)
                 .attach_printable("could not send snapshot metadata"),
+            // The manifest is verified while decoding the snapshot
+            SnapshotEntry::Manifest(_) => Ok(()),
             SnapshotEntry::Account(account) => self
                 .owner
                 .start_send_unpin(Owner::Account(account))
@@ -1,4 +1,7 @@
 mod batch;
 mod channel;
 
-pub use self::{batch::SnapshotRecordBatch, channel::channel};
+pub use self::{
+    batch::{RelationBatch, SnapshotRecordBatch},
+    channel::channel,
+};
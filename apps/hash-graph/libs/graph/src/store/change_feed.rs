@@ -0,0 +1,93 @@
+use error_stack::Report;
+use graph_types::{
+    account::AccountId, knowledge::entity::EntityRecordId, ontology::OntologyTypeRecordId,
+};
+use serde::{Deserialize, Serialize};
+use temporal_versioning::{Timestamp, TransactionTime};
+
+use crate::store::QueryError;
+
+/// The kind of mutation a [`ChangeEvent`] describes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    /// A new record was created, e.g. a new entity, a new draft, or the first version of an
+    /// ontology type.
+    Create,
+    /// A new edition of an entity or a new version of an ontology type was created, or the record
+    /// was unarchived.
+    Update,
+    /// The record was archived.
+    Archive,
+    /// A draft was published, so the record is the live edition of the entity now.
+    DraftPublish,
+}
+
+/// The record which was changed by a mutation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(tag = "recordType", content = "recordId", rename_all = "camelCase")]
+pub enum ChangedRecord {
+    Entity(EntityRecordId),
+    DataType(OntologyTypeRecordId),
+    PropertyType(OntologyTypeRecordId),
+    EntityType(OntologyTypeRecordId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEvent {
+    /// The position of the event in the change feed, which can be used as cursor.
+    pub sequence_id: i64,
+    pub kind: ChangeKind,
+    #[serde(flatten)]
+    pub record: ChangedRecord,
+    pub transaction_time: Timestamp<TransactionTime>,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ReadChangesParams {
+    /// Only changes after this sequence ID are returned.
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(nullable = false))]
+    pub after: Option<i64>,
+    /// The maximum number of changes which are read, defaults to 1000.
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(nullable = false))]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ReadChangesResponse {
+    pub changes: Vec<ChangeEvent>,
+    /// The sequence ID to pass as `after` to continue reading the change feed.
+    ///
+    /// Changes of records the actor is not allowed to view are skipped, so the cursor may be
+    /// ahead of the last returned change.
+    #[cfg_attr(feature = "utoipa", schema(nullable = false))]
+    pub cursor: Option<i64>,
+}
+
+/// Describes the API of a store implementation for the change feed.
+///
+/// The change feed is an ordered log of the creations, updates, archivals, and draft publications
+/// of entities and ontology types. Changes are only appended when a mutation is committed, so a
+/// change which was read once will not be preceded by changes appended later.
+pub trait ChangeFeedStore {
+    /// Reads the changes after the cursor specified in [`ReadChangesParams`].
+    ///
+    /// # Errors
+    ///
+    /// - if the changes cannot be read
+    fn read_changes(
+        &self,
+        actor_id: AccountId,
+        params: ReadChangesParams,
+    ) -> impl Future<Output = Result<ReadChangesResponse, Report<QueryError>>> + Send;
+}
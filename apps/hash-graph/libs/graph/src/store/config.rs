@@ -1,4 +1,5 @@
 use core::{fmt, num::NonZero};
+use std::path::PathBuf;
 
 use derive_where::derive_where;
 
@@ -9,6 +10,82 @@ pub enum DatabaseType {
     Postgres,
 }
 
+/// Specifies whether TLS is used to connect to the database.
+///
+/// The modes are named after the `sslmode` connection parameter of `libpq`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum DatabaseTlsMode {
+    /// Connects without TLS.
+    #[default]
+    Disable,
+    /// Uses TLS if the server supports it. The certificate of the server is not verified.
+    Prefer,
+    /// Requires TLS. The certificate of the server is not verified.
+    Require,
+    /// Requires TLS and verifies that the certificate of the server is signed by a trusted
+    /// certificate authority and matches the host.
+    VerifyFull,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct DatabaseTlsConfig {
+    /// Specifies whether TLS is used to connect to the database.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "ssl-mode",
+            default_value = "disable",
+            value_enum,
+            env = "HASH_GRAPH_PG_SSL_MODE",
+            global = true
+        )
+    )]
+    pub mode: DatabaseTlsMode,
+
+    /// A PEM file containing the certificate authorities the certificate of the server is
+    /// verified against.
+    ///
+    /// If not specified, the Mozilla root certificates are trusted.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "ssl-root-cert",
+            value_name = "FILE",
+            env = "HASH_GRAPH_PG_SSL_ROOT_CERT",
+            global = true
+        )
+    )]
+    pub root_certificates: Option<PathBuf>,
+
+    /// A PEM file containing the certificate chain used to authenticate against the server.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "ssl-cert",
+            value_name = "FILE",
+            env = "HASH_GRAPH_PG_SSL_CERT",
+            requires = "client_key",
+            global = true
+        )
+    )]
+    pub client_certificate: Option<PathBuf>,
+
+    /// A PEM file containing the private key of the client certificate.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "ssl-key",
+            value_name = "FILE",
+            env = "HASH_GRAPH_PG_SSL_KEY",
+            requires = "client_certificate",
+            global = true
+        )
+    )]
+    pub client_key: Option<PathBuf>,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 #[derive_where(Debug)]
 #[cfg_attr(feature = "clap", derive(clap::Args))]
@@ -80,6 +157,9 @@ pub struct DatabaseConnectionInfo {
         )
     )]
     database: String,
+
+    #[cfg_attr(feature = "clap", clap(flatten))]
+    tls: DatabaseTlsConfig,
 }
 
 impl DatabaseConnectionInfo {
@@ -99,9 +179,22 @@ impl DatabaseConnectionInfo {
             host,
             port,
             database,
+            tls: DatabaseTlsConfig {
+                mode: DatabaseTlsMode::Disable,
+                root_certificates: None,
+                client_certificate: None,
+                client_key: None,
+            },
         }
     }
 
+    /// Sets how TLS is used to connect to the database.
+    #[must_use]
+    pub fn with_tls(mut self, tls: DatabaseTlsConfig) -> Self {
+        self.tls = tls;
+        self
+    }
+
     /// Creates a database connection url.
     ///
     /// Note, that this will reveal the password, so the returned output should not be printed. The
@@ -151,6 +244,11 @@ impl DatabaseConnectionInfo {
     pub fn database(&self) -> &str {
         &self.database
     }
+
+    #[must_use]
+    pub const fn tls(&self) -> &DatabaseTlsConfig {
+        &self.tls
+    }
 }
 
 impl fmt::Display for DatabaseConnectionInfo {
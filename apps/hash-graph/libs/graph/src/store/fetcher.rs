@@ -27,9 +27,14 @@ use graph_types::{
 use tarpc::context;
 use temporal_client::TemporalClient;
 use temporal_versioning::{DecisionTime, Timestamp, TransactionTime};
+use time::OffsetDateTime;
 use tokio::net::ToSocketAddrs;
 use tokio_serde::formats::Json;
-use type_fetcher::fetcher::{FetchedOntologyType, FetcherClient};
+use type_fetcher::{
+    cache::OntologyTypeCache,
+    fetcher::{FetchedOntologyType, Fetcher as _, FetcherClient},
+    fetcher_server::FetchServer,
+};
 use type_system::{
     schema::{DataType, EntityType, EntityTypeReference, PropertyType},
     url::VersionedUrl,
@@ -39,7 +44,9 @@ use crate::{
     ontology::domain_validator::DomainValidator,
     store::{
         account::{InsertAccountGroupIdParams, InsertAccountIdParams, InsertWebIdParams},
+        change_feed::{ChangeFeedStore, ReadChangesParams, ReadChangesResponse},
         crud::{QueryResult, Read, ReadPaginated, Sorting},
+        error::DeletionError,
         knowledge::{
             CountEntitiesParams, CreateEntityParams, GetEntitiesParams, GetEntitiesResponse,
             GetEntitySubgraphParams, GetEntitySubgraphResponse, PatchEntityParams,
@@ -47,17 +54,24 @@ use crate::{
         },
         ontology::{
             ArchiveDataTypeParams, ArchiveEntityTypeParams, ArchivePropertyTypeParams,
-            CountDataTypesParams, CountEntityTypesParams, CountPropertyTypesParams,
-            CreateDataTypeParams, CreateEntityTypeParams, CreatePropertyTypeParams,
-            GetDataTypeSubgraphParams, GetDataTypeSubgraphResponse, GetDataTypesParams,
-            GetDataTypesResponse, GetEntityTypeSubgraphParams, GetEntityTypeSubgraphResponse,
-            GetEntityTypesParams, GetEntityTypesResponse, GetPropertyTypeSubgraphParams,
-            GetPropertyTypeSubgraphResponse, GetPropertyTypesParams, GetPropertyTypesResponse,
-            UnarchiveDataTypeParams, UnarchiveEntityTypeParams, UnarchivePropertyTypeParams,
-            UpdateDataTypeEmbeddingParams, UpdateDataTypesParams, UpdateEntityTypeEmbeddingParams,
-            UpdateEntityTypesParams, UpdatePropertyTypeEmbeddingParams, UpdatePropertyTypesParams,
+            CheckDataTypeUpdateParams, CheckDataTypeUpdateResponse, CheckEntityTypeUpdateParams,
+            CheckEntityTypeUpdateResponse, CheckPropertyTypeUpdateParams,
+            CheckPropertyTypeUpdateResponse, CountDataTypesParams, CountEntityTypesParams,
+            CountPropertyTypesParams, CreateDataTypeParams, CreateEntityTypeParams,
+            CreatePropertyTypeParams, GetDataTypeSubgraphParams, GetDataTypeSubgraphResponse,
+            GetDataTypesParams, GetDataTypesResponse, GetEntityTypeSubgraphParams,
+            GetEntityTypeSubgraphResponse, GetEntityTypesParams, GetEntityTypesResponse,
+            GetPropertyTypeSubgraphParams, GetPropertyTypeSubgraphResponse, GetPropertyTypesParams,
+            GetPropertyTypesResponse, UnarchiveDataTypeParams, UnarchiveEntityTypeParams,
+            UnarchivePropertyTypeParams, UpdateDataTypeEmbeddingParams, UpdateDataTypesParams,
+            UpdateEntityTypeEmbeddingParams, UpdateEntityTypesParams,
+            UpdatePropertyTypeEmbeddingParams, UpdatePropertyTypesParams,
         },
         query::Filter,
+        subscription::{
+            CreateEntitySubscriptionParams, EntitySubscription, ParkedChange, SubscriptionId,
+            SubscriptionSecretKey, SubscriptionStore, SubscriptionTarget,
+        },
         AccountStore, ConflictBehavior, DataTypeStore, EntityStore, EntityTypeStore,
         InsertionError, PropertyTypeStore, QueryError, QueryRecord, StoreError, StorePool,
         UpdateError,
@@ -85,9 +99,38 @@ struct TypeFetcherConnectionInfo<A> {
     domain_validator: DomainValidator,
 }
 
+/// Where ontology types are fetched from, if they are not present in the store.
+enum OntologyTypeSource {
+    /// The type fetcher service.
+    Fetcher(FetcherClient),
+    /// The on-disk cache of the type fetcher, used in offline mode.
+    Cache(FetchServer),
+}
+
+impl OntologyTypeSource {
+    async fn fetch_ontology_types(
+        &self,
+        ontology_type_urls: Vec<VersionedUrl>,
+    ) -> Result<Vec<(FetchedOntologyType, OffsetDateTime)>, StoreError> {
+        match self {
+            Self::Fetcher(client) => client
+                .fetch_ontology_types(context::current(), ontology_type_urls)
+                .await
+                .change_context(StoreError)?
+                .change_context(StoreError),
+            Self::Cache(server) => server
+                .clone()
+                .fetch_ontology_types(context::current(), ontology_type_urls)
+                .await
+                .change_context(StoreError),
+        }
+    }
+}
+
 pub struct FetchingPool<P, A> {
     pool: P,
     connection_info: Option<TypeFetcherConnectionInfo<A>>,
+    cache: Option<OntologyTypeCache>,
 }
 
 impl<P, A> FetchingPool<P, A>
@@ -102,13 +145,18 @@ where
                 config: tarpc::client::Config::default(),
                 domain_validator,
             }),
+            cache: None,
         }
     }
 
-    pub const fn new_offline(pool: P) -> Self {
+    /// Creates a pool which does not connect to the type fetcher.
+    ///
+    /// External ontology types are only served from the `cache`, if one is provided.
+    pub const fn new_offline(pool: P, cache: Option<OntologyTypeCache>) -> Self {
         Self {
             pool,
             connection_info: None,
+            cache,
         }
     }
 }
@@ -133,6 +181,7 @@ where
                 .acquire(authorization_api, temporal_client)
                 .await?,
             connection_info: self.connection_info.clone(),
+            cache: self.cache.clone(),
         })
     }
 
@@ -147,6 +196,7 @@ where
                 .acquire_owned(authorization_api, temporal_client)
                 .await?,
             connection_info: self.connection_info.clone(),
+            cache: self.cache.clone(),
         })
     }
 }
@@ -154,6 +204,7 @@ where
 pub struct FetchingStore<S, A> {
     store: S,
     connection_info: Option<TypeFetcherConnectionInfo<A>>,
+    cache: Option<OntologyTypeCache>,
 }
 
 const DATA_TYPE_RELATIONSHIPS: [DataTypeRelationAndSubject; 1] =
@@ -207,6 +258,17 @@ where
         Ok(FetcherClient::new(connection_info.config.clone(), transport).spawn())
     }
 
+    async fn ontology_type_source(&self) -> Result<OntologyTypeSource, StoreError> {
+        match (&self.connection_info, &self.cache) {
+            (None, Some(cache)) => Ok(OntologyTypeSource::Cache(FetchServer {
+                buffer_size: 10,
+                cache: Some(cache.clone()),
+                offline: true,
+            })),
+            _ => self.fetcher_client().await.map(OntologyTypeSource::Fetcher),
+        }
+    }
+
     pub fn store(&mut self) -> &mut S {
         &mut self.store
     }
@@ -355,8 +417,8 @@ where
             return Ok(fetched_ontology_types);
         }
 
-        let fetcher = self
-            .fetcher_client()
+        let source = self
+            .ontology_type_source()
             .await
             .change_context(StoreError)
             .attach_printable_lazy(|| {
@@ -378,11 +440,7 @@ where
                     urls=?ontology_urls
                 );
                 let _enter = span.enter();
-                fetcher
-                    .fetch_ontology_types(context::current(), ontology_urls)
-                    .await
-                    .change_context(StoreError)?
-                    .change_context(StoreError)?
+                source.fetch_ontology_types(ontology_urls).await?
             };
 
             for (ontology_type, fetched_at) in ontology_types {
@@ -803,6 +861,88 @@ where
     }
 }
 
+impl<S, A> ChangeFeedStore for FetchingStore<S, A>
+where
+    S: ChangeFeedStore + Send + Sync,
+    A: Send + Sync,
+{
+    async fn read_changes(
+        &self,
+        actor_id: AccountId,
+        params: ReadChangesParams,
+    ) -> Result<ReadChangesResponse, QueryError> {
+        self.store.read_changes(actor_id, params).await
+    }
+}
+
+impl<S, A> SubscriptionStore for FetchingStore<S, A>
+where
+    S: SubscriptionStore + Send + Sync,
+    A: Send + Sync,
+{
+    async fn create_entity_subscription(
+        &mut self,
+        actor_id: AccountId,
+        params: CreateEntitySubscriptionParams,
+        secret_key: &SubscriptionSecretKey,
+    ) -> Result<EntitySubscription, InsertionError> {
+        self.store
+            .create_entity_subscription(actor_id, params, secret_key)
+            .await
+    }
+
+    async fn get_entity_subscriptions(
+        &self,
+        actor_id: AccountId,
+    ) -> Result<Vec<EntitySubscription>, QueryError> {
+        self.store.get_entity_subscriptions(actor_id).await
+    }
+
+    async fn delete_entity_subscription(
+        &mut self,
+        actor_id: AccountId,
+        subscription_id: SubscriptionId,
+    ) -> Result<(), DeletionError> {
+        self.store
+            .delete_entity_subscription(actor_id, subscription_id)
+            .await
+    }
+
+    async fn get_subscription_targets(&self) -> Result<Vec<SubscriptionTarget>, QueryError> {
+        self.store.get_subscription_targets().await
+    }
+
+    async fn advance_subscription_cursor(
+        &mut self,
+        subscription_id: SubscriptionId,
+        cursor: i64,
+    ) -> Result<(), UpdateError> {
+        self.store
+            .advance_subscription_cursor(subscription_id, cursor)
+            .await
+    }
+
+    async fn park_subscription_change(
+        &mut self,
+        subscription_id: SubscriptionId,
+        change: ParkedChange,
+    ) -> Result<(), UpdateError> {
+        self.store
+            .park_subscription_change(subscription_id, change)
+            .await
+    }
+
+    async fn get_parked_changes(
+        &self,
+        actor_id: AccountId,
+        subscription_id: SubscriptionId,
+    ) -> Result<Vec<ParkedChange>, QueryError> {
+        self.store
+            .get_parked_changes(actor_id, subscription_id)
+            .await
+    }
+}
+
 impl<S, A> DataTypeStore for FetchingStore<S, A>
 where
     S: DataTypeStore + PropertyTypeStore + EntityTypeStore + Send + Sync,
@@ -888,6 +1028,14 @@ where
         self.store.update_data_type(actor_id, params).await
     }
 
+    async fn check_data_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckDataTypeUpdateParams,
+    ) -> Result<CheckDataTypeUpdateResponse, QueryError> {
+        self.store.check_data_type_update(actor_id, params).await
+    }
+
     async fn archive_data_type(
         &mut self,
         actor_id: AccountId,
@@ -1005,6 +1153,16 @@ where
         self.store.update_property_type(actor_id, params).await
     }
 
+    async fn check_property_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckPropertyTypeUpdateParams,
+    ) -> Result<CheckPropertyTypeUpdateResponse, QueryError> {
+        self.store
+            .check_property_type_update(actor_id, params)
+            .await
+    }
+
     async fn archive_property_type(
         &mut self,
         actor_id: AccountId,
@@ -1120,6 +1278,14 @@ where
         self.store.update_entity_type(actor_id, params).await
     }
 
+    async fn check_entity_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckEntityTypeUpdateParams,
+    ) -> Result<CheckEntityTypeUpdateResponse, QueryError> {
+        self.store.check_entity_type_update(actor_id, params).await
+    }
+
     async fn archive_entity_type(
         &mut self,
         actor_id: AccountId,
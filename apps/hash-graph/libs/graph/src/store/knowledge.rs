@@ -1,25 +1,31 @@
 use alloc::borrow::Cow;
 use core::{error::Error, fmt};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use authorization::{schema::EntityRelationAndSubject, zanzibar::Consistency};
-use error_stack::Report;
+use error_stack::{Report, ResultExt};
 use futures::TryFutureExt;
 use graph_types::{
     account::AccountId,
     knowledge::{
-        entity::{Entity, EntityEmbedding, EntityId, EntityUuid, ProvidedEntityEditionProvenance},
+        entity::{
+            Entity, EntityEditionId, EntityEmbedding, EntityId, EntityUuid,
+            ProvidedEntityEditionProvenance,
+        },
         link::LinkData,
         Confidence, EntityTypeIdDiff, PropertyDiff, PropertyPatchOperation, PropertyPath,
-        PropertyWithMetadataObject,
+        PropertyPathElement, PropertyWithMetadata, PropertyWithMetadataObject, ValueWithMetadata,
     },
     owned_by_id::OwnedById,
 };
 use serde::{Deserialize, Serialize};
-use temporal_versioning::{DecisionTime, Timestamp, TransactionTime};
+use serde_json::Value as JsonValue;
+use temporal_versioning::{
+    DecisionTime, LimitedTemporalBound, TemporalBound, Timestamp, TransactionTime,
+};
 use type_system::{
-    schema::{ClosedEntityType, EntityType},
-    url::VersionedUrl,
+    schema::{ClosedEntityType, EntityType, JsonSchemaValueType},
+    url::{BaseUrl, VersionedUrl},
 };
 #[cfg(feature = "utoipa")]
 use utoipa::{
@@ -31,10 +37,17 @@ use validation::ValidateEntityComponents;
 use crate::{
     knowledge::EntityQueryPath,
     store::{
-        crud::Sorting, postgres::CursorField, query::Filter, InsertionError, NullOrdering,
-        Ordering, QueryError, UpdateError,
+        crud::Sorting, ontology::GetDataTypesParams, postgres::CursorField, query::Filter,
+        DataTypeStore, InsertionError, NullOrdering, Ordering, QueryError, UpdateError,
+    },
+    subgraph::{
+        edges::GraphResolveDepths,
+        temporal_axes::{
+            PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
+            VariableTemporalAxisUnresolved,
+        },
+        Subgraph,
     },
-    subgraph::{edges::GraphResolveDepths, temporal_axes::QueryTemporalAxesUnresolved, Subgraph},
 };
 
 #[derive(Debug, Clone, Deserialize)]
@@ -276,6 +289,10 @@ pub struct CountEntitiesParams<'a> {
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct PatchEntityParams {
     pub entity_id: EntityId,
+    /// If specified, the entity is only updated if its current edition is the expected edition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(nullable = false))]
+    pub expected_edition_id: Option<EntityEditionId>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "utoipa", schema(nullable = false))]
     pub decision_time: Option<Timestamp<DecisionTime>>,
@@ -341,6 +358,295 @@ pub struct DiffEntityResult<'e> {
     pub draft_state: Option<bool>,
 }
 
+/// A declarative transformation of the properties of an entity applied by
+/// [`EntityStore::migrate_entities`].
+///
+/// Mappings only operate on the top-level properties of an entity. If the property a mapping
+/// refers to does not exist on an entity, the mapping is skipped for that entity.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(tag = "kind", rename_all = "camelCase", deny_unknown_fields)]
+pub enum PropertyMapping {
+    /// Moves the property at `from` to `to`.
+    Rename { from: BaseUrl, to: BaseUrl },
+    /// Sets the property to `value` if the entity does not have the property, yet.
+    Default {
+        property: BaseUrl,
+        value: PropertyWithMetadata,
+    },
+    /// Removes the property.
+    Drop { property: BaseUrl },
+    /// Converts the value of the property, or each value of a property array, to the JSON type
+    /// of the data type identified by `data_type_id` and assigns the data type to the value.
+    #[serde(rename_all = "camelCase")]
+    Convert {
+        property: BaseUrl,
+        data_type_id: VersionedUrl,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PropertyMappingError {
+    PropertyMetadataMismatch,
+    PropertyExists {
+        property: BaseUrl,
+    },
+    UnknownDataType {
+        data_type_id: VersionedUrl,
+    },
+    Conversion {
+        property: BaseUrl,
+        json_type: JsonSchemaValueType,
+    },
+}
+
+impl fmt::Display for PropertyMappingError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PropertyMetadataMismatch => {
+                fmt.write_str("The properties of the entity do not match their metadata")
+            }
+            Self::PropertyExists { property } => {
+                write!(fmt, "The property `{property}` already exists")
+            }
+            Self::UnknownDataType { data_type_id } => {
+                write!(fmt, "The data type `{data_type_id}` was not resolved")
+            }
+            Self::Conversion {
+                property,
+                json_type,
+            } => write!(
+                fmt,
+                "The value of property `{property}` cannot be converted to `{json_type}`"
+            ),
+        }
+    }
+}
+
+impl Error for PropertyMappingError {}
+
+impl PropertyMapping {
+    fn apply(
+        &self,
+        properties: &mut HashMap<BaseUrl, PropertyWithMetadata>,
+        json_types: &HashMap<VersionedUrl, JsonSchemaValueType>,
+    ) -> Result<(), Report<PropertyMappingError>> {
+        match self {
+            Self::Rename { from, to } => {
+                if let Some(property) = properties.remove(from) {
+                    if properties.contains_key(to) {
+                        return Err(Report::new(PropertyMappingError::PropertyExists {
+                            property: to.clone(),
+                        }));
+                    }
+                    properties.insert(to.clone(), property);
+                }
+            }
+            Self::Default { property, value } => {
+                properties
+                    .entry(property.clone())
+                    .or_insert_with(|| value.clone());
+            }
+            Self::Drop { property } => {
+                properties.remove(property);
+            }
+            Self::Convert {
+                property,
+                data_type_id,
+            } => {
+                if let Some(value) = properties.get_mut(property) {
+                    let json_type = *json_types.get(data_type_id).ok_or_else(|| {
+                        Report::new(PropertyMappingError::UnknownDataType {
+                            data_type_id: data_type_id.clone(),
+                        })
+                    })?;
+                    if !convert_property(value, json_type, data_type_id) {
+                        return Err(Report::new(PropertyMappingError::Conversion {
+                            property: property.clone(),
+                            json_type,
+                        }));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn convert_property(
+    property: &mut PropertyWithMetadata,
+    json_type: JsonSchemaValueType,
+    data_type_id: &VersionedUrl,
+) -> bool {
+    match property {
+        PropertyWithMetadata::Value(ValueWithMetadata { value, metadata }) => {
+            let Some(converted) = convert_value(value, json_type) else {
+                return false;
+            };
+            *value = converted;
+            metadata.data_type_id = Some(data_type_id.clone());
+            true
+        }
+        PropertyWithMetadata::Array { value, .. } => value
+            .iter_mut()
+            .all(|element| convert_property(element, json_type, data_type_id)),
+        PropertyWithMetadata::Object { .. } => false,
+    }
+}
+
+/// Converts `value` to the JSON type `json_type`.
+///
+/// Returns `None` if the value has no lossless representation in the requested type. Numbers
+/// are only converted to integers if they are stored as integers.
+fn convert_value(value: &JsonValue, json_type: JsonSchemaValueType) -> Option<JsonValue> {
+    match (value, json_type) {
+        (JsonValue::Null, JsonSchemaValueType::Null)
+        | (JsonValue::Bool(_), JsonSchemaValueType::Boolean)
+        | (JsonValue::Number(_), JsonSchemaValueType::Number)
+        | (JsonValue::String(_), JsonSchemaValueType::String) => Some(value.clone()),
+        (JsonValue::Number(number), JsonSchemaValueType::Integer) => number
+            .as_i64()
+            .map(JsonValue::from)
+            .or_else(|| number.as_u64().map(JsonValue::from)),
+        (JsonValue::Number(number), JsonSchemaValueType::String) => {
+            Some(JsonValue::String(number.to_string()))
+        }
+        (JsonValue::Number(number), JsonSchemaValueType::Boolean) => match number.as_u64() {
+            Some(0) => Some(JsonValue::Bool(false)),
+            Some(1) => Some(JsonValue::Bool(true)),
+            _ => None,
+        },
+        (JsonValue::Bool(boolean), JsonSchemaValueType::Number | JsonSchemaValueType::Integer) => {
+            Some(JsonValue::from(u8::from(*boolean)))
+        }
+        (JsonValue::Bool(boolean), JsonSchemaValueType::String) => {
+            Some(JsonValue::String(boolean.to_string()))
+        }
+        (JsonValue::String(string), JsonSchemaValueType::Number) => {
+            string.trim().parse().ok().map(JsonValue::Number)
+        }
+        (JsonValue::String(string), JsonSchemaValueType::Integer) => {
+            string.trim().parse::<i64>().ok().map(JsonValue::from)
+        }
+        (JsonValue::String(string), JsonSchemaValueType::Boolean) => {
+            string.trim().parse().ok().map(JsonValue::Bool)
+        }
+        _ => None,
+    }
+}
+
+/// Creates the patch which moves `entity` from the source to the target type of the migration.
+fn migration_patch(
+    entity: Entity,
+    params: &MigrateEntitiesParams,
+    json_types: &HashMap<VersionedUrl, JsonSchemaValueType>,
+) -> Result<PatchEntityParams, Report<PropertyMappingError>> {
+    let mut entity_type_ids = entity.metadata.entity_type_ids;
+    entity_type_ids.remove(&params.source_entity_type_id);
+    entity_type_ids.insert(params.target_entity_type_id.clone());
+
+    let previous_properties =
+        PropertyWithMetadataObject::from_parts(entity.properties, Some(entity.metadata.properties))
+            .change_context(PropertyMappingError::PropertyMetadataMismatch)?
+            .value;
+
+    let mut properties = previous_properties.clone();
+    for mapping in &params.mappings {
+        mapping.apply(&mut properties, json_types)?;
+    }
+
+    let mut operations = Vec::new();
+    for (base_url, previous_property) in previous_properties {
+        match properties.remove(&base_url) {
+            None => operations.push(PropertyPatchOperation::Remove {
+                path: PropertyPath::from_iter([PropertyPathElement::from(base_url)]),
+            }),
+            Some(property) if property != previous_property => {
+                operations.push(PropertyPatchOperation::Replace {
+                    path: PropertyPath::from_iter([PropertyPathElement::from(base_url)]),
+                    property,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    operations.extend(properties.into_iter().map(|(base_url, property)| {
+        PropertyPatchOperation::Add {
+            path: PropertyPath::from_iter([PropertyPathElement::from(base_url)]),
+            property,
+        }
+    }));
+
+    Ok(PatchEntityParams {
+        entity_id: entity.metadata.record_id.entity_id,
+        expected_edition_id: Some(entity.metadata.record_id.edition_id),
+        decision_time: None,
+        entity_type_ids,
+        properties: operations,
+        draft: None,
+        archived: None,
+        confidence: None,
+        provenance: params.provenance.clone(),
+    })
+}
+
+/// Sorts the entities of a migration by their edition.
+///
+/// The migration reads the entities at a pinned transaction time, so the editions are not changed
+/// by the migration itself or by concurrent updates and the order is stable across batches.
+fn migration_sorting(cursor: Option<EntityQueryCursor<'static>>) -> EntityQuerySorting<'static> {
+    EntityQuerySorting {
+        paths: [
+            EntityQueryPath::Uuid,
+            EntityQueryPath::OwnedById,
+            EntityQueryPath::EditionId,
+        ]
+        .into_iter()
+        .map(|path| EntityQuerySortingRecord {
+            path,
+            ordering: Ordering::Ascending,
+            nulls: None,
+        })
+        .collect(),
+        cursor,
+    }
+}
+
+const DEFAULT_MIGRATION_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MigrateEntitiesParams {
+    pub source_entity_type_id: VersionedUrl,
+    pub target_entity_type_id: VersionedUrl,
+    #[serde(default)]
+    pub mappings: Vec<PropertyMapping>,
+    /// The number of entities read from the store at once, defaults to 1000.
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(nullable = false))]
+    pub batch_size: Option<usize>,
+    #[serde(default)]
+    pub provenance: ProvidedEntityEditionProvenance,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EntityMigrationFailure {
+    pub entity_id: EntityId,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MigrateEntitiesResponse {
+    pub migrated_entities: usize,
+    pub failed_entities: Vec<EntityMigrationFailure>,
+}
+
 /// Describes the API of a store implementation for [Entities].
 ///
 /// [Entities]: Entity
@@ -519,9 +825,183 @@ pub trait EntityStore {
         }
     }
 
+    /// Migrates all entities of the source entity type to the target entity type, which has to be
+    /// another version of the same entity type.
+    ///
+    /// The entities, including drafts, are read in batches and the [`PropertyMapping`]s are
+    /// applied in order. The migrated entities are validated against the target type when they
+    /// are patched. Entities which cannot be migrated keep their previous type and are reported in
+    /// the response instead of aborting the migration. This includes entities which were updated
+    /// after the migration started.
+    ///
+    /// # Errors
+    ///
+    /// - if the source and target are not versions of the same entity type
+    /// - if a data type used by a [`PropertyMapping::Convert`] cannot be read
+    /// - if the entities cannot be read
+    fn migrate_entities(
+        &mut self,
+        actor_id: AccountId,
+        params: MigrateEntitiesParams,
+    ) -> impl Future<Output = Result<MigrateEntitiesResponse, Report<UpdateError>>> + Send
+    where
+        Self: DataTypeStore + Send + Sync,
+    {
+        async move {
+            if params.source_entity_type_id.base_url != params.target_entity_type_id.base_url {
+                return Err(Report::new(UpdateError).attach_printable(format!(
+                    "`{}` and `{}` are not versions of the same entity type",
+                    params.source_entity_type_id, params.target_entity_type_id
+                )));
+            }
+
+            // All batches are read at the time the migration started. Entities updated afterwards
+            // are still returned with the edition at that time, so patching them fails and they
+            // are reported instead of being skipped.
+            let now = Timestamp::<()>::now();
+            let temporal_axes = QueryTemporalAxesUnresolved::DecisionTime {
+                pinned: PinnedTemporalAxisUnresolved::new(Some(Timestamp::from_anonymous(now))),
+                variable: VariableTemporalAxisUnresolved::new(
+                    Some(TemporalBound::Inclusive(Timestamp::from_anonymous(now))),
+                    Some(LimitedTemporalBound::Inclusive(Timestamp::from_anonymous(
+                        now,
+                    ))),
+                ),
+            };
+
+            let mut json_types = HashMap::new();
+            for mapping in &params.mappings {
+                let PropertyMapping::Convert { data_type_id, .. } = mapping else {
+                    continue;
+                };
+                if json_types.contains_key(data_type_id) {
+                    continue;
+                }
+                let data_type = self
+                    .get_data_types(
+                        actor_id,
+                        GetDataTypesParams {
+                            filter: Filter::for_versioned_url(data_type_id),
+                            temporal_axes: temporal_axes.clone(),
+                            include_drafts: false,
+                            after: None,
+                            limit: None,
+                            include_count: false,
+                        },
+                    )
+                    .await
+                    .change_context(UpdateError)?
+                    .data_types
+                    .pop()
+                    .ok_or_else(|| {
+                        Report::new(UpdateError)
+                            .attach_printable(format!("data type `{data_type_id}` does not exist"))
+                    })?;
+                json_types.insert(data_type_id.clone(), data_type.schema.json_type);
+            }
+
+            let batch_size = params
+                .batch_size
+                .unwrap_or(DEFAULT_MIGRATION_BATCH_SIZE)
+                .max(1);
+            let mut response = MigrateEntitiesResponse::default();
+            let mut cursor = None;
+            loop {
+                let GetEntitiesResponse {
+                    entities,
+                    cursor: next_cursor,
+                    count: _,
+                } = self
+                    .get_entities(
+                        actor_id,
+                        GetEntitiesParams {
+                            filter: Filter::for_entity_by_type_id(&params.source_entity_type_id),
+                            temporal_axes: temporal_axes.clone(),
+                            sorting: migration_sorting(cursor.take()),
+                            limit: Some(batch_size),
+                            include_drafts: true,
+                            include_count: false,
+                        },
+                    )
+                    .await
+                    .change_context(UpdateError)?;
+                let is_last_batch = entities.len() < batch_size;
+
+                for entity in entities {
+                    let entity_id = entity.metadata.record_id.entity_id;
+                    let result = match migration_patch(entity, &params, &json_types) {
+                        Ok(patch) => self
+                            .patch_entity(actor_id, patch)
+                            .await
+                            .map_err(|report| format!("{report:#}")),
+                        Err(report) => Err(format!("{report:#}")),
+                    };
+                    match result {
+                        Ok(_) => response.migrated_entities += 1,
+                        Err(reason) => response
+                            .failed_entities
+                            .push(EntityMigrationFailure { entity_id, reason }),
+                    }
+                }
+
+                match next_cursor {
+                    Some(next_cursor) if !is_last_batch => cursor = Some(next_cursor),
+                    _ => break,
+                }
+            }
+
+            Ok(response)
+        }
+    }
+
     fn update_entity_embeddings(
         &mut self,
         actor_id: AccountId,
         params: UpdateEntityEmbeddingsParams<'_>,
     ) -> impl Future<Output = Result<(), Report<UpdateError>>> + Send;
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn value_conversion() {
+        assert_eq!(
+            convert_value(&json!("12"), JsonSchemaValueType::Integer),
+            Some(json!(12))
+        );
+        assert_eq!(
+            convert_value(&json!(" 1.5 "), JsonSchemaValueType::Number),
+            Some(json!(1.5))
+        );
+        assert_eq!(
+            convert_value(&json!(12), JsonSchemaValueType::String),
+            Some(json!("12"))
+        );
+        assert_eq!(
+            convert_value(&json!("true"), JsonSchemaValueType::Boolean),
+            Some(json!(true))
+        );
+        assert_eq!(
+            convert_value(&json!(true), JsonSchemaValueType::Integer),
+            Some(json!(1))
+        );
+        assert_eq!(
+            convert_value(&json!(12), JsonSchemaValueType::Number),
+            Some(json!(12))
+        );
+        assert_eq!(
+            convert_value(&json!(1.5), JsonSchemaValueType::Integer),
+            None
+        );
+        assert_eq!(
+            convert_value(&json!("abc"), JsonSchemaValueType::Number),
+            None
+        );
+        assert_eq!(convert_value(&json!(2), JsonSchemaValueType::Boolean), None);
+        assert_eq!(convert_value(&json!({}), JsonSchemaValueType::String), None);
+    }
+}
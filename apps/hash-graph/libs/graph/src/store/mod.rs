@@ -3,12 +3,14 @@ pub mod error;
 pub mod query;
 
 pub mod account;
+pub mod change_feed;
 mod config;
 pub mod knowledge;
 mod migration;
 pub mod ontology;
 mod pool;
 mod record;
+pub mod subscription;
 mod validation;
 
 mod fetcher;
@@ -21,7 +23,11 @@ use utoipa::ToSchema;
 
 pub use self::{
     account::AccountStore,
-    config::{DatabaseConnectionInfo, DatabasePoolConfig, DatabaseType},
+    change_feed::ChangeFeedStore,
+    config::{
+        DatabaseConnectionInfo, DatabasePoolConfig, DatabaseTlsConfig, DatabaseTlsMode,
+        DatabaseType,
+    },
     error::{
         BaseUrlAlreadyExists, InsertionError, OntologyVersionDoesNotExist, QueryError, StoreError,
         UpdateError,
@@ -36,6 +42,7 @@ pub use self::{
     pool::StorePool,
     postgres::{AsClient, PostgresStore, PostgresStorePool},
     record::{QueryRecord, SubgraphRecord},
+    subscription::SubscriptionStore,
     validation::{StoreCache, StoreProvider},
 };
 
@@ -47,11 +54,23 @@ pub use self::{
 /// raised depending on the implementation, e.g. connection issues.
 #[async_trait]
 pub trait Store:
-    AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore
+    AccountStore
+    + DataTypeStore
+    + PropertyTypeStore
+    + EntityTypeStore
+    + EntityStore
+    + ChangeFeedStore
+    + SubscriptionStore
 {
 }
 impl<S> Store for S where
-    S: AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore
+    S: AccountStore
+        + DataTypeStore
+        + PropertyTypeStore
+        + EntityTypeStore
+        + EntityStore
+        + ChangeFeedStore
+        + SubscriptionStore
 {
 }
 
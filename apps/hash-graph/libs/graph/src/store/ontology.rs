@@ -17,7 +17,10 @@ use graph_types::{
 use serde::{Deserialize, Serialize};
 use temporal_versioning::{Timestamp, TransactionTime};
 use type_system::{
-    schema::{DataType, EntityType, PropertyType},
+    schema::{
+        DataType, DataTypeIncompatibility, EntityType, EntityTypeIncompatibility, PropertyType,
+        PropertyTypeIncompatibility,
+    },
     url::{BaseUrl, VersionedUrl},
 };
 
@@ -116,6 +119,25 @@ pub struct UpdateDataTypesParams<R> {
     pub provenance: ProvidedOntologyEditionProvenance,
 }
 
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CheckDataTypeUpdateParams {
+    pub schema: DataType,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CheckDataTypeUpdateResponse {
+    /// Changes of the new schema which may cause values of the previous version to be invalid.
+    pub incompatibilities: Vec<DataTypeIncompatibility>,
+    /// The number of existing entities affected by the update.
+    pub checked_entities: usize,
+    /// The number of existing entities which would fail validation after the update.
+    pub invalid_entities: usize,
+}
+
 #[derive(Debug, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -238,6 +260,20 @@ pub trait DataTypeStore {
     where
         R: IntoIterator<Item = DataTypeRelationAndSubject> + Send + Sync;
 
+    /// Checks if updating an existing [`DataType`] to the provided schema is compatible with the
+    /// current version of the data type without updating it.
+    ///
+    /// The existing entities affected by the update are validated against the new schema.
+    ///
+    /// # Errors
+    ///
+    /// - if the current version of the [`DataType`] doesn't exist.
+    fn check_data_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckDataTypeUpdateParams,
+    ) -> impl Future<Output = Result<CheckDataTypeUpdateResponse, QueryError>> + Send;
+
     /// Archives the definition of an existing [`DataType`].
     ///
     /// # Errors
@@ -354,6 +390,25 @@ pub struct UpdatePropertyTypesParams<R> {
     pub provenance: ProvidedOntologyEditionProvenance,
 }
 
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CheckPropertyTypeUpdateParams {
+    pub schema: PropertyType,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CheckPropertyTypeUpdateResponse {
+    /// Changes of the new schema which may cause values of the previous version to be invalid.
+    pub incompatibilities: Vec<PropertyTypeIncompatibility>,
+    /// The number of existing entities affected by the update.
+    pub checked_entities: usize,
+    /// The number of existing entities which would fail validation after the update.
+    pub invalid_entities: usize,
+}
+
 #[derive(Debug, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -476,6 +531,20 @@ pub trait PropertyTypeStore {
     where
         R: IntoIterator<Item = PropertyTypeRelationAndSubject> + Send + Sync;
 
+    /// Checks if updating an existing [`PropertyType`] to the provided schema is compatible with
+    /// the current version of the property type without updating it.
+    ///
+    /// The existing entities affected by the update are validated against the new schema.
+    ///
+    /// # Errors
+    ///
+    /// - if the current version of the [`PropertyType`] doesn't exist.
+    fn check_property_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckPropertyTypeUpdateParams,
+    ) -> impl Future<Output = Result<CheckPropertyTypeUpdateResponse, QueryError>> + Send;
+
     /// Archives the definition of an existing [`PropertyType`].
     ///
     /// # Errors
@@ -595,6 +664,25 @@ pub struct UpdateEntityTypesParams<R> {
     pub provenance: ProvidedOntologyEditionProvenance,
 }
 
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CheckEntityTypeUpdateParams {
+    pub schema: EntityType,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CheckEntityTypeUpdateResponse {
+    /// Changes of the new schema which may cause values of the previous version to be invalid.
+    pub incompatibilities: Vec<EntityTypeIncompatibility>,
+    /// The number of existing entities affected by the update.
+    pub checked_entities: usize,
+    /// The number of existing entities which would fail validation after the update.
+    pub invalid_entities: usize,
+}
+
 #[derive(Debug, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -717,6 +805,20 @@ pub trait EntityTypeStore {
     where
         R: IntoIterator<Item = EntityTypeRelationAndSubject> + Send + Sync;
 
+    /// Checks if updating an existing [`EntityType`] to the provided schema is compatible with the
+    /// current version of the entity type without updating it.
+    ///
+    /// The existing entities affected by the update are validated against the new schema.
+    ///
+    /// # Errors
+    ///
+    /// - if the current version of the [`EntityType`] doesn't exist.
+    fn check_entity_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckEntityTypeUpdateParams,
+    ) -> impl Future<Output = Result<CheckEntityTypeUpdateResponse, QueryError>> + Send;
+
     /// Archives the definition of an existing [`EntityType`].
     ///
     /// # Errors
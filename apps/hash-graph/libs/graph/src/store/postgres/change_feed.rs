@@ -0,0 +1,365 @@
+use std::collections::HashSet;
+
+use authorization::{
+    schema::{DataTypePermission, EntityPermission, EntityTypePermission, PropertyTypePermission},
+    zanzibar::Consistency,
+    AuthorizationApi,
+};
+use error_stack::{Report, ResultExt};
+use graph_types::{
+    account::AccountId,
+    knowledge::entity::{DraftId, EntityEditionId, EntityId, EntityRecordId, EntityUuid},
+    ontology::{DataTypeId, EntityTypeId, OntologyTypeRecordId, PropertyTypeId},
+    owned_by_id::OwnedById,
+};
+use temporal_versioning::{Timestamp, TransactionTime};
+use tokio_postgres::{GenericClient, Transaction};
+use type_system::url::{BaseUrl, OntologyTypeVersion};
+
+use crate::store::{
+    change_feed::{
+        ChangeEvent, ChangeFeedStore, ChangeKind, ChangedRecord, ReadChangesParams,
+        ReadChangesResponse,
+    },
+    AsClient, PostgresStore, QueryError, StoreError,
+};
+
+/// Key of the transaction-level advisory lock which serializes appending to the change feed.
+///
+/// The lock is held from appending the changes until the transaction is committed, so changes
+/// become visible in the order of their sequence IDs.
+const CHANGE_FEED_LOCK: i64 = 0x6368_616E_6765;
+
+const DEFAULT_CHANGE_FEED_LIMIT: usize = 1000;
+
+/// The type of an ontology record in the change feed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum OntologyChangeRecord {
+    DataType,
+    PropertyType,
+    EntityType,
+}
+
+impl OntologyChangeRecord {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::DataType => "dataType",
+            Self::PropertyType => "propertyType",
+            Self::EntityType => "entityType",
+        }
+    }
+}
+
+const fn change_kind_as_str(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Create => "create",
+        ChangeKind::Update => "update",
+        ChangeKind::Archive => "archive",
+        ChangeKind::DraftPublish => "draftPublish",
+    }
+}
+
+fn parse_change_kind(kind: &str) -> Result<ChangeKind, Report<QueryError>> {
+    match kind {
+        "create" => Ok(ChangeKind::Create),
+        "update" => Ok(ChangeKind::Update),
+        "archive" => Ok(ChangeKind::Archive),
+        "draftPublish" => Ok(ChangeKind::DraftPublish),
+        _ => Err(Report::new(QueryError).attach_printable(format!("unknown change kind `{kind}`"))),
+    }
+}
+
+impl<A> PostgresStore<Transaction<'_>, A>
+where
+    A: Send + Sync,
+{
+    async fn lock_change_feed(&self) -> Result<(), Report<StoreError>> {
+        self.as_client()
+            .execute("SELECT pg_advisory_xact_lock($1);", &[&CHANGE_FEED_LOCK])
+            .await
+            .change_context(StoreError)?;
+        Ok(())
+    }
+
+    /// Appends changes of entities to the change feed.
+    ///
+    /// This should be called directly before committing the transaction as the change feed is
+    /// locked until the transaction ends.
+    pub(crate) async fn record_entity_changes(
+        &self,
+        kind: ChangeKind,
+        records: impl IntoIterator<Item = EntityRecordId> + Send,
+        transaction_time: Timestamp<TransactionTime>,
+    ) -> Result<(), Report<StoreError>> {
+        let mut web_ids = Vec::new();
+        let mut entity_uuids = Vec::new();
+        let mut draft_ids = Vec::new();
+        let mut edition_ids = Vec::new();
+        for record in records {
+            web_ids.push(record.entity_id.owned_by_id);
+            entity_uuids.push(record.entity_id.entity_uuid);
+            draft_ids.push(record.entity_id.draft_id);
+            edition_ids.push(record.edition_id);
+        }
+        if edition_ids.is_empty() {
+            return Ok(());
+        }
+
+        self.lock_change_feed().await?;
+        self.as_client()
+            .execute(
+                "
+                    INSERT INTO change_feed (
+                        kind,
+                        record_type,
+                        web_id,
+                        entity_uuid,
+                        draft_id,
+                        entity_edition_id,
+                        transaction_time
+                    )
+                    SELECT $1, 'entity', web_id, entity_uuid, draft_id, entity_edition_id, $6
+                    FROM UNNEST($2::UUID[], $3::UUID[], $4::UUID[], $5::UUID[])
+                        AS records(web_id, entity_uuid, draft_id, entity_edition_id);
+                ",
+                &[
+                    &change_kind_as_str(kind),
+                    &web_ids,
+                    &entity_uuids,
+                    &draft_ids,
+                    &edition_ids,
+                    &transaction_time,
+                ],
+            )
+            .await
+            .change_context(StoreError)?;
+        Ok(())
+    }
+
+    /// Appends changes of ontology types to the change feed.
+    ///
+    /// This should be called directly before committing the transaction as the change feed is
+    /// locked until the transaction ends.
+    pub(crate) async fn record_ontology_changes(
+        &self,
+        kind: ChangeKind,
+        record_type: OntologyChangeRecord,
+        records: impl IntoIterator<Item = &OntologyTypeRecordId> + Send,
+    ) -> Result<(), Report<StoreError>> {
+        let (base_urls, versions): (Vec<_>, Vec<_>) = records
+            .into_iter()
+            .map(|record_id| (record_id.base_url.as_str(), record_id.version))
+            .unzip();
+        if base_urls.is_empty() {
+            return Ok(());
+        }
+
+        self.lock_change_feed().await?;
+        self.as_client()
+            .execute(
+                "
+                    INSERT INTO change_feed (
+                        kind,
+                        record_type,
+                        base_url,
+                        version,
+                        transaction_time
+                    )
+                    SELECT $1, $2, base_url, version, now()
+                    FROM UNNEST($3::TEXT[], $4::BIGINT[]) AS records(base_url, version);
+                ",
+                &[
+                    &change_kind_as_str(kind),
+                    &record_type.as_str(),
+                    &base_urls,
+                    &versions,
+                ],
+            )
+            .await
+            .change_context(StoreError)?;
+        Ok(())
+    }
+}
+
+impl<C, A> ChangeFeedStore for PostgresStore<C, A>
+where
+    C: AsClient,
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn read_changes(
+        &self,
+        actor_id: AccountId,
+        params: ReadChangesParams,
+    ) -> Result<ReadChangesResponse, Report<QueryError>> {
+        let limit =
+            i64::try_from(params.limit.unwrap_or(DEFAULT_CHANGE_FEED_LIMIT)).unwrap_or(i64::MAX);
+        let rows = self
+            .as_client()
+            .query(
+                "
+                    SELECT
+                        sequence_id,
+                        kind,
+                        record_type,
+                        web_id,
+                        entity_uuid,
+                        draft_id,
+                        entity_edition_id,
+                        base_url,
+                        version,
+                        transaction_time
+                    FROM change_feed
+                    WHERE sequence_id > $1
+                    ORDER BY sequence_id
+                    LIMIT $2;
+                ",
+                &[&params.after.unwrap_or(0), &limit],
+            )
+            .await
+            .change_context(QueryError)?;
+
+        let cursor = rows.last().map(|row| row.get::<_, i64>(0)).or(params.after);
+        let changes = rows
+            .into_iter()
+            .map(|row| {
+                let record_type: &str = row.get(2);
+                let record = if record_type == "entity" {
+                    ChangedRecord::Entity(EntityRecordId {
+                        entity_id: EntityId {
+                            owned_by_id: row.get::<_, OwnedById>(3),
+                            entity_uuid: row.get::<_, EntityUuid>(4),
+                            draft_id: row.get::<_, Option<DraftId>>(5),
+                        },
+                        edition_id: row.get::<_, EntityEditionId>(6),
+                    })
+                } else {
+                    let record_id = OntologyTypeRecordId {
+                        base_url: row.get::<_, BaseUrl>(7),
+                        version: row.get::<_, OntologyTypeVersion>(8),
+                    };
+                    match record_type {
+                        "dataType" => ChangedRecord::DataType(record_id),
+                        "propertyType" => ChangedRecord::PropertyType(record_id),
+                        "entityType" => ChangedRecord::EntityType(record_id),
+                        _ => {
+                            return Err(Report::new(QueryError)
+                                .attach_printable(format!("unknown record type `{record_type}`")));
+                        }
+                    }
+                };
+                Ok(ChangeEvent {
+                    sequence_id: row.get(0),
+                    kind: parse_change_kind(row.get(1))?,
+                    record,
+                    transaction_time: row.get(9),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let visible = self.visible_records(actor_id, &changes).await?;
+        Ok(ReadChangesResponse {
+            changes: changes
+                .into_iter()
+                .filter(|change| visible.contains(&change.record))
+                .collect(),
+            cursor,
+        })
+    }
+}
+
+impl<C, A> PostgresStore<C, A>
+where
+    C: AsClient,
+    A: AuthorizationApi,
+{
+    /// Returns the records of `changes` which the actor is allowed to view.
+    async fn visible_records(
+        &self,
+        actor_id: AccountId,
+        changes: &[ChangeEvent],
+    ) -> Result<HashSet<ChangedRecord>, Report<QueryError>> {
+        let mut entities = Vec::new();
+        let mut data_types = Vec::new();
+        let mut property_types = Vec::new();
+        let mut entity_types = Vec::new();
+        for change in changes {
+            match &change.record {
+                ChangedRecord::Entity(record_id) => entities.push(record_id.entity_id),
+                ChangedRecord::DataType(record_id) => {
+                    data_types.push(DataTypeId::from_record_id(record_id));
+                }
+                ChangedRecord::PropertyType(record_id) => {
+                    property_types.push(PropertyTypeId::from_record_id(record_id));
+                }
+                ChangedRecord::EntityType(record_id) => {
+                    entity_types.push(EntityTypeId::from_record_id(record_id));
+                }
+            }
+        }
+
+        let (entity_permissions, _) = self
+            .authorization_api
+            .check_entities_permission(
+                actor_id,
+                EntityPermission::View,
+                entities,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?;
+        let (data_type_permissions, _) = self
+            .authorization_api
+            .check_data_types_permission(
+                actor_id,
+                DataTypePermission::View,
+                data_types,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?;
+        let (property_type_permissions, _) = self
+            .authorization_api
+            .check_property_types_permission(
+                actor_id,
+                PropertyTypePermission::View,
+                property_types,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?;
+        let (entity_type_permissions, _) = self
+            .authorization_api
+            .check_entity_types_permission(
+                actor_id,
+                EntityTypePermission::View,
+                entity_types,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?;
+
+        Ok(changes
+            .iter()
+            .filter(|change| match &change.record {
+                ChangedRecord::Entity(record_id) => entity_permissions
+                    .get(&record_id.entity_id.entity_uuid)
+                    .copied()
+                    .unwrap_or(false),
+                ChangedRecord::DataType(record_id) => data_type_permissions
+                    .get(&DataTypeId::from_record_id(record_id))
+                    .copied()
+                    .unwrap_or(false),
+                ChangedRecord::PropertyType(record_id) => property_type_permissions
+                    .get(&PropertyTypeId::from_record_id(record_id))
+                    .copied()
+                    .unwrap_or(false),
+                ChangedRecord::EntityType(record_id) => entity_type_permissions
+                    .get(&EntityTypeId::from_record_id(record_id))
+                    .copied()
+                    .unwrap_or(false),
+            })
+            .map(|change| change.record.clone())
+            .collect())
+    }
+}
@@ -41,17 +41,18 @@ use temporal_versioning::{
 use tokio_postgres::{error::SqlState, GenericClient, Row};
 use type_system::{schema::ClosedEntityType, url::VersionedUrl};
 use uuid::Uuid;
-use validation::{Validate, ValidateEntityComponents};
+use validation::{OntologyTypeProvider, Validate, ValidateEntityComponents};
 
 use crate::{
     knowledge::EntityQueryPath,
     ontology::EntityTypeQueryPath,
     store::{
+        change_feed::ChangeKind,
         crud::{QueryResult, Read, ReadPaginated, Sorting},
-        error::{DeletionError, EntityDoesNotExist, RaceConditionOnUpdate},
+        error::{DeletionError, EntityDoesNotExist, EntityEditionConflict, RaceConditionOnUpdate},
         knowledge::{
-            CountEntitiesParams, CreateEntityParams, EntityQuerySorting, EntityValidationType,
-            GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams,
+            CountEntitiesParams, CreateEntityParams, EntityQuerySorting, EntityQuerySortingRecord,
+            EntityValidationType, GetEntitiesParams, GetEntitiesResponse, GetEntitySubgraphParams,
             GetEntitySubgraphResponse, PatchEntityParams, UpdateEntityEmbeddingsParams,
             ValidateEntityError, ValidateEntityParams,
         },
@@ -70,7 +71,7 @@ use crate::{
         },
         query::{Filter, FilterExpression, Parameter, ParameterList},
         validation::StoreProvider,
-        AsClient, EntityStore, InsertionError, PostgresStore, QueryError, StoreCache,
+        AsClient, EntityStore, InsertionError, Ordering, PostgresStore, QueryError, StoreCache,
         SubgraphRecord, UpdateError,
     },
     subgraph::{
@@ -85,6 +86,9 @@ use crate::{
     },
 };
 
+/// The number of entities read at once when validating the entities of a type.
+const VALIDATION_PAGE_SIZE: usize = 1000;
+
 impl<C, A> PostgresStore<C, A>
 where
     C: AsClient,
@@ -313,6 +317,147 @@ where
         Ok(())
     }
 
+    /// Validates the current editions of the entities matching `filter`, which the actor is
+    /// allowed to view, using the schemas provided by `cache`.
+    ///
+    /// Schemas which are not contained in the cache are read from the store. Drafts are validated
+    /// the same way as when they are created, so missing required properties are not reported for
+    /// them.
+    ///
+    /// Returns the number of checked entities and the number of entities which failed validation.
+    #[tracing::instrument(level = "info", skip(self, filter, cache))]
+    pub(crate) async fn count_invalid_entities(
+        &self,
+        actor_id: AccountId,
+        filter: &Filter<'_, Entity>,
+        cache: StoreCache,
+    ) -> Result<(usize, usize), QueryError> {
+        let temporal_axes = QueryTemporalAxesUnresolved::DecisionTime {
+            pinned: PinnedTemporalAxisUnresolved::new(None),
+            variable: VariableTemporalAxisUnresolved::new(None, None),
+        }
+        .resolve();
+
+        let validator_provider = StoreProvider {
+            store: self,
+            cache,
+            authorization: Some((
+                &self.authorization_api,
+                actor_id,
+                Consistency::FullyConsistent,
+            )),
+        };
+
+        // The transaction time is pinned, so sorting by the edition identifiers yields a stable
+        // order and entities updated while validating are neither skipped nor checked twice.
+        let mut sorting = EntityQuerySorting {
+            paths: [
+                EntityQueryPath::Uuid,
+                EntityQueryPath::OwnedById,
+                EntityQueryPath::EditionId,
+            ]
+            .into_iter()
+            .map(|path| EntityQuerySortingRecord {
+                path,
+                ordering: Ordering::Ascending,
+                nulls: None,
+            })
+            .collect(),
+            cursor: None,
+        };
+
+        let mut checked_entities = 0;
+        let mut invalid_entities = 0;
+        loop {
+            let (rows, artifacts) =
+                ReadPaginated::<Entity, EntityQuerySorting>::read_paginated_vec(
+                    self,
+                    filter,
+                    Some(&temporal_axes),
+                    &sorting,
+                    Some(VALIDATION_PAGE_SIZE),
+                    true,
+                )
+                .await?;
+            let num_rows = rows.len();
+            if let Some(row) = rows.last() {
+                sorting.set_cursor(row.decode_cursor(&artifacts));
+            }
+
+            // An entity may be returned multiple times if several of its types match the filter
+            let entities = rows
+                .into_iter()
+                .map(|row: Row| {
+                    let entity: Entity = row.decode_record(&artifacts);
+                    (entity.metadata.record_id.entity_id, entity)
+                })
+                .collect::<HashMap<_, _>>();
+
+            let (permissions, _) = self
+                .authorization_api
+                .check_entities_permission(
+                    actor_id,
+                    EntityPermission::View,
+                    entities.keys().copied(),
+                    Consistency::FullyConsistent,
+                )
+                .await
+                .change_context(QueryError)?;
+
+            for (entity_id, entity) in entities {
+                if !permissions
+                    .get(&entity_id.entity_uuid)
+                    .copied()
+                    .unwrap_or(false)
+                {
+                    continue;
+                }
+                checked_entities += 1;
+
+                let mut schema = ClosedEntityType::default();
+                for entity_type_id in &entity.metadata.entity_type_ids {
+                    let entity_type = OntologyTypeProvider::<ClosedEntityType>::provide_type(
+                        &validator_provider,
+                        entity_type_id,
+                    )
+                    .await?;
+                    schema.extend([ClosedEntityType::clone(&entity_type)]);
+                }
+                let components = if entity_id.draft_id.is_some() {
+                    ValidateEntityComponents::draft()
+                } else {
+                    ValidateEntityComponents::full()
+                };
+
+                let properties = PropertyWithMetadataObject::from_parts(
+                    entity.properties,
+                    Some(entity.metadata.properties),
+                )
+                .change_context(QueryError)?;
+                let properties_are_valid = properties
+                    .validate(&schema, components, &validator_provider)
+                    .await
+                    .is_ok();
+                let link_data_is_valid = entity
+                    .link_data
+                    .as_ref()
+                    .validate(&schema, components, &validator_provider)
+                    .await
+                    .is_ok();
+
+                if !properties_are_valid || !link_data_is_valid {
+                    invalid_entities += 1;
+                }
+            }
+
+            if num_rows < VALIDATION_PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok((checked_entities, invalid_entities))
+    }
+
     #[tracing::instrument(level = "info", skip(self, params))]
     async fn get_entities_impl(
         &self,
@@ -788,6 +933,15 @@ where
             .change_context(InsertionError)
             .attach(StatusCode::InvalidArgument)?;
 
+        transaction
+            .record_entity_changes(
+                ChangeKind::Create,
+                entities.iter().map(|entity| entity.metadata.record_id),
+                transaction_time,
+            )
+            .await
+            .change_context(InsertionError)?;
+
         let commit_result = transaction.commit().await.change_context(InsertionError);
         if let Err(mut error) = commit_result {
             if let Err(auth_error) = self
@@ -1178,6 +1332,19 @@ where
                     .attach_printable(params.entity_id)
                     .change_context(UpdateError)
             })?;
+        if let Some(expected_edition_id) = params.expected_edition_id {
+            if expected_edition_id != locked_row.entity_edition_id {
+                bail!(
+                    Report::new(EntityEditionConflict)
+                        .attach_printable(format!("expected edition: {expected_edition_id:?}"))
+                        .attach_printable(format!(
+                            "current edition: {:?}",
+                            locked_row.entity_edition_id
+                        ))
+                        .change_context(UpdateError)
+                );
+            }
+        }
         let ClosedTemporalBound::Inclusive(locked_transaction_time) =
             *locked_row.transaction_time.start();
         let ClosedTemporalBound::Inclusive(locked_decision_time) =
@@ -1440,6 +1607,27 @@ where
             .change_context(UpdateError)
             .attach(StatusCode::InvalidArgument)?;
 
+        let change_kind = if archived && !previous_entity.metadata.archived {
+            ChangeKind::Archive
+        } else {
+            match (was_draft_before, draft) {
+                (true, false) => ChangeKind::DraftPublish,
+                (false, true) => ChangeKind::Create,
+                (true, true) | (false, false) => ChangeKind::Update,
+            }
+        };
+        transaction
+            .record_entity_changes(
+                change_kind,
+                [EntityRecordId {
+                    entity_id: params.entity_id,
+                    edition_id,
+                }],
+                transaction_time,
+            )
+            .await
+            .change_context(UpdateError)?;
+
         transaction.commit().await.change_context(UpdateError)?;
 
         let entity_metadata = EntityMetadata {
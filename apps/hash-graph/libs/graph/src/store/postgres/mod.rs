@@ -1,9 +1,12 @@
+mod change_feed;
 mod crud;
 mod knowledge;
 mod migration;
 mod ontology;
 mod pool;
 pub(crate) mod query;
+mod subscription;
+mod tls;
 mod traversal_context;
 
 use alloc::sync::Arc;
@@ -32,26 +32,28 @@ use type_system::{
 use crate::{
     ontology::DataTypeQueryPath,
     store::{
-        crud::{QueryResult, ReadPaginated, VertexIdSorting},
+        change_feed::ChangeKind,
+        crud::{QueryResult, Read, ReadPaginated, VertexIdSorting},
         error::DeletionError,
         ontology::{
-            ArchiveDataTypeParams, CountDataTypesParams, CreateDataTypeParams,
-            GetDataTypeSubgraphParams, GetDataTypeSubgraphResponse, GetDataTypesParams,
-            GetDataTypesResponse, UnarchiveDataTypeParams, UpdateDataTypeEmbeddingParams,
-            UpdateDataTypesParams,
+            ArchiveDataTypeParams, CheckDataTypeUpdateParams, CheckDataTypeUpdateResponse,
+            CountDataTypesParams, CreateDataTypeParams, GetDataTypeSubgraphParams,
+            GetDataTypeSubgraphResponse, GetDataTypesParams, GetDataTypesResponse,
+            UnarchiveDataTypeParams, UpdateDataTypeEmbeddingParams, UpdateDataTypesParams,
         },
         postgres::{
+            change_feed::OntologyChangeRecord,
             crud::QueryRecordDecode,
             ontology::{
-                read::OntologyTypeTraversalData, OntologyId,
+                previous_version, read::OntologyTypeTraversalData, OntologyId,
                 PostgresOntologyTypeClassificationMetadata,
             },
             query::{Distinctness, PostgresRecord, ReferenceTable, SelectCompiler, Table},
             TraversalContext,
         },
         query::{Filter, FilterExpression, ParameterList},
-        AsClient, DataTypeStore, InsertionError, PostgresStore, QueryError, SubgraphRecord,
-        UpdateError,
+        AsClient, DataTypeStore, InsertionError, PostgresStore, QueryError, StoreCache,
+        SubgraphRecord, UpdateError,
     },
     subgraph::{
         edges::{EdgeDirection, GraphResolveDepths, OntologyEdgeKind},
@@ -505,6 +507,17 @@ where
             .await
             .change_context(InsertionError)?;
 
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Create,
+                OntologyChangeRecord::DataType,
+                inserted_data_type_metadata
+                    .iter()
+                    .map(|metadata| &metadata.record_id),
+            )
+            .await
+            .change_context(InsertionError)?;
+
         if let Err(mut error) = transaction.commit().await.change_context(InsertionError) {
             if let Err(auth_error) = self
                 .authorization_api
@@ -808,6 +821,15 @@ where
             .await
             .change_context(UpdateError)?;
 
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Update,
+                OntologyChangeRecord::DataType,
+                [&OntologyTypeRecordId::from(params.schema.id.clone())],
+            )
+            .await
+            .change_context(UpdateError)?;
+
         if let Err(mut error) = transaction.commit().await.change_context(UpdateError) {
             if let Err(auth_error) = self
                 .authorization_api
@@ -852,14 +874,92 @@ where
         }
     }
 
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn check_data_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckDataTypeUpdateParams,
+    ) -> Result<CheckDataTypeUpdateResponse, QueryError> {
+        let old_url = previous_version(&params.schema.id)?;
+        let old_ontology_id = DataTypeId::from_url(&old_url);
+        self.authorization_api
+            .check_data_type_permission(
+                actor_id,
+                DataTypePermission::View,
+                old_ontology_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?
+            .assert_permission()
+            .change_context(QueryError)?;
+
+        DataTypeValidator
+            .validate_ref(&params.schema)
+            .await
+            .change_context(QueryError)?;
+
+        let old_data_type = Read::<DataTypeWithMetadata>::read_one(
+            self,
+            &Filter::for_versioned_url(&old_url),
+            Some(
+                &QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(None),
+                    variable: VariableTemporalAxisUnresolved::new(None, None),
+                }
+                .resolve(),
+            ),
+            false,
+        )
+        .await?;
+        let incompatibilities = old_data_type.schema.incompatible_changes(&params.schema);
+
+        // Entities refer to the previous version, so the new schema replaces it for validation
+        let mut schema = params.schema;
+        schema.id = old_url;
+        let cache = StoreCache::default();
+        cache.replace_data_type(old_ontology_id, schema).await;
+
+        let (checked_entities, invalid_entities) = self
+            .count_invalid_entities(
+                actor_id,
+                &Filter::for_entities_by_data_type_id(old_ontology_id),
+                cache,
+            )
+            .await?;
+
+        Ok(CheckDataTypeUpdateResponse {
+            incompatibilities,
+            checked_entities,
+            invalid_entities,
+        })
+    }
+
     #[tracing::instrument(level = "info", skip(self))]
     async fn archive_data_type(
         &mut self,
         actor_id: AccountId,
         params: ArchiveDataTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.archive_ontology_type(&params.data_type_id, EditionArchivedById::new(actor_id))
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let metadata = transaction
+            .archive_ontology_type(&params.data_type_id, EditionArchivedById::new(actor_id))
+            .await?;
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Archive,
+                OntologyChangeRecord::DataType,
+                [&OntologyTypeRecordId::from(VersionedUrl::clone(
+                    &params.data_type_id,
+                ))],
+            )
             .await
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
     }
 
     #[tracing::instrument(level = "info", skip(self))]
@@ -868,15 +968,32 @@ where
         actor_id: AccountId,
         params: UnarchiveDataTypeParams,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.unarchive_ontology_type(
-            &params.data_type_id,
-            &OntologyEditionProvenance {
-                created_by_id: EditionCreatedById::new(actor_id),
-                archived_by_id: None,
-                user_defined: params.provenance,
-            },
-        )
-        .await
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let metadata = transaction
+            .unarchive_ontology_type(
+                &params.data_type_id,
+                &OntologyEditionProvenance {
+                    created_by_id: EditionCreatedById::new(actor_id),
+                    archived_by_id: None,
+                    user_defined: params.provenance,
+                },
+            )
+            .await?;
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Update,
+                OntologyChangeRecord::DataType,
+                [&OntologyTypeRecordId::from(VersionedUrl::clone(
+                    &params.data_type_id,
+                ))],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
     }
 
     #[tracing::instrument(level = "info", skip(self, params))]
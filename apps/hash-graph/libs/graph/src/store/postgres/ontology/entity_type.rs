@@ -34,26 +34,28 @@ use type_system::{
 use crate::{
     ontology::EntityTypeQueryPath,
     store::{
-        crud::{QueryResult, ReadPaginated, VertexIdSorting},
+        change_feed::ChangeKind,
+        crud::{QueryResult, Read, ReadPaginated, VertexIdSorting},
         error::DeletionError,
         ontology::{
-            ArchiveEntityTypeParams, CountEntityTypesParams, CreateEntityTypeParams,
-            GetEntityTypeSubgraphParams, GetEntityTypeSubgraphResponse, GetEntityTypesParams,
-            GetEntityTypesResponse, UnarchiveEntityTypeParams, UpdateEntityTypeEmbeddingParams,
-            UpdateEntityTypesParams,
+            ArchiveEntityTypeParams, CheckEntityTypeUpdateParams, CheckEntityTypeUpdateResponse,
+            CountEntityTypesParams, CreateEntityTypeParams, GetEntityTypeSubgraphParams,
+            GetEntityTypeSubgraphResponse, GetEntityTypesParams, GetEntityTypesResponse,
+            UnarchiveEntityTypeParams, UpdateEntityTypeEmbeddingParams, UpdateEntityTypesParams,
         },
         postgres::{
+            change_feed::OntologyChangeRecord,
             crud::QueryRecordDecode,
             ontology::{
-                read::OntologyTypeTraversalData, OntologyId,
+                previous_version, read::OntologyTypeTraversalData, OntologyId,
                 PostgresOntologyTypeClassificationMetadata,
             },
             query::{Distinctness, PostgresRecord, ReferenceTable, SelectCompiler, Table},
             TraversalContext,
         },
         query::{Filter, FilterExpression, ParameterList},
-        AsClient, EntityTypeStore, InsertionError, PostgresStore, QueryError, SubgraphRecord,
-        UpdateError,
+        AsClient, EntityTypeStore, InsertionError, PostgresStore, QueryError, StoreCache,
+        SubgraphRecord, UpdateError,
     },
     subgraph::{
         edges::{EdgeDirection, GraphResolveDepths, OntologyEdgeKind},
@@ -678,6 +680,17 @@ where
             .await
             .change_context(InsertionError)?;
 
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Create,
+                OntologyChangeRecord::EntityType,
+                inserted_entity_type_metadata
+                    .iter()
+                    .map(|metadata| &metadata.record_id),
+            )
+            .await
+            .change_context(InsertionError)?;
+
         if let Err(mut error) = transaction.commit().await.change_context(InsertionError) {
             if let Err(auth_error) = self
                 .authorization_api
@@ -950,6 +963,15 @@ where
             .await
             .change_context(UpdateError)?;
 
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Update,
+                OntologyChangeRecord::EntityType,
+                [&metadata.record_id],
+            )
+            .await
+            .change_context(UpdateError)?;
+
         if let Err(mut error) = transaction.commit().await.change_context(UpdateError) {
             if let Err(auth_error) = self
                 .authorization_api
@@ -998,14 +1020,108 @@ where
         }
     }
 
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn check_entity_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckEntityTypeUpdateParams,
+    ) -> Result<CheckEntityTypeUpdateResponse, QueryError> {
+        let old_url = previous_version(&params.schema.id)?;
+        let old_ontology_id = EntityTypeId::from_url(&old_url);
+        self.authorization_api
+            .check_entity_type_permission(
+                actor_id,
+                EntityTypePermission::View,
+                old_ontology_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?
+            .assert_permission()
+            .change_context(QueryError)?;
+
+        EntityTypeValidator
+            .validate_ref(&params.schema)
+            .await
+            .change_context(QueryError)?;
+
+        let old_entity_type = Read::<EntityTypeWithMetadata>::read_one(
+            self,
+            &Filter::for_versioned_url(&old_url),
+            Some(
+                &QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(None),
+                    variable: VariableTemporalAxisUnresolved::new(None, None),
+                }
+                .resolve(),
+            ),
+            false,
+        )
+        .await?;
+        let incompatibilities = old_entity_type.schema.incompatible_changes(&params.schema);
+
+        let new_url = params.schema.id.clone();
+        let mut closed_schema = self
+            .resolve_entity_types([params.schema])
+            .await?
+            .pop()
+            .ok_or_else(|| Report::new(QueryError).attach_printable("entity type not found"))?
+            .closed_schema;
+
+        // Entities refer to the previous version, so the new schema replaces it for validation
+        if let Some(schema_data) = closed_schema.schemas.remove(&new_url) {
+            closed_schema.schemas.insert(old_url.clone(), schema_data);
+        }
+        let cache = StoreCache::default();
+        cache
+            .replace_entity_type(old_ontology_id, closed_schema)
+            .await;
+
+        // Links are validated against the type of their left entity, so removed links may
+        // invalidate link entities of other types.
+        let (checked_entities, invalid_entities) = self
+            .count_invalid_entities(
+                actor_id,
+                &Filter::Any(vec![
+                    Filter::for_entity_by_type_id(&old_url),
+                    Filter::for_links_by_left_entity_type_id(old_ontology_id),
+                ]),
+                cache,
+            )
+            .await?;
+
+        Ok(CheckEntityTypeUpdateResponse {
+            incompatibilities,
+            checked_entities,
+            invalid_entities,
+        })
+    }
+
     #[tracing::instrument(level = "info", skip(self))]
     async fn archive_entity_type(
         &mut self,
         actor_id: AccountId,
         params: ArchiveEntityTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.archive_ontology_type(&params.entity_type_id, EditionArchivedById::new(actor_id))
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let metadata = transaction
+            .archive_ontology_type(&params.entity_type_id, EditionArchivedById::new(actor_id))
+            .await?;
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Archive,
+                OntologyChangeRecord::EntityType,
+                [&OntologyTypeRecordId::from(VersionedUrl::clone(
+                    &params.entity_type_id,
+                ))],
+            )
             .await
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
     }
 
     #[tracing::instrument(level = "info", skip(self))]
@@ -1014,15 +1130,32 @@ where
         actor_id: AccountId,
         params: UnarchiveEntityTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.unarchive_ontology_type(
-            &params.entity_type_id,
-            &OntologyEditionProvenance {
-                created_by_id: EditionCreatedById::new(actor_id),
-                archived_by_id: None,
-                user_defined: params.provenance,
-            },
-        )
-        .await
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let metadata = transaction
+            .unarchive_ontology_type(
+                &params.entity_type_id,
+                &OntologyEditionProvenance {
+                    created_by_id: EditionCreatedById::new(actor_id),
+                    archived_by_id: None,
+                    user_defined: params.provenance,
+                },
+            )
+            .await?;
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Update,
+                OntologyChangeRecord::EntityType,
+                [&OntologyTypeRecordId::from(VersionedUrl::clone(
+                    &params.entity_type_id,
+                ))],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
     }
 
     #[tracing::instrument(level = "info", skip(self, params))]
@@ -18,7 +18,7 @@ use graph_types::{
 use serde::Deserialize;
 use time::OffsetDateTime;
 use tokio_postgres::{Row, Transaction};
-use type_system::url::BaseUrl;
+use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
 
 pub use self::ontology_id::OntologyId;
 use crate::{
@@ -31,7 +31,7 @@ use crate::{
             query::{Distinctness, PostgresSorting, SelectCompiler},
         },
         query::Parameter,
-        AsClient, Ordering, PostgresStore, SubgraphRecord,
+        AsClient, Ordering, PostgresStore, QueryError, SubgraphRecord,
     },
     subgraph::temporal_axes::QueryTemporalAxes,
 };
@@ -219,3 +219,17 @@ impl From<PostgresOntologyTypeClassificationMetadata> for OntologyTypeClassifica
         }
     }
 }
+
+/// Returns the URL of the version which is replaced when updating an ontology type to `url`.
+fn previous_version(url: &VersionedUrl) -> Result<VersionedUrl, Report<QueryError>> {
+    let version = url.version.inner().checked_sub(1).ok_or_else(|| {
+        Report::new(QueryError).attach_printable(
+            "The version of the ontology type is already at the lowest possible value",
+        )
+    })?;
+
+    Ok(VersionedUrl {
+        base_url: url.base_url.clone(),
+        version: OntologyTypeVersion::new(version),
+    })
+}
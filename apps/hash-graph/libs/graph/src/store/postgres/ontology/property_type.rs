@@ -34,30 +34,37 @@ use type_system::{
 use crate::{
     ontology::PropertyTypeQueryPath,
     store::{
-        crud::{QueryResult, ReadPaginated, VertexIdSorting},
+        change_feed::ChangeKind,
+        crud::{QueryResult, Read, ReadPaginated, VertexIdSorting},
         error::DeletionError,
         ontology::{
-            ArchivePropertyTypeParams, CountPropertyTypesParams, CreatePropertyTypeParams,
+            ArchivePropertyTypeParams, CheckPropertyTypeUpdateParams,
+            CheckPropertyTypeUpdateResponse, CountPropertyTypesParams, CreatePropertyTypeParams,
             GetPropertyTypeSubgraphParams, GetPropertyTypeSubgraphResponse, GetPropertyTypesParams,
             GetPropertyTypesResponse, UnarchivePropertyTypeParams,
             UpdatePropertyTypeEmbeddingParams, UpdatePropertyTypesParams,
         },
         postgres::{
+            change_feed::OntologyChangeRecord,
             crud::QueryRecordDecode,
             ontology::{
-                read::OntologyTypeTraversalData, OntologyId,
+                previous_version, read::OntologyTypeTraversalData, OntologyId,
                 PostgresOntologyTypeClassificationMetadata,
             },
             query::{Distinctness, PostgresRecord, ReferenceTable, SelectCompiler, Table},
             TraversalContext,
         },
-        AsClient, InsertionError, PostgresStore, PropertyTypeStore, QueryError, SubgraphRecord,
-        UpdateError,
+        query::Filter,
+        AsClient, InsertionError, PostgresStore, PropertyTypeStore, QueryError, StoreCache,
+        SubgraphRecord, UpdateError,
     },
     subgraph::{
         edges::{EdgeDirection, GraphResolveDepths, OntologyEdgeKind},
         identifier::{DataTypeVertexId, GraphElementVertexId, PropertyTypeVertexId},
-        temporal_axes::{QueryTemporalAxes, VariableAxis},
+        temporal_axes::{
+            PinnedTemporalAxisUnresolved, QueryTemporalAxes, QueryTemporalAxesUnresolved,
+            VariableAxis, VariableTemporalAxisUnresolved,
+        },
         Subgraph,
     },
 };
@@ -496,6 +503,17 @@ where
             .await
             .change_context(InsertionError)?;
 
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Create,
+                OntologyChangeRecord::PropertyType,
+                inserted_property_type_metadata
+                    .iter()
+                    .map(|metadata| &metadata.record_id),
+            )
+            .await
+            .change_context(InsertionError)?;
+
         if let Err(mut error) = transaction.commit().await.change_context(InsertionError) {
             if let Err(auth_error) = self
                 .authorization_api
@@ -745,6 +763,15 @@ where
             .await
             .change_context(UpdateError)?;
 
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Update,
+                OntologyChangeRecord::PropertyType,
+                [&OntologyTypeRecordId::from(params.schema.id.clone())],
+            )
+            .await
+            .change_context(UpdateError)?;
+
         if let Err(mut error) = transaction.commit().await.change_context(UpdateError) {
             if let Err(auth_error) = self
                 .authorization_api
@@ -791,14 +818,94 @@ where
         }
     }
 
+    #[tracing::instrument(level = "info", skip(self, params))]
+    async fn check_property_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckPropertyTypeUpdateParams,
+    ) -> Result<CheckPropertyTypeUpdateResponse, QueryError> {
+        let old_url = previous_version(&params.schema.id)?;
+        let old_ontology_id = PropertyTypeId::from_url(&old_url);
+        self.authorization_api
+            .check_property_type_permission(
+                actor_id,
+                PropertyTypePermission::View,
+                old_ontology_id,
+                Consistency::FullyConsistent,
+            )
+            .await
+            .change_context(QueryError)?
+            .assert_permission()
+            .change_context(QueryError)?;
+
+        PropertyTypeValidator
+            .validate_ref(&params.schema)
+            .await
+            .change_context(QueryError)?;
+
+        let old_property_type = Read::<PropertyTypeWithMetadata>::read_one(
+            self,
+            &Filter::for_versioned_url(&old_url),
+            Some(
+                &QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(None),
+                    variable: VariableTemporalAxisUnresolved::new(None, None),
+                }
+                .resolve(),
+            ),
+            false,
+        )
+        .await?;
+        let incompatibilities = old_property_type
+            .schema
+            .incompatible_changes(&params.schema);
+
+        // Entities refer to the previous version, so the new schema replaces it for validation
+        let mut schema = params.schema;
+        schema.id = old_url;
+        let cache = StoreCache::default();
+        cache.replace_property_type(old_ontology_id, schema).await;
+
+        let (checked_entities, invalid_entities) = self
+            .count_invalid_entities(
+                actor_id,
+                &Filter::for_entities_by_property_type_id(old_ontology_id),
+                cache,
+            )
+            .await?;
+
+        Ok(CheckPropertyTypeUpdateResponse {
+            incompatibilities,
+            checked_entities,
+            invalid_entities,
+        })
+    }
+
     #[tracing::instrument(level = "info", skip(self))]
     async fn archive_property_type(
         &mut self,
         actor_id: AccountId,
         params: ArchivePropertyTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.archive_ontology_type(&params.property_type_id, EditionArchivedById::new(actor_id))
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let metadata = transaction
+            .archive_ontology_type(&params.property_type_id, EditionArchivedById::new(actor_id))
+            .await?;
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Archive,
+                OntologyChangeRecord::PropertyType,
+                [&OntologyTypeRecordId::from(VersionedUrl::clone(
+                    &params.property_type_id,
+                ))],
+            )
             .await
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
     }
 
     #[tracing::instrument(level = "info", skip(self))]
@@ -807,15 +914,32 @@ where
         actor_id: AccountId,
         params: UnarchivePropertyTypeParams<'_>,
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
-        self.unarchive_ontology_type(
-            &params.property_type_id,
-            &OntologyEditionProvenance {
-                created_by_id: EditionCreatedById::new(actor_id),
-                archived_by_id: None,
-                user_defined: params.provenance,
-            },
-        )
-        .await
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let metadata = transaction
+            .unarchive_ontology_type(
+                &params.property_type_id,
+                &OntologyEditionProvenance {
+                    created_by_id: EditionCreatedById::new(actor_id),
+                    archived_by_id: None,
+                    user_defined: params.provenance,
+                },
+            )
+            .await?;
+        transaction
+            .record_ontology_changes(
+                ChangeKind::Update,
+                OntologyChangeRecord::PropertyType,
+                [&OntologyTypeRecordId::from(VersionedUrl::clone(
+                    &params.property_type_id,
+                ))],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(metadata)
     }
 
     #[tracing::instrument(level = "info", skip(self, params))]
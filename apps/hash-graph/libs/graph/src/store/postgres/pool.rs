@@ -7,13 +7,11 @@ use deadpool_postgres::{
 };
 use error_stack::{Report, ResultExt};
 use temporal_client::TemporalClient;
-use tokio_postgres::{
-    tls::{MakeTlsConnect, TlsConnect},
-    Client, GenericClient, Socket, Transaction,
-};
+use tokio_postgres::{Client, GenericClient, Transaction};
 
 use crate::store::{
-    config::DatabasePoolConfig, DatabaseConnectionInfo, PostgresStore, StoreError, StorePool,
+    config::DatabasePoolConfig, postgres::tls::MakeRustlsConnect, DatabaseConnectionInfo,
+    PostgresStore, StoreError, StorePool,
 };
 
 pub struct PostgresStorePool {
@@ -23,25 +21,17 @@ pub struct PostgresStorePool {
 impl PostgresStorePool {
     /// Creates a new `PostgresDatabasePool`.
     ///
+    /// The connections use TLS as specified by [`DatabaseConnectionInfo::tls`].
+    ///
     /// # Errors
     ///
+    /// - if the TLS certificates cannot be read
     /// - if creating a connection returns an error.
-    #[tracing::instrument(skip(tls))]
-    pub async fn new<Tls>(
+    #[tracing::instrument]
+    pub async fn new(
         db_info: &DatabaseConnectionInfo,
         pool_config: &DatabasePoolConfig,
-        tls: Tls,
-    ) -> Result<Self, Report<StoreError>>
-    where
-        Tls: Clone
-            + MakeTlsConnect<
-                Socket,
-                Stream: Send + Sync,
-                TlsConnect: TlsConnect<Socket, Future: Send> + Send + Sync,
-            > + Send
-            + Sync
-            + 'static,
-    {
+    ) -> Result<Self, Report<StoreError>> {
         tracing::debug!(url=%db_info, "Creating connection pool to Postgres");
 
         let config = deadpool_postgres::Config {
@@ -50,6 +40,7 @@ impl PostgresStorePool {
             host: Some(db_info.host().to_owned()),
             port: Some(db_info.port()),
             dbname: Some(db_info.database().to_owned()),
+            ssl_mode: Some(db_info.tls().mode.ssl_mode()),
             pool: Some(PoolConfig {
                 max_size: pool_config.max_connections.get(),
                 timeouts: Timeouts {
@@ -67,7 +58,10 @@ impl PostgresStorePool {
 
         Ok(Self {
             pool: config
-                .builder(tls)
+                .builder(
+                    MakeRustlsConnect::new(db_info.tls())
+                        .attach_printable("could not configure TLS")?,
+                )
                 .change_context(StoreError)
                 .attach_printable_lazy(|| db_info.clone())?
                 .post_create(Hook::sync_fn(|_client, _metrics| {
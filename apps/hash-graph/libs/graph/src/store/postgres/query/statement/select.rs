@@ -1077,6 +1077,7 @@ mod tests {
     mod predefined {
         use graph_types::{
             knowledge::entity::{EntityId, EntityUuid},
+            ontology::DataTypeId,
             owned_by_id::OwnedById,
         };
         use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
@@ -1149,5 +1150,51 @@ mod tests {
                 ],
             );
         }
+
+        #[test]
+        fn for_entities_by_data_type_id() {
+            let data_type_id = DataTypeId::new(Uuid::new_v4());
+
+            let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
+            let pinned_timestamp = temporal_axes.pinned_timestamp();
+            let mut compiler = SelectCompiler::<Entity>::with_asterisk(Some(&temporal_axes), false);
+
+            let filter = Filter::for_entities_by_data_type_id(data_type_id);
+            compiler.add_filter(&filter);
+
+            test_compilation(
+                &compiler,
+                r#"
+                SELECT *
+                FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+                INNER JOIN "entity_is_of_type" AS "entity_is_of_type_0_1_0"
+                  ON "entity_is_of_type_0_1_0"."entity_edition_id" = "entity_temporal_metadata_0_0_0"."entity_edition_id"
+                INNER JOIN "ontology_temporal_metadata" AS "ontology_temporal_metadata_0_2_0"
+                  ON "ontology_temporal_metadata_0_2_0"."ontology_id" = "entity_is_of_type_0_1_0"."entity_type_ontology_id"
+                INNER JOIN "closed_entity_type_constrains_properties_on" AS "closed_entity_type_constrains_properties_on_0_3_0"
+                  ON "closed_entity_type_constrains_properties_on_0_3_0"."source_entity_type_ontology_id" = "ontology_temporal_metadata_0_2_0"."ontology_id"
+                INNER JOIN "ontology_temporal_metadata" AS "ontology_temporal_metadata_0_4_0"
+                  ON "ontology_temporal_metadata_0_4_0"."ontology_id" = "closed_entity_type_constrains_properties_on_0_3_0"."target_property_type_ontology_id"
+                INNER JOIN "property_type_constrains_values_on" AS "property_type_constrains_values_on_0_5_0"
+                  ON "property_type_constrains_values_on_0_5_0"."source_property_type_ontology_id" = "ontology_temporal_metadata_0_4_0"."ontology_id"
+                INNER JOIN "ontology_temporal_metadata" AS "ontology_temporal_metadata_0_6_0"
+                  ON "ontology_temporal_metadata_0_6_0"."ontology_id" = "property_type_constrains_values_on_0_5_0"."target_data_type_ontology_id"
+                INNER JOIN "data_types" AS "data_types_0_7_0"
+                  ON "data_types_0_7_0"."ontology_id" = "ontology_temporal_metadata_0_6_0"."ontology_id"
+                WHERE "entity_temporal_metadata_0_0_0"."draft_id" IS NULL
+                  AND "entity_temporal_metadata_0_0_0"."transaction_time" @> $1::TIMESTAMPTZ
+                  AND "entity_temporal_metadata_0_0_0"."decision_time" && $2
+                  AND "ontology_temporal_metadata_0_2_0"."transaction_time" @> $1::TIMESTAMPTZ
+                  AND "ontology_temporal_metadata_0_4_0"."transaction_time" @> $1::TIMESTAMPTZ
+                  AND "ontology_temporal_metadata_0_6_0"."transaction_time" @> $1::TIMESTAMPTZ
+                  AND "data_types_0_7_0"."ontology_id" = $3
+                "#,
+                &[
+                    &pinned_timestamp,
+                    &temporal_axes.variable_interval(),
+                    &data_type_id.into_uuid(),
+                ],
+            );
+        }
     }
 }
@@ -0,0 +1,253 @@
+use authorization::AuthorizationApi;
+use error_stack::{Report, ResultExt};
+use graph_types::{account::AccountId, knowledge::entity::Entity};
+use hash_status::StatusCode;
+use postgres_types::Json;
+use reqwest::Url;
+use serde::Deserialize;
+use tokio_postgres::GenericClient;
+use uuid::Uuid;
+
+use crate::store::{
+    error::DeletionError,
+    query::Filter,
+    subscription::{
+        CreateEntitySubscriptionParams, EntitySubscription, ParkedChange, SubscriptionId,
+        SubscriptionSecretKey, SubscriptionStore, SubscriptionTarget,
+    },
+    AsClient, InsertionError, PostgresStore, QueryError, UpdateError,
+};
+
+impl<C, A> SubscriptionStore for PostgresStore<C, A>
+where
+    C: AsClient,
+    A: AuthorizationApi,
+{
+    #[tracing::instrument(level = "info", skip(self, params, secret_key))]
+    async fn create_entity_subscription(
+        &mut self,
+        actor_id: AccountId,
+        params: CreateEntitySubscriptionParams,
+        secret_key: &SubscriptionSecretKey,
+    ) -> Result<EntitySubscription, Report<InsertionError>> {
+        let url = Url::parse(&params.url)
+            .change_context(InsertionError)
+            .attach(StatusCode::InvalidArgument)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(Report::new(InsertionError)
+                .attach(StatusCode::InvalidArgument)
+                .attach_printable(format!("unsupported URL scheme `{}`", url.scheme())));
+        }
+        Filter::<Entity>::deserialize(&params.filter)
+            .change_context(InsertionError)
+            .attach(StatusCode::InvalidArgument)
+            .attach_printable("invalid entity filter")?;
+        let sealed_secret = secret_key
+            .seal(&params.secret)
+            .change_context(InsertionError)?;
+
+        let subscription_id = SubscriptionId::new(Uuid::new_v4());
+        self.as_client()
+            .query(
+                "
+                    INSERT INTO entity_subscriptions (
+                        subscription_id,
+                        actor_id,
+                        url,
+                        filter,
+                        sealed_secret,
+                        cursor
+                    ) VALUES (
+                        $1, $2, $3, $4, $5,
+                        (SELECT COALESCE(MAX(sequence_id), 0) FROM change_feed)
+                    );
+                ",
+                &[
+                    &subscription_id,
+                    &actor_id,
+                    &params.url,
+                    &Json(&params.filter),
+                    &sealed_secret,
+                ],
+            )
+            .await
+            .change_context(InsertionError)?;
+
+        Ok(EntitySubscription {
+            subscription_id,
+            url: params.url,
+            filter: params.filter,
+        })
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_entity_subscriptions(
+        &self,
+        actor_id: AccountId,
+    ) -> Result<Vec<EntitySubscription>, Report<QueryError>> {
+        Ok(self
+            .as_client()
+            .query(
+                "
+                    SELECT subscription_id, url, filter
+                    FROM entity_subscriptions
+                    WHERE actor_id = $1;
+                ",
+                &[&actor_id],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| EntitySubscription {
+                subscription_id: row.get(0),
+                url: row.get(1),
+                filter: row.get::<_, Json<_>>(2).0,
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn delete_entity_subscription(
+        &mut self,
+        actor_id: AccountId,
+        subscription_id: SubscriptionId,
+    ) -> Result<(), Report<DeletionError>> {
+        let deleted = self
+            .as_client()
+            .execute(
+                "
+                    DELETE FROM entity_subscriptions
+                    WHERE subscription_id = $1
+                      AND actor_id = $2;
+                ",
+                &[&subscription_id, &actor_id],
+            )
+            .await
+            .change_context(DeletionError)?;
+
+        if deleted == 0 {
+            return Err(Report::new(DeletionError)
+                .attach(StatusCode::NotFound)
+                .attach_printable(format!("subscription `{subscription_id}` does not exist")));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_subscription_targets(
+        &self,
+    ) -> Result<Vec<SubscriptionTarget>, Report<QueryError>> {
+        Ok(self
+            .as_client()
+            .query(
+                "
+                    SELECT subscription_id, actor_id, url, filter, sealed_secret, cursor
+                    FROM entity_subscriptions;
+                ",
+                &[],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| SubscriptionTarget {
+                subscription_id: row.get(0),
+                actor_id: row.get(1),
+                url: row.get(2),
+                filter: row.get::<_, Json<_>>(3).0,
+                sealed_secret: row.get(4),
+                cursor: row.get(5),
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn advance_subscription_cursor(
+        &mut self,
+        subscription_id: SubscriptionId,
+        cursor: i64,
+    ) -> Result<(), Report<UpdateError>> {
+        self.as_client()
+            .execute(
+                "
+                    UPDATE entity_subscriptions
+                    SET cursor = GREATEST(cursor, $2)
+                    WHERE subscription_id = $1;
+                ",
+                &[&subscription_id, &cursor],
+            )
+            .await
+            .change_context(UpdateError)?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn park_subscription_change(
+        &mut self,
+        subscription_id: SubscriptionId,
+        change: ParkedChange,
+    ) -> Result<(), Report<UpdateError>> {
+        self.as_client()
+            .execute(
+                "
+                    INSERT INTO entity_subscription_parked_changes (
+                        subscription_id,
+                        sequence_id,
+                        reason
+                    ) VALUES ($1, $2, $3)
+                    ON CONFLICT (subscription_id, sequence_id)
+                    DO UPDATE SET reason = EXCLUDED.reason;
+                ",
+                &[&subscription_id, &change.sequence_id, &change.reason],
+            )
+            .await
+            .change_context(UpdateError)?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_parked_changes(
+        &self,
+        actor_id: AccountId,
+        subscription_id: SubscriptionId,
+    ) -> Result<Vec<ParkedChange>, Report<QueryError>> {
+        let exists = self
+            .as_client()
+            .query_opt(
+                "
+                    SELECT 1
+                    FROM entity_subscriptions
+                    WHERE subscription_id = $1
+                      AND actor_id = $2;
+                ",
+                &[&subscription_id, &actor_id],
+            )
+            .await
+            .change_context(QueryError)?
+            .is_some();
+        if !exists {
+            return Err(Report::new(QueryError)
+                .attach(StatusCode::NotFound)
+                .attach_printable(format!("subscription `{subscription_id}` does not exist")));
+        }
+
+        Ok(self
+            .as_client()
+            .query(
+                "
+                    SELECT sequence_id, reason
+                    FROM entity_subscription_parked_changes
+                    WHERE subscription_id = $1
+                    ORDER BY sequence_id;
+                ",
+                &[&subscription_id],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| ParkedChange {
+                sequence_id: row.get(0),
+                reason: row.get(1),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,380 @@
+//! TLS connector for the connections to Postgres.
+//!
+//! `tokio-postgres` only ships the [`NoTls`] connector, so this module adapts [`tokio_rustls`] to
+//! the [`MakeTlsConnect`] interface.
+//!
+//! [`NoTls`]: tokio_postgres::NoTls
+
+use alloc::sync::Arc;
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::{fs, io, path::Path};
+
+use deadpool_postgres::SslMode;
+use error_stack::{Report, ResultExt};
+use futures::future::BoxFuture;
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_postgres::tls::{ChannelBinding, MakeTlsConnect, TlsConnect, TlsStream};
+use tokio_rustls::{client, TlsConnector};
+
+use crate::store::{DatabaseTlsConfig, DatabaseTlsMode, StoreError};
+
+/// Accepts any certificate of the server.
+///
+/// Used for the `prefer` and `require` modes, which only ask for an encrypted connection. The
+/// handshake signatures are still checked, so the server has to own the key of its certificate.
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, Report<StoreError>> {
+    let certificates = rustls_pemfile::certs(&mut io::BufReader::new(
+        fs::File::open(path)
+            .change_context(StoreError)
+            .attach_printable_lazy(|| path.display().to_string())?,
+    ))
+    .collect::<Result<Vec<_>, _>>()
+    .change_context(StoreError)
+    .attach_printable_lazy(|| path.display().to_string())?;
+
+    if certificates.is_empty() {
+        return Err(Report::new(StoreError)
+            .attach_printable("the file does not contain a PEM encoded certificate")
+            .attach_printable(path.display().to_string()));
+    }
+    Ok(certificates)
+}
+
+fn read_private_key(path: &Path) -> Result<PrivateKeyDer<'static>, Report<StoreError>> {
+    rustls_pemfile::private_key(&mut io::BufReader::new(
+        fs::File::open(path)
+            .change_context(StoreError)
+            .attach_printable_lazy(|| path.display().to_string())?,
+    ))
+    .change_context(StoreError)
+    .attach_printable_lazy(|| path.display().to_string())?
+    .ok_or_else(|| {
+        Report::new(StoreError)
+            .attach_printable("the file does not contain a PEM encoded private key")
+            .attach_printable(path.display().to_string())
+    })
+}
+
+fn root_certificates(config: &DatabaseTlsConfig) -> Result<RootCertStore, Report<StoreError>> {
+    let mut roots = RootCertStore::empty();
+    if let Some(path) = &config.root_certificates {
+        for certificate in read_certificates(path)? {
+            roots
+                .add(certificate)
+                .change_context(StoreError)
+                .attach_printable_lazy(|| path.display().to_string())?;
+        }
+    } else {
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    }
+    Ok(roots)
+}
+
+/// Creates the connector for the connections to Postgres.
+#[derive(Debug, Clone)]
+pub(crate) struct MakeRustlsConnect {
+    config: Arc<ClientConfig>,
+}
+
+impl MakeRustlsConnect {
+    /// Reads the certificates specified in the `config`.
+    ///
+    /// # Errors
+    ///
+    /// - if a certificate or the private key cannot be read
+    pub(crate) fn new(config: &DatabaseTlsConfig) -> Result<Self, Report<StoreError>> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+            .with_safe_default_protocol_versions()
+            .change_context(StoreError)?;
+
+        let builder = match config.mode {
+            DatabaseTlsMode::VerifyFull => {
+                builder.with_root_certificates(root_certificates(config)?)
+            }
+            DatabaseTlsMode::Disable | DatabaseTlsMode::Prefer | DatabaseTlsMode::Require => {
+                builder
+                    .dangerous()
+                    .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+            }
+        };
+
+        let config = match (&config.client_certificate, &config.client_key) {
+            (Some(certificate), Some(key)) => builder
+                .with_client_auth_cert(read_certificates(certificate)?, read_private_key(key)?)
+                .change_context(StoreError)
+                .attach_printable_lazy(|| certificate.display().to_string())?,
+            _ => builder.with_no_client_auth(),
+        };
+
+        Ok(Self {
+            config: Arc::new(config),
+        })
+    }
+}
+
+impl DatabaseTlsMode {
+    pub(crate) const fn ssl_mode(self) -> SslMode {
+        match self {
+            Self::Disable => SslMode::Disable,
+            Self::Prefer => SslMode::Prefer,
+            Self::Require | Self::VerifyFull => SslMode::Require,
+        }
+    }
+}
+
+impl<S> MakeTlsConnect<S> for MakeRustlsConnect
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    type Error = rustls::pki_types::InvalidDnsNameError;
+    type Stream = RustlsStream<S>;
+    type TlsConnect = RustlsConnect;
+
+    fn make_tls_connect(&mut self, domain: &str) -> Result<Self::TlsConnect, Self::Error> {
+        Ok(RustlsConnect {
+            server_name: ServerName::try_from(domain)?.to_owned(),
+            connector: TlsConnector::from(Arc::clone(&self.config)),
+        })
+    }
+}
+
+pub(crate) struct RustlsConnect {
+    server_name: ServerName<'static>,
+    connector: TlsConnector,
+}
+
+impl<S> TlsConnect<S> for RustlsConnect
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    type Error = io::Error;
+    type Future = BoxFuture<'static, io::Result<Self::Stream>>;
+    type Stream = RustlsStream<S>;
+
+    fn connect(self, stream: S) -> Self::Future {
+        Box::pin(async move {
+            self.connector
+                .connect(self.server_name, stream)
+                .await
+                .map(RustlsStream)
+        })
+    }
+}
+
+pub(crate) struct RustlsStream<S>(client::TlsStream<S>);
+
+impl<S> TlsStream for RustlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    // Channel binding requires the hash algorithm of the certificate signature. Without it, the
+    // authentication falls back to `SCRAM-SHA-256`.
+    fn channel_binding(&self) -> ChannelBinding {
+        ChannelBinding::none()
+    }
+}
+
+impl<S> AsyncRead for RustlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl<S> AsyncWrite for RustlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt, DuplexStream};
+    use tokio_rustls::TlsAcceptor;
+
+    use super::*;
+
+    fn certificate(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("certificates")
+            .join(name)
+    }
+
+    fn acceptor() -> TlsAcceptor {
+        let config = rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .expect("could not set protocol versions")
+        .with_no_client_auth()
+        .with_single_cert(
+            read_certificates(&certificate("server.pem")).expect("could not read certificate"),
+            read_private_key(&certificate("server.key")).expect("could not read private key"),
+        )
+        .expect("could not create server config");
+        TlsAcceptor::from(Arc::new(config))
+    }
+
+    /// Performs a handshake against a server with a certificate for `localhost` and exchanges a
+    /// message.
+    async fn handshake(config: &DatabaseTlsConfig, domain: &str) -> io::Result<()> {
+        let (client, server) = duplex(16 * 1024);
+        let server = tokio::spawn(async move {
+            let mut stream = acceptor().accept(server).await?;
+            stream.write_all(b"ping").await?;
+            stream.flush().await?;
+            Ok::<_, io::Error>(stream)
+        });
+
+        let mut connector = MakeRustlsConnect::new(config).expect("could not create connector");
+        let connect = MakeTlsConnect::<DuplexStream>::make_tls_connect(&mut connector, domain)
+            .expect("invalid domain");
+        let client = connect.connect(client).await;
+        let _server = server.await.expect("server panicked");
+
+        let mut message = [0; 4];
+        client?.read_exact(&mut message).await?;
+        assert_eq!(&message, b"ping");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn require_accepts_any_certificate() {
+        let config = DatabaseTlsConfig {
+            mode: DatabaseTlsMode::Require,
+            ..DatabaseTlsConfig::default()
+        };
+        handshake(&config, "localhost")
+            .await
+            .expect("handshake should succeed");
+        handshake(&config, "example.com")
+            .await
+            .expect("handshake should succeed");
+    }
+
+    #[tokio::test]
+    async fn verify_full_checks_certificate() {
+        let config = DatabaseTlsConfig {
+            mode: DatabaseTlsMode::VerifyFull,
+            root_certificates: Some(certificate("ca.pem")),
+            client_certificate: Some(certificate("server.pem")),
+            client_key: Some(certificate("server.key")),
+        };
+        handshake(&config, "localhost")
+            .await
+            .expect("handshake should succeed");
+        handshake(&config, "example.com")
+            .await
+            .expect_err("the certificate is not valid for the host");
+
+        let untrusted = DatabaseTlsConfig {
+            mode: DatabaseTlsMode::VerifyFull,
+            ..DatabaseTlsConfig::default()
+        };
+        handshake(&untrusted, "localhost")
+            .await
+            .expect_err("the certificate authority is not trusted");
+    }
+
+    #[test]
+    fn reports_invalid_files() {
+        let missing = DatabaseTlsConfig {
+            mode: DatabaseTlsMode::VerifyFull,
+            root_certificates: Some(certificate("missing.pem")),
+            ..DatabaseTlsConfig::default()
+        };
+        let report = MakeRustlsConnect::new(&missing).expect_err("the file does not exist");
+        assert!(format!("{report:?}").contains("missing.pem"), "{report:?}");
+
+        let not_a_certificate = DatabaseTlsConfig {
+            mode: DatabaseTlsMode::VerifyFull,
+            root_certificates: Some(certificate("server.key")),
+            ..DatabaseTlsConfig::default()
+        };
+        let report =
+            MakeRustlsConnect::new(&not_a_certificate).expect_err("the file is not a certificate");
+        assert!(format!("{report:?}").contains("server.key"), "{report:?}");
+    }
+}
@@ -16,13 +16,13 @@ use uuid::Uuid;
 
 use crate::{
     knowledge::EntityQueryPath,
-    ontology::{DataTypeQueryPath, EntityTypeQueryPath},
+    ontology::{DataTypeQueryPath, EntityTypeQueryPath, PropertyTypeQueryPath},
     store::{
         query::{OntologyQueryPath, ParameterType, QueryPath},
         QueryRecord, SubgraphRecord,
     },
     subgraph::{
-        edges::{EdgeDirection, OntologyEdgeKind, SharedEdgeKind},
+        edges::{EdgeDirection, KnowledgeGraphEdgeKind, OntologyEdgeKind, SharedEdgeKind},
         identifier::VertexId,
     },
 };
@@ -158,6 +158,78 @@ impl<'p> Filter<'p, Entity> {
         }
     }
 
+    /// Creates a `Filter` to search for link entities whose left entity is of the [`EntityType`]
+    /// identified by its [`EntityTypeId`].
+    ///
+    /// [`EntityType`]: type_system::schema::EntityType
+    #[must_use]
+    pub fn for_links_by_left_entity_type_id(entity_type_id: EntityTypeId) -> Self {
+        Self::Equal(
+            Some(FilterExpression::Path(EntityQueryPath::EntityEdge {
+                edge_kind: KnowledgeGraphEdgeKind::HasLeftEntity,
+                path: Box::new(EntityQueryPath::EntityTypeEdge {
+                    edge_kind: SharedEdgeKind::IsOfType,
+                    path: EntityTypeQueryPath::OntologyId,
+                    inheritance_depth: Some(0),
+                }),
+                direction: EdgeDirection::Outgoing,
+            })),
+            Some(FilterExpression::Parameter(Parameter::Uuid(
+                entity_type_id.into_uuid(),
+            ))),
+        )
+    }
+
+    /// Creates a `Filter` to search for entities whose types constrain properties on the
+    /// [`PropertyType`] identified by its [`PropertyTypeId`], including inherited properties.
+    ///
+    /// [`PropertyType`]: type_system::schema::PropertyType
+    #[must_use]
+    pub const fn for_entities_by_property_type_id(property_type_id: PropertyTypeId) -> Self {
+        Self::Equal(
+            Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                edge_kind: SharedEdgeKind::IsOfType,
+                path: EntityTypeQueryPath::PropertyTypeEdge {
+                    edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                    path: PropertyTypeQueryPath::OntologyId,
+                    inheritance_depth: None,
+                },
+                inheritance_depth: Some(0),
+            })),
+            Some(FilterExpression::Parameter(Parameter::Uuid(
+                property_type_id.into_uuid(),
+            ))),
+        )
+    }
+
+    /// Creates a `Filter` to search for entities with properties whose property types constrain
+    /// values on the [`DataType`] identified by its [`DataTypeId`].
+    ///
+    /// Only data types which are directly referenced by the property types of the entity types
+    /// are considered.
+    ///
+    /// [`DataType`]: type_system::schema::DataType
+    #[must_use]
+    pub const fn for_entities_by_data_type_id(data_type_id: DataTypeId) -> Self {
+        Self::Equal(
+            Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                edge_kind: SharedEdgeKind::IsOfType,
+                path: EntityTypeQueryPath::PropertyTypeEdge {
+                    edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                    path: PropertyTypeQueryPath::DataTypeEdge {
+                        edge_kind: OntologyEdgeKind::ConstrainsValuesOn,
+                        path: DataTypeQueryPath::OntologyId,
+                    },
+                    inheritance_depth: None,
+                },
+                inheritance_depth: Some(0),
+            })),
+            Some(FilterExpression::Parameter(Parameter::Uuid(
+                data_type_id.into_uuid(),
+            ))),
+        )
+    }
+
     #[must_use]
     pub fn for_entity_by_type_id(entity_type_id: &'p VersionedUrl) -> Self {
         Filter::All(vec![
@@ -0,0 +1,262 @@
+use core::{error::Error, fmt, str::FromStr};
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use error_stack::{Report, ResultExt};
+use graph_types::account::AccountId;
+use postgres_types::{FromSql, ToSql};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+#[cfg(feature = "utoipa")]
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::store::{error::DeletionError, InsertionError, QueryError, UpdateError};
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, FromSql, ToSql,
+)]
+#[postgres(transparent)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+#[repr(transparent)]
+pub struct SubscriptionId(Uuid);
+
+impl SubscriptionId {
+    #[must_use]
+    pub const fn new(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    #[must_use]
+    pub const fn into_uuid(self) -> Uuid {
+        self.0
+    }
+}
+
+impl fmt::Display for SubscriptionId {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+#[derive(Debug)]
+pub struct SubscriptionSecretError;
+
+impl fmt::Display for SubscriptionSecretError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Could not seal or open the secret of a subscription")
+    }
+}
+
+impl Error for SubscriptionSecretError {}
+
+/// The key used to encrypt the secrets of subscriptions before they are stored.
+///
+/// Secrets are encrypted with AES-256-GCM, the key is parsed from 64 hexadecimal characters.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SubscriptionSecretKey([u8; 32]);
+
+impl fmt::Debug for SubscriptionSecretKey {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("SubscriptionSecretKey(..)")
+    }
+}
+
+impl FromStr for SubscriptionSecretKey {
+    type Err = SubscriptionSecretError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0; 32];
+        hex::decode_to_slice(key, &mut bytes).map_err(|_error| SubscriptionSecretError)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl SubscriptionSecretKey {
+    #[must_use]
+    pub const fn new(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    /// Encrypts `secret`, the nonce is prepended to the returned ciphertext.
+    ///
+    /// # Errors
+    ///
+    /// - if the secret cannot be encrypted
+    pub fn seal(&self, secret: &str) -> Result<Vec<u8>, Report<SubscriptionSecretError>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+            .encrypt(&nonce, secret.as_bytes())
+            .map_err(|_error| Report::new(SubscriptionSecretError))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypts a secret returned by [`seal`].
+    ///
+    /// # Errors
+    ///
+    /// - if the secret was sealed with a different key or was modified
+    ///
+    /// [`seal`]: Self::seal
+    pub fn open(&self, sealed: &[u8]) -> Result<String, Report<SubscriptionSecretError>> {
+        // The nonce of AES-GCM is 96 bits
+        if sealed.len() < 12 {
+            return Err(Report::new(SubscriptionSecretError)
+                .attach_printable("the sealed secret is too short"));
+        }
+        let (nonce, ciphertext) = sealed.split_at(12);
+        let secret = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_error| {
+                Report::new(SubscriptionSecretError)
+                    .attach_printable("the secret was sealed with a different key")
+            })?;
+        String::from_utf8(secret).change_context(SubscriptionSecretError)
+    }
+}
+
+/// A subscription which delivers changes of entities to a webhook.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EntitySubscription {
+    pub subscription_id: SubscriptionId,
+    /// The URL the changes are posted to.
+    pub url: String,
+    /// The [`Filter`] an entity has to match to be delivered.
+    ///
+    /// [`Filter`]: crate::store::query::Filter
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub filter: JsonValue,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CreateEntitySubscriptionParams {
+    /// The HTTP(S) URL the changes are posted to.
+    pub url: String,
+    /// The [`Filter`] an entity has to match to be delivered.
+    ///
+    /// [`Filter`]: crate::store::query::Filter
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub filter: JsonValue,
+    /// The secret used to sign the payloads.
+    pub secret: String,
+}
+
+/// A subscription including the information required to deliver changes to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionTarget {
+    pub subscription_id: SubscriptionId,
+    /// The actor who created the subscription, only changes visible to them are delivered.
+    pub actor_id: AccountId,
+    pub url: String,
+    pub filter: JsonValue,
+    /// The secret used to sign the payloads, sealed with the [`SubscriptionSecretKey`].
+    pub sealed_secret: Vec<u8>,
+    /// The sequence ID of the last change in the change feed which was handled.
+    pub cursor: i64,
+}
+
+/// A change which could not be delivered to the webhook of a subscription.
+///
+/// The change itself can be read from the change feed by its sequence ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ParkedChange {
+    pub sequence_id: i64,
+    /// The reason the last delivery attempt failed.
+    pub reason: String,
+}
+
+/// Describes the API of a store implementation for subscriptions to entity changes.
+pub trait SubscriptionStore {
+    /// Registers a new [`EntitySubscription`].
+    ///
+    /// Only changes which are committed after the subscription was created are delivered. The
+    /// secret is stored sealed with `secret_key`.
+    ///
+    /// # Errors
+    ///
+    /// - if the URL is not an HTTP(S) URL
+    /// - if the filter is not a valid entity filter
+    /// - if the subscription cannot be stored
+    fn create_entity_subscription(
+        &mut self,
+        actor_id: AccountId,
+        params: CreateEntitySubscriptionParams,
+        secret_key: &SubscriptionSecretKey,
+    ) -> impl Future<Output = Result<EntitySubscription, Report<InsertionError>>> + Send;
+
+    /// Returns the [`EntitySubscription`]s created by the actor.
+    ///
+    /// # Errors
+    ///
+    /// - if the subscriptions cannot be read
+    fn get_entity_subscriptions(
+        &self,
+        actor_id: AccountId,
+    ) -> impl Future<Output = Result<Vec<EntitySubscription>, Report<QueryError>>> + Send;
+
+    /// Removes an [`EntitySubscription`] created by the actor.
+    ///
+    /// # Errors
+    ///
+    /// - if the actor has no subscription with the specified ID
+    /// - if the subscription cannot be removed
+    fn delete_entity_subscription(
+        &mut self,
+        actor_id: AccountId,
+        subscription_id: SubscriptionId,
+    ) -> impl Future<Output = Result<(), Report<DeletionError>>> + Send;
+
+    /// Returns all subscriptions to deliver changes to.
+    ///
+    /// # Errors
+    ///
+    /// - if the subscriptions cannot be read
+    fn get_subscription_targets(
+        &self,
+    ) -> impl Future<Output = Result<Vec<SubscriptionTarget>, Report<QueryError>>> + Send;
+
+    /// Marks the changes up to `cursor` as handled for the subscription.
+    ///
+    /// # Errors
+    ///
+    /// - if the cursor cannot be updated
+    fn advance_subscription_cursor(
+        &mut self,
+        subscription_id: SubscriptionId,
+        cursor: i64,
+    ) -> impl Future<Output = Result<(), Report<UpdateError>>> + Send;
+
+    /// Records a change which could not be delivered to the subscription.
+    ///
+    /// # Errors
+    ///
+    /// - if the change cannot be stored
+    fn park_subscription_change(
+        &mut self,
+        subscription_id: SubscriptionId,
+        change: ParkedChange,
+    ) -> impl Future<Output = Result<(), Report<UpdateError>>> + Send;
+
+    /// Returns the [`ParkedChange`]s of a subscription created by the actor.
+    ///
+    /// # Errors
+    ///
+    /// - if the actor has no subscription with the specified ID
+    /// - if the changes cannot be read
+    fn get_parked_changes(
+        &self,
+        actor_id: AccountId,
+        subscription_id: SubscriptionId,
+    ) -> impl Future<Output = Result<Vec<ParkedChange>, Report<QueryError>>> + Send;
+}
@@ -120,6 +120,37 @@ pub struct StoreCache {
     entities: CacheHashMap<EntityId, Entity>,
 }
 
+impl StoreCache {
+    /// Replaces the [`DataType`] stored under `data_type_id` for subsequent validations.
+    ///
+    /// This is used to validate values against a schema which is not stored, yet.
+    pub(crate) async fn replace_data_type(&self, data_type_id: DataTypeId, schema: DataType) {
+        self.data_types.grant(data_type_id, schema).await;
+    }
+
+    /// Replaces the [`PropertyType`] stored under `property_type_id` for subsequent validations.
+    ///
+    /// This is used to validate values against a schema which is not stored, yet.
+    pub(crate) async fn replace_property_type(
+        &self,
+        property_type_id: PropertyTypeId,
+        schema: PropertyType,
+    ) {
+        self.property_types.grant(property_type_id, schema).await;
+    }
+
+    /// Replaces the [`ClosedEntityType`] stored under `entity_type_id` for subsequent validations.
+    ///
+    /// This is used to validate entities against a schema which is not stored, yet.
+    pub(crate) async fn replace_entity_type(
+        &self,
+        entity_type_id: EntityTypeId,
+        schema: ClosedEntityType,
+    ) {
+        self.entity_types.grant(entity_type_id, schema).await;
+    }
+}
+
 #[derive(Debug)]
 pub struct StoreProvider<'a, S, A> {
     pub store: &'a S,
@@ -1,5 +1,6 @@
 pub mod edges;
 pub mod identifier;
+pub mod rdf;
 pub mod temporal_axes;
 pub mod vertices;
 
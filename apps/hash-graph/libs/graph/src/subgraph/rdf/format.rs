@@ -0,0 +1,313 @@
+use core::fmt::Write as _;
+use std::{collections::HashMap, io};
+
+use serde_json::{json, Map as JsonMap, Value as JsonValue};
+
+use crate::subgraph::rdf::{RdfFormat, Term, Triple, PREFIXES, RDF, RDF_JSON, XSD_STRING};
+
+pub(super) fn write(
+    format: RdfFormat,
+    triples: &[Triple],
+    mut writer: impl io::Write,
+) -> io::Result<()> {
+    match format {
+        RdfFormat::NTriples => write_n_triples(triples, &mut writer),
+        RdfFormat::Turtle => write_turtle(triples, &mut writer),
+        RdfFormat::JsonLd => {
+            serde_json::to_writer_pretty(&mut writer, &json_ld(triples)).map_err(io::Error::from)
+        }
+    }
+}
+
+fn escape_iri(iri: &str) -> String {
+    let mut escaped = String::with_capacity(iri.len());
+    for character in iri.chars() {
+        match character {
+            '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' | '\0'..=' ' => {
+                let _ = write!(escaped, "\\u{:04X}", u32::from(character));
+            }
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn escape_literal(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Writes a term in N-Triples syntax, which is valid Turtle as well.
+fn n_triples_term(term: &Term) -> String {
+    match term {
+        Term::Iri(iri) => format!("<{}>", escape_iri(iri)),
+        Term::Blank(id) => format!("_:b{id}"),
+        Term::Literal { value, datatype } if *datatype == XSD_STRING => {
+            format!("\"{}\"", escape_literal(value))
+        }
+        Term::Literal { value, datatype } => {
+            format!("\"{}\"^^<{datatype}>", escape_literal(value))
+        }
+    }
+}
+
+fn write_n_triples(triples: &[Triple], writer: &mut impl io::Write) -> io::Result<()> {
+    for triple in triples {
+        writeln!(
+            writer,
+            "{} <{}> {} .",
+            n_triples_term(&triple.subject),
+            escape_iri(&triple.predicate),
+            n_triples_term(&triple.object),
+        )?;
+    }
+    Ok(())
+}
+
+/// Abbreviates an IRI using one of the known prefixes if the local name allows it.
+fn compact_iri(iri: &str) -> Option<String> {
+    PREFIXES.iter().find_map(|(prefix, namespace)| {
+        let local = iri.strip_prefix(namespace)?;
+        (!local.is_empty()
+            && local
+                .chars()
+                .all(|character| character.is_ascii_alphanumeric() || character == '_'))
+        .then(|| format!("{prefix}:{local}"))
+    })
+}
+
+fn turtle_iri(iri: &str) -> String {
+    compact_iri(iri).unwrap_or_else(|| format!("<{}>", escape_iri(iri)))
+}
+
+fn turtle_term(term: &Term) -> String {
+    match term {
+        Term::Iri(iri) => turtle_iri(iri),
+        Term::Literal { value, datatype } if *datatype != XSD_STRING => {
+            format!("\"{}\"^^{}", escape_literal(value), turtle_iri(datatype))
+        }
+        Term::Blank(_) | Term::Literal { .. } => n_triples_term(term),
+    }
+}
+
+/// Groups the triples by subject while keeping the order in which the subjects appear first.
+fn group_by_subject(triples: &[Triple]) -> Vec<(&Term, Vec<&Triple>)> {
+    let mut groups: Vec<(&Term, Vec<&Triple>)> = Vec::new();
+    let mut indices = HashMap::new();
+    for triple in triples {
+        let index = *indices.entry(&triple.subject).or_insert_with(|| {
+            groups.push((&triple.subject, Vec::new()));
+            groups.len() - 1
+        });
+        groups[index].1.push(triple);
+    }
+    groups
+}
+
+fn write_turtle(triples: &[Triple], writer: &mut impl io::Write) -> io::Result<()> {
+    for (prefix, namespace) in PREFIXES {
+        writeln!(writer, "@prefix {prefix}: <{namespace}> .")?;
+    }
+
+    for (subject, group) in group_by_subject(triples) {
+        writeln!(writer)?;
+        write!(writer, "{}", turtle_term(subject))?;
+        for (index, triple) in group.iter().enumerate() {
+            let predicate = if triple.predicate == format!("{RDF}type") {
+                "a".to_owned()
+            } else {
+                turtle_iri(&triple.predicate)
+            };
+            let separator = if index == 0 { "\n" } else { " ;\n" };
+            write!(
+                writer,
+                "{separator}    {predicate} {}",
+                turtle_term(&triple.object)
+            )?;
+        }
+        writeln!(writer, " .")?;
+    }
+    Ok(())
+}
+
+fn json_ld_id(term: &Term) -> String {
+    match term {
+        Term::Iri(iri) => compact_iri(iri).unwrap_or_else(|| iri.clone()),
+        Term::Blank(id) => format!("_:b{id}"),
+        Term::Literal { value, .. } => value.clone(),
+    }
+}
+
+fn json_ld_object(term: &Term) -> JsonValue {
+    match term {
+        Term::Iri(_) | Term::Blank(_) => json!({ "@id": json_ld_id(term) }),
+        Term::Literal { value, datatype } if *datatype == XSD_STRING => {
+            json!({ "@value": value })
+        }
+        Term::Literal { value, datatype } if *datatype == RDF_JSON => json!({
+            "@value": serde_json::from_str::<JsonValue>(value).unwrap_or(JsonValue::Null),
+            "@type": "@json",
+        }),
+        Term::Literal { value, datatype } => json!({
+            "@value": value,
+            "@type": json_ld_id(&Term::Iri((*datatype).to_owned())),
+        }),
+    }
+}
+
+/// Creates a flattened JSON-LD document.
+fn json_ld(triples: &[Triple]) -> JsonValue {
+    let context = PREFIXES
+        .iter()
+        .map(|(prefix, namespace)| ((*prefix).to_owned(), JsonValue::from(*namespace)))
+        .collect::<JsonMap<_, _>>();
+
+    let graph = group_by_subject(triples)
+        .into_iter()
+        .map(|(subject, group)| {
+            let mut node = JsonMap::new();
+            node.insert("@id".to_owned(), JsonValue::from(json_ld_id(subject)));
+            for triple in group {
+                let (key, value) = match &triple.object {
+                    Term::Iri(_) if triple.predicate == format!("{RDF}type") => (
+                        "@type".to_owned(),
+                        JsonValue::from(json_ld_id(&triple.object)),
+                    ),
+                    object => (
+                        json_ld_id(&Term::Iri(triple.predicate.clone())),
+                        json_ld_object(object),
+                    ),
+                };
+                match node
+                    .entry(key)
+                    .or_insert_with(|| JsonValue::Array(Vec::new()))
+                {
+                    JsonValue::Array(values) => values.push(value),
+                    _ => unreachable!("node values are always arrays"),
+                }
+            }
+            JsonValue::Object(node)
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "@context": context,
+        "@graph": graph,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subgraph::rdf::{HASH, XSD_INTEGER};
+
+    fn triples() -> Vec<Triple> {
+        let subject = Term::Iri("urn:hash:entity:a".to_owned());
+        vec![
+            Triple {
+                subject: subject.clone(),
+                predicate: format!("{RDF}type"),
+                object: Term::Iri("https://example.com/person/v/1".to_owned()),
+            },
+            Triple {
+                subject: subject.clone(),
+                predicate: "https://example.com/name/".to_owned(),
+                object: Term::string("Alice \"A\"\n"),
+            },
+            Triple {
+                subject,
+                predicate: format!("{HASH}version"),
+                object: Term::typed("1", XSD_INTEGER),
+            },
+        ]
+    }
+
+    fn write_to_string(format: RdfFormat) -> String {
+        let mut output = Vec::new();
+        write(format, &triples(), &mut output).expect("could not write triples");
+        String::from_utf8(output).expect("output is not UTF-8")
+    }
+
+    #[test]
+    fn n_triples() {
+        assert_eq!(
+            write_to_string(RdfFormat::NTriples),
+            "<urn:hash:entity:a> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://example.com/person/v/1> .\n\
+             <urn:hash:entity:a> <https://example.com/name/> \"Alice \\\"A\\\"\\n\" .\n\
+             <urn:hash:entity:a> <https://hash.ai/ns/graph#version> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n"
+        );
+    }
+
+    #[test]
+    fn turtle() {
+        let turtle = write_to_string(RdfFormat::Turtle);
+        assert!(
+            turtle.starts_with("@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .\n")
+        );
+        assert!(turtle.ends_with(
+            "\n<urn:hash:entity:a>\n    a <https://example.com/person/v/1> ;\n    \
+             <https://example.com/name/> \"Alice \\\"A\\\"\\n\" ;\n    \
+             hash:version \"1\"^^xsd:integer .\n"
+        ));
+    }
+
+    #[test]
+    fn json_ld() {
+        let document = serde_json::from_str::<JsonValue>(&write_to_string(RdfFormat::JsonLd))
+            .expect("output is not valid JSON");
+        assert_eq!(document["@context"]["hash"], HASH);
+        assert_eq!(
+            document["@graph"],
+            json!([{
+                "@id": "urn:hash:entity:a",
+                "@type": ["https://example.com/person/v/1"],
+                "https://example.com/name/": [{ "@value": "Alice \"A\"\n" }],
+                "hash:version": [{ "@value": "1", "@type": "xsd:integer" }],
+            }])
+        );
+    }
+
+    #[test]
+    fn groups_interleaved_subjects() {
+        let triple = |subject: &str, value: &str| Triple {
+            subject: Term::Iri(subject.to_owned()),
+            predicate: "https://example.com/name/".to_owned(),
+            object: Term::string(value),
+        };
+        let triples = [
+            triple("urn:a", "1"),
+            triple("urn:b", "2"),
+            triple("urn:a", "3"),
+        ];
+
+        let groups = group_by_subject(&triples)
+            .into_iter()
+            .map(|(subject, group)| (subject, group.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            [
+                (&Term::Iri("urn:a".to_owned()), 2),
+                (&Term::Iri("urn:b".to_owned()), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn escapes_iris() {
+        assert_eq!(
+            escape_iri("https://example.com/a b>"),
+            "https://example.com/a\\u0020b\\u003E"
+        );
+    }
+}
@@ -0,0 +1,827 @@
+//! Linked data representation of a [`Subgraph`].
+//!
+//! The vertices of a subgraph are converted into RDF triples, which can be written as JSON-LD,
+//! N-Triples, or Turtle:
+//!
+//! - Ontology types are identified by their [`VersionedUrl`], and the [`BaseUrl`] of a property
+//!   type is used as predicate for the property values of entities.
+//! - Entities are identified by `urn:hash:entity:<entity ID>`. Each edition of an entity is
+//!   identified by `urn:hash:edition:<edition ID>` and refers to its entity using `hash:editionOf`.
+//!   The entity types, properties, and metadata are attached to the edition, so multiple editions
+//!   of an entity in the same subgraph are not merged.
+//! - Link entity editions additionally describe a reified `rdf:Statement` between the left and the
+//!   right entity, using the link entity type as predicate. The statement itself is asserted as
+//!   well.
+//! - Temporal metadata is attached as annotations using the `hash:` vocabulary.
+//!
+//! [`VersionedUrl`]: type_system::url::VersionedUrl
+
+mod format;
+
+use core::str::FromStr;
+use std::io;
+
+use graph_types::{
+    knowledge::{
+        entity::{Entity, EntityEditionId, EntityId},
+        Property, PropertyObject,
+    },
+    ontology::{
+        DataTypeWithMetadata, EntityTypeWithMetadata, OntologyTemporalMetadata,
+        PropertyTypeWithMetadata,
+    },
+};
+use mime::Mime;
+use serde_json::Value as JsonValue;
+use temporal_versioning::{
+    ClosedTemporalBound, LeftClosedTemporalInterval, OpenTemporalBound, Timestamp,
+};
+use type_system::url::{BaseUrl, VersionedUrl};
+
+use crate::subgraph::{vertices::Vertices, Subgraph};
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+/// The vocabulary used for the graph specific terms.
+pub const HASH: &str = "https://hash.ai/ns/graph#";
+
+/// The prefixes used when writing Turtle and JSON-LD.
+const PREFIXES: [(&str, &str); 4] = [("rdf", RDF), ("rdfs", RDFS), ("xsd", XSD), ("hash", HASH)];
+
+/// A linked data serialization format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum RdfFormat {
+    /// JSON-LD, `application/ld+json`
+    JsonLd,
+    /// N-Triples, `application/n-triples`
+    NTriples,
+    /// Turtle, `text/turtle`
+    Turtle,
+}
+
+impl RdfFormat {
+    #[must_use]
+    pub const fn media_type(self) -> &'static str {
+        match self {
+            Self::JsonLd => "application/ld+json",
+            Self::NTriples => "application/n-triples",
+            Self::Turtle => "text/turtle",
+        }
+    }
+
+    #[must_use]
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        match media_type {
+            "application/ld+json" => Some(Self::JsonLd),
+            "application/n-triples" => Some(Self::NTriples),
+            "text/turtle" => Some(Self::Turtle),
+            _ => None,
+        }
+    }
+
+    /// Selects the format requested by an `Accept` header.
+    ///
+    /// Returns `None` if plain JSON is preferred over all linked data formats or if no linked data
+    /// format is accepted at all.
+    #[must_use]
+    pub fn negotiate(accept: &str) -> Option<Self> {
+        let mut preferred: Option<(f32, Option<Self>)> = None;
+        for media_range in accept.split(',') {
+            let Ok(media_range) = Mime::from_str(media_range.trim()) else {
+                continue;
+            };
+            let quality = media_range
+                .get_param("q")
+                .and_then(|quality| quality.as_str().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            let format = match media_range.essence_str() {
+                "application/json" | "application/*" | "*/*" => None,
+                media_type => match Self::from_media_type(media_type) {
+                    Some(format) => Some(format),
+                    None => continue,
+                },
+            };
+            if preferred.map_or(true, |(preferred, _)| quality > preferred) {
+                preferred = Some((quality, format));
+            }
+        }
+
+        preferred
+            .filter(|(quality, _)| *quality > 0.0)
+            .and_then(|(_, format)| format)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Term {
+    Iri(String),
+    Blank(usize),
+    Literal {
+        value: String,
+        datatype: &'static str,
+    },
+}
+
+impl Term {
+    fn iri(namespace: &str, name: &str) -> Self {
+        Self::Iri(format!("{namespace}{name}"))
+    }
+
+    fn string(value: impl Into<String>) -> Self {
+        Self::Literal {
+            value: value.into(),
+            datatype: XSD_STRING,
+        }
+    }
+
+    fn typed(value: impl Into<String>, datatype: &'static str) -> Self {
+        Self::Literal {
+            value: value.into(),
+            datatype,
+        }
+    }
+}
+
+impl From<&BaseUrl> for Term {
+    fn from(url: &BaseUrl) -> Self {
+        Self::Iri(url.to_string())
+    }
+}
+
+impl From<&VersionedUrl> for Term {
+    fn from(url: &VersionedUrl) -> Self {
+        Self::Iri(url.to_string())
+    }
+}
+
+impl From<&EntityId> for Term {
+    fn from(entity_id: &EntityId) -> Self {
+        Self::Iri(format!("urn:hash:entity:{entity_id}"))
+    }
+}
+
+impl From<EntityEditionId> for Term {
+    fn from(edition_id: EntityEditionId) -> Self {
+        Self::Iri(format!("urn:hash:edition:{}", edition_id.as_uuid()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Triple {
+    pub subject: Term,
+    pub predicate: String,
+    pub object: Term,
+}
+
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_DATE_TIME: &str = "http://www.w3.org/2001/XMLSchema#dateTime";
+const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+
+/// Collects the triples describing the vertices of a subgraph.
+#[derive(Debug, Default)]
+pub(crate) struct Triples {
+    triples: Vec<Triple>,
+    blank_nodes: usize,
+}
+
+impl Triples {
+    pub(crate) fn from_vertices(vertices: &Vertices) -> Self {
+        let mut triples = Self::default();
+
+        let mut data_types = vertices.data_types.values().collect::<Vec<_>>();
+        data_types.sort_by(|lhs, rhs| lhs.schema.id.cmp(&rhs.schema.id));
+        for data_type in data_types {
+            triples.data_type(data_type);
+        }
+
+        let mut property_types = vertices.property_types.values().collect::<Vec<_>>();
+        property_types.sort_by(|lhs, rhs| lhs.schema.id.cmp(&rhs.schema.id));
+        for property_type in property_types {
+            triples.property_type(property_type);
+        }
+
+        let mut entity_types = vertices.entity_types.values().collect::<Vec<_>>();
+        entity_types.sort_by(|lhs, rhs| lhs.schema.id.cmp(&rhs.schema.id));
+        for entity_type in entity_types {
+            triples.entity_type(entity_type);
+        }
+
+        let mut entities = vertices.entities.values().collect::<Vec<_>>();
+        entities.sort_by_key(|entity| {
+            (
+                entity.metadata.record_id.entity_id.to_string(),
+                entity.metadata.record_id.edition_id,
+            )
+        });
+        for entity in entities {
+            triples.entity(entity);
+        }
+
+        triples
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<Triple> {
+        self.triples
+    }
+
+    fn push(&mut self, subject: &Term, predicate: impl Into<String>, object: Term) {
+        self.triples.push(Triple {
+            subject: subject.clone(),
+            predicate: predicate.into(),
+            object,
+        });
+    }
+
+    fn blank_node(&mut self) -> Term {
+        self.blank_nodes += 1;
+        Term::Blank(self.blank_nodes)
+    }
+
+    fn timestamp<A>(&mut self, subject: &Term, predicate: &str, timestamp: &Timestamp<A>) {
+        // The serialized form of a timestamp is ISO 8601, which is a valid `xsd:dateTime`
+        let value = match serde_json::to_value(timestamp) {
+            Ok(JsonValue::String(value)) => value,
+            _ => timestamp.to_string(),
+        };
+        self.push(
+            subject,
+            format!("{HASH}{predicate}"),
+            Term::typed(value, XSD_DATE_TIME),
+        );
+    }
+
+    fn interval<A>(
+        &mut self,
+        subject: &Term,
+        axis: &str,
+        interval: &LeftClosedTemporalInterval<A>,
+    ) {
+        let ClosedTemporalBound::Inclusive(start) = interval.start();
+        self.timestamp(subject, &format!("{axis}Start"), start);
+        if let OpenTemporalBound::Exclusive(end) = interval.end() {
+            self.timestamp(subject, &format!("{axis}End"), end);
+        }
+    }
+
+    /// Describes the properties every ontology type has.
+    fn ontology_type(
+        &mut self,
+        subject: &Term,
+        kind: &str,
+        id: &VersionedUrl,
+        title: &str,
+        description: Option<&str>,
+        temporal_versioning: &OntologyTemporalMetadata,
+    ) {
+        self.push(subject, format!("{RDF}type"), Term::iri(HASH, kind));
+        self.push(subject, format!("{RDFS}label"), Term::string(title));
+        if let Some(description) = description {
+            self.push(subject, format!("{RDFS}comment"), Term::string(description));
+        }
+        self.push(subject, format!("{HASH}baseUrl"), Term::from(&id.base_url));
+        self.push(
+            subject,
+            format!("{HASH}version"),
+            Term::typed(id.version.inner().to_string(), XSD_INTEGER),
+        );
+        self.interval(
+            subject,
+            "transactionTime",
+            &temporal_versioning.transaction_time,
+        );
+    }
+
+    fn data_type(&mut self, data_type: &DataTypeWithMetadata) {
+        let subject = Term::from(&data_type.schema.id);
+        self.push(&subject, format!("{RDF}type"), Term::iri(RDFS, "Datatype"));
+        self.ontology_type(
+            &subject,
+            "DataType",
+            &data_type.schema.id,
+            &data_type.schema.title,
+            data_type.schema.description.as_deref(),
+            &data_type.metadata.temporal_versioning,
+        );
+        for parent in &data_type.schema.all_of {
+            self.push(
+                &subject,
+                format!("{RDFS}subClassOf"),
+                Term::from(&parent.url),
+            );
+        }
+    }
+
+    fn property_type(&mut self, property_type: &PropertyTypeWithMetadata) {
+        let subject = Term::from(&property_type.schema.id);
+        self.ontology_type(
+            &subject,
+            "PropertyType",
+            &property_type.schema.id,
+            &property_type.schema.title,
+            property_type.schema.description.as_deref(),
+            &property_type.metadata.temporal_versioning,
+        );
+        // The base URL is used as predicate for the property values
+        self.push(
+            &Term::from(&property_type.schema.id.base_url),
+            format!("{RDF}type"),
+            Term::iri(RDF, "Property"),
+        );
+
+        let mut data_types = property_type
+            .schema
+            .data_type_references()
+            .into_iter()
+            .map(|reference| &reference.url)
+            .collect::<Vec<_>>();
+        data_types.sort();
+        for data_type in data_types {
+            self.push(
+                &subject,
+                format!("{HASH}expectsDataType"),
+                Term::from(data_type),
+            );
+        }
+
+        let mut property_types = property_type
+            .schema
+            .property_type_references()
+            .into_iter()
+            .map(|reference| &reference.url)
+            .collect::<Vec<_>>();
+        property_types.sort();
+        for property_type in property_types {
+            self.push(
+                &subject,
+                format!("{HASH}expectsPropertyType"),
+                Term::from(property_type),
+            );
+        }
+    }
+
+    fn entity_type(&mut self, entity_type: &EntityTypeWithMetadata) {
+        let subject = Term::from(&entity_type.schema.id);
+        self.push(&subject, format!("{RDF}type"), Term::iri(RDFS, "Class"));
+        self.ontology_type(
+            &subject,
+            "EntityType",
+            &entity_type.schema.id,
+            &entity_type.schema.title,
+            entity_type.schema.description.as_deref(),
+            &entity_type.metadata.temporal_versioning,
+        );
+
+        let mut parents = entity_type
+            .schema
+            .all_of
+            .iter()
+            .map(|reference| &reference.url)
+            .collect::<Vec<_>>();
+        parents.sort();
+        for parent in parents {
+            self.push(&subject, format!("{RDFS}subClassOf"), Term::from(parent));
+        }
+
+        let mut properties = entity_type
+            .schema
+            .property_type_references()
+            .into_iter()
+            .map(|reference| &reference.url)
+            .collect::<Vec<_>>();
+        properties.sort();
+        for property in properties {
+            self.push(&subject, format!("{HASH}property"), Term::from(property));
+        }
+
+        let mut links = entity_type.schema.links.keys().collect::<Vec<_>>();
+        links.sort();
+        for link in links {
+            self.push(&subject, format!("{HASH}link"), Term::from(link));
+        }
+    }
+
+    fn entity(&mut self, entity: &Entity) {
+        let subject = Term::from(entity.metadata.record_id.edition_id);
+        self.push(
+            &subject,
+            format!("{HASH}editionOf"),
+            Term::from(&entity.metadata.record_id.entity_id),
+        );
+
+        let mut entity_types = entity.metadata.entity_type_ids.iter().collect::<Vec<_>>();
+        entity_types.sort();
+        for entity_type in &entity_types {
+            self.push(&subject, format!("{RDF}type"), Term::from(*entity_type));
+        }
+
+        self.property_object(&subject, &entity.properties);
+
+        if let Some(link_data) = &entity.link_data {
+            let left = Term::from(&link_data.left_entity_id);
+            let right = Term::from(&link_data.right_entity_id);
+
+            self.push(&subject, format!("{RDF}type"), Term::iri(RDF, "Statement"));
+            self.push(&subject, format!("{RDF}subject"), left.clone());
+            self.push(&subject, format!("{RDF}object"), right.clone());
+            for entity_type in entity_types {
+                self.push(&subject, format!("{RDF}predicate"), Term::from(entity_type));
+                self.push(&left, entity_type.to_string(), right.clone());
+            }
+        }
+
+        self.push(
+            &subject,
+            format!("{HASH}archived"),
+            Term::typed(entity.metadata.archived.to_string(), XSD_BOOLEAN),
+        );
+        self.interval(
+            &subject,
+            "decisionTime",
+            &entity.metadata.temporal_versioning.decision_time,
+        );
+        self.interval(
+            &subject,
+            "transactionTime",
+            &entity.metadata.temporal_versioning.transaction_time,
+        );
+    }
+
+    fn property_object(&mut self, subject: &Term, properties: &PropertyObject) {
+        let mut properties = properties.iter().collect::<Vec<_>>();
+        properties.sort_by_key(|(base_url, _)| *base_url);
+        for (base_url, property) in properties {
+            self.property(subject, base_url, property);
+        }
+    }
+
+    fn property(&mut self, subject: &Term, base_url: &BaseUrl, property: &Property) {
+        match property {
+            // Lists of property values are represented as multiple values of the same predicate
+            Property::Array(values) => {
+                for value in values {
+                    self.property(subject, base_url, value);
+                }
+            }
+            Property::Object(object) => {
+                let node = self.blank_node();
+                self.push(subject, base_url.to_string(), node.clone());
+                self.property_object(&node, object);
+            }
+            Property::Value(value) => {
+                let object = match value {
+                    JsonValue::Null => Term::iri(RDF, "nil"),
+                    JsonValue::Bool(value) => Term::typed(value.to_string(), XSD_BOOLEAN),
+                    JsonValue::Number(value) if value.is_f64() => {
+                        Term::typed(value.to_string(), XSD_DOUBLE)
+                    }
+                    JsonValue::Number(value) => Term::typed(value.to_string(), XSD_INTEGER),
+                    JsonValue::String(value) => Term::string(value),
+                    JsonValue::Array(_) | JsonValue::Object(_) => {
+                        Term::typed(value.to_string(), RDF_JSON)
+                    }
+                };
+                self.push(subject, base_url.to_string(), object);
+            }
+        }
+    }
+}
+
+impl Subgraph {
+    /// Writes the vertices of the subgraph as linked data.
+    ///
+    /// See the [module documentation](self::rdf) for how the subgraph is represented.
+    ///
+    /// # Errors
+    ///
+    /// - if writing to `writer` fails
+    pub fn write_rdf(&self, format: RdfFormat, writer: impl io::Write) -> io::Result<()> {
+        format::write(
+            format,
+            &Triples::from_vertices(&self.vertices).into_vec(),
+            writer,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graph_types::{
+        knowledge::{entity::EntityMetadata, link::LinkData},
+        ontology::DataTypeMetadata,
+    };
+    use serde_json::json;
+    use type_system::schema::DataType;
+
+    use super::*;
+
+    const PERSON: &str = "https://example.com/@alice/types/entity-type/person/v/1";
+    const FRIEND_OF: &str = "https://example.com/@alice/types/entity-type/friend-of/v/1";
+    const NAME: &str = "https://example.com/@alice/types/property-type/name/";
+    const OWNER: &str = "7d1b5a31-4c3f-4c36-8a3e-7e7a2f0ab0ad";
+
+    fn entity_id(uuid: &str) -> String {
+        format!("{OWNER}~{uuid}")
+    }
+
+    const EDITION: &str = "0b7a2e41-0f5e-4d2c-9d0f-6e1b2f9a8c11";
+
+    fn entity(uuid: &str, entity_type: &str, properties: JsonValue) -> Entity {
+        let metadata = serde_json::from_value::<EntityMetadata>(json!({
+            "recordId": {
+                "entityId": entity_id(uuid),
+                "editionId": EDITION,
+            },
+            "temporalVersioning": {
+                "decisionTime": {
+                    "start": { "kind": "inclusive", "limit": "2024-01-01T00:00:00Z" },
+                    "end": { "kind": "unbounded" },
+                },
+                "transactionTime": {
+                    "start": { "kind": "inclusive", "limit": "2024-01-02T00:00:00Z" },
+                    "end": { "kind": "exclusive", "limit": "2024-01-03T00:00:00Z" },
+                },
+            },
+            "entityTypeIds": [entity_type],
+            "archived": false,
+            "provenance": {
+                "createdById": OWNER,
+                "createdAtTransactionTime": "2024-01-02T00:00:00Z",
+                "createdAtDecisionTime": "2024-01-01T00:00:00Z",
+                "edition": { "createdById": OWNER },
+            },
+        }))
+        .expect("invalid entity metadata");
+
+        Entity {
+            properties: serde_json::from_value(properties).expect("invalid properties"),
+            link_data: None,
+            metadata,
+        }
+    }
+
+    fn contains(triples: &[Triple], subject: &Term, predicate: &str, object: &Term) -> bool {
+        triples.iter().any(|triple| {
+            triple.subject == *subject && triple.predicate == predicate && triple.object == *object
+        })
+    }
+
+    #[test]
+    fn negotiation() {
+        assert_eq!(RdfFormat::negotiate("text/turtle"), Some(RdfFormat::Turtle));
+        assert_eq!(
+            RdfFormat::negotiate("application/json;q=0.5, application/ld+json"),
+            Some(RdfFormat::JsonLd)
+        );
+        assert_eq!(
+            RdfFormat::negotiate("application/n-triples;q=0.8, application/json"),
+            None
+        );
+        assert_eq!(RdfFormat::negotiate("*/*"), None);
+        assert_eq!(RdfFormat::negotiate("text/turtle;q=0"), None);
+        assert_eq!(RdfFormat::negotiate("text/html"), None);
+    }
+
+    #[test]
+    fn entity_properties() {
+        let alice = entity(
+            "5a2c4b9e-6f0d-4e1a-9b3c-2d8f7e6a5b41",
+            PERSON,
+            json!({
+                NAME: "Alice",
+                "https://example.com/@alice/types/property-type/age/": 42,
+                "https://example.com/@alice/types/property-type/nicknames/": ["Al", "Ally"],
+                "https://example.com/@alice/types/property-type/address/": {
+                    "https://example.com/@alice/types/property-type/city/": "Berlin",
+                },
+            }),
+        );
+
+        let mut triples = Triples::default();
+        triples.entity(&alice);
+        let triples = triples.into_vec();
+
+        let subject = Term::Iri(format!("urn:hash:edition:{EDITION}"));
+        assert!(contains(
+            &triples,
+            &subject,
+            &format!("{HASH}editionOf"),
+            &Term::Iri(format!(
+                "urn:hash:entity:{}",
+                entity_id("5a2c4b9e-6f0d-4e1a-9b3c-2d8f7e6a5b41")
+            ))
+        ));
+        assert!(contains(
+            &triples,
+            &subject,
+            &format!("{RDF}type"),
+            &Term::Iri(PERSON.to_owned())
+        ));
+        assert!(contains(&triples, &subject, NAME, &Term::string("Alice")));
+        assert!(contains(
+            &triples,
+            &subject,
+            "https://example.com/@alice/types/property-type/age/",
+            &Term::typed("42", XSD_INTEGER)
+        ));
+        assert!(contains(
+            &triples,
+            &subject,
+            "https://example.com/@alice/types/property-type/nicknames/",
+            &Term::string("Ally")
+        ));
+        assert!(contains(
+            &triples,
+            &subject,
+            "https://example.com/@alice/types/property-type/address/",
+            &Term::Blank(1)
+        ));
+        assert!(contains(
+            &triples,
+            &Term::Blank(1),
+            "https://example.com/@alice/types/property-type/city/",
+            &Term::string("Berlin")
+        ));
+        assert!(contains(
+            &triples,
+            &subject,
+            &format!("{HASH}transactionTimeEnd"),
+            &Term::typed("2024-01-03T00:00:00.000000000Z", XSD_DATE_TIME)
+        ));
+        assert!(
+            !triples
+                .iter()
+                .any(|triple| triple.predicate == format!("{HASH}decisionTimeEnd"))
+        );
+    }
+
+    #[test]
+    fn link_entities_are_reified() {
+        let mut link = entity("c4e3b2a1-9f8e-4d7c-8b6a-5f4e3d2c1b0a", FRIEND_OF, json!({}));
+        link.link_data = Some(
+            serde_json::from_value::<LinkData>(json!({
+                "leftEntityId": entity_id("5a2c4b9e-6f0d-4e1a-9b3c-2d8f7e6a5b41"),
+                "rightEntityId": entity_id("e1d2c3b4-a5f6-4e7d-8c9b-0a1b2c3d4e5f"),
+            }))
+            .expect("invalid link data"),
+        );
+
+        let mut triples = Triples::default();
+        triples.entity(&link);
+        let triples = triples.into_vec();
+
+        let statement = Term::Iri(format!("urn:hash:edition:{EDITION}"));
+        let left = Term::Iri(format!(
+            "urn:hash:entity:{}",
+            entity_id("5a2c4b9e-6f0d-4e1a-9b3c-2d8f7e6a5b41")
+        ));
+        let right = Term::Iri(format!(
+            "urn:hash:entity:{}",
+            entity_id("e1d2c3b4-a5f6-4e7d-8c9b-0a1b2c3d4e5f")
+        ));
+
+        assert!(contains(&triples, &left, FRIEND_OF, &right));
+        assert!(contains(
+            &triples,
+            &statement,
+            &format!("{RDF}type"),
+            &Term::iri(RDF, "Statement")
+        ));
+        assert!(contains(
+            &triples,
+            &statement,
+            &format!("{RDF}subject"),
+            &left
+        ));
+        assert!(contains(
+            &triples,
+            &statement,
+            &format!("{RDF}predicate"),
+            &Term::Iri(FRIEND_OF.to_owned())
+        ));
+        assert!(contains(
+            &triples,
+            &statement,
+            &format!("{RDF}object"),
+            &right
+        ));
+    }
+
+    #[test]
+    fn editions_are_distinct() {
+        let first = entity(
+            "5a2c4b9e-6f0d-4e1a-9b3c-2d8f7e6a5b41",
+            PERSON,
+            json!({ NAME: "Alice" }),
+        );
+        let mut second = entity(
+            "5a2c4b9e-6f0d-4e1a-9b3c-2d8f7e6a5b41",
+            PERSON,
+            json!({ NAME: "Alicia" }),
+        );
+        second.metadata.record_id.edition_id = EntityEditionId::new(
+            "9c8b7a65-4321-4fed-8cba-0987654321fe"
+                .parse()
+                .expect("invalid edition ID"),
+        );
+
+        let mut triples = Triples::default();
+        triples.entity(&first);
+        triples.entity(&second);
+        let triples = triples.into_vec();
+
+        let names = triples
+            .iter()
+            .filter(|triple| triple.predicate == NAME)
+            .map(|triple| (&triple.subject, &triple.object))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                (
+                    &Term::Iri(format!("urn:hash:edition:{EDITION}")),
+                    &Term::string("Alice")
+                ),
+                (
+                    &Term::Iri("urn:hash:edition:9c8b7a65-4321-4fed-8cba-0987654321fe".to_owned()),
+                    &Term::string("Alicia")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn data_types() {
+        let data_type = DataTypeWithMetadata {
+            schema: serde_json::from_value::<DataType>(json!({
+                "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+                "kind": "dataType",
+                "$id": "https://example.com/@alice/types/data-type/email/v/2",
+                "title": "Email",
+                "description": "An email address",
+                "allOf": [{ "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1" }],
+                "type": "string",
+                "format": "email",
+            }))
+            .expect("invalid data type"),
+            metadata: serde_json::from_value::<DataTypeMetadata>(json!({
+                "recordId": {
+                    "baseUrl": "https://example.com/@alice/types/data-type/email/",
+                    "version": 2,
+                },
+                "ownedById": OWNER,
+                "temporalVersioning": {
+                    "transactionTime": {
+                        "start": { "kind": "inclusive", "limit": "2024-01-02T00:00:00Z" },
+                        "end": { "kind": "unbounded" },
+                    },
+                },
+                "provenance": { "edition": { "createdById": OWNER } },
+            }))
+            .expect("invalid data type metadata"),
+        };
+
+        let mut triples = Triples::default();
+        triples.data_type(&data_type);
+        let triples = triples.into_vec();
+
+        let subject = Term::Iri("https://example.com/@alice/types/data-type/email/v/2".to_owned());
+        assert!(contains(
+            &triples,
+            &subject,
+            &format!("{RDF}type"),
+            &Term::iri(RDFS, "Datatype")
+        ));
+        assert!(contains(
+            &triples,
+            &subject,
+            &format!("{RDFS}label"),
+            &Term::string("Email")
+        ));
+        assert!(contains(
+            &triples,
+            &subject,
+            &format!("{RDFS}subClassOf"),
+            &Term::Iri(
+                "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1".to_owned()
+            )
+        ));
+        assert!(contains(
+            &triples,
+            &subject,
+            &format!("{HASH}version"),
+            &Term::typed("2", XSD_INTEGER)
+        ));
+        assert!(contains(
+            &triples,
+            &subject,
+            &format!("{HASH}transactionTimeStart"),
+            &Term::typed("2024-01-02T00:00:00.000000000Z", XSD_DATE_TIME)
+        ));
+    }
+}
@@ -0,0 +1,870 @@
+//! Delivery of entity changes to the webhooks of [`EntitySubscription`]s.
+//!
+//! The [`WebhookDispatcher`] follows the [change feed] for every subscription and posts each
+//! entity change matching the subscription's filter to its URL. Every payload is signed with the
+//! secret of the subscription, the signature is sent as [`SIGNATURE_HEADER`]. Failed deliveries
+//! are retried with exponential backoff. After [`WebhookConfig::max_attempts`] the change is
+//! parked, so it can be inspected later, and the following changes are delivered.
+//!
+//! Changes are delivered at least once: the cursor of a subscription is only advanced after a
+//! batch of changes was handled. Webhooks are only called if the [`WebhookTargetPolicy`] allows
+//! the host and all addresses it resolves to.
+//!
+//! [`EntitySubscription`]: crate::store::subscription::EntitySubscription
+//! [change feed]: crate::store::change_feed
+
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+use std::collections::HashMap;
+
+use authorization::AuthorizationApiPool;
+use error_stack::{Context, Report, ResultExt};
+use futures::{stream, StreamExt};
+use graph_types::knowledge::entity::{Entity, EntityEditionId};
+use hmac::{Hmac, Mac};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::CONTENT_TYPE,
+    redirect, Client, Url,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use temporal_versioning::TemporalBound;
+use tokio::{net::lookup_host, time::sleep};
+
+use crate::{
+    knowledge::EntityQueryPath,
+    store::{
+        change_feed::{ChangeEvent, ChangeFeedStore, ChangedRecord, ReadChangesParams},
+        knowledge::GetEntitiesParams,
+        query::{Filter, FilterExpression, ParameterList},
+        subscription::{
+            ParkedChange, SubscriptionId, SubscriptionSecretKey, SubscriptionStore,
+            SubscriptionTarget,
+        },
+        EntityQuerySorting, EntityStore, StorePool,
+    },
+    subgraph::temporal_axes::{
+        PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
+    },
+};
+
+/// The header containing the HMAC-SHA256 signature of the payload, e.g. `sha256=<hex digest>`.
+pub const SIGNATURE_HEADER: &str = "X-Hash-Signature-256";
+
+/// The header containing the ID of the subscription a payload was delivered for.
+pub const SUBSCRIPTION_HEADER: &str = "X-Hash-Subscription-Id";
+
+#[derive(Debug)]
+#[must_use]
+pub struct WebhookError;
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Could not deliver webhook")
+    }
+}
+
+impl Context for WebhookError {}
+
+/// Restricts the URLs webhooks are delivered to.
+///
+/// By default, any public host is allowed, but hosts resolving to loopback, private, link-local,
+/// or otherwise non-public addresses are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct WebhookTargetPolicy {
+    /// If set, webhooks are only delivered to these hosts and their subdomains.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-allowed-hosts",
+            value_delimiter = ',',
+            env = "HASH_GRAPH_WEBHOOK_ALLOWED_HOSTS"
+        )
+    )]
+    pub allowed_hosts: Vec<String>,
+
+    /// Webhooks are never delivered to these hosts and their subdomains.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-denied-hosts",
+            value_delimiter = ',',
+            env = "HASH_GRAPH_WEBHOOK_DENIED_HOSTS"
+        )
+    )]
+    pub denied_hosts: Vec<String>,
+
+    /// Allows delivering webhooks to loopback, private, and link-local addresses.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-allow-private-networks",
+            default_value_t = false,
+            env = "HASH_GRAPH_WEBHOOK_ALLOW_PRIVATE_NETWORKS"
+        )
+    )]
+    pub allow_private_networks: bool,
+}
+
+/// Returns if `host` is `domain` or a subdomain of it.
+fn matches_domain(host: &str, domain: &str) -> bool {
+    let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+    host.to_ascii_lowercase()
+        .strip_suffix(&domain)
+        .is_some_and(|subdomain| subdomain.is_empty() || subdomain.ends_with('.'))
+}
+
+const fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+    // Shared address space of carrier-grade NATs, RFC 6598
+    let shared = first == 100 && second & 0b1100_0000 == 0b0100_0000;
+    // "This network" and addresses reserved for future use
+    let reserved = first == 0 || first >= 240;
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || shared
+        || reserved)
+}
+
+const fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ipv4) = ip.to_ipv4_mapped() {
+        return is_public_ipv4(ipv4);
+    }
+    let [first, second, ..] = ip.segments();
+    // Unique local addresses, RFC 4193
+    let unique_local = first & 0xFE00 == 0xFC00;
+    let link_local = first & 0xFFC0 == 0xFE80;
+    // Documentation addresses, RFC 3849
+    let documentation = first == 0x2001 && second == 0x0DB8;
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || unique_local
+        || link_local
+        || documentation)
+}
+
+impl WebhookTargetPolicy {
+    /// Returns if webhooks may be delivered to `host`, regardless of the addresses it resolves to.
+    #[must_use]
+    pub fn is_host_allowed(&self, host: &str) -> bool {
+        (self.allowed_hosts.is_empty()
+            || self
+                .allowed_hosts
+                .iter()
+                .any(|domain| matches_domain(host, domain)))
+            && !self
+                .denied_hosts
+                .iter()
+                .any(|domain| matches_domain(host, domain))
+    }
+
+    /// Returns if webhooks may be delivered to `address`.
+    #[must_use]
+    pub const fn is_address_allowed(&self, address: IpAddr) -> bool {
+        self.allow_private_networks
+            || match address {
+                IpAddr::V4(ip) => is_public_ipv4(ip),
+                IpAddr::V6(ip) => is_public_ipv6(ip),
+            }
+    }
+
+    /// Checks if webhooks may be delivered to `url`.
+    ///
+    /// Addresses of domain names are checked when they are resolved for the delivery.
+    ///
+    /// # Errors
+    ///
+    /// - if the URL is not an HTTP(S) URL
+    /// - if the host or the IP address of the URL is not allowed
+    pub fn check_url(&self, url: &Url) -> Result<(), Report<WebhookError>> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(Report::new(WebhookError)
+                .attach_printable(format!("unsupported URL scheme `{}`", url.scheme())));
+        }
+        let allowed = url.host_str().is_some_and(|host| {
+            let host = host.trim_start_matches('[').trim_end_matches(']');
+            self.is_host_allowed(host)
+                && host
+                    .parse::<IpAddr>()
+                    .map_or(true, |address| self.is_address_allowed(address))
+        });
+        if allowed {
+            Ok(())
+        } else {
+            Err(Report::new(WebhookError)
+                .attach_printable(format!("webhooks must not be delivered to `{url}`")))
+        }
+    }
+}
+
+/// Resolves the hosts of webhooks and drops the addresses forbidden by the policy.
+///
+/// The connection is established to the checked addresses, so the host cannot resolve to a
+/// different address between checking and connecting.
+struct PolicyResolver {
+    policy: WebhookTargetPolicy,
+}
+
+impl Resolve for PolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = self.policy.clone();
+        Box::pin(async move {
+            let addresses = lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|address| policy.is_address_allowed(address.ip()))
+                .collect::<Vec<SocketAddr>>();
+            if addresses.is_empty() {
+                return Err(format!(
+                    "`{}` does not resolve to an address webhooks may be delivered to",
+                    name.as_str()
+                )
+                .into());
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct WebhookConfig {
+    /// The interval in milliseconds in which the change feed is checked for new changes.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long,
+            default_value_t = Self::default().poll_interval_ms,
+            env = "HASH_GRAPH_WEBHOOK_POLL_INTERVAL_MS"
+        )
+    )]
+    pub poll_interval_ms: u64,
+
+    /// The number of attempts to deliver a change before it is parked.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-max-attempts",
+            default_value_t = Self::default().max_attempts,
+            env = "HASH_GRAPH_WEBHOOK_MAX_ATTEMPTS"
+        )
+    )]
+    pub max_attempts: u32,
+
+    /// The delay in milliseconds before the first retry, it doubles for every further retry.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-initial-backoff-ms",
+            default_value_t = Self::default().initial_backoff_ms,
+            env = "HASH_GRAPH_WEBHOOK_INITIAL_BACKOFF_MS"
+        )
+    )]
+    pub initial_backoff_ms: u64,
+
+    /// The upper bound for the delay in milliseconds between two retries.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-max-backoff-ms",
+            default_value_t = Self::default().max_backoff_ms,
+            env = "HASH_GRAPH_WEBHOOK_MAX_BACKOFF_MS"
+        )
+    )]
+    pub max_backoff_ms: u64,
+
+    /// The number of subscriptions, which are delivered to at the same time.
+    #[cfg_attr(
+        feature = "clap",
+        clap(
+            long = "webhook-max-concurrency",
+            default_value_t = Self::default().max_concurrency,
+            env = "HASH_GRAPH_WEBHOOK_MAX_CONCURRENCY"
+        )
+    )]
+    pub max_concurrency: usize,
+
+    /// The key used to encrypt the secrets of subscriptions, as 64 hexadecimal characters.
+    ///
+    /// If not set, subscriptions cannot be created and no webhooks are delivered.
+    #[cfg_attr(
+        feature = "clap",
+        clap(long = "webhook-secret-key", env = "HASH_GRAPH_WEBHOOK_SECRET_KEY")
+    )]
+    pub secret_key: Option<SubscriptionSecretKey>,
+
+    #[cfg_attr(feature = "clap", clap(flatten))]
+    pub target_policy: WebhookTargetPolicy,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: 1000,
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 60_000,
+            max_concurrency: 16,
+            secret_key: None,
+            target_policy: WebhookTargetPolicy::default(),
+        }
+    }
+}
+
+impl WebhookConfig {
+    /// Returns the delay before the retry following the failed `attempt`, starting at `0`.
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(2_u64.saturating_pow(attempt))
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+/// The JSON body posted to the webhook of a subscription.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityChangePayload<'a> {
+    pub subscription_id: SubscriptionId,
+    pub change: &'a ChangeEvent,
+    /// The entity edition the change refers to.
+    pub entity: &'a Entity,
+}
+
+/// Signs `payload` with `secret` as sent in the [`SIGNATURE_HEADER`].
+#[must_use]
+#[expect(clippy::missing_panics_doc, reason = "HMAC accepts keys of any size")]
+pub fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC should accept keys of any size");
+    mac.update(payload);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Posts signed payloads to webhooks.
+#[derive(Debug, Clone)]
+pub struct WebhookClient {
+    client: Client,
+    config: WebhookConfig,
+}
+
+impl WebhookClient {
+    /// Creates a client, which only connects to the addresses allowed by
+    /// [`WebhookConfig::target_policy`].
+    ///
+    /// Redirects are not followed and proxies are not used, as both would bypass the policy.
+    ///
+    /// # Errors
+    ///
+    /// - if the HTTP client cannot be created
+    pub fn new(config: WebhookConfig) -> Result<Self, Report<WebhookError>> {
+        let client = Client::builder()
+            .dns_resolver(Arc::new(PolicyResolver {
+                policy: config.target_policy.clone(),
+            }))
+            .redirect(redirect::Policy::none())
+            .no_proxy()
+            .build()
+            .change_context(WebhookError)?;
+        Ok(Self { client, config })
+    }
+
+    /// Posts `payload` to `url` until the webhook responds with a success status code.
+    ///
+    /// # Errors
+    ///
+    /// - if the URL is not allowed by the [`WebhookTargetPolicy`]
+    /// - if the webhook did not accept the payload after [`WebhookConfig::max_attempts`]
+    pub async fn deliver(
+        &self,
+        subscription_id: SubscriptionId,
+        url: &str,
+        secret: &str,
+        payload: Vec<u8>,
+    ) -> Result<(), Report<WebhookError>> {
+        let url = Url::parse(url).change_context(WebhookError)?;
+        self.config.target_policy.check_url(&url)?;
+        let signature = sign_payload(secret, &payload);
+
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .client
+                .post(url.clone())
+                .header(CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, &signature)
+                .header(SUBSCRIPTION_HEADER, subscription_id.to_string())
+                .body(payload.clone())
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+
+            match result {
+                Ok(_) => return Ok(()),
+                Err(error) => {
+                    attempt += 1;
+                    if attempt >= self.config.max_attempts {
+                        return Err(Report::new(error)
+                            .change_context(WebhookError)
+                            .attach_printable(format!("gave up after {attempt} attempts")));
+                    }
+                    let backoff = self.config.backoff(attempt - 1);
+                    tracing::debug!(%subscription_id, %error, ?backoff, "retrying webhook delivery");
+                    sleep(backoff).await;
+                }
+            }
+        }
+    }
+}
+
+/// Delivers the changes of the change feed to the webhooks of all subscriptions.
+pub struct WebhookDispatcher<S, A> {
+    store_pool: Arc<S>,
+    authorization_api_pool: Arc<A>,
+    client: WebhookClient,
+    secret_key: SubscriptionSecretKey,
+    poll_interval: Duration,
+    max_concurrency: usize,
+}
+
+impl<S, A> WebhookDispatcher<S, A>
+where
+    S: StorePool + Send + Sync,
+    A: AuthorizationApiPool + Send + Sync,
+{
+    /// Creates a dispatcher, which opens the secrets of the subscriptions with `secret_key`.
+    ///
+    /// # Errors
+    ///
+    /// - if the HTTP client cannot be created
+    pub fn new(
+        store_pool: Arc<S>,
+        authorization_api_pool: Arc<A>,
+        config: WebhookConfig,
+        secret_key: SubscriptionSecretKey,
+    ) -> Result<Self, Report<WebhookError>> {
+        Ok(Self {
+            store_pool,
+            authorization_api_pool,
+            secret_key,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            max_concurrency: config.max_concurrency.max(1),
+            client: WebhookClient::new(config)?,
+        })
+    }
+
+    /// Continuously delivers new changes.
+    #[expect(
+        clippy::infinite_loop,
+        reason = "The dispatcher runs until the server shuts down"
+    )]
+    pub async fn run(self) -> ! {
+        loop {
+            if let Err(report) = self.dispatch().await {
+                tracing::error!(error = ?report, "Could not dispatch webhooks");
+            }
+            sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn acquire_store(&self) -> Result<S::Store<'_, A::Api<'_>>, Report<WebhookError>> {
+        let authorization_api = self
+            .authorization_api_pool
+            .acquire()
+            .await
+            .change_context(WebhookError)?;
+        self.store_pool
+            .acquire(authorization_api, None)
+            .await
+            .change_context(WebhookError)
+    }
+
+    /// Delivers the changes which were committed since the last dispatch.
+    ///
+    /// Subscriptions are handled independently of each other, at most
+    /// [`WebhookConfig::max_concurrency`] at the same time.
+    ///
+    /// # Errors
+    ///
+    /// - if the subscriptions cannot be read
+    pub async fn dispatch(&self) -> Result<(), Report<WebhookError>> {
+        let targets = self
+            .acquire_store()
+            .await?
+            .get_subscription_targets()
+            .await
+            .change_context(WebhookError)?;
+
+        stream::iter(targets)
+            .for_each_concurrent(self.max_concurrency, |target| async move {
+                let subscription_id = target.subscription_id;
+                if let Err(report) = self.dispatch_subscription(target).await {
+                    tracing::error!(%subscription_id, error = ?report, "Could not dispatch changes");
+                }
+            })
+            .await;
+
+        Ok(())
+    }
+
+    /// Reads the changes following the cursor of the subscription and the entities they refer to.
+    async fn read_changes(
+        &self,
+        target: &SubscriptionTarget,
+    ) -> Result<
+        Option<(Vec<ChangeEvent>, HashMap<EntityEditionId, Entity>, i64)>,
+        Report<WebhookError>,
+    > {
+        let filter = Filter::<Entity>::deserialize(&target.filter)
+            .change_context(WebhookError)
+            .attach_printable("invalid entity filter")?;
+        let store = self.acquire_store().await?;
+
+        let response = store
+            .read_changes(
+                target.actor_id,
+                ReadChangesParams {
+                    after: Some(target.cursor),
+                    limit: None,
+                },
+            )
+            .await
+            .change_context(WebhookError)?;
+        let Some(cursor) = response.cursor.filter(|cursor| *cursor > target.cursor) else {
+            return Ok(None);
+        };
+
+        let edition_ids = response
+            .changes
+            .iter()
+            .filter_map(|change| match &change.record {
+                ChangedRecord::Entity(record_id) => Some(record_id.edition_id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let entities = if edition_ids.is_empty() {
+            HashMap::new()
+        } else {
+            store
+                .get_entities(
+                    target.actor_id,
+                    GetEntitiesParams {
+                        filter: Filter::All(vec![
+                            Filter::In(
+                                FilterExpression::Path(EntityQueryPath::EditionId),
+                                ParameterList::EntityEditionIds(&edition_ids),
+                            ),
+                            filter,
+                        ]),
+                        // Changed editions may have been superseded already
+                        temporal_axes: QueryTemporalAxesUnresolved::TransactionTime {
+                            pinned: PinnedTemporalAxisUnresolved::new(None),
+                            variable: VariableTemporalAxisUnresolved::new(
+                                Some(TemporalBound::Unbounded),
+                                None,
+                            ),
+                        },
+                        sorting: EntityQuerySorting {
+                            paths: Vec::new(),
+                            cursor: None,
+                        },
+                        limit: None,
+                        include_drafts: true,
+                        include_count: false,
+                    },
+                )
+                .await
+                .change_context(WebhookError)?
+                .entities
+                .into_iter()
+                .map(|entity| (entity.metadata.record_id.edition_id, entity))
+                .collect()
+        };
+
+        Ok(Some((response.changes, entities, cursor)))
+    }
+
+    /// Delivers the pending changes of a single subscription and advances its cursor.
+    ///
+    /// No database connection is held while the changes are delivered.
+    async fn dispatch_subscription(
+        &self,
+        target: SubscriptionTarget,
+    ) -> Result<(), Report<WebhookError>> {
+        let secret = self
+            .secret_key
+            .open(&target.sealed_secret)
+            .change_context(WebhookError)?;
+
+        let Some((changes, entities, cursor)) = self.read_changes(&target).await? else {
+            return Ok(());
+        };
+
+        let mut parked_changes = Vec::new();
+        for change in &changes {
+            let ChangedRecord::Entity(record_id) = &change.record else {
+                continue;
+            };
+            let Some(entity) = entities.get(&record_id.edition_id) else {
+                continue;
+            };
+            if let Err(report) = self.deliver(&target, &secret, change, entity).await {
+                tracing::warn!(
+                    subscription_id = %target.subscription_id,
+                    sequence_id = change.sequence_id,
+                    error = ?report,
+                    "Parking change which could not be delivered"
+                );
+                parked_changes.push(ParkedChange {
+                    sequence_id: change.sequence_id,
+                    reason: format!("{report:#}"),
+                });
+            }
+        }
+
+        let mut store = self.acquire_store().await?;
+        for change in parked_changes {
+            store
+                .park_subscription_change(target.subscription_id, change)
+                .await
+                .change_context(WebhookError)?;
+        }
+        store
+            .advance_subscription_cursor(target.subscription_id, cursor)
+            .await
+            .change_context(WebhookError)
+    }
+
+    async fn deliver(
+        &self,
+        target: &SubscriptionTarget,
+        secret: &str,
+        change: &ChangeEvent,
+        entity: &Entity,
+    ) -> Result<(), Report<WebhookError>> {
+        let payload = serde_json::to_vec(&EntityChangePayload {
+            subscription_id: target.subscription_id,
+            change,
+            entity,
+        })
+        .change_context(WebhookError)?;
+
+        self.client
+            .deliver(target.subscription_id, &target.url, secret, payload)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use std::sync::Mutex;
+
+    use axum::{
+        body::Bytes,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Router,
+    };
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+
+    use super::*;
+
+    type Requests = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    /// Starts a receiver which rejects the first `failures` requests.
+    async fn receiver(failures: usize) -> (String, Requests) {
+        let requests = Requests::default();
+        let router = Router::new().route(
+            "/hook",
+            post({
+                let requests = Arc::clone(&requests);
+                move |headers: HeaderMap, body: Bytes| async move {
+                    let mut requests = requests.lock().expect("lock should not be poisoned");
+                    requests.push((headers, body));
+                    if requests.len() > failures {
+                        StatusCode::OK
+                    } else {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    }
+                }
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("could not bind receiver");
+        let address = listener
+            .local_addr()
+            .expect("receiver should have an address");
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        (format!("http://{address}/hook"), requests)
+    }
+
+    fn client(max_attempts: u32) -> WebhookClient {
+        WebhookClient::new(WebhookConfig {
+            poll_interval_ms: 10,
+            max_attempts,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 10,
+            target_policy: WebhookTargetPolicy {
+                allow_private_networks: true,
+                ..WebhookTargetPolicy::default()
+            },
+            ..WebhookConfig::default()
+        })
+        .expect("could not create client")
+    }
+
+    #[test]
+    fn backoff() {
+        let config = WebhookConfig {
+            poll_interval_ms: 1000,
+            max_attempts: 10,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1000,
+            ..WebhookConfig::default()
+        };
+        assert_eq!(config.backoff(0), Duration::from_millis(100));
+        assert_eq!(config.backoff(1), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(800));
+        assert_eq!(config.backoff(4), Duration::from_millis(1000));
+        assert_eq!(config.backoff(100), Duration::from_millis(1000));
+    }
+
+    #[tokio::test]
+    async fn deliver_with_retries() {
+        let (url, requests) = receiver(2).await;
+        let subscription_id = SubscriptionId::new(Uuid::new_v4());
+        let payload = br#"{"change":{}}"#.to_vec();
+
+        client(3)
+            .deliver(subscription_id, &url, "secret", payload.clone())
+            .await
+            .expect("payload should be delivered");
+
+        let requests = requests.lock().expect("lock should not be poisoned");
+        assert_eq!(requests.len(), 3);
+        for (headers, body) in requests.iter() {
+            assert_eq!(body.as_ref(), payload);
+            assert_eq!(
+                headers[SIGNATURE_HEADER],
+                sign_payload("secret", &payload).as_str()
+            );
+            assert_eq!(
+                headers[SUBSCRIPTION_HEADER],
+                subscription_id.to_string().as_str()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn give_up_delivery() {
+        let (url, requests) = receiver(usize::MAX).await;
+
+        _ = client(3)
+            .deliver(
+                SubscriptionId::new(Uuid::new_v4()),
+                &url,
+                "secret",
+                b"{}".to_vec(),
+            )
+            .await
+            .expect_err("payload should not be delivered");
+
+        assert_eq!(
+            requests.lock().expect("lock should not be poisoned").len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_private_network() {
+        let (url, requests) = receiver(0).await;
+
+        _ = WebhookClient::new(WebhookConfig::default())
+            .expect("could not create client")
+            .deliver(
+                SubscriptionId::new(Uuid::new_v4()),
+                &url,
+                "secret",
+                b"{}".to_vec(),
+            )
+            .await
+            .expect_err("payload should not be delivered to a loopback address");
+
+        assert!(
+            requests
+                .lock()
+                .expect("lock should not be poisoned")
+                .is_empty()
+        );
+    }
+
+    fn check(policy: &WebhookTargetPolicy, url: &str) -> bool {
+        policy
+            .check_url(&Url::parse(url).expect("invalid URL"))
+            .is_ok()
+    }
+
+    #[test]
+    fn target_policy() {
+        let policy = WebhookTargetPolicy::default();
+        assert!(check(&policy, "https://example.com/hook"));
+        assert!(check(&policy, "https://93.184.215.14/hook"));
+        assert!(!check(&policy, "ftp://example.com/hook"));
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://10.1.2.3/hook",
+            "http://172.16.0.1/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(!check(&policy, url), "`{url}` should be rejected");
+        }
+
+        let policy = WebhookTargetPolicy {
+            allowed_hosts: vec!["example.com".to_owned()],
+            denied_hosts: vec!["internal.example.com".to_owned()],
+            allow_private_networks: false,
+        };
+        assert!(check(&policy, "https://example.com/hook"));
+        assert!(check(&policy, "https://hooks.example.com/hook"));
+        assert!(!check(&policy, "https://internal.example.com/hook"));
+        assert!(!check(&policy, "https://api.internal.example.com/hook"));
+        assert!(!check(&policy, "https://notexample.com/hook"));
+        assert!(!check(&policy, "https://example.org/hook"));
+    }
+
+    #[test]
+    fn signature() {
+        // Test vector 2 of RFC 4231
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
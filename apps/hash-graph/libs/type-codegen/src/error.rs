@@ -0,0 +1,14 @@
+use thiserror::Error;
+use type_system::url::VersionedUrl;
+
+#[derive(Debug, Error)]
+pub enum GenerateError {
+    #[error("the data type `{0}` is referenced but has not been provided")]
+    MissingDataType(VersionedUrl),
+    #[error("the property type `{0}` is referenced but has not been provided")]
+    MissingPropertyType(VersionedUrl),
+    #[error("the entity type `{0}` is referenced but has not been provided")]
+    MissingEntityType(VersionedUrl),
+    #[error("the generated code is not valid Rust")]
+    InvalidCode,
+}
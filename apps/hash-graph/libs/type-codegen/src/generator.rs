@@ -0,0 +1,577 @@
+use std::collections::{HashMap, HashSet};
+
+use error_stack::{Report, Result, ResultExt};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use type_system::{
+    schema::{
+        ClosedEntityType, DataType, EntityType, JsonSchemaValueType, PropertyType,
+        PropertyTypeReference, PropertyValues, ValueOrArray,
+    },
+    url::{BaseUrl, VersionedUrl},
+};
+
+use crate::{
+    error::GenerateError,
+    naming::{field_name, type_name, unique_names},
+};
+
+fn docs(description: Option<&str>, id: &VersionedUrl) -> TokenStream {
+    let description = description
+        .into_iter()
+        .flat_map(str::lines)
+        .map(|line| format!(" {line}"))
+        .chain(description.map(|_| String::new()));
+    let id = format!(" Generated from <{id}>.");
+
+    quote! {
+        #(#[doc = #description])*
+        #[doc = #id]
+    }
+}
+
+#[expect(
+    clippy::struct_field_names,
+    reason = "the fields are named after the kind of the ontology type"
+)]
+struct Names {
+    data_types: HashMap<VersionedUrl, Ident>,
+    property_types: HashMap<VersionedUrl, Ident>,
+    entity_types: HashMap<VersionedUrl, Ident>,
+}
+
+impl Names {
+    fn data_type_path(&self, id: &VersionedUrl) -> Result<TokenStream, GenerateError> {
+        let name = self
+            .data_types
+            .get(id)
+            .ok_or_else(|| Report::new(GenerateError::MissingDataType(id.clone())))?;
+
+        Ok(quote!(super::data_type::#name))
+    }
+
+    fn property_type_path(&self, id: &VersionedUrl) -> Result<TokenStream, GenerateError> {
+        let name = self
+            .property_types
+            .get(id)
+            .ok_or_else(|| Report::new(GenerateError::MissingPropertyType(id.clone())))?;
+
+        Ok(quote!(super::property_type::#name))
+    }
+}
+
+/// Generates Rust types from Block Protocol ontology types.
+///
+/// Every ontology type is emitted into the module of its kind, i.e. `data_type`, `property_type`,
+/// or `entity_type`, and named after its title. Types sharing a title are suffixed with their
+/// version.
+///
+/// - Data types become type aliases of the Rust type matching their JSON type.
+/// - Property types become type aliases, structs for property objects, or untagged enums if they
+///   allow more than one value. Data types sharing a JSON type cannot be told apart by their value,
+///   so they share a variant.
+/// - Entity types are closed before generation, so the generated struct contains the properties of
+///   all parent entity types as well. Links are not part of the generated struct.
+///
+/// Structs (de)serialize from the same representation as a [`PropertyObject`] and can be converted
+/// to and from it.
+///
+/// [`PropertyObject`]: https://docs.rs/graph-types/latest/graph_types/knowledge/struct.PropertyObject.html
+#[derive(Debug, Default)]
+#[expect(
+    clippy::struct_field_names,
+    reason = "the fields are named after the kind of the ontology type"
+)]
+pub struct Generator {
+    data_types: HashMap<VersionedUrl, DataType>,
+    property_types: HashMap<VersionedUrl, PropertyType>,
+    entity_types: HashMap<VersionedUrl, EntityType>,
+}
+
+impl Generator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_data_type(&mut self, data_type: DataType) {
+        self.data_types.insert(data_type.id.clone(), data_type);
+    }
+
+    pub fn add_property_type(&mut self, property_type: PropertyType) {
+        self.property_types
+            .insert(property_type.id.clone(), property_type);
+    }
+
+    pub fn add_entity_type(&mut self, entity_type: EntityType) {
+        self.entity_types
+            .insert(entity_type.id.clone(), entity_type);
+    }
+
+    /// Generates the Rust source code for all provided ontology types.
+    ///
+    /// # Errors
+    ///
+    /// - [`MissingDataType`], [`MissingPropertyType`], or [`MissingEntityType`] if an ontology type
+    ///   refers to an ontology type which has not been provided
+    ///
+    /// [`MissingDataType`]: GenerateError::MissingDataType
+    /// [`MissingPropertyType`]: GenerateError::MissingPropertyType
+    /// [`MissingEntityType`]: GenerateError::MissingEntityType
+    pub fn generate(&self) -> Result<String, GenerateError> {
+        let file = syn::parse2::<syn::File>(self.generate_tokens()?)
+            .change_context(GenerateError::InvalidCode)?;
+
+        Ok(prettyplease::unparse(&file))
+    }
+
+    fn names(&self) -> Names {
+        fn type_names<'t>(
+            types: impl Iterator<Item = (&'t VersionedUrl, &'t str)>,
+        ) -> HashMap<VersionedUrl, Ident> {
+            let mut types: Vec<_> = types.collect();
+            types.sort_unstable_by_key(|(id, _)| *id);
+
+            unique_names(
+                types
+                    .into_iter()
+                    .map(|(id, title)| (id.clone(), type_name(title), id.version.inner()))
+                    .collect(),
+                |name, version| format!("{name}V{version}"),
+                |name, index| format!("{name}{index}"),
+            )
+        }
+
+        Names {
+            data_types: type_names(
+                self.data_types
+                    .values()
+                    .map(|data_type| (&data_type.id, data_type.title.as_str())),
+            ),
+            property_types: type_names(
+                self.property_types
+                    .values()
+                    .map(|property_type| (&property_type.id, property_type.title.as_str())),
+            ),
+            entity_types: type_names(
+                self.entity_types
+                    .values()
+                    .map(|entity_type| (&entity_type.id, entity_type.title.as_str())),
+            ),
+        }
+    }
+
+    fn generate_tokens(&self) -> Result<TokenStream, GenerateError> {
+        let names = self.names();
+
+        let mut data_types: Vec<_> = self.data_types.values().collect();
+        data_types.sort_unstable_by_key(|data_type| &data_type.id);
+        let data_types = data_types
+            .into_iter()
+            .map(|data_type| Self::data_type(data_type, &names.data_types[&data_type.id]));
+
+        let mut property_types: Vec<_> = self.property_types.values().collect();
+        property_types.sort_unstable_by_key(|property_type| &property_type.id);
+        let property_types = property_types
+            .into_iter()
+            .map(|property_type| self.property_type(&names, property_type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut entity_types: Vec<_> = self.entity_types.values().collect();
+        entity_types.sort_unstable_by_key(|entity_type| &entity_type.id);
+        let entity_types = entity_types
+            .into_iter()
+            .map(|entity_type| self.entity_type(&names, entity_type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(quote! {
+            /// Data types of the Block Protocol type system.
+            pub mod data_type {
+                #(#data_types)*
+            }
+
+            /// Property types of the Block Protocol type system.
+            pub mod property_type {
+                #(#property_types)*
+            }
+
+            /// Entity types of the Block Protocol type system.
+            pub mod entity_type {
+                #(#entity_types)*
+            }
+        })
+    }
+
+    /// Returns the Rust type used to represent values of the given JSON type.
+    fn json_type(json_type: JsonSchemaValueType) -> TokenStream {
+        match json_type {
+            JsonSchemaValueType::Null => quote!(()),
+            JsonSchemaValueType::Boolean => quote!(bool),
+            // `Number` is used instead of `f64` to not lose precision and to preserve the
+            // representation of the value
+            JsonSchemaValueType::Number => quote!(::serde_json::Number),
+            JsonSchemaValueType::Integer => quote!(i64),
+            JsonSchemaValueType::String => quote!(::std::string::String),
+            JsonSchemaValueType::Array => quote!(::std::vec::Vec<::serde_json::Value>),
+            JsonSchemaValueType::Object => {
+                quote!(::serde_json::Map<::std::string::String, ::serde_json::Value>)
+            }
+        }
+    }
+
+    fn data_type(data_type: &DataType, name: &Ident) -> TokenStream {
+        let docs = docs(data_type.description.as_deref(), &data_type.id);
+        let ty = Self::json_type(data_type.json_type);
+
+        quote! {
+            #docs
+            pub type #name = #ty;
+        }
+    }
+
+    fn property_type(
+        &self,
+        names: &Names,
+        property_type: &PropertyType,
+    ) -> Result<TokenStream, GenerateError> {
+        let name = &names.property_types[&property_type.id];
+        let docs = docs(property_type.description.as_deref(), &property_type.id);
+
+        let mut items = Vec::new();
+        match property_type.one_of.as_slice() {
+            [PropertyValues::PropertyTypeObject(object)] => {
+                items.push(self.object(
+                    names,
+                    name,
+                    &docs,
+                    &object.properties,
+                    &object.required,
+                )?);
+            }
+            [value] => {
+                let ty = self.property_value(names, name, value, &mut items)?;
+                items.push(quote! {
+                    #docs
+                    pub type #name = #ty;
+                });
+            }
+            values => {
+                let one_of = self.one_of(names, name, &docs, values, &mut items)?;
+                items.push(one_of);
+            }
+        }
+
+        Ok(quote!(#(#items)*))
+    }
+
+    /// Returns the type of a property value.
+    ///
+    /// Types required to represent the value are pushed to `items` and named after `name`.
+    fn property_value(
+        &self,
+        names: &Names,
+        name: &Ident,
+        value: &PropertyValues,
+        items: &mut Vec<TokenStream>,
+    ) -> Result<TokenStream, GenerateError> {
+        match value {
+            PropertyValues::DataTypeReference(reference) => names.data_type_path(&reference.url),
+            PropertyValues::PropertyTypeObject(object) => {
+                let object = self.object(
+                    names,
+                    name,
+                    &TokenStream::new(),
+                    &object.properties,
+                    &object.required,
+                )?;
+                items.push(object);
+
+                Ok(quote!(#name))
+            }
+            PropertyValues::ArrayOfPropertyValues(array) => {
+                let item_name = format_ident!("{name}Item");
+                let item = match array.items.possibilities.as_slice() {
+                    [value] => self.property_value(names, &item_name, value, items)?,
+                    values => {
+                        let item =
+                            self.one_of(names, &item_name, &TokenStream::new(), values, items)?;
+                        items.push(item);
+
+                        quote!(#item_name)
+                    }
+                };
+
+                Ok(quote!(::std::vec::Vec<#item>))
+            }
+        }
+    }
+
+    /// Creates an untagged enum with a variant for every possible value.
+    ///
+    /// Data types sharing the same JSON type are represented by the same Rust type, so they cannot
+    /// be told apart when deserializing. These data types share a single variant named after their
+    /// JSON type.
+    fn one_of(
+        &self,
+        names: &Names,
+        name: &Ident,
+        docs: &TokenStream,
+        values: &[PropertyValues],
+        items: &mut Vec<TokenStream>,
+    ) -> Result<TokenStream, GenerateError> {
+        enum Variant<'v> {
+            Value(&'v PropertyValues),
+            DataTypes(JsonSchemaValueType, Vec<&'v VersionedUrl>),
+        }
+
+        let mut json_types = HashMap::<JsonSchemaValueType, Vec<&VersionedUrl>>::new();
+        for value in values {
+            if let PropertyValues::DataTypeReference(reference) = value {
+                let data_type = self.data_types.get(&reference.url).ok_or_else(|| {
+                    Report::new(GenerateError::MissingDataType(reference.url.clone()))
+                })?;
+                json_types
+                    .entry(data_type.json_type)
+                    .or_default()
+                    .push(&reference.url);
+            }
+        }
+
+        let mut variants = Vec::new();
+        for value in values {
+            let PropertyValues::DataTypeReference(reference) = value else {
+                variants.push(Variant::Value(value));
+                continue;
+            };
+
+            let json_type = self.data_types[&reference.url].json_type;
+            match json_types.remove(&json_type) {
+                Some(data_types) if data_types.len() > 1 => {
+                    variants.push(Variant::DataTypes(json_type, data_types));
+                }
+                Some(_) => variants.push(Variant::Value(value)),
+                // The data types of this JSON type have already been merged into a variant
+                None => {}
+            }
+        }
+
+        let variant_names = unique_names(
+            variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    let variant = match variant {
+                        Variant::Value(PropertyValues::DataTypeReference(reference)) => {
+                            names.data_types[&reference.url].to_string()
+                        }
+                        Variant::Value(PropertyValues::PropertyTypeObject(_)) => {
+                            "Object".to_owned()
+                        }
+                        Variant::Value(PropertyValues::ArrayOfPropertyValues(_)) => {
+                            "Array".to_owned()
+                        }
+                        Variant::DataTypes(json_type, _) => type_name(&json_type.to_string()),
+                    };
+
+                    (index, variant, 0)
+                })
+                .collect(),
+            |name, _| name.to_owned(),
+            |name, index| format!("{name}{index}"),
+        );
+
+        let variants = variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let variant_name = &variant_names[&index];
+                match variant {
+                    Variant::Value(value) => {
+                        let ty = self.property_value(
+                            names,
+                            &format_ident!("{name}{variant_name}"),
+                            value,
+                            items,
+                        )?;
+
+                        Ok(quote!(#variant_name(#ty)))
+                    }
+                    Variant::DataTypes(json_type, data_types) => {
+                        let ty = Self::json_type(*json_type);
+                        let data_types = data_types.iter().map(|id| format!(" - <{id}>"));
+
+                        Ok(quote! {
+                            /// A value of one of the data types
+                            ///
+                            #(#[doc = #data_types])*
+                            #variant_name(#ty)
+                        })
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>, GenerateError>>()?;
+
+        Ok(quote! {
+            #docs
+            #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+            #[serde(untagged)]
+            pub enum #name {
+                #(#variants,)*
+            }
+        })
+    }
+
+    /// Creates a struct with a field for every property, which can be converted to and from a
+    /// `PropertyObject`.
+    fn object(
+        &self,
+        names: &Names,
+        name: &Ident,
+        docs: &TokenStream,
+        properties: &HashMap<BaseUrl, ValueOrArray<PropertyTypeReference>>,
+        required: &HashSet<BaseUrl>,
+    ) -> Result<TokenStream, GenerateError> {
+        let mut properties: Vec<_> = properties
+            .iter()
+            .map(|(base_url, value)| {
+                let reference = match value {
+                    ValueOrArray::Value(reference) => reference,
+                    ValueOrArray::Array(array) => &array.items,
+                };
+                let property_type = self.property_types.get(&reference.url).ok_or_else(|| {
+                    Report::new(GenerateError::MissingPropertyType(reference.url.clone()))
+                })?;
+
+                Ok((base_url, value, property_type))
+            })
+            .collect::<Result<_, GenerateError>>()?;
+        properties.sort_unstable_by_key(|(base_url, ..)| *base_url);
+
+        let field_names = unique_names(
+            properties
+                .iter()
+                .map(|(base_url, _, property_type)| {
+                    (
+                        *base_url,
+                        field_name(&property_type.title),
+                        property_type.id.version.inner(),
+                    )
+                })
+                .collect(),
+            |name, version| format!("{name}_v{version}"),
+            |name, index| format!("{name}_{index}"),
+        );
+
+        let mut fields = properties
+            .into_iter()
+            .map(|(base_url, value, property_type)| {
+                let field = &field_names[base_url];
+                let docs = property_type
+                    .description
+                    .iter()
+                    .flat_map(|description| description.lines())
+                    .map(|line| format!(" {line}"));
+
+                let ty = match value {
+                    ValueOrArray::Value(reference) => names.property_type_path(&reference.url)?,
+                    ValueOrArray::Array(array) => {
+                        let item = names.property_type_path(&array.items.url)?;
+                        quote!(::std::vec::Vec<#item>)
+                    }
+                };
+
+                let rename = base_url.as_str();
+                let tokens = if required.contains(base_url) {
+                    quote! {
+                        #(#[doc = #docs])*
+                        #[serde(rename = #rename)]
+                        pub #field: #ty
+                    }
+                } else {
+                    quote! {
+                        #(#[doc = #docs])*
+                        #[serde(
+                            rename = #rename,
+                            default,
+                            skip_serializing_if = "::core::option::Option::is_none"
+                        )]
+                        pub #field: ::core::option::Option<#ty>
+                    }
+                };
+
+                Ok((field.to_string(), tokens))
+            })
+            .collect::<Result<Vec<_>, GenerateError>>()?;
+        fields.sort_unstable_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        let fields = fields.into_iter().map(|(_, field)| field);
+
+        Ok(quote! {
+            #docs
+            #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+            #[serde(deny_unknown_fields)]
+            pub struct #name {
+                #(#fields,)*
+            }
+
+            impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for #name {
+                type Error = ::serde_json::Error;
+
+                fn try_from(
+                    properties: ::graph_types::knowledge::PropertyObject,
+                ) -> ::core::result::Result<Self, Self::Error> {
+                    ::serde_json::from_value(::serde_json::to_value(properties)?)
+                }
+            }
+
+            impl ::core::convert::TryFrom<#name> for ::graph_types::knowledge::PropertyObject {
+                type Error = ::serde_json::Error;
+
+                fn try_from(value: #name) -> ::core::result::Result<Self, Self::Error> {
+                    ::serde_json::from_value(::serde_json::to_value(value)?)
+                }
+            }
+        })
+    }
+
+    /// Merges the entity type with all of its parents.
+    fn close(&self, entity_type: &EntityType) -> Result<ClosedEntityType, GenerateError> {
+        let mut closed = ClosedEntityType::from(entity_type.clone());
+
+        let mut visited = HashSet::from([&entity_type.id]);
+        let mut parents: Vec<_> = entity_type
+            .all_of
+            .iter()
+            .map(|parent| &parent.url)
+            .collect();
+        while let Some(id) = parents.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+
+            let parent = self
+                .entity_types
+                .get(id)
+                .ok_or_else(|| Report::new(GenerateError::MissingEntityType(id.clone())))?;
+            parents.extend(parent.all_of.iter().map(|parent| &parent.url));
+            closed.extend([parent.clone()]);
+        }
+
+        Ok(closed)
+    }
+
+    fn entity_type(
+        &self,
+        names: &Names,
+        entity_type: &EntityType,
+    ) -> Result<TokenStream, GenerateError> {
+        let closed = self.close(entity_type)?;
+
+        self.object(
+            names,
+            &names.entity_types[&entity_type.id],
+            &docs(entity_type.description.as_deref(), &entity_type.id),
+            &closed.properties,
+            &closed.required,
+        )
+    }
+}
@@ -0,0 +1,12 @@
+//! Rust code generation from Block Protocol ontology types.
+//!
+//! The [`Generator`] takes data types, property types, and entity types and emits Rust types with
+//! [`serde`] implementations, which can be used instead of untyped property objects.
+//!
+//! [`serde`]: https://docs.rs/serde
+
+pub use self::{error::GenerateError, generator::Generator};
+
+mod error;
+mod generator;
+mod naming;
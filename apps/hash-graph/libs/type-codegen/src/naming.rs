@@ -0,0 +1,79 @@
+use core::hash::Hash;
+use std::collections::HashMap;
+
+use heck::{ToSnakeCase as _, ToUpperCamelCase as _};
+use proc_macro2::{Ident, Span};
+
+/// Converts a title into a name suitable for a type or a variant.
+pub(crate) fn type_name(title: &str) -> String {
+    let name = title.to_upper_camel_case();
+
+    if name.starts_with(|char: char| char.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("Type{name}")
+    }
+}
+
+/// Converts a title into a name suitable for a field.
+pub(crate) fn field_name(title: &str) -> String {
+    let name = title.to_snake_case();
+
+    if name.starts_with(|char: char| char.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("property_{name}")
+    }
+}
+
+/// Creates an identifier from a name, escaping it if it's a keyword.
+pub(crate) fn ident(name: &str) -> Ident {
+    match name {
+        // These keywords cannot be used as raw identifiers
+        "self" | "Self" | "super" | "crate" => Ident::new(&format!("{name}_"), Span::call_site()),
+        _ => syn::parse_str::<Ident>(name)
+            .unwrap_or_else(|_| Ident::new_raw(name, Span::call_site())),
+    }
+}
+
+/// Makes the names of `entries` unique.
+///
+/// Entries sharing a name are first disambiguated by their version using `versioned`. If this is
+/// not sufficient, an increasing index is appended to every but the first entry. The entries are
+/// expected to be in a deterministic order, so that the resulting names are stable.
+pub(crate) fn unique_names<K>(
+    entries: Vec<(K, String, u32)>,
+    versioned: impl Fn(&str, u32) -> String,
+    indexed: impl Fn(&str, usize) -> String,
+) -> HashMap<K, Ident>
+where
+    K: Eq + Hash,
+{
+    let mut occurrences = HashMap::<String, usize>::new();
+    for (_, name, _) in &entries {
+        *occurrences.entry(name.clone()).or_default() += 1;
+    }
+
+    let mut indices = HashMap::<String, usize>::new();
+    entries
+        .into_iter()
+        .map(|(key, name, version)| {
+            let name = if occurrences[&name] > 1 {
+                versioned(&name, version)
+            } else {
+                name
+            };
+
+            let index = indices.entry(name.clone()).or_default();
+            *index += 1;
+
+            let name = if *index > 1 {
+                indexed(&name, *index)
+            } else {
+                name
+            };
+
+            (key, ident(&name))
+        })
+        .collect()
+}
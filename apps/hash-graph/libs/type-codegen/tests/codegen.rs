@@ -0,0 +1,264 @@
+#![expect(
+    clippy::restriction,
+    reason = "the generated code is not written to satisfy all lints"
+)]
+
+use expect_test::expect_file;
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::knowledge::PropertyObject;
+use type_codegen::{GenerateError, Generator};
+use type_system::schema::{DataType, EntityType, PropertyType};
+
+#[expect(
+    dead_code,
+    unreachable_pub,
+    reason = "not all generated types are used in the tests"
+)]
+mod generated {
+    include!("generated/test_data.rs");
+}
+
+fn parse<T: serde::de::DeserializeOwned>(json: &str) -> T {
+    serde_json::from_str(json).expect("should be a valid schema")
+}
+
+fn generator() -> Generator {
+    let mut generator = Generator::new();
+
+    for data_type in [
+        data_type::BOOLEAN_V1,
+        data_type::EMPTY_LIST_V1,
+        data_type::NULL_V1,
+        data_type::NUMBER_V1,
+        data_type::OBJECT_V1,
+        data_type::OBJECT_V2,
+        data_type::TEXT_V1,
+        data_type::LENGTH_V1,
+        data_type::METER_V1,
+        data_type::CENTIMETER_V1,
+        data_type::CENTIMETER_V2,
+    ] {
+        generator.add_data_type(parse::<DataType>(data_type));
+    }
+
+    for property_type in [
+        property_type::ADDRESS_LINE_1_V1,
+        property_type::AGE_V1,
+        property_type::BLURB_V1,
+        property_type::BUILT_AT,
+        property_type::CITY_V1,
+        property_type::CONTACT_INFORMATION_V1,
+        property_type::CONTRIVED_PROPERTY_V1,
+        property_type::EMAIL_V1,
+        property_type::FAVORITE_FILM_V1,
+        property_type::FAVORITE_QUOTE_V1,
+        property_type::FAVORITE_SONG_V1,
+        property_type::HOBBY_V1,
+        property_type::INTERESTS_V1,
+        property_type::LENGTH_V1,
+        property_type::NAME_V1,
+        property_type::NUMBERS_V1,
+        property_type::PHONE_NUMBER_V1,
+        property_type::POSTCODE_NUMBER_V1,
+        property_type::PUBLISHED_ON_V1,
+        property_type::TEXT_V1,
+        property_type::USER_ID_V1,
+        property_type::USER_ID_V2,
+    ] {
+        generator.add_property_type(parse::<PropertyType>(property_type));
+    }
+
+    // `CHURCH_V1` is omitted as it refers to a property type which is not part of the test data
+    for entity_type in [
+        entity_type::LINK_V1,
+        entity_type::link::ACQUAINTANCE_OF_V1,
+        entity_type::link::CONTAINS_V1,
+        entity_type::link::FRIEND_OF_V1,
+        entity_type::link::LOCATED_AT_V1,
+        entity_type::link::OWNS_V1,
+        entity_type::link::OWNS_V2,
+        entity_type::link::SUBMITTED_BY_V1,
+        entity_type::link::TENANT_V1,
+        entity_type::link::WRITTEN_BY_V1,
+        entity_type::BLOCK_V1,
+        entity_type::BOOK_V1,
+        entity_type::BUILDING_V1,
+        entity_type::LINE_V1,
+        entity_type::ORGANIZATION_V1,
+        entity_type::PAGE_V1,
+        entity_type::PAGE_V2,
+        entity_type::PERSON_V1,
+        entity_type::PLAYLIST_V1,
+        entity_type::SONG_V1,
+        entity_type::UK_ADDRESS_V1,
+    ] {
+        generator.add_entity_type(parse::<EntityType>(entity_type));
+    }
+
+    generator
+}
+
+#[test]
+fn generate_test_data() {
+    let code = generator()
+        .generate()
+        .expect("should be able to generate code");
+
+    expect_file!["generated/test_data.rs"].assert_eq(&code);
+}
+
+#[test]
+fn missing_property_type() {
+    let mut generator = generator();
+    generator.add_entity_type(parse::<EntityType>(entity_type::CHURCH_V1));
+
+    let report = generator
+        .generate()
+        .expect_err("should fail because of the missing property type");
+    assert!(matches!(
+        report.current_context(),
+        GenerateError::MissingPropertyType(url)
+            if url.base_url.as_str()
+                == "https://blockprotocol.org/@alice/types/property-type/number-bells/"
+    ));
+}
+
+#[test]
+fn missing_parent_entity_type() {
+    let mut generator = Generator::new();
+    generator.add_entity_type(parse::<EntityType>(entity_type::link::FRIEND_OF_V1));
+
+    let report = generator
+        .generate()
+        .expect_err("should fail because of the missing parent");
+    assert!(matches!(
+        report.current_context(),
+        GenerateError::MissingEntityType(_)
+    ));
+}
+
+fn round_trip<T>(properties: &str) -> T
+where
+    T: TryFrom<PropertyObject, Error = serde_json::Error> + Clone,
+    PropertyObject: TryFrom<T, Error = serde_json::Error>,
+{
+    let properties = parse::<PropertyObject>(properties);
+
+    let value = T::try_from(properties.clone()).expect("should be able to convert properties");
+    let converted =
+        PropertyObject::try_from(value.clone()).expect("should be able to convert value");
+    assert_eq!(properties, converted);
+
+    value
+}
+
+#[test]
+fn round_trip_entities() {
+    use generated::entity_type::{
+        Block, Book, Building, Organization, PageV1, PageV2, Person, Playlist, Song, UkAddress,
+    };
+
+    let address = round_trip::<UkAddress>(entity::ADDRESS_V1);
+    assert_eq!(address.city, "London");
+    assert_eq!(address.postcode, "SW1A 1AA");
+
+    let block = round_trip::<Block>(entity::BLOCK_V1);
+    assert_eq!(block.name, "Text");
+
+    let book = round_trip::<Book>(entity::BOOK_V1);
+    assert_eq!(book.name, ["The Time Machine"]);
+    assert_eq!(book.blurb.as_deref(), Some("brulb"));
+
+    let building = round_trip::<Building>(entity::BUILDING_V1);
+    assert_eq!(building.built_at, None);
+
+    round_trip::<Organization>(entity::ORGANIZATION_V1);
+    round_trip::<PageV1>(entity::PAGE_V1);
+    round_trip::<PageV2>(entity::PAGE_V2);
+    round_trip::<Playlist>(entity::PLAYLIST_V1);
+    round_trip::<Song>(entity::SONG_V1);
+
+    let alice = round_trip::<Person>(entity::PERSON_ALICE_V1);
+    assert_eq!(alice.name.as_deref(), Some("Alice"));
+    assert_eq!(alice.age, None);
+
+    let bob = round_trip::<Person>(entity::PERSON_BOB_V1);
+    assert_eq!(bob.age, Some(serde_json::Number::from(42)));
+
+    round_trip::<Person>(entity::PERSON_CHARLES_V1);
+}
+
+#[test]
+fn round_trip_nested_properties() {
+    use generated::{
+        entity_type::Person,
+        property_type::{ContactInformation, ContrivedProperty, Interests, UserIdV2},
+    };
+
+    let person = round_trip::<Person>(
+        r#"{
+            "https://blockprotocol.org/@alice/types/property-type/name/": "Alice",
+            "https://blockprotocol.org/@alice/types/property-type/interests/": {
+                "https://blockprotocol.org/@alice/types/property-type/favorite-film/": "Metropolis",
+                "https://blockprotocol.org/@alice/types/property-type/hobby/": ["chess", "go"]
+            }
+        }"#,
+    );
+    assert_eq!(
+        person.interests,
+        Some(Interests {
+            favorite_film: Some("Metropolis".to_owned()),
+            favorite_song: None,
+            hobby: Some(vec!["chess".to_owned(), "go".to_owned()]),
+        })
+    );
+
+    let contact = round_trip::<ContactInformation>(
+        r#"{
+            "https://blockprotocol.org/@alice/types/property-type/email/": "alice@example.com"
+        }"#,
+    );
+    assert_eq!(contact.email, "alice@example.com");
+    assert_eq!(contact.phone_number, None);
+
+    assert_eq!(
+        serde_json::from_str::<ContrivedProperty>("[1, 2, 3]").expect("should be a number array"),
+        ContrivedProperty::Array(vec![1.into(), 2.into(), 3.into()])
+    );
+    assert_eq!(
+        serde_json::from_str::<UserIdV2>("42").expect("should be a number"),
+        UserIdV2::Number(42.into())
+    );
+    assert_eq!(
+        serde_json::from_str::<UserIdV2>(r#""alice""#).expect("should be a text"),
+        UserIdV2::Text("alice".to_owned())
+    );
+}
+
+#[test]
+fn round_trip_data_types_of_the_same_json_type() {
+    use generated::{entity_type::Line, property_type::Length};
+
+    // `Length` allows the length and the meter data type, which are both numbers, so a meter value
+    // has to round-trip through the same variant as a length value.
+    let line = round_trip::<Line>(
+        r#"{
+            "http://localhost:3000/@alice/types/property-type/length/": 5
+        }"#,
+    );
+    assert_eq!(line.length, Some(Length::Number(5.into())));
+}
+
+#[test]
+fn unknown_property() {
+    use generated::entity_type::Song;
+
+    let properties = parse::<PropertyObject>(
+        r#"{
+            "https://blockprotocol.org/@alice/types/property-type/name/": "Rocket Man",
+            "https://blockprotocol.org/@alice/types/property-type/age/": 42
+        }"#,
+    );
+
+    Song::try_from(properties).expect_err("should reject properties not in the entity type");
+}
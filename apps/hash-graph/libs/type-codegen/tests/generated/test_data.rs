@@ -0,0 +1,699 @@
+/// Data types of the Block Protocol type system.
+pub mod data_type {
+    /// A True or False value
+    ///
+    /// Generated from <https://blockprotocol.org/@blockprotocol/types/data-type/boolean/v/1>.
+    pub type Boolean = bool;
+    /// An Empty List
+    ///
+    /// Generated from <https://blockprotocol.org/@blockprotocol/types/data-type/empty-list/v/1>.
+    pub type EmptyList = ::std::vec::Vec<::serde_json::Value>;
+    /// A placeholder value representing 'nothing'
+    ///
+    /// Generated from <https://blockprotocol.org/@blockprotocol/types/data-type/null/v/1>.
+    pub type Null = ();
+    /// An arithmetical value (in the Real number system)
+    ///
+    /// Generated from <https://blockprotocol.org/@blockprotocol/types/data-type/number/v/1>.
+    pub type Number = ::serde_json::Number;
+    /// A plain JSON object with no pre-defined structure
+    ///
+    /// Generated from <https://blockprotocol.org/@blockprotocol/types/data-type/object/v/1>.
+    pub type ObjectV1 = ::serde_json::Map<::std::string::String, ::serde_json::Value>;
+    /// An updated plain JSON object with no pre-defined structure
+    ///
+    /// Generated from <https://blockprotocol.org/@blockprotocol/types/data-type/object/v/2>.
+    pub type ObjectV2 = ::serde_json::Map<::std::string::String, ::serde_json::Value>;
+    /// An ordered sequence of characters
+    ///
+    /// Generated from <https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1>.
+    pub type Text = ::std::string::String;
+    /// A length in centimeters
+    ///
+    /// Generated from <https://hash.ai/@hash/types/data-type/centimeter/v/1>.
+    pub type CentimeterV1 = ::serde_json::Number;
+    /// A length in centimeters
+    ///
+    /// Generated from <https://hash.ai/@hash/types/data-type/centimeter/v/2>.
+    pub type CentimeterV2 = ::serde_json::Number;
+    /// A length in a unit of measure
+    ///
+    /// Generated from <https://hash.ai/@hash/types/data-type/length/v/1>.
+    pub type Length = ::serde_json::Number;
+    /// A length in meters
+    ///
+    /// Generated from <https://hash.ai/@hash/types/data-type/meter/v/1>.
+    pub type Meter = ::serde_json::Number;
+}
+/// Property types of the Block Protocol type system.
+pub mod property_type {
+    /// A length
+    ///
+    /// Generated from <http://localhost:3000/@alice/types/property-type/length/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(untagged)]
+    pub enum Length {
+        /// A value of one of the data types
+        ///
+        /// - <https://hash.ai/@hash/types/data-type/length/v/1>
+        /// - <https://hash.ai/@hash/types/data-type/meter/v/1>
+        Number(::serde_json::Number),
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/address-line-1/v/1>.
+    pub type AddressLine1 = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/age/v/1>.
+    pub type Age = super::data_type::Number;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/blurb/v/1>.
+    pub type Blurb = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/built-at/v/1>.
+    pub type BuiltAt = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/city/v/1>.
+    pub type City = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/contact-information/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct ContactInformation {
+        #[serde(rename = "https://blockprotocol.org/@alice/types/property-type/email/")]
+        pub email: super::property_type::Email,
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/phone-number/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub phone_number: ::core::option::Option<super::property_type::PhoneNumber>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for ContactInformation {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<ContactInformation>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            value: ContactInformation,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/contrived-property/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(untagged)]
+    pub enum ContrivedProperty {
+        Number(super::data_type::Number),
+        Array(::std::vec::Vec<super::data_type::Number>),
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/email/v/1>.
+    pub type Email = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/favorite-film/v/1>.
+    pub type FavoriteFilm = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/favorite-quote/v/1>.
+    pub type FavoriteQuote = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/favorite-song/v/1>.
+    pub type FavoriteSong = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/hobby/v/1>.
+    pub type Hobby = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/interests/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Interests {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/favorite-film/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub favorite_film: ::core::option::Option<super::property_type::FavoriteFilm>,
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/favorite-song/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub favorite_song: ::core::option::Option<super::property_type::FavoriteSong>,
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/hobby/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub hobby: ::core::option::Option<::std::vec::Vec<super::property_type::Hobby>>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for Interests {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Interests>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Interests) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/name/v/1>.
+    pub type Name = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/numbers/v/1>.
+    pub type Numbers = ::std::vec::Vec<super::data_type::Number>;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/phone-number/v/1>.
+    pub type PhoneNumber = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/postcode/v/1>.
+    pub type Postcode = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/published-on/v/1>.
+    pub type PublishedOn = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/text/v/1>.
+    pub type Text = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/user-id/v/1>.
+    pub type UserIdV1 = super::data_type::Text;
+    /// Generated from <https://blockprotocol.org/@alice/types/property-type/user-id/v/2>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(untagged)]
+    pub enum UserIdV2 {
+        Text(super::data_type::Text),
+        Number(super::data_type::Number),
+    }
+}
+/// Entity types of the Block Protocol type system.
+pub mod entity_type {
+    /// A line with a length
+    ///
+    /// Generated from <http://localhost:3000/@alice/types/entity-type/line/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Line {
+        /// A length
+        #[serde(
+            rename = "http://localhost:3000/@alice/types/property-type/length/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub length: ::core::option::Option<super::property_type::Length>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for Line {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Line> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Line) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Someone who is known but not a close friend
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/acquaintance-of/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct AcquaintanceOf {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for AcquaintanceOf {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<AcquaintanceOf>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: AcquaintanceOf) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/block/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Block {
+        #[serde(rename = "https://blockprotocol.org/@alice/types/property-type/name/")]
+        pub name: super::property_type::Name,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for Block {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Block> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Block) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/book/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Book {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/blurb/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub blurb: ::core::option::Option<super::property_type::Blurb>,
+        #[serde(rename = "https://blockprotocol.org/@alice/types/property-type/name/")]
+        pub name: ::std::vec::Vec<super::property_type::Name>,
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/published-on/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub published_on: ::core::option::Option<super::property_type::PublishedOn>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for Book {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Book> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Book) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/building/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Building {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/built-at/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub built_at: ::core::option::Option<super::property_type::BuiltAt>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for Building {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Building>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Building) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Have or hold within
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/contains/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Contains {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for Contains {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Contains>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Contains) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Someone who has a shared bond of mutual affection
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/friend-of/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct FriendOf {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for FriendOf {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<FriendOf>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: FriendOf) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Residing at a specific position
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/located-at/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct LocatedAt {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for LocatedAt {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<LocatedAt>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: LocatedAt) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/organization/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Organization {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/name/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub name: ::core::option::Option<super::property_type::Name>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for Organization {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Organization>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Organization) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Have (something) as one's own; possess
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/owns/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct OwnsV1 {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for OwnsV1 {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<OwnsV1> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: OwnsV1) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Have (something) as one's own; possess
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/owns/v/2>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct OwnsV2 {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for OwnsV2 {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<OwnsV2> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: OwnsV2) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/page/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct PageV1 {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/text/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub text: ::core::option::Option<super::property_type::Text>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for PageV1 {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<PageV1> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: PageV1) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/page/v/2>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct PageV2 {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/text/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub text: ::core::option::Option<super::property_type::Text>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for PageV2 {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<PageV2> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: PageV2) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/person/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Person {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/age/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub age: ::core::option::Option<super::property_type::Age>,
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/interests/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub interests: ::core::option::Option<super::property_type::Interests>,
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/name/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub name: ::core::option::Option<super::property_type::Name>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for Person {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Person> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Person) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/playlist/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Playlist {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/name/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub name: ::core::option::Option<super::property_type::Name>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for Playlist {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Playlist>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Playlist) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/song/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Song {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/name/",
+            default,
+            skip_serializing_if = "::core::option::Option::is_none"
+        )]
+        pub name: ::core::option::Option<super::property_type::Name>,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for Song {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Song> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Song) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Suggested, proposed, or presented by
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/submitted-by/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct SubmittedBy {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for SubmittedBy {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<SubmittedBy>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: SubmittedBy) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Someone who occupies land or property rented from a landlord
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/tenant/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Tenant {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for Tenant {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Tenant> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Tenant) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/uk-address/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct UkAddress {
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/address-line-1/"
+        )]
+        pub address_line_1: super::property_type::AddressLine1,
+        #[serde(rename = "https://blockprotocol.org/@alice/types/property-type/city/")]
+        pub city: super::property_type::City,
+        #[serde(
+            rename = "https://blockprotocol.org/@alice/types/property-type/postcode/"
+        )]
+        pub postcode: super::property_type::Postcode,
+    }
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for UkAddress {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<UkAddress>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: UkAddress) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Written or composed by
+    ///
+    /// Generated from <https://blockprotocol.org/@alice/types/entity-type/written-by/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct WrittenBy {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject>
+    for WrittenBy {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<WrittenBy>
+    for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: WrittenBy) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+    /// Generated from <https://blockprotocol.org/@blockprotocol/types/entity-type/link/v/1>.
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Link {}
+    impl ::core::convert::TryFrom<::graph_types::knowledge::PropertyObject> for Link {
+        type Error = ::serde_json::Error;
+        fn try_from(
+            properties: ::graph_types::knowledge::PropertyObject,
+        ) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(properties)?)
+        }
+    }
+    impl ::core::convert::TryFrom<Link> for ::graph_types::knowledge::PropertyObject {
+        type Error = ::serde_json::Error;
+        fn try_from(value: Link) -> ::core::result::Result<Self, Self::Error> {
+            ::serde_json::from_value(::serde_json::to_value(value)?)
+        }
+    }
+}
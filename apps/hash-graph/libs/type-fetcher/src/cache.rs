@@ -0,0 +1,251 @@
+use alloc::sync::Arc;
+use core::{error::Error, fmt};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use error_stack::{Report, Result, ResultExt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+use tokio::fs;
+use type_system::url::VersionedUrl;
+
+use crate::fetcher::FetchedOntologyType;
+
+#[derive(Debug)]
+pub struct CacheError;
+
+impl Error for CacheError {}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("the ontology type cache encountered an error")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CacheEntry {
+    url: VersionedUrl,
+    hash: String,
+    fetched_at: OffsetDateTime,
+}
+
+fn hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// A persistent, content-addressed cache of fetched ontology types.
+///
+/// The schemas are stored as they were fetched under `objects/`, named after the SHA-256 hash of
+/// their content. Every [`VersionedUrl`] has an entry under `urls/`, which points to the object
+/// and records when the schema was fetched. As objects are verified against their hash when they
+/// are read, a cache directory can safely be used as a mirror for offline deployments.
+#[derive(Debug, Clone)]
+pub struct OntologyTypeCache {
+    root: Arc<Path>,
+}
+
+impl OntologyTypeCache {
+    /// Opens the cache located at `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directories of the cache could not be created.
+    pub async fn open(root: &Path) -> Result<Self, CacheError> {
+        for directory in [root.join("objects"), root.join("urls")] {
+            fs::create_dir_all(&directory)
+                .await
+                .change_context(CacheError)
+                .attach_printable_lazy(|| directory.display().to_string())?;
+        }
+
+        Ok(Self {
+            root: Arc::from(root),
+        })
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.root
+            .join("objects")
+            .join(hash.chars().take(2).collect::<String>())
+            .join(format!("{hash}.json"))
+    }
+
+    fn entry_path(&self, url: &VersionedUrl) -> PathBuf {
+        self.root
+            .join("urls")
+            .join(format!("{}.json", hash(url.to_string().as_bytes())))
+    }
+
+    /// Writes the file atomically, so that concurrent readers never observe partial content.
+    ///
+    /// The content is first written to a uniquely named temporary file next to `path`, which is
+    /// then moved into place. If writing fails, the temporary file is removed again.
+    async fn write(path: &Path, content: &[u8]) -> Result<(), CacheError> {
+        let parent = path.parent().unwrap_or_else(|| Path::new("."));
+        fs::create_dir_all(parent)
+            .await
+            .change_context(CacheError)
+            .attach_printable_lazy(|| parent.display().to_string())?;
+
+        let temporary = tempfile::Builder::new()
+            .suffix(".tmp")
+            .tempfile_in(parent)
+            .change_context(CacheError)
+            .attach_printable_lazy(|| parent.display().to_string())?
+            .into_temp_path();
+        fs::write(&temporary, content)
+            .await
+            .change_context(CacheError)
+            .attach_printable_lazy(|| temporary.display().to_string())?;
+        temporary
+            .persist(path)
+            .map_err(|error| Report::new(error.error))
+            .change_context(CacheError)
+            .attach_printable_lazy(|| path.display().to_string())
+    }
+
+    /// Returns the ontology type stored for `url` together with the time it was fetched.
+    ///
+    /// Returns `None` if the ontology type is not present in the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache could not be read or the stored schema is corrupted.
+    pub async fn get(
+        &self,
+        url: &VersionedUrl,
+    ) -> Result<Option<(FetchedOntologyType, OffsetDateTime)>, CacheError> {
+        let entry_path = self.entry_path(url);
+        let entry = match fs::read(&entry_path).await {
+            Ok(entry) => entry,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(Report::new(error)
+                    .change_context(CacheError)
+                    .attach_printable(entry_path.display().to_string()));
+            }
+        };
+        let entry: CacheEntry = serde_json::from_slice(&entry)
+            .change_context(CacheError)
+            .attach_printable_lazy(|| entry_path.display().to_string())?;
+
+        if entry.url != *url {
+            return Err(Report::new(CacheError)
+                .attach_printable(format!("cache entry is stored for {}", entry.url))
+                .attach_printable(entry_path.display().to_string()));
+        }
+
+        let object_path = self.object_path(&entry.hash);
+        let object = fs::read(&object_path)
+            .await
+            .change_context(CacheError)
+            .attach_printable_lazy(|| object_path.display().to_string())?;
+        if hash(&object) != entry.hash {
+            return Err(Report::new(CacheError)
+                .attach_printable("cached schema does not match its hash")
+                .attach_printable(object_path.display().to_string()));
+        }
+
+        let ontology_type = serde_json::from_slice(&object)
+            .change_context(CacheError)
+            .attach_printable_lazy(|| object_path.display().to_string())?;
+
+        Ok(Some((ontology_type, entry.fetched_at)))
+    }
+
+    /// Stores the raw schema of the ontology type identified by `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema could not be written to the cache.
+    pub async fn insert(
+        &self,
+        url: &VersionedUrl,
+        schema: &[u8],
+        fetched_at: OffsetDateTime,
+    ) -> Result<(), CacheError> {
+        let entry = CacheEntry {
+            url: url.clone(),
+            hash: hash(schema),
+            fetched_at,
+        };
+
+        let object_path = self.object_path(&entry.hash);
+        if !fs::try_exists(&object_path).await.unwrap_or(false) {
+            Self::write(&object_path, schema).await?;
+        }
+
+        Self::write(
+            &self.entry_path(url),
+            &serde_json::to_vec_pretty(&entry).change_context(CacheError)?,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn round_trip() {
+        let root = TempDir::new().expect("should be able to create directory");
+        let cache = OntologyTypeCache::open(root.path())
+            .await
+            .expect("should be able to open cache");
+
+        let url: VersionedUrl = "https://example.com/@example/types/data-type/text/v/1"
+            .parse()
+            .expect("should be a valid URL");
+        let schema = br#"{
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+            "kind": "dataType",
+            "$id": "https://example.com/@example/types/data-type/text/v/1",
+            "title": "Text",
+            "description": "An ordered sequence of characters",
+            "type": "string"
+        }"#;
+        let fetched_at = OffsetDateTime::UNIX_EPOCH;
+
+        assert!(
+            cache
+                .get(&url)
+                .await
+                .expect("should be able to read cache")
+                .is_none()
+        );
+
+        cache
+            .insert(&url, schema, fetched_at)
+            .await
+            .expect("should be able to write cache");
+
+        let (ontology_type, cached_at) = cache
+            .get(&url)
+            .await
+            .expect("should be able to read cache")
+            .expect("type should be cached");
+        assert!(matches!(ontology_type, FetchedOntologyType::DataType(_)));
+        assert_eq!(cached_at, fetched_at);
+
+        // corrupting the object is detected
+        fs::write(cache.object_path(&hash(schema)), b"{}")
+            .await
+            .expect("should be able to write object");
+        _ = cache
+            .get(&url)
+            .await
+            .expect_err("corrupted object should not be returned");
+    }
+}
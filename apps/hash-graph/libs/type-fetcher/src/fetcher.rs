@@ -13,6 +13,8 @@ use type_system::{
 pub enum FetcherError {
     NetworkError(String),
     SerializationError(String),
+    CacheError(String),
+    OfflineError(String),
 }
 
 impl Error for FetcherError {}
@@ -22,9 +24,10 @@ impl fmt::Display for FetcherError {
         fmt.write_str("the type fetcher encountered an error during execution: ")?;
 
         match self {
-            Self::NetworkError(message) | Self::SerializationError(message) => {
-                fmt.write_str(message)
-            }
+            Self::NetworkError(message)
+            | Self::SerializationError(message)
+            | Self::CacheError(message)
+            | Self::OfflineError(message) => fmt.write_str(message),
         }
     }
 }
@@ -1,4 +1,5 @@
-use core::time::Duration;
+use core::{iter::once, mem, time::Duration};
+use std::collections::HashSet;
 
 use futures::{stream, StreamExt, TryStreamExt};
 use reqwest::{
@@ -9,11 +10,157 @@ use tarpc::context::Context;
 use time::OffsetDateTime;
 use type_system::url::VersionedUrl;
 
-use crate::fetcher::{FetchedOntologyType, Fetcher, FetcherError};
+use crate::{
+    cache::OntologyTypeCache,
+    fetcher::{FetchedOntologyType, Fetcher, FetcherError},
+};
 
 #[derive(Clone)]
 pub struct FetchServer {
     pub buffer_size: usize,
+    /// Cache in which fetched ontology types are stored and from which they are served.
+    pub cache: Option<OntologyTypeCache>,
+    /// Only serves ontology types from the cache without accessing the network.
+    pub offline: bool,
+}
+
+/// Returns the URLs of all ontology types the provided ontology type refers to.
+fn referenced_urls(ontology_type: &FetchedOntologyType) -> Vec<&VersionedUrl> {
+    match ontology_type {
+        FetchedOntologyType::DataType(data_type) => data_type
+            .data_type_references()
+            .map(|(reference, _)| &reference.url)
+            .collect(),
+        FetchedOntologyType::PropertyType(property_type) => property_type
+            .property_type_references()
+            .into_iter()
+            .map(|reference| &reference.url)
+            .chain(
+                property_type
+                    .data_type_references()
+                    .into_iter()
+                    .map(|reference| &reference.url),
+            )
+            .collect(),
+        FetchedOntologyType::EntityType(entity_type) => entity_type
+            .property_type_references()
+            .into_iter()
+            .map(|reference| &reference.url)
+            .chain(entity_type.all_of.iter().map(|reference| &reference.url))
+            .chain(entity_type.link_mappings().into_iter().flat_map(
+                |(link_entity_type, destination_entity_type_constraint)| {
+                    once(link_entity_type)
+                        .chain(destination_entity_type_constraint.unwrap_or_default())
+                        .map(|reference| &reference.url)
+                },
+            ))
+            .collect(),
+    }
+}
+
+impl FetchServer {
+    async fn fetch_ontology_type(
+        &self,
+        client: &Client,
+        url: VersionedUrl,
+    ) -> Result<(FetchedOntologyType, OffsetDateTime), FetcherError> {
+        if let Some(cache) = &self.cache {
+            match cache.get(&url).await {
+                Ok(Some(cached)) => return Ok(cached),
+                Ok(None) => {}
+                Err(report) if self.offline => {
+                    tracing::error!(error=?report, %url, "Could not read ontology type from cache");
+                    return Err(FetcherError::CacheError(format!(
+                        "Error reading {url} from cache: {report:?}"
+                    )));
+                }
+                Err(report) => {
+                    tracing::warn!(error=?report, %url, "Could not read ontology type from cache");
+                }
+            }
+        }
+
+        if self.offline {
+            tracing::error!(%url, "Ontology type is not available in offline mode");
+            return Err(FetcherError::OfflineError(format!(
+                "{url} is not available in offline mode"
+            )));
+        }
+
+        let schema = client
+            .get(url.to_url())
+            .header(ACCEPT, "application/json")
+            .header(USER_AGENT, "HASH Graph")
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|err| {
+                tracing::error!(error=?err, %url, "Could not fetch ontology type");
+                FetcherError::NetworkError(format!("Error fetching {url}: {err:?}"))
+            })?
+            .bytes()
+            .await
+            .map_err(|err| {
+                tracing::error!(error=?err, %url, "Could not fetch ontology type");
+                FetcherError::NetworkError(format!("Error fetching {url}: {err:?}"))
+            })?;
+
+        let ontology_type =
+            serde_json::from_slice::<FetchedOntologyType>(&schema).map_err(|err| {
+                tracing::error!(error=?err, %url, "Could not deserialize response");
+                FetcherError::SerializationError(format!("Error deserializing {url}: {err:?}"))
+            })?;
+        let fetched_at = OffsetDateTime::now_utc();
+
+        if let Some(cache) = &self.cache {
+            if let Err(report) = cache.insert(&url, &schema, fetched_at).await {
+                tracing::warn!(error=?report, %url, "Could not store ontology type in cache");
+            }
+        }
+
+        Ok((ontology_type, fetched_at))
+    }
+
+    /// Populates the cache with the provided ontology types and all ontology types they refer to.
+    ///
+    /// Ontology types which are already present in the cache are not fetched again. Returns the
+    /// number of ontology types which are available in the cache afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no cache is configured or if any ontology type could not be fetched.
+    pub async fn mirror_ontology_types(
+        &self,
+        ontology_type_urls: impl IntoIterator<Item = VersionedUrl> + Send,
+    ) -> Result<usize, FetcherError> {
+        if self.cache.is_none() {
+            return Err(FetcherError::CacheError(
+                "No cache is configured to mirror ontology types into".to_owned(),
+            ));
+        }
+
+        let client = Client::new();
+        let mut queue = ontology_type_urls.into_iter().collect::<Vec<_>>();
+        let mut seen = queue.iter().cloned().collect::<HashSet<_>>();
+
+        while !queue.is_empty() {
+            let ontology_types = stream::iter(mem::take(&mut queue))
+                .map(|url| self.fetch_ontology_type(&client, url))
+                .buffer_unordered(self.buffer_size)
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            for (ontology_type, _) in &ontology_types {
+                for url in referenced_urls(ontology_type) {
+                    if seen.insert(url.clone()) {
+                        queue.push(url.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(seen.len())
+    }
 }
 
 #[tarpc::server]
@@ -25,34 +172,92 @@ impl Fetcher for FetchServer {
     ) -> Result<Vec<(FetchedOntologyType, OffsetDateTime)>, FetcherError> {
         let client = Client::new();
         stream::iter(ontology_type_urls)
-            .map(|url| {
-                let client = client.clone();
-                async move {
-                    let ontology_type = client
-                        .get(url.to_url())
-                        .header(ACCEPT, "application/json")
-                        .header(USER_AGENT, "HASH Graph")
-                        .timeout(Duration::from_secs(10))
-                        .send()
-                        .await
-                        .map_err(|err| {
-                            tracing::error!(error=?err, %url, "Could not fetch ontology type");
-                            FetcherError::NetworkError(format!("Error fetching {url}: {err:?}"))
-                        })?
-                        .json::<FetchedOntologyType>()
-                        .await
-                        .map_err(|err| {
-                            tracing::error!(error=?err, %url, "Could not deserialize response");
-                            FetcherError::SerializationError(format!(
-                                "Error deserializing {url}: {err:?}"
-                            ))
-                        })?;
-
-                    Ok::<_, FetcherError>((ontology_type, OffsetDateTime::now_utc()))
-                }
-            })
+            .map(|url| self.fetch_ontology_type(&client, url))
             .buffer_unordered(self.buffer_size)
             .try_collect()
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tarpc::context;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const TEXT_SCHEMA: &[u8] = br#"{
+        "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+        "kind": "dataType",
+        "$id": "https://example.invalid/@example/types/data-type/text/v/1",
+        "title": "Text",
+        "description": "An ordered sequence of characters",
+        "type": "string"
+    }"#;
+
+    fn url(name: &str) -> VersionedUrl {
+        format!("https://example.invalid/@example/types/data-type/{name}/v/1")
+            .parse()
+            .expect("should be a valid URL")
+    }
+
+    async fn server(offline: bool) -> (FetchServer, TempDir) {
+        let directory = tempfile::tempdir().expect("should be able to create directory");
+        let cache = OntologyTypeCache::open(directory.path())
+            .await
+            .expect("should be able to open cache");
+        cache
+            .insert(&url("text"), TEXT_SCHEMA, OffsetDateTime::UNIX_EPOCH)
+            .await
+            .expect("should be able to write cache");
+
+        let server = FetchServer {
+            buffer_size: 1,
+            cache: Some(cache),
+            offline,
+        };
+
+        (server, directory)
+    }
+
+    #[tokio::test]
+    async fn cache_hit() {
+        // `.invalid` can never be resolved, so the type has to be served from the cache
+        let (server, _directory) = server(false).await;
+
+        let ontology_types = server
+            .fetch_ontology_types(context::current(), vec![url("text")])
+            .await
+            .expect("cached type should be returned");
+
+        let [(ontology_type, fetched_at)] = ontology_types.as_slice() else {
+            panic!("exactly one type should be returned");
+        };
+        assert!(matches!(ontology_type, FetchedOntologyType::DataType(_)));
+        assert_eq!(*fetched_at, OffsetDateTime::UNIX_EPOCH);
+    }
+
+    #[tokio::test]
+    async fn offline_cache_hit() {
+        let (server, _directory) = server(true).await;
+
+        let ontology_types = server
+            .fetch_ontology_types(context::current(), vec![url("text")])
+            .await
+            .expect("cached type should be returned");
+
+        assert_eq!(ontology_types.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn offline_cache_miss() {
+        let (server, _directory) = server(true).await;
+
+        let error = server
+            .fetch_ontology_types(context::current(), vec![url("text"), url("number")])
+            .await
+            .expect_err("uncached type should not be available offline");
+
+        assert!(matches!(error, FetcherError::OfflineError(_)));
+    }
+}
@@ -1,2 +1,5 @@
+extern crate alloc;
+
+pub mod cache;
 pub mod fetcher;
 pub mod fetcher_server;
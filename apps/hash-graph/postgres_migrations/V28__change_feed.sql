@@ -0,0 +1,16 @@
+CREATE TABLE "change_feed" (
+    "sequence_id"       BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    "kind"              TEXT                     NOT NULL,
+    "record_type"       TEXT                     NOT NULL,
+    "web_id"            UUID,
+    "entity_uuid"       UUID,
+    "draft_id"          UUID,
+    "entity_edition_id" UUID,
+    "base_url"          TEXT,
+    "version"           BIGINT,
+    "transaction_time"  TIMESTAMP WITH TIME ZONE NOT NULL,
+    CHECK ("kind" IN ('create', 'update', 'archive', 'draftPublish')),
+    CHECK ("record_type" IN ('entity', 'dataType', 'propertyType', 'entityType')),
+    CHECK (("record_type" = 'entity') = ("entity_edition_id" IS NOT NULL)),
+    CHECK (("record_type" = 'entity') = ("base_url" IS NULL))
+);
@@ -0,0 +1,15 @@
+CREATE TABLE "entity_subscriptions" (
+    "subscription_id" UUID    PRIMARY KEY,
+    "actor_id"        UUID    NOT NULL REFERENCES "accounts",
+    "url"             TEXT    NOT NULL,
+    "filter"          JSONB   NOT NULL,
+    "sealed_secret"   BYTEA   NOT NULL,
+    "cursor"          BIGINT  NOT NULL
+);
+
+CREATE TABLE "entity_subscription_parked_changes" (
+    "subscription_id" UUID    NOT NULL REFERENCES "entity_subscriptions" ON DELETE CASCADE,
+    "sequence_id"     BIGINT  NOT NULL,
+    "reason"          TEXT    NOT NULL,
+    PRIMARY KEY ("subscription_id", "sequence_id")
+);
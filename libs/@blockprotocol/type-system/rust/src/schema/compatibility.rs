@@ -0,0 +1,492 @@
+//! Compatibility analysis between two versions of an ontology type.
+//!
+//! Updating an ontology type creates a new version of it, but the new version is not required to
+//! accept the same values as the previous one. The functions in this module report the changes
+//! between two versions which may cause values, which are valid for the previous version, to be
+//! rejected by the new version.
+
+use serde::Serialize;
+
+use crate::{
+    schema::{
+        ArraySchema, DataType, EntityType, JsonSchemaValueType, PropertyType, PropertyValues,
+        ValueOrArray,
+    },
+    url::{BaseUrl, VersionedUrl},
+};
+
+/// Relative tolerance when checking if one `multipleOf` factor is a multiple of another.
+const MULTIPLE_OF_TOLERANCE: f64 = 1e-9;
+
+/// Returns `true` if `new` accepts fewer items than `old`.
+const fn narrows_items<T, U>(old: &ArraySchema<T>, new: &ArraySchema<U>) -> bool {
+    let min_raised = match (old.min_items, new.min_items) {
+        (_, None) => false,
+        (None, Some(new)) => new > 0,
+        (Some(old), Some(new)) => new > old,
+    };
+    let max_lowered = match (old.max_items, new.max_items) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(old), Some(new)) => new < old,
+    };
+    min_raised || max_lowered
+}
+
+/// A change between two versions of an [`EntityType`] which may invalidate existing entities.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum EntityTypeIncompatibility {
+    /// The property is not allowed anymore.
+    #[serde(rename_all = "camelCase")]
+    PropertyRemoved { property: BaseUrl },
+    /// The property was optional or did not exist and is required now.
+    #[serde(rename_all = "camelCase")]
+    PropertyRequired { property: BaseUrl },
+    /// The property refers to a different property type.
+    #[serde(rename_all = "camelCase")]
+    PropertyTypeChanged {
+        property: BaseUrl,
+        old: VersionedUrl,
+        new: VersionedUrl,
+    },
+    /// The property changed from a single value to an array or vice versa.
+    #[serde(rename_all = "camelCase")]
+    PropertyCardinalityChanged { property: BaseUrl },
+    /// The property allows fewer items in its array.
+    #[serde(rename_all = "camelCase")]
+    PropertyItemsNarrowed { property: BaseUrl },
+    /// The link is not allowed anymore.
+    #[serde(rename_all = "camelCase")]
+    LinkRemoved { link: VersionedUrl },
+    /// The link allows fewer destination entity types.
+    #[serde(rename_all = "camelCase")]
+    LinkDestinationsNarrowed { link: VersionedUrl },
+    /// The link allows fewer links of its type.
+    #[serde(rename_all = "camelCase")]
+    LinkItemsNarrowed { link: VersionedUrl },
+    /// The entity type inherits from an additional entity type, which may add constraints.
+    #[serde(rename_all = "camelCase")]
+    ParentAdded { parent: VersionedUrl },
+    /// The entity type does not inherit from the entity type anymore.
+    #[serde(rename_all = "camelCase")]
+    ParentRemoved { parent: VersionedUrl },
+}
+
+impl EntityType {
+    /// Returns the changes from `self` to `new` which may invalidate entities of `self`.
+    #[must_use]
+    pub fn incompatible_changes(&self, new: &Self) -> Vec<EntityTypeIncompatibility> {
+        let mut changes = Vec::new();
+
+        for (property, old_value) in &self.properties {
+            let Some(new_value) = new.properties.get(property) else {
+                changes.push(EntityTypeIncompatibility::PropertyRemoved {
+                    property: property.clone(),
+                });
+                continue;
+            };
+
+            let (old_reference, new_reference) = match (old_value, new_value) {
+                (ValueOrArray::Value(old), ValueOrArray::Value(new)) => (old, new),
+                (ValueOrArray::Array(old), ValueOrArray::Array(new)) => {
+                    if narrows_items(old, new) {
+                        changes.push(EntityTypeIncompatibility::PropertyItemsNarrowed {
+                            property: property.clone(),
+                        });
+                    }
+                    (&old.items, &new.items)
+                }
+                (ValueOrArray::Value(_), ValueOrArray::Array(_))
+                | (ValueOrArray::Array(_), ValueOrArray::Value(_)) => {
+                    changes.push(EntityTypeIncompatibility::PropertyCardinalityChanged {
+                        property: property.clone(),
+                    });
+                    continue;
+                }
+            };
+            if old_reference.url != new_reference.url {
+                changes.push(EntityTypeIncompatibility::PropertyTypeChanged {
+                    property: property.clone(),
+                    old: old_reference.url.clone(),
+                    new: new_reference.url.clone(),
+                });
+            }
+        }
+
+        changes.extend(new.required.difference(&self.required).map(|property| {
+            EntityTypeIncompatibility::PropertyRequired {
+                property: property.clone(),
+            }
+        }));
+
+        for (link, old_link) in &self.links {
+            let Some(new_link) = new.links.get(link) else {
+                changes.push(EntityTypeIncompatibility::LinkRemoved { link: link.clone() });
+                continue;
+            };
+
+            let destinations_narrowed = match (&old_link.items, &new_link.items) {
+                (_, None) => false,
+                (None, Some(_)) => true,
+                (Some(old), Some(new)) => old
+                    .possibilities
+                    .iter()
+                    .any(|destination| !new.possibilities.contains(destination)),
+            };
+            if destinations_narrowed {
+                changes.push(EntityTypeIncompatibility::LinkDestinationsNarrowed {
+                    link: link.clone(),
+                });
+            }
+            if narrows_items(old_link, new_link) {
+                changes.push(EntityTypeIncompatibility::LinkItemsNarrowed { link: link.clone() });
+            }
+        }
+
+        changes.extend(new.all_of.difference(&self.all_of).map(|parent| {
+            EntityTypeIncompatibility::ParentAdded {
+                parent: parent.url.clone(),
+            }
+        }));
+        changes.extend(self.all_of.difference(&new.all_of).map(|parent| {
+            EntityTypeIncompatibility::ParentRemoved {
+                parent: parent.url.clone(),
+            }
+        }));
+
+        changes
+    }
+}
+
+/// A change between two versions of a [`PropertyType`] which may invalidate existing values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum PropertyTypeIncompatibility {
+    /// The values are not accepted by any possibility of the new version.
+    #[serde(rename_all = "camelCase")]
+    ValuesRemoved {
+        #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+        values: PropertyValues,
+    },
+}
+
+impl PropertyType {
+    /// Returns the changes from `self` to `new` which may invalidate values of `self`.
+    #[must_use]
+    pub fn incompatible_changes(&self, new: &Self) -> Vec<PropertyTypeIncompatibility> {
+        self.one_of
+            .iter()
+            .filter(|values| !new.one_of.contains(values))
+            .map(|values| PropertyTypeIncompatibility::ValuesRemoved {
+                values: values.clone(),
+            })
+            .collect()
+    }
+}
+
+/// A change between two versions of a [`DataType`] which may invalidate existing values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum DataTypeIncompatibility {
+    /// The JSON type of the values changed.
+    #[serde(rename_all = "camelCase")]
+    TypeChanged {
+        #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+        old: JsonSchemaValueType,
+        #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+        new: JsonSchemaValueType,
+    },
+    /// The value is restricted to a different constant.
+    ConstChanged,
+    /// Values of the previous version are not contained in the enumeration anymore.
+    EnumNarrowed,
+    /// The value is required to be a multiple of a different number.
+    MultipleOfChanged,
+    /// The minimum value was raised or became exclusive.
+    MinimumRaised,
+    /// The maximum value was lowered or became exclusive.
+    MaximumLowered,
+    /// The minimum length of the value was raised.
+    MinLengthRaised,
+    /// The maximum length of the value was lowered.
+    MaxLengthLowered,
+    /// The value is required to match a different pattern.
+    PatternChanged,
+    /// The value is required to match a different format.
+    FormatChanged,
+    /// The data type inherits from an additional data type, which may add constraints.
+    #[serde(rename_all = "camelCase")]
+    ParentAdded { parent: VersionedUrl },
+}
+
+impl DataType {
+    /// Returns the changes from `self` to `new` which may invalidate values of `self`.
+    #[must_use]
+    pub fn incompatible_changes(&self, new: &Self) -> Vec<DataTypeIncompatibility> {
+        let mut changes = Vec::new();
+
+        if self.json_type != new.json_type {
+            changes.push(DataTypeIncompatibility::TypeChanged {
+                old: self.json_type,
+                new: new.json_type,
+            });
+        }
+
+        if new.const_value.is_some() && self.const_value != new.const_value {
+            changes.push(DataTypeIncompatibility::ConstChanged);
+        }
+        if !new.enum_values.is_empty()
+            && (self.enum_values.is_empty()
+                || self
+                    .enum_values
+                    .iter()
+                    .any(|value| !new.enum_values.contains(value)))
+        {
+            changes.push(DataTypeIncompatibility::EnumNarrowed);
+        }
+
+        if let Some(new_multiple_of) = new.multiple_of {
+            // Values of the old version are still valid if the old factor is a multiple of the new.
+            // The factors are usually decimals, which cannot be represented exactly, e.g. `0.3 /
+            // 0.1` is slightly below `3`, so the ratio is compared with a relative tolerance.
+            let is_divisor = self.multiple_of.is_some_and(|old_multiple_of| {
+                #[expect(
+                    clippy::float_arithmetic,
+                    reason = "Comparing the factors requires floating point arithmetic"
+                )]
+                {
+                    let ratio = old_multiple_of / new_multiple_of;
+                    let factor = ratio.round();
+                    factor.abs() >= 1.0
+                        && (ratio - factor).abs() <= factor.abs() * MULTIPLE_OF_TOLERANCE
+                }
+            });
+            if !is_divisor {
+                changes.push(DataTypeIncompatibility::MultipleOfChanged);
+            }
+        }
+        if let Some(new_minimum) = new.minimum {
+            let raised = self.minimum.map_or(true, |old_minimum| {
+                new_minimum > old_minimum
+                    || (new_minimum >= old_minimum
+                        && new.exclusive_minimum
+                        && !self.exclusive_minimum)
+            });
+            if raised {
+                changes.push(DataTypeIncompatibility::MinimumRaised);
+            }
+        }
+        if let Some(new_maximum) = new.maximum {
+            let lowered = self.maximum.map_or(true, |old_maximum| {
+                new_maximum < old_maximum
+                    || (new_maximum <= old_maximum
+                        && new.exclusive_maximum
+                        && !self.exclusive_maximum)
+            });
+            if lowered {
+                changes.push(DataTypeIncompatibility::MaximumLowered);
+            }
+        }
+
+        if new
+            .min_length
+            .is_some_and(|new_min_length| self.min_length.map_or(true, |old| new_min_length > old))
+        {
+            changes.push(DataTypeIncompatibility::MinLengthRaised);
+        }
+        if new
+            .max_length
+            .is_some_and(|new_max_length| self.max_length.map_or(true, |old| new_max_length < old))
+        {
+            changes.push(DataTypeIncompatibility::MaxLengthLowered);
+        }
+        if let Some(new_pattern) = &new.pattern {
+            if self.pattern.as_ref().map_or(true, |old_pattern| {
+                old_pattern.as_str() != new_pattern.as_str()
+            }) {
+                changes.push(DataTypeIncompatibility::PatternChanged);
+            }
+        }
+        if new.format.is_some() && self.format != new.format {
+            changes.push(DataTypeIncompatibility::FormatChanged);
+        }
+
+        changes.extend(
+            new.all_of
+                .iter()
+                .filter(|parent| !self.all_of.contains(parent) && parent.url != self.id)
+                .map(|parent| DataTypeIncompatibility::ParentAdded {
+                    parent: parent.url.clone(),
+                }),
+        );
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn entity_type(value: serde_json::Value) -> EntityType {
+        serde_json::from_value(value).expect("should be a valid entity type")
+    }
+
+    fn data_type(value: serde_json::Value) -> DataType {
+        serde_json::from_value(value).expect("should be a valid data type")
+    }
+
+    #[test]
+    fn entity_type_changes() {
+        let old = entity_type(json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+            "kind": "entityType",
+            "$id": "https://example.com/@example/types/entity-type/person/v/1",
+            "type": "object",
+            "title": "Person",
+            "properties": {
+                "https://example.com/@example/types/property-type/name/": {
+                    "$ref": "https://example.com/@example/types/property-type/name/v/1"
+                },
+                "https://example.com/@example/types/property-type/age/": {
+                    "$ref": "https://example.com/@example/types/property-type/age/v/1"
+                }
+            },
+            "links": {
+                "https://example.com/@example/types/entity-type/friend-of/v/1": {
+                    "type": "array",
+                    "items": {}
+                }
+            }
+        }));
+        let new = entity_type(json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+            "kind": "entityType",
+            "$id": "https://example.com/@example/types/entity-type/person/v/2",
+            "type": "object",
+            "title": "Person",
+            "properties": {
+                "https://example.com/@example/types/property-type/name/": {
+                    "$ref": "https://example.com/@example/types/property-type/name/v/1"
+                }
+            },
+            "required": ["https://example.com/@example/types/property-type/name/"]
+        }));
+
+        let changes = old.incompatible_changes(&new);
+        assert_eq!(changes.len(), 3, "{changes:#?}");
+        assert!(
+            changes.contains(&EntityTypeIncompatibility::PropertyRemoved {
+                property: BaseUrl::new(
+                    "https://example.com/@example/types/property-type/age/".to_owned()
+                )
+                .expect("should be a valid base URL"),
+            })
+        );
+        assert!(
+            changes.contains(&EntityTypeIncompatibility::PropertyRequired {
+                property: BaseUrl::new(
+                    "https://example.com/@example/types/property-type/name/".to_owned()
+                )
+                .expect("should be a valid base URL"),
+            })
+        );
+        assert!(
+            changes
+                .iter()
+                .any(|change| matches!(change, EntityTypeIncompatibility::LinkRemoved { .. }))
+        );
+
+        assert!(new.incompatible_changes(&new).is_empty());
+    }
+
+    #[test]
+    fn data_type_changes() {
+        let old = data_type(json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+            "kind": "dataType",
+            "$id": "https://example.com/@example/types/data-type/age/v/1",
+            "title": "Age",
+            "type": "number",
+            "minimum": 0,
+            "maximum": 200
+        }));
+        let widened = data_type(json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+            "kind": "dataType",
+            "$id": "https://example.com/@example/types/data-type/age/v/2",
+            "title": "Age",
+            "type": "number",
+            "minimum": 0
+        }));
+        let narrowed = data_type(json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+            "kind": "dataType",
+            "$id": "https://example.com/@example/types/data-type/age/v/2",
+            "title": "Age",
+            "type": "number",
+            "minimum": 0,
+            "exclusiveMinimum": true,
+            "maximum": 150
+        }));
+
+        assert!(old.incompatible_changes(&widened).is_empty());
+        assert_eq!(
+            old.incompatible_changes(&narrowed),
+            [
+                DataTypeIncompatibility::MinimumRaised,
+                DataTypeIncompatibility::MaximumLowered,
+            ]
+        );
+    }
+
+    #[test]
+    fn multiple_of_changes() {
+        let multiple_of = |multiple_of: f64| {
+            data_type(json!({
+                "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+                "kind": "dataType",
+                "$id": "https://example.com/@example/types/data-type/amount/v/1",
+                "title": "Amount",
+                "type": "number",
+                "multipleOf": multiple_of
+            }))
+        };
+
+        // `0.3 % 0.1` is almost `0.1`, but every multiple of `0.3` is a multiple of `0.1`
+        assert!(
+            multiple_of(0.3)
+                .incompatible_changes(&multiple_of(0.1))
+                .is_empty()
+        );
+        assert!(
+            multiple_of(0.7)
+                .incompatible_changes(&multiple_of(0.1))
+                .is_empty()
+        );
+        assert!(
+            multiple_of(1.0)
+                .incompatible_changes(&multiple_of(0.01))
+                .is_empty()
+        );
+        assert!(
+            multiple_of(6.0)
+                .incompatible_changes(&multiple_of(3.0))
+                .is_empty()
+        );
+
+        for (old, new) in [(0.1, 0.3), (0.25, 0.1), (0.3, 0.2), (1.0, 3.0)] {
+            assert_eq!(
+                multiple_of(old).incompatible_changes(&multiple_of(new)),
+                [DataTypeIncompatibility::MultipleOfChanged],
+                "{old} -> {new}"
+            );
+        }
+    }
+}
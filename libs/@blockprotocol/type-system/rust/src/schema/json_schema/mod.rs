@@ -0,0 +1,1755 @@
+//! Import of [JSON Schema] documents as ontology types.
+//!
+//! A JSON Schema (draft 2020-12) document is converted into a set of [`DataType`]s,
+//! [`PropertyType`]s, and [`EntityType`]s:
+//!
+//! - object schemas (the root schema and object schemas in `$defs`) become entity types,
+//! - every property of an object becomes a property type,
+//! - primitive schemas with constraints become data types inheriting from the Block Protocol
+//!   primitive data types, unconstrained primitives refer to the primitive data type directly,
+//! - `$ref`s to object schemas become links to the referenced entity type, and `$ref`s in `allOf`
+//!   become entity type inheritance.
+//!
+//! Keywords which cannot be represented in the type system are reported as
+//! [`UnsupportedKeyword`]s instead of failing the import.
+//!
+//! [JSON Schema]: https://json-schema.org/draft/2020-12/json-schema-core
+
+mod naming;
+
+use std::collections::{HashMap, HashSet};
+
+use error_stack::{bail, Report, ResultExt as _};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use thiserror::Error;
+
+use self::naming::{slug, title};
+use super::data_type::{DataTypeSchemaTag, DataTypeTag};
+use crate::{
+    schema::{
+        ArraySchema, DataType, DataTypeLabel, DataTypeReference, EntityType, EntityTypeReference,
+        JsonSchemaValueType, ObjectSchema, OneOfSchema, PropertyType, PropertyTypeReference,
+        PropertyValues, ValueOrArray,
+    },
+    url::{BaseUrl, OntologyTypeVersion, VersionedUrl},
+};
+
+const TEXT_DATA_TYPE: &str = "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1";
+const NUMBER_DATA_TYPE: &str =
+    "https://blockprotocol.org/@blockprotocol/types/data-type/number/v/1";
+const BOOLEAN_DATA_TYPE: &str =
+    "https://blockprotocol.org/@blockprotocol/types/data-type/boolean/v/1";
+const NULL_DATA_TYPE: &str = "https://blockprotocol.org/@blockprotocol/types/data-type/null/v/1";
+const LINK_ENTITY_TYPE: &str =
+    "https://blockprotocol.org/@blockprotocol/types/entity-type/link/v/1";
+
+/// Keywords which do not affect the shape of the data and are ignored on import.
+const ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "examples",
+    "default",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+#[derive(Debug, Error)]
+pub enum JsonSchemaImportError {
+    #[error("the root of the document is not an object schema")]
+    NotAnObjectSchema,
+    #[error("the root of the document does not have a title")]
+    MissingTitle,
+    #[error("the reference `{reference}` cannot be resolved")]
+    MissingReference { reference: String },
+    #[error("the reference `{reference}` is part of a cycle")]
+    CyclicReference { reference: String },
+    #[error("`{url}` is not a valid type URL")]
+    InvalidUrl { url: String },
+}
+
+/// A keyword of the imported document which cannot be mapped onto the type system.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Error)]
+#[error("`{keyword}` at `{pointer}` cannot be mapped onto the type system")]
+pub struct UnsupportedKeyword {
+    /// The JSON pointer (as URI fragment) to the schema containing the keyword.
+    pub pointer: String,
+    pub keyword: String,
+}
+
+/// The ontology types created from a JSON Schema document.
+///
+/// The types are ordered such that referenced types are created before the types referring to
+/// them, so they can be passed to the store as-is.
+#[derive(Debug, Default)]
+pub struct ImportedTypes {
+    pub data_types: Vec<DataType>,
+    pub property_types: Vec<PropertyType>,
+    pub entity_types: Vec<EntityType>,
+    pub unsupported: Vec<UnsupportedKeyword>,
+}
+
+/// Converts JSON Schema documents into ontology types.
+///
+/// All created types are placed below the base URL of the importer, e.g. the entity type for
+/// `$defs/person` is created as `<base URL>entity-type/person/v/1`.
+#[derive(Debug, Clone)]
+pub struct JsonSchemaImporter {
+    base_url: BaseUrl,
+}
+
+impl JsonSchemaImporter {
+    #[must_use]
+    pub const fn new(base_url: BaseUrl) -> Self {
+        Self { base_url }
+    }
+
+    /// Imports the JSON Schema document.
+    ///
+    /// # Errors
+    ///
+    /// - [`NotAnObjectSchema`] if the root of the document is not an object schema
+    /// - [`MissingTitle`] if the root of the document does not have a title
+    /// - [`MissingReference`] if a local `$ref` does not point to a definition
+    /// - [`CyclicReference`] if definitions inherit from or alias each other in a cycle
+    /// - [`InvalidUrl`] if a type URL cannot be created from the base URL
+    ///
+    /// [`NotAnObjectSchema`]: JsonSchemaImportError::NotAnObjectSchema
+    /// [`MissingTitle`]: JsonSchemaImportError::MissingTitle
+    /// [`MissingReference`]: JsonSchemaImportError::MissingReference
+    /// [`CyclicReference`]: JsonSchemaImportError::CyclicReference
+    /// [`InvalidUrl`]: JsonSchemaImportError::InvalidUrl
+    pub fn import(
+        &self,
+        document: &JsonValue,
+    ) -> Result<ImportedTypes, Report<JsonSchemaImportError>> {
+        let Some(root) = document.as_object().filter(|root| is_object_schema(root)) else {
+            bail!(JsonSchemaImportError::NotAnObjectSchema);
+        };
+        let root_title = root
+            .get("title")
+            .and_then(JsonValue::as_str)
+            .ok_or(JsonSchemaImportError::MissingTitle)?;
+
+        let mut importer = Importer {
+            base_url: &self.base_url,
+            definitions: HashMap::new(),
+            types: ImportedTypes::default(),
+        };
+
+        importer.collect_definitions("#", root_title, root)?;
+        for keyword in ["$defs", "definitions"] {
+            for (name, schema) in root
+                .get(keyword)
+                .and_then(JsonValue::as_object)
+                .into_iter()
+                .flatten()
+            {
+                let pointer = format!("#/{keyword}/{}", escape_pointer(name));
+                match schema.as_object() {
+                    Some(schema) => importer.collect_definitions(&pointer, name, schema)?,
+                    None => importer.unsupported(&pointer, "$ref"),
+                }
+            }
+        }
+
+        let mut pointers = importer.definitions.keys().cloned().collect::<Vec<_>>();
+        pointers.sort();
+        for pointer in pointers {
+            importer.resolve(&pointer)?;
+        }
+
+        Ok(importer.types)
+    }
+}
+
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn is_object_schema(schema: &JsonMap<String, JsonValue>) -> bool {
+    match schema.get("type") {
+        Some(JsonValue::String(json_type)) => json_type == "object",
+        Some(_) => false,
+        None => schema.contains_key("properties"),
+    }
+}
+
+fn versioned_url(
+    base_url: &BaseUrl,
+    kind: &str,
+    slug: &str,
+) -> Result<VersionedUrl, Report<JsonSchemaImportError>> {
+    let url = format!("{base_url}{kind}/{slug}/");
+    Ok(VersionedUrl {
+        base_url: BaseUrl::new(url.clone())
+            .change_context(JsonSchemaImportError::InvalidUrl { url })?,
+        version: OntologyTypeVersion::new(1),
+    })
+}
+
+fn primitive_url(url: &str) -> VersionedUrl {
+    url.parse()
+        .unwrap_or_else(|_| unreachable!("`{url}` is a valid versioned URL"))
+}
+
+/// Returns the URL of the first type named `name` which either does not exist yet or is equal to
+/// `candidate`.
+///
+/// If no type with the same content exists, the type is added to `types`.
+fn deduplicate<T: Serialize>(
+    types: &mut Vec<T>,
+    base_url: &BaseUrl,
+    kind: &str,
+    name: &str,
+    id: impl Fn(&mut T) -> &mut VersionedUrl,
+    is_reserved: impl Fn(&BaseUrl) -> bool,
+    mut candidate: T,
+) -> Result<VersionedUrl, Report<JsonSchemaImportError>> {
+    let name = slug(name);
+    for index in 1_usize.. {
+        let url = if index == 1 {
+            versioned_url(base_url, kind, &name)?
+        } else {
+            versioned_url(base_url, kind, &format!("{name}-{index}"))?
+        };
+        if is_reserved(&url.base_url) {
+            continue;
+        }
+
+        *id(&mut candidate) = url.clone();
+        let Some(index) = types.iter_mut().position(|existing| *id(existing) == url) else {
+            types.push(candidate);
+            return Ok(url);
+        };
+        let existing =
+            serde_json::to_value(&types[index]).expect("ontology types should be serializable");
+        if existing
+            == serde_json::to_value(&candidate).expect("ontology types should be serializable")
+        {
+            return Ok(url);
+        }
+    }
+
+    unreachable!("the amount of types is bounded by the size of the document")
+}
+
+#[derive(Debug)]
+enum Definition<'s> {
+    /// An object schema, which is imported as entity type.
+    Entity {
+        url: VersionedUrl,
+        title: String,
+        schema: &'s JsonMap<String, JsonValue>,
+        state: State,
+    },
+    /// A primitive schema, which is imported as data type.
+    Data {
+        name: &'s str,
+        schema: &'s JsonMap<String, JsonValue>,
+        state: State,
+        url: Option<VersionedUrl>,
+    },
+    /// Any other schema, which is inlined where it is referenced.
+    Inline {
+        name: &'s str,
+        schema: &'s JsonMap<String, JsonValue>,
+        state: State,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum State {
+    Pending,
+    InProgress,
+    Done,
+}
+
+struct Importer<'s> {
+    base_url: &'s BaseUrl,
+    definitions: HashMap<String, Definition<'s>>,
+    types: ImportedTypes,
+}
+
+impl<'s> Importer<'s> {
+    fn unsupported(&mut self, pointer: &str, keyword: &str) {
+        self.types.unsupported.push(UnsupportedKeyword {
+            pointer: pointer.to_owned(),
+            keyword: keyword.to_owned(),
+        });
+    }
+
+    /// Reports every keyword of `schema` which is neither handled nor an annotation.
+    fn unsupported_keywords(
+        &mut self,
+        pointer: &str,
+        schema: &JsonMap<String, JsonValue>,
+        handled: &[&str],
+    ) {
+        let mut keywords = schema
+            .keys()
+            .filter(|keyword| {
+                !handled.contains(&keyword.as_str()) && !ANNOTATIONS.contains(&keyword.as_str())
+            })
+            .collect::<Vec<_>>();
+        keywords.sort();
+        for keyword in keywords {
+            self.unsupported(pointer, keyword);
+        }
+    }
+
+    fn collect_definitions(
+        &mut self,
+        pointer: &str,
+        name: &'s str,
+        schema: &'s JsonMap<String, JsonValue>,
+    ) -> Result<(), Report<JsonSchemaImportError>> {
+        let definition = if is_object_schema(schema) {
+            let title = schema
+                .get("title")
+                .and_then(JsonValue::as_str)
+                .map_or_else(|| title(name), ToOwned::to_owned);
+            Definition::Entity {
+                url: versioned_url(self.base_url, "entity-type", &slug(name))?,
+                title,
+                schema,
+                state: State::Pending,
+            }
+        } else if primitive_type(schema).is_some_and(|json_type| json_type != "array") {
+            Definition::Data {
+                name,
+                schema,
+                state: State::Pending,
+                url: None,
+            }
+        } else {
+            Definition::Inline {
+                name,
+                schema,
+                state: State::Pending,
+            }
+        };
+
+        if let Definition::Entity { url, .. } = &definition {
+            let conflicts = self.definitions.values().any(|existing| {
+                matches!(existing, Definition::Entity { url: existing, .. } if existing == url)
+            });
+            if conflicts {
+                // `$defs` and `definitions` contain an entry with the same name
+                self.unsupported(pointer, "$defs");
+                return Ok(());
+            }
+        }
+
+        self.definitions.insert(pointer.to_owned(), definition);
+        Ok(())
+    }
+
+    /// Returns the entity type URL if `reference` points to an object schema.
+    fn entity_type_reference(&self, reference: &str) -> Option<VersionedUrl> {
+        match self.definitions.get(reference) {
+            Some(Definition::Entity { url, .. }) => Some(url.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the referenced entity types if `schema` only consists of references to object
+    /// schemas.
+    fn entity_type_references(
+        &self,
+        schema: &JsonMap<String, JsonValue>,
+    ) -> Option<Vec<VersionedUrl>> {
+        if let Some(reference) = schema.get("$ref").and_then(JsonValue::as_str) {
+            return self.entity_type_reference(reference).map(|url| vec![url]);
+        }
+
+        let possibilities = schema
+            .get("oneOf")
+            .or_else(|| schema.get("anyOf"))?
+            .as_array()?;
+        possibilities
+            .iter()
+            .map(|possibility| {
+                let reference = possibility.get("$ref")?.as_str()?;
+                self.entity_type_reference(reference)
+            })
+            .collect::<Option<Vec<_>>>()
+            .filter(|urls| !urls.is_empty())
+    }
+
+    /// Imports the definition behind `pointer` if it is not imported already.
+    fn resolve(&mut self, pointer: &str) -> Result<(), Report<JsonSchemaImportError>> {
+        let Some(definition) = self.definitions.get_mut(pointer) else {
+            bail!(JsonSchemaImportError::MissingReference {
+                reference: pointer.to_owned(),
+            });
+        };
+
+        match definition {
+            Definition::Entity {
+                url,
+                title,
+                schema,
+                state,
+            } => match *state {
+                State::Done => Ok(()),
+                State::InProgress => bail!(JsonSchemaImportError::CyclicReference {
+                    reference: pointer.to_owned(),
+                }),
+                State::Pending => {
+                    *state = State::InProgress;
+                    let (url, title, schema) = (url.clone(), title.clone(), *schema);
+                    let entity_type = self.entity_type(pointer, url, title, schema)?;
+                    self.types.entity_types.push(entity_type);
+                    self.set_state(pointer, State::Done);
+                    Ok(())
+                }
+            },
+            Definition::Data {
+                name,
+                schema,
+                state,
+                ..
+            } => match *state {
+                State::Done => Ok(()),
+                State::InProgress => bail!(JsonSchemaImportError::CyclicReference {
+                    reference: pointer.to_owned(),
+                }),
+                State::Pending => {
+                    *state = State::InProgress;
+                    let (name, schema) = (*name, *schema);
+                    let url = self.data_type(pointer, name, schema, true)?;
+                    if let Some(Definition::Data { url: slot, .. }) =
+                        self.definitions.get_mut(pointer)
+                    {
+                        *slot = url;
+                    }
+                    self.set_state(pointer, State::Done);
+                    Ok(())
+                }
+            },
+            // Inlined definitions are imported where they are referenced
+            Definition::Inline { .. } => Ok(()),
+        }
+    }
+
+    fn set_state(&mut self, pointer: &str, new_state: State) {
+        if let Some(
+            Definition::Entity { state, .. }
+            | Definition::Data { state, .. }
+            | Definition::Inline { state, .. },
+        ) = self.definitions.get_mut(pointer)
+        {
+            *state = new_state;
+        }
+    }
+
+    fn entity_type(
+        &mut self,
+        pointer: &str,
+        url: VersionedUrl,
+        title: String,
+        schema: &'s JsonMap<String, JsonValue>,
+    ) -> Result<EntityType, Report<JsonSchemaImportError>> {
+        #[expect(deprecated)]
+        let mut entity_type = EntityType {
+            id: url,
+            title,
+            description: schema
+                .get("description")
+                .and_then(JsonValue::as_str)
+                .map(ToOwned::to_owned),
+            properties: HashMap::new(),
+            required: HashSet::new(),
+            all_of: HashSet::new(),
+            links: HashMap::new(),
+            examples: Vec::new(),
+        };
+
+        self.object_into_entity_type(pointer, schema, &mut entity_type)?;
+
+        Ok(entity_type)
+    }
+
+    fn object_into_entity_type(
+        &mut self,
+        pointer: &str,
+        schema: &'s JsonMap<String, JsonValue>,
+        entity_type: &mut EntityType,
+    ) -> Result<(), Report<JsonSchemaImportError>> {
+        let mut handled = vec!["type", "properties", "required", "allOf"];
+        if pointer == "#" {
+            handled.extend(["$defs", "definitions"]);
+        }
+        for keyword in ["additionalProperties", "unevaluatedProperties"] {
+            // Entity types are closed, so only forbidding additional properties can be mapped
+            if schema.get(keyword) == Some(&JsonValue::Bool(false)) {
+                handled.push(keyword);
+            }
+        }
+        self.unsupported_keywords(pointer, schema, &handled);
+
+        self.inherit(pointer, schema, entity_type)?;
+
+        let required = schema
+            .get("required")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(JsonValue::as_str)
+            .collect::<HashSet<_>>();
+
+        let mut properties = schema
+            .get("properties")
+            .and_then(JsonValue::as_object)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        properties.sort_by_key(|(name, _)| *name);
+
+        for (name, property) in properties {
+            let property_pointer = format!("{pointer}/properties/{}", escape_pointer(name));
+            let Some(property) = property.as_object() else {
+                // Boolean schemas do not carry any type information
+                self.unsupported(&property_pointer, "properties");
+                continue;
+            };
+            let is_required = required.contains(name.as_str());
+
+            if let Some(destinations) = self.entity_type_references(property) {
+                self.unsupported_keywords(&property_pointer, property, &["$ref", "oneOf", "anyOf"]);
+                self.link(
+                    name,
+                    property,
+                    destinations,
+                    is_required.then_some(1),
+                    Some(1),
+                    entity_type,
+                )?;
+                continue;
+            }
+
+            if primitive_type(property) == Some("array") {
+                if let Some(destinations) = property
+                    .get("items")
+                    .and_then(JsonValue::as_object)
+                    .and_then(|items| self.entity_type_references(items))
+                {
+                    self.unsupported_keywords(
+                        &property_pointer,
+                        property,
+                        &["type", "items", "minItems", "maxItems"],
+                    );
+                    self.link(
+                        name,
+                        property,
+                        destinations,
+                        array_bound(property, "minItems"),
+                        array_bound(property, "maxItems"),
+                        entity_type,
+                    )?;
+                    continue;
+                }
+            }
+
+            let Some(value) = self.property_type_value(&property_pointer, name, property)? else {
+                continue;
+            };
+            let base_url = match &value {
+                ValueOrArray::Value(reference) => reference.url.base_url.clone(),
+                ValueOrArray::Array(array) => array.items.url.base_url.clone(),
+            };
+            if is_required {
+                entity_type.required.insert(base_url.clone());
+            }
+            entity_type.properties.insert(base_url, value);
+        }
+
+        let mut required = required.into_iter().collect::<Vec<_>>();
+        required.sort_unstable();
+        for name in required {
+            let in_properties = schema
+                .get("properties")
+                .and_then(|properties| properties.get(name))
+                .is_some();
+            if !in_properties {
+                self.unsupported(pointer, "required");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the schemas in `allOf` to the entity type.
+    ///
+    /// References to object schemas become parents of the entity type, while inline object
+    /// schemas are merged into it.
+    fn inherit(
+        &mut self,
+        pointer: &str,
+        schema: &'s JsonMap<String, JsonValue>,
+        entity_type: &mut EntityType,
+    ) -> Result<(), Report<JsonSchemaImportError>> {
+        for (index, parent) in schema
+            .get("allOf")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            let parent_pointer = format!("{pointer}/allOf/{index}");
+            match parent.as_object() {
+                Some(parent) if parent.contains_key("$ref") => {
+                    let Some(reference) = parent.get("$ref").and_then(JsonValue::as_str) else {
+                        self.unsupported(&parent_pointer, "$ref");
+                        continue;
+                    };
+                    self.unsupported_keywords(&parent_pointer, parent, &["$ref"]);
+                    match self.entity_type_reference(reference) {
+                        Some(url) => {
+                            self.resolve(reference)?;
+                            entity_type.all_of.insert(EntityTypeReference { url });
+                        }
+                        None if reference.starts_with('#') => {
+                            self.resolve(reference)?;
+                            // Only object schemas can be inherited from
+                            self.unsupported(&parent_pointer, "$ref");
+                        }
+                        None => self.unsupported(&parent_pointer, "$ref"),
+                    }
+                }
+                Some(parent) if is_object_schema(parent) => {
+                    self.object_into_entity_type(&parent_pointer, parent, entity_type)?;
+                }
+                _ => self.unsupported(pointer, "allOf"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a link entity type for the property `name` and adds it to the `entity_type`.
+    fn link(
+        &mut self,
+        name: &str,
+        schema: &JsonMap<String, JsonValue>,
+        destinations: Vec<VersionedUrl>,
+        min_items: Option<usize>,
+        max_items: Option<usize>,
+        entity_type: &mut EntityType,
+    ) -> Result<(), Report<JsonSchemaImportError>> {
+        #[expect(deprecated)]
+        let link_entity_type = EntityType {
+            id: primitive_url(LINK_ENTITY_TYPE),
+            title: schema
+                .get("title")
+                .and_then(JsonValue::as_str)
+                .map_or_else(|| title(name), ToOwned::to_owned),
+            description: schema
+                .get("description")
+                .and_then(JsonValue::as_str)
+                .map(ToOwned::to_owned),
+            properties: HashMap::new(),
+            required: HashSet::new(),
+            all_of: HashSet::from([EntityTypeReference {
+                url: primitive_url(LINK_ENTITY_TYPE),
+            }]),
+            links: HashMap::new(),
+            examples: Vec::new(),
+        };
+
+        let reserved = self
+            .definitions
+            .values()
+            .filter_map(|definition| match definition {
+                Definition::Entity { url, .. } => Some(url.base_url.clone()),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        let url = deduplicate(
+            &mut self.types.entity_types,
+            self.base_url,
+            "entity-type",
+            name,
+            |entity_type| &mut entity_type.id,
+            |base_url| reserved.contains(base_url),
+            link_entity_type,
+        )?;
+
+        entity_type.links.insert(
+            url,
+            ArraySchema {
+                items: Some(OneOfSchema {
+                    possibilities: destinations
+                        .into_iter()
+                        .map(|url| EntityTypeReference { url })
+                        .collect(),
+                }),
+                min_items,
+                max_items,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Creates the property type for the property `name` of an object.
+    ///
+    /// Arrays are represented as arrays of the property type created from their items, as
+    /// this is how lists of values are expressed for objects in the type system.
+    fn property_type_value(
+        &mut self,
+        pointer: &str,
+        name: &str,
+        schema: &JsonMap<String, JsonValue>,
+    ) -> Result<Option<ValueOrArray<PropertyTypeReference>>, Report<JsonSchemaImportError>> {
+        if primitive_type(schema) == Some("array") {
+            if let Some(items) = schema.get("items").and_then(JsonValue::as_object) {
+                self.unsupported_keywords(
+                    pointer,
+                    schema,
+                    &["type", "items", "minItems", "maxItems"],
+                );
+                let items_pointer = format!("{pointer}/items");
+                return Ok(self
+                    .property_type(&items_pointer, name, schema, items)?
+                    .map(|url| {
+                        ValueOrArray::Array(ArraySchema {
+                            items: PropertyTypeReference { url },
+                            min_items: array_bound(schema, "minItems"),
+                            max_items: array_bound(schema, "maxItems"),
+                        })
+                    }));
+            }
+        }
+
+        Ok(self
+            .property_type(pointer, name, schema, schema)?
+            .map(|url| ValueOrArray::Value(PropertyTypeReference { url })))
+    }
+
+    /// Creates a property type named `name` for the values of `schema`.
+    ///
+    /// The title and description are taken from `annotations`.
+    fn property_type(
+        &mut self,
+        pointer: &str,
+        name: &str,
+        annotations: &JsonMap<String, JsonValue>,
+        schema: &JsonMap<String, JsonValue>,
+    ) -> Result<Option<VersionedUrl>, Report<JsonSchemaImportError>> {
+        let one_of = self.property_values(pointer, name, schema)?;
+        if one_of.is_empty() {
+            return Ok(None);
+        }
+
+        let property_type = PropertyType {
+            id: primitive_url(TEXT_DATA_TYPE),
+            title: annotations
+                .get("title")
+                .and_then(JsonValue::as_str)
+                .map_or_else(|| title(name), ToOwned::to_owned),
+            description: annotations
+                .get("description")
+                .and_then(JsonValue::as_str)
+                .map(ToOwned::to_owned),
+            one_of,
+        };
+
+        deduplicate(
+            &mut self.types.property_types,
+            self.base_url,
+            "property-type",
+            name,
+            |property_type| &mut property_type.id,
+            |_| false,
+            property_type,
+        )
+        .map(Some)
+    }
+
+    /// Returns the possible property values of `schema`.
+    ///
+    /// Values which cannot be mapped are reported and omitted.
+    fn property_values(
+        &mut self,
+        pointer: &str,
+        name: &str,
+        schema: &JsonMap<String, JsonValue>,
+    ) -> Result<Vec<PropertyValues>, Report<JsonSchemaImportError>> {
+        if let Some(reference) = schema.get("$ref") {
+            self.unsupported_keywords(pointer, schema, &["$ref"]);
+            let Some(reference) = reference.as_str().filter(|reference| {
+                reference.starts_with("#/$defs/") || reference.starts_with("#/definitions/")
+            }) else {
+                // Only references to definitions of the same document are supported
+                self.unsupported(pointer, "$ref");
+                return Ok(Vec::new());
+            };
+
+            self.resolve(reference)?;
+            return match self.definitions.get(reference) {
+                Some(Definition::Data { url: Some(url), .. }) => {
+                    Ok(vec![PropertyValues::DataTypeReference(DataTypeReference {
+                        url: url.clone(),
+                    })])
+                }
+                Some(Definition::Inline {
+                    state: State::InProgress,
+                    ..
+                }) => bail!(JsonSchemaImportError::CyclicReference {
+                    reference: reference.to_owned(),
+                }),
+                Some(Definition::Inline { name, schema, .. }) => {
+                    let (name, schema) = (*name, *schema);
+                    self.set_state(reference, State::InProgress);
+                    let values = self.property_values(reference, name, schema);
+                    self.set_state(reference, State::Pending);
+                    values
+                }
+                // Entities can only be referenced as links from entity types
+                Some(Definition::Entity { .. } | Definition::Data { url: None, .. }) | None => {
+                    self.unsupported(pointer, "$ref");
+                    Ok(Vec::new())
+                }
+            };
+        }
+
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(possibilities) = schema.get(keyword).and_then(JsonValue::as_array) {
+                self.unsupported_keywords(pointer, schema, &[keyword]);
+                let mut values = Vec::new();
+                for (index, possibility) in possibilities.iter().enumerate() {
+                    let possibility_pointer = format!("{pointer}/{keyword}/{index}");
+                    match possibility.as_object() {
+                        Some(possibility) => values.extend(self.property_values(
+                            &possibility_pointer,
+                            name,
+                            possibility,
+                        )?),
+                        None => self.unsupported(&possibility_pointer, keyword),
+                    }
+                }
+                return Ok(values);
+            }
+        }
+
+        if let Some(JsonValue::Array(json_types)) = schema.get("type") {
+            let mut values = Vec::new();
+            for json_type in json_types {
+                let mut schema = schema.clone();
+                schema.insert("type".to_owned(), json_type.clone());
+                // The schema is only borrowed for the duration of the call, so it's fine to
+                // create the values from a temporary copy
+                values.extend(self.property_values(pointer, name, &schema)?);
+            }
+            return Ok(values);
+        }
+
+        match primitive_type(schema) {
+            Some("object") => {
+                self.unsupported_keywords(pointer, schema, &["type", "properties", "required"]);
+                let object = self.property_object(pointer, schema)?;
+                Ok(vec![PropertyValues::PropertyTypeObject(object)])
+            }
+            Some("array") => {
+                self.unsupported_keywords(
+                    pointer,
+                    schema,
+                    &["type", "items", "minItems", "maxItems"],
+                );
+                let Some(items) = schema.get("items").and_then(JsonValue::as_object) else {
+                    self.unsupported(pointer, "items");
+                    return Ok(Vec::new());
+                };
+                let possibilities =
+                    self.property_values(&format!("{pointer}/items"), name, items)?;
+                if possibilities.is_empty() {
+                    return Ok(Vec::new());
+                }
+                Ok(vec![PropertyValues::ArrayOfPropertyValues(ArraySchema {
+                    items: OneOfSchema { possibilities },
+                    min_items: array_bound(schema, "minItems"),
+                    max_items: array_bound(schema, "maxItems"),
+                })])
+            }
+            Some(_) => Ok(self
+                .data_type(pointer, name, schema, false)?
+                .map(|url| PropertyValues::DataTypeReference(DataTypeReference { url }))
+                .into_iter()
+                .collect()),
+            None => {
+                self.unsupported(pointer, "type");
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    fn property_object(
+        &mut self,
+        pointer: &str,
+        schema: &JsonMap<String, JsonValue>,
+    ) -> Result<ObjectSchema<ValueOrArray<PropertyTypeReference>>, Report<JsonSchemaImportError>>
+    {
+        let required = schema
+            .get("required")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(JsonValue::as_str)
+            .collect::<HashSet<_>>();
+
+        let mut object = ObjectSchema {
+            properties: HashMap::new(),
+            required: HashSet::new(),
+        };
+
+        let mut properties = schema
+            .get("properties")
+            .and_then(JsonValue::as_object)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        properties.sort_by_key(|(name, _)| *name);
+
+        for (name, property) in properties {
+            let property_pointer = format!("{pointer}/properties/{}", escape_pointer(name));
+            let Some(property) = property.as_object() else {
+                self.unsupported(&property_pointer, "properties");
+                continue;
+            };
+            let Some(value) = self.property_type_value(&property_pointer, name, property)? else {
+                continue;
+            };
+            let base_url = match &value {
+                ValueOrArray::Value(reference) => reference.url.base_url.clone(),
+                ValueOrArray::Array(array) => array.items.url.base_url.clone(),
+            };
+            if required.contains(name.as_str()) {
+                object.required.insert(base_url.clone());
+            }
+            object.properties.insert(base_url, value);
+        }
+
+        Ok(object)
+    }
+
+    /// Creates a data type for a primitive schema.
+    ///
+    /// If the schema does not have any constraints and is not `named`, the corresponding
+    /// primitive data type is used instead.
+    fn data_type(
+        &mut self,
+        pointer: &str,
+        name: &str,
+        schema: &JsonMap<String, JsonValue>,
+        named: bool,
+    ) -> Result<Option<VersionedUrl>, Report<JsonSchemaImportError>> {
+        let Some((json_type, parent, handled)) =
+            primitive_type(schema).and_then(primitive_data_type)
+        else {
+            self.unsupported(pointer, "type");
+            return Ok(None);
+        };
+
+        let mut data_type = DataType {
+            schema: DataTypeSchemaTag::V3,
+            kind: DataTypeTag::DataType,
+            id: primitive_url(parent),
+            all_of: vec![DataTypeReference {
+                url: primitive_url(parent),
+            }],
+            title: schema
+                .get("title")
+                .and_then(JsonValue::as_str)
+                .map_or_else(|| title(name), ToOwned::to_owned),
+            description: schema
+                .get("description")
+                .and_then(JsonValue::as_str)
+                .map(ToOwned::to_owned),
+            label: DataTypeLabel::default(),
+            json_type,
+            const_value: schema.get("const").cloned(),
+            enum_values: schema
+                .get("enum")
+                .and_then(JsonValue::as_array)
+                .cloned()
+                .unwrap_or_default(),
+            multiple_of: None,
+            maximum: None,
+            exclusive_maximum: false,
+            minimum: None,
+            exclusive_minimum: false,
+            min_length: None,
+            max_length: None,
+            pattern: None,
+            format: None,
+        };
+
+        let mut handled = handled.to_vec();
+        handled.extend(["type", "const", "enum"]);
+        self.unsupported_keywords(pointer, schema, &handled);
+
+        for keyword in handled {
+            let Some(value) = schema.get(keyword) else {
+                continue;
+            };
+            if apply_constraint(&mut data_type, schema, keyword, value).is_none() {
+                self.unsupported(pointer, keyword);
+            }
+        }
+
+        let is_constrained = serde_json::to_value(&data_type).ok().is_some_and(|value| {
+            value.as_object().is_some_and(|value| {
+                value.keys().any(|key| {
+                    !matches!(
+                        key.as_str(),
+                        "$schema" | "kind" | "$id" | "allOf" | "title" | "description" | "type"
+                    )
+                })
+            })
+        });
+        if !named && !is_constrained && json_type != JsonSchemaValueType::Integer {
+            return Ok(Some(primitive_url(parent)));
+        }
+
+        deduplicate(
+            &mut self.types.data_types,
+            self.base_url,
+            "data-type",
+            name,
+            |data_type| &mut data_type.id,
+            |_| false,
+            data_type,
+        )
+        .map(Some)
+    }
+}
+
+/// Returns the `type` of the schema, inferring it from `const` and `enum` if not present.
+fn primitive_type(schema: &JsonMap<String, JsonValue>) -> Option<&'static str> {
+    fn value_type(value: &JsonValue) -> &'static str {
+        match value {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "boolean",
+            JsonValue::Number(number) if number.is_f64() => "number",
+            JsonValue::Number(_) => "integer",
+            JsonValue::String(_) => "string",
+            JsonValue::Array(_) => "array",
+            JsonValue::Object(_) => "object",
+        }
+    }
+
+    match schema.get("type") {
+        Some(JsonValue::String(json_type)) => [
+            "null", "boolean", "number", "integer", "string", "array", "object",
+        ]
+        .into_iter()
+        .find(|known| known == json_type),
+        Some(_) => None,
+        None => {
+            let values = schema.get("const").map(core::slice::from_ref).or_else(|| {
+                schema
+                    .get("enum")
+                    .and_then(JsonValue::as_array)
+                    .map(Vec::as_slice)
+            })?;
+            let mut types = values.iter().map(value_type);
+            let first = types.next()?;
+            types
+                .try_fold(first, |json_type, next| match (json_type, next) {
+                    _ if json_type == next => Some(json_type),
+                    ("integer" | "number", "integer" | "number") => Some("number"),
+                    _ => None,
+                })
+                .filter(|json_type| !matches!(*json_type, "array" | "object"))
+        }
+    }
+}
+
+const NUMBER_CONSTRAINTS: &[&str] = &[
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+];
+
+/// Returns the value type, the parent data type, and the supported constraints for a primitive
+/// JSON Schema type.
+fn primitive_data_type(
+    json_type: &str,
+) -> Option<(JsonSchemaValueType, &'static str, &'static [&'static str])> {
+    match json_type {
+        "string" => Some((
+            JsonSchemaValueType::String,
+            TEXT_DATA_TYPE,
+            &["minLength", "maxLength", "pattern", "format"],
+        )),
+        "number" => Some((
+            JsonSchemaValueType::Number,
+            NUMBER_DATA_TYPE,
+            NUMBER_CONSTRAINTS,
+        )),
+        "integer" => Some((
+            JsonSchemaValueType::Integer,
+            NUMBER_DATA_TYPE,
+            NUMBER_CONSTRAINTS,
+        )),
+        "boolean" => Some((JsonSchemaValueType::Boolean, BOOLEAN_DATA_TYPE, &[])),
+        "null" => Some((JsonSchemaValueType::Null, NULL_DATA_TYPE, &[])),
+        _ => None,
+    }
+}
+
+/// Applies the constraint `keyword` of `schema` to the data type.
+///
+/// Returns `None` if the constraint cannot be represented.
+fn apply_constraint(
+    data_type: &mut DataType,
+    schema: &JsonMap<String, JsonValue>,
+    keyword: &str,
+    value: &JsonValue,
+) -> Option<()> {
+    match keyword {
+        "minLength" => value.as_u64().and_then(|value| {
+            data_type.min_length = Some(usize::try_from(value).ok()?);
+            Some(())
+        }),
+        "maxLength" => value.as_u64().and_then(|value| {
+            data_type.max_length = Some(usize::try_from(value).ok()?);
+            Some(())
+        }),
+        "pattern" => value
+            .as_str()
+            .and_then(|pattern| Regex::new(pattern).ok())
+            .map(|pattern| data_type.pattern = Some(pattern)),
+        "format" => serde_json::from_value(value.clone())
+            .ok()
+            .map(|format| data_type.format = Some(format)),
+        "multipleOf" => value
+            .as_f64()
+            .map(|value| data_type.multiple_of = Some(value)),
+        "minimum" => value.as_f64().map(|value| {
+            data_type.minimum = Some(value);
+        }),
+        "maximum" => value.as_f64().map(|value| {
+            data_type.maximum = Some(value);
+        }),
+        // Exclusive bounds are numbers since draft 6 but a flag on the bound in the type
+        // system, so they can only be mapped if they are not combined with an inclusive
+        // bound
+        "exclusiveMinimum" => value
+            .as_f64()
+            .filter(|_| !schema.contains_key("minimum"))
+            .map(|value| {
+                data_type.minimum = Some(value);
+                data_type.exclusive_minimum = true;
+            }),
+        "exclusiveMaximum" => value
+            .as_f64()
+            .filter(|_| !schema.contains_key("maximum"))
+            .map(|value| {
+                data_type.maximum = Some(value);
+                data_type.exclusive_maximum = true;
+            }),
+        _ => Some(()),
+    }
+}
+
+fn array_bound(schema: &JsonMap<String, JsonValue>, keyword: &str) -> Option<usize> {
+    schema
+        .get(keyword)
+        .and_then(JsonValue::as_u64)
+        .and_then(|bound| usize::try_from(bound).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        schema::{DataTypeValidator, EntityTypeValidator, PropertyTypeValidator},
+        Validator,
+    };
+
+    const BASE_URL: &str = "https://example.com/@acme/types/";
+
+    fn import(document: &JsonValue) -> ImportedTypes {
+        JsonSchemaImporter::new(BaseUrl::new(BASE_URL.to_owned()).expect("invalid base URL"))
+            .import(document)
+            .expect("should be able to import the document")
+    }
+
+    fn url(path: &str) -> VersionedUrl {
+        VersionedUrl::from_str(&format!("{BASE_URL}{path}/v/1")).expect("invalid URL")
+    }
+
+    fn base_url(path: &str) -> BaseUrl {
+        url(path).base_url
+    }
+
+    fn entity_type<'t>(types: &'t ImportedTypes, path: &str) -> &'t EntityType {
+        let id = url(path);
+        types
+            .entity_types
+            .iter()
+            .find(|entity_type| entity_type.id == id)
+            .unwrap_or_else(|| panic!("entity type `{id}` should be imported"))
+    }
+
+    fn property_type<'t>(types: &'t ImportedTypes, path: &str) -> &'t PropertyType {
+        let id = url(path);
+        types
+            .property_types
+            .iter()
+            .find(|property_type| property_type.id == id)
+            .unwrap_or_else(|| panic!("property type `{id}` should be imported"))
+    }
+
+    fn data_type<'t>(types: &'t ImportedTypes, path: &str) -> &'t DataType {
+        let id = url(path);
+        types
+            .data_types
+            .iter()
+            .find(|data_type| data_type.id == id)
+            .unwrap_or_else(|| panic!("data type `{id}` should be imported"))
+    }
+
+    /// Ensures the types are valid, can be round-tripped through their JSON representation, and
+    /// only refer to imported types, Block Protocol primitives, or types created before them.
+    async fn ensure_consistent(types: &ImportedTypes) {
+        let mut data_types = [
+            TEXT_DATA_TYPE,
+            NUMBER_DATA_TYPE,
+            BOOLEAN_DATA_TYPE,
+            NULL_DATA_TYPE,
+        ]
+        .into_iter()
+        .map(primitive_url)
+        .collect::<HashSet<_>>();
+        for data_type in &types.data_types {
+            DataTypeValidator
+                .validate_ref(data_type)
+                .await
+                .expect("data type should be valid");
+            serde_json::from_value::<DataType>(
+                serde_json::to_value(data_type).expect("should be able to serialize"),
+            )
+            .expect("data type should be deserializable");
+            for reference in &data_type.all_of {
+                assert!(data_types.contains(&reference.url), "{}", reference.url);
+            }
+            data_types.insert(data_type.id.clone());
+        }
+
+        let property_types = types
+            .property_types
+            .iter()
+            .map(|property_type| property_type.id.clone())
+            .collect::<HashSet<_>>();
+        for property_type in &types.property_types {
+            PropertyTypeValidator
+                .validate_ref(property_type)
+                .await
+                .expect("property type should be valid");
+            serde_json::from_value::<PropertyType>(
+                serde_json::to_value(property_type).expect("should be able to serialize"),
+            )
+            .expect("property type should be deserializable");
+            for reference in property_type.data_type_references() {
+                assert!(data_types.contains(&reference.url), "{}", reference.url);
+            }
+            for reference in property_type.property_type_references() {
+                assert!(property_types.contains(&reference.url), "{}", reference.url);
+            }
+        }
+
+        let mut entity_types = HashSet::from([primitive_url(LINK_ENTITY_TYPE)]);
+        let all_entity_types = types
+            .entity_types
+            .iter()
+            .map(|entity_type| entity_type.id.clone())
+            .collect::<HashSet<_>>();
+        for entity_type in &types.entity_types {
+            EntityTypeValidator
+                .validate_ref(entity_type)
+                .await
+                .expect("entity type should be valid");
+            serde_json::from_value::<EntityType>(
+                serde_json::to_value(entity_type).expect("should be able to serialize"),
+            )
+            .expect("entity type should be deserializable");
+            for reference in entity_type.property_type_references() {
+                assert!(property_types.contains(&reference.url), "{}", reference.url);
+            }
+            for parent in &entity_type.all_of {
+                assert!(entity_types.contains(&parent.url), "{}", parent.url);
+            }
+            for (link, destinations) in entity_type.link_mappings() {
+                assert!(all_entity_types.contains(&link.url), "{}", link.url);
+                for destination in destinations.into_iter().flatten() {
+                    assert!(
+                        all_entity_types.contains(&destination.url),
+                        "{}",
+                        destination.url
+                    );
+                }
+            }
+            entity_types.insert(entity_type.id.clone());
+        }
+    }
+
+    fn person() -> JsonValue {
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$id": "https://example.com/person.schema.json",
+            "title": "Person",
+            "description": "A human being",
+            "type": "object",
+            "properties": {
+                "firstName": { "type": "string", "minLength": 1 },
+                "age": { "type": "integer", "minimum": 0 },
+                "email": { "type": "string", "format": "email" },
+                "verified": { "type": "boolean" },
+                "nickname": { "type": ["string", "null"] },
+                "status": { "enum": ["active", "inactive"] },
+                "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 10 },
+                "coordinates": {
+                    "type": "array",
+                    "items": { "type": "array", "items": { "type": "number" } }
+                },
+                "postcode": { "$ref": "#/$defs/postcode" },
+                "contact": {
+                    "type": "object",
+                    "properties": {
+                        "phone": { "type": "string" },
+                        "fax": { "type": "string" }
+                    },
+                    "required": ["phone"]
+                },
+                "address": { "$ref": "#/$defs/address" },
+                "friends": { "type": "array", "items": { "$ref": "#" } }
+            },
+            "required": ["firstName", "address"],
+            "additionalProperties": false,
+            "$defs": {
+                "postcode": {
+                    "description": "A UK postcode",
+                    "type": "string",
+                    "pattern": "^[A-Z0-9 ]+$"
+                },
+                "address": {
+                    "title": "Postal Address",
+                    "type": "object",
+                    "properties": {
+                        "street": { "type": "string" },
+                        "postcode": { "$ref": "#/$defs/postcode" }
+                    }
+                }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn entity_types() {
+        let types = import(&person());
+        ensure_consistent(&types).await;
+        assert_eq!(types.unsupported, []);
+
+        let person = entity_type(&types, "entity-type/person");
+        assert_eq!(person.title, "Person");
+        assert_eq!(person.description.as_deref(), Some("A human being"));
+        assert_eq!(
+            person.properties.keys().cloned().collect::<HashSet<_>>(),
+            HashSet::from([
+                base_url("property-type/first-name"),
+                base_url("property-type/age"),
+                base_url("property-type/email"),
+                base_url("property-type/verified"),
+                base_url("property-type/nickname"),
+                base_url("property-type/status"),
+                base_url("property-type/tags"),
+                base_url("property-type/coordinates"),
+                base_url("property-type/postcode"),
+                base_url("property-type/contact"),
+            ])
+        );
+        assert_eq!(
+            person.required,
+            HashSet::from([base_url("property-type/first-name")])
+        );
+
+        let address = entity_type(&types, "entity-type/address");
+        assert_eq!(address.title, "Postal Address");
+        assert_eq!(
+            address.properties.keys().cloned().collect::<HashSet<_>>(),
+            HashSet::from([
+                base_url("property-type/street"),
+                base_url("property-type/postcode"),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn links() {
+        let types = import(&person());
+        ensure_consistent(&types).await;
+
+        let person = entity_type(&types, "entity-type/person");
+        let address_link = &person.links[&url("entity-type/address-2")];
+        assert_eq!(
+            address_link.items,
+            Some(OneOfSchema {
+                possibilities: vec![EntityTypeReference {
+                    url: url("entity-type/address"),
+                }],
+            })
+        );
+        assert_eq!(address_link.min_items, Some(1));
+        assert_eq!(address_link.max_items, Some(1));
+
+        let friends_link = &person.links[&url("entity-type/friends")];
+        assert_eq!(
+            friends_link.items,
+            Some(OneOfSchema {
+                possibilities: vec![EntityTypeReference {
+                    url: url("entity-type/person"),
+                }],
+            })
+        );
+        assert_eq!(friends_link.min_items, None);
+        assert_eq!(friends_link.max_items, None);
+
+        let friends = entity_type(&types, "entity-type/friends");
+        assert_eq!(friends.title, "Friends");
+        assert_eq!(
+            friends.all_of,
+            HashSet::from([EntityTypeReference {
+                url: primitive_url(LINK_ENTITY_TYPE),
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn property_types() {
+        let types = import(&person());
+        ensure_consistent(&types).await;
+
+        let nickname = property_type(&types, "property-type/nickname");
+        assert_eq!(
+            nickname.one_of,
+            [
+                PropertyValues::DataTypeReference(DataTypeReference {
+                    url: primitive_url(TEXT_DATA_TYPE),
+                }),
+                PropertyValues::DataTypeReference(DataTypeReference {
+                    url: primitive_url(NULL_DATA_TYPE),
+                }),
+            ]
+        );
+
+        let person = entity_type(&types, "entity-type/person");
+        assert_eq!(
+            person.properties[&base_url("property-type/tags")],
+            ValueOrArray::Array(ArraySchema {
+                items: PropertyTypeReference {
+                    url: url("property-type/tags"),
+                },
+                min_items: None,
+                max_items: Some(10),
+            })
+        );
+
+        let coordinates = property_type(&types, "property-type/coordinates");
+        assert_eq!(
+            coordinates.one_of,
+            [PropertyValues::ArrayOfPropertyValues(ArraySchema {
+                items: OneOfSchema {
+                    possibilities: vec![PropertyValues::DataTypeReference(DataTypeReference {
+                        url: primitive_url(NUMBER_DATA_TYPE),
+                    })],
+                },
+                min_items: None,
+                max_items: None,
+            })]
+        );
+
+        let contact = property_type(&types, "property-type/contact");
+        assert_eq!(
+            contact.one_of,
+            [PropertyValues::PropertyTypeObject(ObjectSchema {
+                properties: HashMap::from([
+                    (
+                        base_url("property-type/phone"),
+                        ValueOrArray::Value(PropertyTypeReference {
+                            url: url("property-type/phone"),
+                        })
+                    ),
+                    (
+                        base_url("property-type/fax"),
+                        ValueOrArray::Value(PropertyTypeReference {
+                            url: url("property-type/fax"),
+                        })
+                    ),
+                ]),
+                required: HashSet::from([base_url("property-type/phone")]),
+            })]
+        );
+    }
+
+    #[tokio::test]
+    async fn data_types() {
+        let types = import(&person());
+        ensure_consistent(&types).await;
+
+        let first_name = data_type(&types, "data-type/first-name");
+        assert_eq!(first_name.title, "First Name");
+        assert_eq!(first_name.json_type, JsonSchemaValueType::String);
+        assert_eq!(first_name.min_length, Some(1));
+        assert_eq!(
+            first_name.all_of,
+            [DataTypeReference {
+                url: primitive_url(TEXT_DATA_TYPE),
+            }]
+        );
+
+        let age = data_type(&types, "data-type/age");
+        assert_eq!(age.json_type, JsonSchemaValueType::Integer);
+        assert_eq!(age.minimum, Some(0.0));
+        assert_eq!(
+            age.all_of,
+            [DataTypeReference {
+                url: primitive_url(NUMBER_DATA_TYPE),
+            }]
+        );
+
+        let email = data_type(&types, "data-type/email");
+        assert_eq!(
+            serde_json::to_value(email.format).expect("should be able to serialize"),
+            json!("email")
+        );
+
+        let status = data_type(&types, "data-type/status");
+        assert_eq!(status.json_type, JsonSchemaValueType::String);
+        assert_eq!(status.enum_values, [json!("active"), json!("inactive")]);
+
+        // Named definitions are shared between all properties referring to them
+        let postcode = data_type(&types, "data-type/postcode");
+        assert_eq!(postcode.description.as_deref(), Some("A UK postcode"));
+        assert_eq!(
+            postcode.pattern.as_ref().map(Regex::as_str),
+            Some("^[A-Z0-9 ]+$")
+        );
+        assert_eq!(
+            types
+                .data_types
+                .iter()
+                .filter(|data_type| data_type.id.base_url.as_str().contains("postcode"))
+                .count(),
+            1
+        );
+
+        // Unconstrained primitives refer to the Block Protocol data types
+        assert!(!types.data_types.iter().any(|data_type| {
+            data_type.id == url("data-type/verified") || data_type.id == url("data-type/tags")
+        }));
+    }
+
+    #[tokio::test]
+    async fn inheritance() {
+        let types = import(&json!({
+            "title": "Employee",
+            "allOf": [
+                { "$ref": "#/$defs/person" },
+                {
+                    "type": "object",
+                    "properties": { "salary": { "type": "number" } }
+                }
+            ],
+            "properties": { "employer": { "type": "string" } },
+            "$defs": {
+                "person": {
+                    "type": "object",
+                    "properties": { "name": { "type": "string" } },
+                    "required": ["name"]
+                }
+            }
+        }));
+        ensure_consistent(&types).await;
+        assert_eq!(types.unsupported, []);
+
+        let employee = entity_type(&types, "entity-type/employee");
+        assert_eq!(
+            employee.all_of,
+            HashSet::from([EntityTypeReference {
+                url: url("entity-type/person"),
+            }])
+        );
+        assert_eq!(
+            employee.properties.keys().cloned().collect::<HashSet<_>>(),
+            HashSet::from([
+                base_url("property-type/salary"),
+                base_url("property-type/employer"),
+            ])
+        );
+
+        let order = types
+            .entity_types
+            .iter()
+            .map(|entity_type| entity_type.id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            [url("entity-type/person"), url("entity-type/employee")]
+        );
+    }
+
+    #[tokio::test]
+    async fn deduplication() {
+        let types = import(&json!({
+            "title": "Book",
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "author": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                    }
+                },
+                "publisher": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string", "maxLength": 100 },
+                    }
+                }
+            }
+        }));
+        ensure_consistent(&types).await;
+
+        let name = property_type(&types, "property-type/name");
+        let name_2 = property_type(&types, "property-type/name-2");
+        assert_eq!(
+            name.one_of,
+            [PropertyValues::DataTypeReference(DataTypeReference {
+                url: primitive_url(TEXT_DATA_TYPE),
+            })]
+        );
+        assert_eq!(
+            name_2.one_of,
+            [PropertyValues::DataTypeReference(DataTypeReference {
+                url: url("data-type/name"),
+            })]
+        );
+        assert_eq!(types.property_types.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn unsupported_keywords() {
+        let types = import(&json!({
+            "title": "Document",
+            "type": "object",
+            "properties": {
+                "metadata": { "type": "object", "additionalProperties": { "type": "string" } },
+                "license": { "$ref": "https://example.com/license.schema.json" },
+                "revision": { "type": "integer", "minimum": 1, "exclusiveMinimum": 0 },
+                "keywords": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "uniqueItems": true
+                },
+                "anything": {}
+            },
+            "patternProperties": { "^x-": { "type": "string" } },
+            "if": { "required": ["license"] },
+            "then": { "required": ["revision"] }
+        }));
+        ensure_consistent(&types).await;
+
+        let unsupported = types
+            .unsupported
+            .iter()
+            .map(|keyword| (keyword.pointer.as_str(), keyword.keyword.as_str()))
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            unsupported,
+            HashSet::from([
+                ("#", "if"),
+                ("#", "patternProperties"),
+                ("#", "then"),
+                ("#/properties/anything", "type"),
+                ("#/properties/keywords", "uniqueItems"),
+                ("#/properties/license", "$ref"),
+                ("#/properties/metadata", "additionalProperties"),
+                ("#/properties/revision", "exclusiveMinimum"),
+            ])
+        );
+
+        let document = entity_type(&types, "entity-type/document");
+        assert!(
+            !document
+                .properties
+                .contains_key(&base_url("property-type/license"))
+        );
+        assert!(
+            !document
+                .properties
+                .contains_key(&base_url("property-type/anything"))
+        );
+    }
+
+    #[test]
+    fn not_an_object_schema() {
+        let report =
+            JsonSchemaImporter::new(BaseUrl::new(BASE_URL.to_owned()).expect("invalid base URL"))
+                .import(&json!({ "title": "Name", "type": "string" }))
+                .expect_err("should not be able to import a string schema");
+        assert!(matches!(
+            report.current_context(),
+            JsonSchemaImportError::NotAnObjectSchema
+        ));
+    }
+
+    #[test]
+    fn missing_reference() {
+        let report =
+            JsonSchemaImporter::new(BaseUrl::new(BASE_URL.to_owned()).expect("invalid base URL"))
+                .import(&json!({
+                    "title": "Person",
+                    "properties": { "address": { "$ref": "#/$defs/address" } }
+                }))
+                .expect_err("should not be able to resolve the reference");
+        assert!(matches!(
+            report.current_context(),
+            JsonSchemaImportError::MissingReference { reference } if reference == "#/$defs/address"
+        ));
+    }
+
+    #[test]
+    fn cyclic_inheritance() {
+        let report =
+            JsonSchemaImporter::new(BaseUrl::new(BASE_URL.to_owned()).expect("invalid base URL"))
+                .import(&json!({
+                    "title": "Root",
+                    "properties": {},
+                    "$defs": {
+                        "a": { "type": "object", "allOf": [{ "$ref": "#/$defs/b" }] },
+                        "b": { "type": "object", "allOf": [{ "$ref": "#/$defs/a" }] }
+                    }
+                }))
+                .expect_err("should detect the cycle");
+        assert!(matches!(
+            report.current_context(),
+            JsonSchemaImportError::CyclicReference { .. }
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+/// Splits an identifier like `firstName`, `first_name`, or `First Name` into its words.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous: Option<char> = None;
+
+    for character in name.chars() {
+        if !character.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(core::mem::take(&mut current));
+            }
+            previous = None;
+            continue;
+        }
+
+        let is_boundary = previous.is_some_and(|previous| {
+            (previous.is_lowercase() || previous.is_numeric()) && character.is_uppercase()
+        });
+        if is_boundary && !current.is_empty() {
+            words.push(core::mem::take(&mut current));
+        }
+
+        current.push(character);
+        previous = Some(character);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+/// Converts a name into the kebab-case form used in type URLs.
+pub(super) fn slug(name: &str) -> String {
+    let slug = words(name)
+        .into_iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        "unnamed".to_owned()
+    } else {
+        slug
+    }
+}
+
+/// Converts a name into a human readable title.
+pub(super) fn title(name: &str) -> String {
+    let title = words(name)
+        .into_iter()
+        .enumerate()
+        .map(|(index, word)| {
+            let mut characters = word.chars();
+            let first = characters.next().map(|first| {
+                if index == 0 {
+                    first.to_uppercase().collect::<String>()
+                } else {
+                    first.to_string()
+                }
+            });
+            first
+                .into_iter()
+                .chain([characters.as_str().to_owned()])
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if title.is_empty() {
+        "Unnamed".to_owned()
+    } else {
+        title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs() {
+        assert_eq!(slug("firstName"), "first-name");
+        assert_eq!(slug("first_name"), "first-name");
+        assert_eq!(slug("Postal Address"), "postal-address");
+        assert_eq!(slug("ISBN"), "isbn");
+        assert_eq!(slug("address2Line"), "address2-line");
+        assert_eq!(slug("__"), "unnamed");
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(title("firstName"), "First Name");
+        assert_eq!(title("first_name"), "First name");
+        assert_eq!(title("ISBN"), "ISBN");
+        assert_eq!(title("Postal Address"), "Postal Address");
+    }
+}
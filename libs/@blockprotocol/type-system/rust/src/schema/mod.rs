@@ -5,8 +5,10 @@
 //! specification, and are used to validate instances of types using [`serde`]. To aid with the
 //! de/serialization, intermediary structs and helpers are defined across various submodules.
 
+mod compatibility;
 mod data_type;
 mod entity_type;
+mod json_schema;
 mod property_type;
 
 mod array;
@@ -15,6 +17,9 @@ mod one_of;
 
 pub use self::{
     array::{ArraySchema, ValueOrArray},
+    compatibility::{
+        DataTypeIncompatibility, EntityTypeIncompatibility, PropertyTypeIncompatibility,
+    },
     data_type::{
         ClosedDataType, ClosedDataTypeMetadata, DataType, DataTypeLabel, DataTypeReference,
         DataTypeValidator, JsonSchemaValueType, OntologyTypeResolver, ValidateDataTypeError,
@@ -23,6 +28,7 @@ pub use self::{
         ClosedEntityType, ClosedEntityTypeSchemaData, EntityType, EntityTypeReference,
         EntityTypeValidationError, EntityTypeValidator,
     },
+    json_schema::{ImportedTypes, JsonSchemaImportError, JsonSchemaImporter, UnsupportedKeyword},
     object::{ObjectSchema, ObjectSchemaValidationError, ObjectSchemaValidator},
     one_of::{OneOfSchema, OneOfSchemaValidationError, OneOfSchemaValidator},
     property_type::{
@@ -1,3 +1,5 @@
+pub mod status;
+
 use alloc::sync::Arc;
 use core::error::Error;
 
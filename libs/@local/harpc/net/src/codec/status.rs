@@ -0,0 +1,365 @@
+//! Error envelope based on [`hash_status::Status`].
+//!
+//! Errors are encoded as JSON serialized [`Status`], which is the same representation used by the
+//! REST API. The [`StatusCode`] is taken from the error (or report) if it provides one, otherwise
+//! it is derived from the [`ErrorCode`] sent alongside the response.
+
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use bytes::{Bytes, BytesMut};
+use error_stack::{AttachmentKind, FrameKind, Report, ResultExt};
+use futures::{Stream, StreamExt};
+use harpc_wire_protocol::response::kind::ErrorCode;
+use hash_status::{Status, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use super::{ErrorDecoder, ErrorEncoder, WireError};
+use crate::session::error::TransactionError;
+
+/// Derives the [`StatusCode`] from an [`ErrorCode`] if the error does not provide one.
+#[must_use]
+pub const fn status_code(code: ErrorCode) -> StatusCode {
+    match code {
+        ErrorCode::CONNECTION_CLOSED | ErrorCode::CONNECTION_SHUTDOWN => StatusCode::Unavailable,
+        ErrorCode::CONNECTION_TRANSACTION_LIMIT_REACHED
+        | ErrorCode::INSTANCE_TRANSACTION_LIMIT_REACHED => StatusCode::ResourceExhausted,
+        ErrorCode::TRANSACTION_LAGGING => StatusCode::Aborted,
+        ErrorCode::INTERNAL_SERVER_ERROR => StatusCode::Internal,
+        _ => StatusCode::Unknown,
+    }
+}
+
+/// A single entry of the error chain.
+///
+/// Entries are ordered from the outermost to the innermost one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum ErrorDetail {
+    /// A context of a report or an error in the chain of sources.
+    Context(String),
+    /// A printable attachment of a report.
+    Attachment(String),
+}
+
+fn serialize(status: &Status<ErrorDetail>) -> Bytes {
+    match serde_json::to_vec(status) {
+        Ok(bytes) => Bytes::from(bytes),
+        Err(error) => {
+            // serialization of the status is infallible in practice, but we need to send *some*
+            // error to the client
+            tracing::error!(?error, "unable to serialize error status");
+
+            Bytes::new()
+        }
+    }
+}
+
+/// Encodes errors and reports into a [`Status`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StatusErrorEncoder;
+
+impl ErrorEncoder for StatusErrorEncoder {
+    async fn encode_report<C>(&self, report: Report<C>) -> TransactionError {
+        let code = report
+            .request_ref::<ErrorCode>()
+            .next()
+            .copied()
+            .or_else(|| report.request_value::<ErrorCode>().next())
+            .unwrap_or(ErrorCode::INTERNAL_SERVER_ERROR);
+
+        let status_code = report
+            .request_ref::<StatusCode>()
+            .next()
+            .copied()
+            .or_else(|| report.request_value::<StatusCode>().next())
+            .unwrap_or_else(|| status_code(code));
+
+        let details = report
+            .frames()
+            .filter_map(|frame| match frame.kind() {
+                FrameKind::Context(context) => Some(ErrorDetail::Context(context.to_string())),
+                FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+                    Some(ErrorDetail::Attachment(attachment.to_string()))
+                }
+                FrameKind::Attachment(_) => None,
+            })
+            .collect();
+
+        TransactionError {
+            code,
+            bytes: serialize(&Status::new(status_code, Some(report.to_string()), details)),
+        }
+    }
+
+    async fn encode_error<E>(&self, error: E) -> TransactionError
+    where
+        E: WireError + Send,
+    {
+        let code = error.code();
+
+        let status_code = core::error::request_value::<StatusCode>(&error)
+            .or_else(|| core::error::request_ref::<StatusCode>(&error).copied())
+            .unwrap_or_else(|| status_code(code));
+
+        let details = core::iter::successors(Some(&error as &dyn Error), |&error| error.source())
+            .map(|error| ErrorDetail::Context(error.to_string()))
+            .collect();
+
+        TransactionError {
+            code,
+            bytes: serialize(&Status::new(status_code, Some(error.to_string()), details)),
+        }
+    }
+}
+
+/// An error that has been returned by the remote peer.
+///
+/// The error provides its [`StatusCode`], so that it is picked up once it is part of a report.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RemoteError {
+    code: StatusCode,
+    message: Option<String>,
+    details: Vec<ErrorDetail>,
+}
+
+impl RemoteError {
+    #[must_use]
+    pub const fn code(&self) -> StatusCode {
+        self.code
+    }
+
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    #[must_use]
+    pub fn details(&self) -> &[ErrorDetail] {
+        &self.details
+    }
+}
+
+impl From<Status<ErrorDetail>> for RemoteError {
+    fn from(status: Status<ErrorDetail>) -> Self {
+        Self {
+            code: status.code(),
+            message: status.message().clone(),
+            details: status.contents().to_vec(),
+        }
+    }
+}
+
+impl Display for RemoteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => f.write_str(message),
+            None => write!(f, "remote peer returned an error: {}", self.code),
+        }
+    }
+}
+
+impl Error for RemoteError {
+    fn provide<'a>(&'a self, request: &mut core::error::Request<'a>) {
+        request.provide_value(self.code);
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+#[error("unable to decode the error status of the response")]
+pub struct StatusDecodeError;
+
+/// Decodes a [`Status`] created by [`StatusErrorEncoder`] into a [`RemoteError`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct StatusErrorDecoder;
+
+impl StatusErrorDecoder {
+    async fn decode(
+        bytes: impl Stream<Item = Bytes> + Send + Sync + 'static,
+    ) -> error_stack::Result<RemoteError, StatusDecodeError> {
+        let buffer = bytes
+            .fold(BytesMut::new(), |mut buffer, bytes| async move {
+                buffer.extend_from_slice(&bytes);
+                buffer
+            })
+            .await;
+
+        serde_json::from_slice::<Status<ErrorDetail>>(&buffer)
+            .map(RemoteError::from)
+            .change_context(StatusDecodeError)
+    }
+}
+
+impl ErrorDecoder for StatusErrorDecoder {
+    type Error = StatusDecodeError;
+    type Output = RemoteError;
+
+    async fn decode_report(
+        &self,
+        bytes: impl Stream<Item = Bytes> + Send + Sync + 'static,
+    ) -> error_stack::Result<Self::Output, Self::Error> {
+        Self::decode(bytes).await
+    }
+
+    async fn decode_error(
+        &self,
+        bytes: impl Stream<Item = Bytes> + Send + Sync + 'static,
+    ) -> error_stack::Result<Self::Output, Self::Error> {
+        Self::decode(bytes).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{
+        error::Error,
+        fmt::{self, Display, Formatter},
+    };
+
+    use bytes::Bytes;
+    use error_stack::Report;
+    use futures::stream;
+    use harpc_wire_protocol::response::kind::ErrorCode;
+    use hash_status::StatusCode;
+
+    use super::{ErrorDetail, RemoteError, StatusErrorDecoder, StatusErrorEncoder};
+    use crate::{
+        codec::{ErrorDecoder, ErrorEncoder},
+        session::error::{InstanceTransactionLimitReachedError, TransactionError},
+    };
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("unable to read entity")]
+    struct ReadError;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("entity does not exist")]
+    struct NotFoundError;
+
+    #[derive(Debug)]
+    struct ProvidingError;
+
+    impl Display for ProvidingError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str("permission denied")
+        }
+    }
+
+    impl Error for ProvidingError {
+        fn provide<'a>(&'a self, request: &mut core::error::Request<'a>) {
+            request.provide_value(StatusCode::PermissionDenied);
+        }
+    }
+
+    impl super::WireError for ProvidingError {
+        fn code(&self) -> ErrorCode {
+            ErrorCode::INTERNAL_SERVER_ERROR
+        }
+    }
+
+    async fn decode(error: TransactionError) -> RemoteError {
+        StatusErrorDecoder
+            .decode_report(stream::iter([error.bytes]))
+            .await
+            .expect("should be able to decode status")
+    }
+
+    #[tokio::test]
+    async fn report_round_trip() {
+        let report = Report::new(NotFoundError)
+            .attach(StatusCode::NotFound)
+            .attach_printable("entity: 0123")
+            .change_context(ReadError);
+
+        let error = StatusErrorEncoder.encode_report(report).await;
+        assert_eq!(error.code, ErrorCode::INTERNAL_SERVER_ERROR);
+
+        let remote = decode(error).await;
+        assert_eq!(remote.code(), StatusCode::NotFound);
+        assert_eq!(remote.message(), Some("unable to read entity"));
+        assert_eq!(
+            remote.details(),
+            [
+                ErrorDetail::Context("unable to read entity".to_owned()),
+                ErrorDetail::Attachment("entity: 0123".to_owned()),
+                ErrorDetail::Context("entity does not exist".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn report_error_code() {
+        let report = Report::new(ReadError).attach(ErrorCode::CONNECTION_SHUTDOWN);
+
+        let error = StatusErrorEncoder.encode_report(report).await;
+        assert_eq!(error.code, ErrorCode::CONNECTION_SHUTDOWN);
+
+        let remote = decode(error).await;
+        assert_eq!(remote.code(), StatusCode::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn error_round_trip() {
+        let error = StatusErrorEncoder
+            .encode_error(InstanceTransactionLimitReachedError)
+            .await;
+        assert_eq!(error.code, ErrorCode::INSTANCE_TRANSACTION_LIMIT_REACHED);
+
+        let remote = decode(error).await;
+        assert_eq!(remote.code(), StatusCode::ResourceExhausted);
+        assert_eq!(
+            remote.details(),
+            [ErrorDetail::Context(
+                InstanceTransactionLimitReachedError.to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn error_provides_status_code() {
+        let error = StatusErrorEncoder.encode_error(ProvidingError).await;
+
+        let remote = decode(error).await;
+        assert_eq!(remote.code(), StatusCode::PermissionDenied);
+        assert_eq!(remote.message(), Some("permission denied"));
+    }
+
+    #[tokio::test]
+    async fn remote_error_provides_status_code() {
+        let error = StatusErrorEncoder.encode_error(ProvidingError).await;
+        let report = Report::new(decode(error).await);
+
+        assert_eq!(
+            report.request_value::<StatusCode>().next(),
+            Some(StatusCode::PermissionDenied)
+        );
+    }
+
+    #[tokio::test]
+    async fn decode_chunked() {
+        let TransactionError { bytes, .. } = StatusErrorEncoder
+            .encode_error(InstanceTransactionLimitReachedError)
+            .await;
+
+        let (head, tail) = bytes.split_at(16);
+        let remote = StatusErrorDecoder
+            .decode_error(stream::iter([
+                Bytes::copy_from_slice(head),
+                Bytes::copy_from_slice(tail),
+            ]))
+            .await
+            .expect("should be able to decode status");
+
+        assert_eq!(remote.code(), StatusCode::ResourceExhausted);
+    }
+
+    #[tokio::test]
+    async fn decode_malformed() {
+        let _report = StatusErrorDecoder
+            .decode_error(stream::iter([Bytes::from_static(b"plain|error")]))
+            .await
+            .expect_err("should not be able to decode plain text");
+    }
+}
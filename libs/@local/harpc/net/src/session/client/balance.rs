@@ -0,0 +1,310 @@
+use alloc::sync::Arc;
+use core::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use error_stack::{Report, ResultExt};
+use futures::Stream;
+use harpc_wire_protocol::request::{procedure::ProcedureDescriptor, service::ServiceDescriptor};
+use libp2p::{Multiaddr, PeerId};
+use tokio::{select, sync::watch};
+use tokio_util::{
+    sync::{CancellationToken, DropGuard},
+    task::TaskTracker,
+};
+
+use super::{
+    config::SessionConfig,
+    connection::{Connection, ConnectionParts, ResponseStream},
+    dialer::Dialer,
+};
+use crate::{
+    session::{error::NoHealthyPeerError, metrics::SessionMetrics},
+    transport::connection::OutgoingConnection,
+};
+
+/// Strategy used to select the peer a transaction is sent to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum BalanceStrategy {
+    /// Send transactions to the healthy peers in turn.
+    #[default]
+    RoundRobin,
+    /// Send transactions to the healthy peer with the fewest outstanding transactions.
+    LeastOutstandingRequests,
+}
+
+/// Configuration for spreading transactions across multiple peers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BalanceConfig {
+    /// Strategy used to select the peer a transaction is sent to.
+    ///
+    /// **Default:** [`BalanceStrategy::RoundRobin`]
+    pub strategy: BalanceStrategy,
+
+    /// Interval in which peers without a healthy connection are dialed again.
+    ///
+    /// **Default:** `1s`
+    pub health_check_interval: Duration,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        Self {
+            strategy: BalanceStrategy::default(),
+            health_check_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Decrements the number of outstanding transactions of a peer once dropped.
+#[derive(Debug)]
+pub(crate) struct OutstandingGuard(Arc<AtomicUsize>);
+
+impl OutstandingGuard {
+    fn new(outstanding: &Arc<AtomicUsize>) -> Self {
+        outstanding.fetch_add(1, Ordering::AcqRel);
+
+        Self(Arc::clone(outstanding))
+    }
+}
+
+impl Drop for OutstandingGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+struct Peer {
+    dialer: Dialer,
+
+    connection: watch::Sender<Option<(PeerId, Arc<Connection>)>>,
+    outstanding: Arc<AtomicUsize>,
+}
+
+impl Peer {
+    fn new(dialer: Dialer) -> Self {
+        let (connection, _) = watch::channel(None);
+
+        Self {
+            dialer,
+            connection,
+            outstanding: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn connect(
+        &self,
+        config: SessionConfig,
+        tasks: &TaskTracker,
+        metrics: &SessionMetrics,
+        OutgoingConnection {
+            peer_id,
+            sink,
+            stream,
+        }: OutgoingConnection,
+    ) {
+        let connection = Connection::spawn(
+            ConnectionParts {
+                config,
+                tasks,
+                cancel: self.dialer.cancellation_token().child_token(),
+                metrics: metrics.clone(),
+            },
+            sink,
+            stream,
+        );
+
+        self.connection
+            .send_replace(Some((peer_id, Arc::new(connection))));
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connection
+            .borrow()
+            .as_ref()
+            .is_some_and(|(_, connection)| connection.is_healthy())
+    }
+
+    /// Returns the connection to the peer if it is open and the peer responds to pings.
+    fn healthy_connection(&self) -> Option<Arc<Connection>> {
+        let transport = self.dialer.transport()?;
+
+        self.connection
+            .borrow()
+            .as_ref()
+            .filter(|(peer_id, connection)| {
+                connection.is_healthy() && transport.is_responsive(*peer_id)
+            })
+            .map(|(_, connection)| Arc::clone(connection))
+    }
+}
+
+/// Re-establishes connections to peers whose connection has been closed.
+struct HealthCheckTask {
+    config: SessionConfig,
+    tasks: TaskTracker,
+    metrics: SessionMetrics,
+
+    peers: Arc<[Peer]>,
+    interval: Duration,
+}
+
+impl HealthCheckTask {
+    #[expect(
+        clippy::integer_division_remainder_used,
+        reason = "required for select! macro"
+    )]
+    async fn run(self, cancel: CancellationToken) {
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            select! {
+                _ = interval.tick() => {}
+                () = cancel.cancelled() => break,
+            }
+
+            for peer in &*self.peers {
+                if peer.is_connected() {
+                    continue;
+                }
+
+                match peer.dialer.dial().await {
+                    Ok(connection) => {
+                        tracing::info!(address = %peer.dialer.address(), "reconnected to peer");
+
+                        peer.connect(self.config, &self.tasks, &self.metrics, connection);
+                    }
+                    Err(report) => {
+                        tracing::debug!(address = %peer.dialer.address(), ?report, "peer is unreachable");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A client which spreads transactions across connections to multiple peers.
+///
+/// Peers are excluded while their connection is closed or they don't respond to the pings of the
+/// transport layer. Closed connections are re-established in the background, once a peer is
+/// healthy again it will receive transactions again.
+pub struct BalancedConnection {
+    strategy: BalanceStrategy,
+
+    peers: Arc<[Peer]>,
+    next: AtomicUsize,
+
+    _guard: DropGuard,
+}
+
+impl BalancedConnection {
+    pub(crate) async fn connect(
+        config: SessionConfig,
+        balance: BalanceConfig,
+        tasks: &TaskTracker,
+        metrics: &SessionMetrics,
+        dialers: Vec<Dialer>,
+        cancel: CancellationToken,
+    ) -> Self {
+        let peers: Arc<[Peer]> = dialers.into_iter().map(Peer::new).collect();
+
+        for peer in &*peers {
+            match peer.dialer.dial().await {
+                Ok(connection) => peer.connect(config, tasks, metrics, connection),
+                Err(report) => {
+                    tracing::warn!(address = %peer.dialer.address(), ?report, "unable to connect to peer");
+                }
+            }
+        }
+
+        tasks.spawn(
+            HealthCheckTask {
+                config,
+                tasks: tasks.clone(),
+                metrics: metrics.clone(),
+                peers: Arc::clone(&peers),
+                interval: balance.health_check_interval,
+            }
+            .run(cancel.clone()),
+        );
+
+        Self {
+            strategy: balance.strategy,
+
+            peers,
+            next: AtomicUsize::new(0),
+
+            _guard: cancel.drop_guard(),
+        }
+    }
+
+    /// The addresses of all peers, regardless of their health.
+    pub fn addresses(&self) -> impl Iterator<Item = &Multiaddr> {
+        self.peers.iter().map(|peer| peer.dialer.address())
+    }
+
+    /// Number of peers which currently receive transactions.
+    pub fn healthy_peers(&self) -> usize {
+        self.peers
+            .iter()
+            .filter(|peer| peer.healthy_connection().is_some())
+            .count()
+    }
+
+    /// Selects the peer for the next transaction.
+    #[expect(
+        clippy::integer_division_remainder_used,
+        reason = "only evaluated if there is at least one peer"
+    )]
+    fn select(&self) -> Option<(&Peer, Arc<Connection>)> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        // Starting at a different peer every time spreads transactions across peers with the
+        // same number of outstanding transactions.
+        let mut candidates = (0..self.peers.len())
+            .filter_map(|offset| {
+                self.peers
+                    .get(start.wrapping_add(offset) % self.peers.len())
+            })
+            .filter_map(|peer| Some((peer, peer.healthy_connection()?)));
+
+        match self.strategy {
+            BalanceStrategy::RoundRobin => candidates.next(),
+            BalanceStrategy::LeastOutstandingRequests => {
+                candidates.min_by_key(|(peer, _)| peer.outstanding.load(Ordering::Acquire))
+            }
+        }
+    }
+
+    /// Call a service procedure on one of the healthy peers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if none of the peers is healthy, or the connection to the selected peer
+    /// has been closed in the meantime.
+    pub async fn call(
+        &self,
+        service: ServiceDescriptor,
+        procedure: ProcedureDescriptor,
+        payload: impl Stream<Item = Bytes> + Send + 'static,
+    ) -> error_stack::Result<ResponseStream, NoHealthyPeerError> {
+        let error = NoHealthyPeerError {
+            peers: self.peers.len(),
+        };
+
+        let Some((peer, connection)) = self.select() else {
+            return Err(Report::new(error));
+        };
+
+        let outstanding = OutstandingGuard::new(&peer.outstanding);
+
+        connection
+            .call(service, procedure, payload)
+            .await
+            .map(|stream| stream.with_outstanding(outstanding))
+            .change_context(error)
+    }
+}
@@ -57,6 +57,14 @@ pub struct SessionConfig {
     ///
     /// **Default:** `false`
     pub no_delay: bool,
+
+    /// Re-establish the connection once the underlying connection has been closed.
+    /// If enabled, the next call on an unhealthy connection dials the peer again, using the
+    /// configured backoff between attempts. Transactions that were in-flight when the connection
+    /// was closed are not retried.
+    ///
+    /// **Default:** `None` (disabled)
+    pub reconnect: Option<ReconnectConfig>,
 }
 
 impl Default for SessionConfig {
@@ -71,6 +79,48 @@ impl Default for SessionConfig {
             per_transaction_response_byte_stream_buffer_size: non_zero!(32),
 
             no_delay: false,
+
+            reconnect: None,
+        }
+    }
+}
+
+/// Configuration for re-establishing a client connection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReconnectConfig {
+    /// Maximum number of attempts to re-establish the connection before the call fails.
+    ///
+    /// **Default:** `5`
+    pub max_attempts: NonZero<usize>,
+
+    /// Delay after the first failed attempt, which is doubled for every subsequent failed attempt.
+    ///
+    /// **Default:** `100ms`
+    pub initial_backoff: Duration,
+
+    /// Upper bound of the delay between two attempts.
+    ///
+    /// **Default:** `10s`
+    pub max_backoff: Duration,
+}
+
+impl ReconnectConfig {
+    /// Returns the delay after the `attempt`-th failed attempt.
+    pub(crate) fn backoff(&self, attempt: usize) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+
+        self.initial_backoff
+            .saturating_mul(2_u32.saturating_pow(exponent))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: non_zero!(5),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
         }
     }
 }
@@ -6,7 +6,7 @@ mod test;
 use alloc::sync::Arc;
 
 use bytes::Bytes;
-use error_stack::Report;
+use error_stack::{Report, ResultExt};
 use futures::{prelude::future::FutureExt, Sink, Stream, StreamExt};
 use harpc_wire_protocol::{
     request::{procedure::ProcedureDescriptor, service::ServiceDescriptor, Request},
@@ -16,7 +16,7 @@ use scc::ebr::Guard;
 use tachyonix::SendTimeoutError;
 use tokio::{
     io, pin, select,
-    sync::{mpsc, Notify},
+    sync::{mpsc, watch, Mutex, Notify},
     task::AbortHandle,
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -27,8 +27,19 @@ use tokio_util::{
 
 use self::collection::{TransactionCollection, TransactionState, TransactionStorage};
 pub use self::stream::ResponseStream;
-use super::{config::SessionConfig, transaction::TransactionTask};
-use crate::session::{error::ConnectionPartiallyClosedError, gc::ConnectionGarbageCollectorTask};
+use super::{
+    config::{ReconnectConfig, SessionConfig},
+    dialer::Dialer,
+    transaction::TransactionTask,
+};
+use crate::{
+    session::{
+        error::{ConnectionPartiallyClosedError, SessionError},
+        gc::ConnectionGarbageCollectorTask,
+        metrics::SessionMetrics,
+    },
+    transport::connection::OutgoingConnection,
+};
 
 /// Delegate requests to the respective transaction
 ///
@@ -244,13 +255,12 @@ pub(crate) struct ConnectionParts<'a> {
     pub config: SessionConfig,
     pub tasks: &'a TaskTracker,
     pub cancel: CancellationToken,
+    pub metrics: SessionMetrics,
 }
 
-pub struct Connection {
-    config: SessionConfig,
-
+/// The tasks driving a single underlying connection.
+struct ConnectionHandle {
     tx: mpsc::Sender<Request>,
-    tasks: TaskTracker,
 
     transactions: TransactionCollection,
 
@@ -260,13 +270,13 @@ pub struct Connection {
     _guard: DropGuard,
 }
 
-// TODO: BufferedResponse that will only return the last (valid) response
-impl Connection {
-    pub(crate) fn spawn<S, T>(
+impl ConnectionHandle {
+    fn spawn<S, T>(
         ConnectionParts {
             config,
             tasks,
             cancel,
+            metrics,
         }: ConnectionParts,
         sink: S,
         stream: T,
@@ -303,15 +313,13 @@ impl Connection {
             ConnectionGarbageCollectorTask {
                 every: config.per_connection_transaction_garbage_collect_interval,
                 index: Arc::clone(transactions.storage()),
+                metrics,
             }
             .run(guard_gc),
         );
 
         Self {
-            config,
-
             tx,
-            tasks: tasks.clone(),
 
             transactions,
 
@@ -322,11 +330,153 @@ impl Connection {
         }
     }
 
+    fn is_healthy(&self) -> bool {
+        !self.request_delegate_handle.is_finished() && !self.response_delegate_handle.is_finished()
+    }
+
+    fn closed_error(&self) -> ConnectionPartiallyClosedError {
+        ConnectionPartiallyClosedError {
+            read: self.response_delegate_handle.is_finished(),
+            write: self.request_delegate_handle.is_finished(),
+        }
+    }
+}
+
+struct Reconnect {
+    config: ReconnectConfig,
+    dialer: Dialer,
+
+    // Ensures that only a single call re-establishes the connection at a time.
+    lock: Mutex<()>,
+}
+
+pub struct Connection {
+    config: SessionConfig,
+
+    tasks: TaskTracker,
+    metrics: SessionMetrics,
+
+    // `watch` is used as a cell which can be swapped without holding a lock across await points.
+    handle: watch::Sender<Arc<ConnectionHandle>>,
+    reconnect: Option<Reconnect>,
+}
+
+// TODO: BufferedResponse that will only return the last (valid) response
+impl Connection {
+    pub(crate) fn spawn<S, T>(parts: ConnectionParts, sink: S, stream: T) -> Self
+    where
+        S: Sink<Request, Error: Send> + Send + 'static,
+        T: Stream<Item = Result<Response, Report<io::Error>>> + Send + 'static,
+    {
+        let config = parts.config;
+        let tasks = parts.tasks.clone();
+        let metrics = parts.metrics.clone();
+
+        let (handle, _) = watch::channel(Arc::new(ConnectionHandle::spawn(parts, sink, stream)));
+
+        Self {
+            config,
+
+            tasks,
+            metrics,
+
+            handle,
+            reconnect: None,
+        }
+    }
+
+    pub(crate) fn with_reconnect(self, config: ReconnectConfig, dialer: Dialer) -> Self {
+        Self {
+            reconnect: Some(Reconnect {
+                config,
+                dialer,
+                lock: Mutex::new(()),
+            }),
+            ..self
+        }
+    }
+
     /// Check if the connection is healthy
     ///
     /// This returns false if either the underlying read or write stream have been closed.
+    ///
+    /// If reconnecting is enabled, an unhealthy connection will be re-established on the next
+    /// call.
     pub fn is_healthy(&self) -> bool {
-        !self.request_delegate_handle.is_finished() && !self.response_delegate_handle.is_finished()
+        self.handle.borrow().is_healthy()
+    }
+
+    async fn redial(
+        &self,
+        reconnect: &Reconnect,
+    ) -> error_stack::Result<ConnectionHandle, SessionError> {
+        let mut attempt = 0;
+
+        loop {
+            match reconnect.dialer.dial().await {
+                Ok(OutgoingConnection { sink, stream, .. }) => {
+                    return Ok(ConnectionHandle::spawn(
+                        ConnectionParts {
+                            config: self.config,
+                            tasks: &self.tasks,
+                            cancel: reconnect.dialer.cancellation_token().child_token(),
+                            metrics: self.metrics.clone(),
+                        },
+                        sink,
+                        stream,
+                    ));
+                }
+                Err(report) => {
+                    attempt += 1;
+
+                    if attempt >= reconnect.config.max_attempts.get()
+                        || reconnect.dialer.cancellation_token().is_cancelled()
+                    {
+                        return Err(report);
+                    }
+
+                    let backoff = reconnect.config.backoff(attempt);
+                    tracing::warn!(?report, attempt, ?backoff, "failed to reconnect, retrying");
+
+                    tokio::time::sleep(backoff).await;
+                }
+            }
+        }
+    }
+
+    /// Returns a handle to a healthy underlying connection, reconnecting if enabled.
+    async fn healthy_handle(
+        &self,
+    ) -> error_stack::Result<Arc<ConnectionHandle>, ConnectionPartiallyClosedError> {
+        let handle = Arc::clone(&self.handle.borrow());
+        if handle.is_healthy() {
+            return Ok(handle);
+        }
+
+        let Some(reconnect) = &self.reconnect else {
+            return Err(Report::new(handle.closed_error()));
+        };
+
+        let _guard = reconnect.lock.lock().await;
+
+        // Another call might have re-established the connection while we were waiting
+        let handle = Arc::clone(&self.handle.borrow());
+        if handle.is_healthy() {
+            return Ok(handle);
+        }
+
+        tracing::info!("connection has been closed, reconnecting");
+
+        let replacement = Arc::new(
+            self.redial(reconnect)
+                .await
+                .change_context(handle.closed_error())?,
+        );
+
+        // The previous handle is dropped once all remaining transactions have finished
+        self.handle.send_replace(Arc::clone(&replacement));
+
+        Ok(replacement)
     }
 
     /// Call a service procedure
@@ -334,7 +484,8 @@ impl Connection {
     /// # Errors
     ///
     /// This will return an error if the connection is unhealthy, meaning that the underlying
-    /// connection is currently in its process of being closed.
+    /// connection is currently in its process of being closed, and it could not be
+    /// re-established.
     pub async fn call(
         &self,
         service: ServiceDescriptor,
@@ -345,14 +496,9 @@ impl Connection {
         // underlying connection is closed) and the `ResponseStream` will return `None` it is a good
         // indicator to the user that the connection is unhealthy, and as to why, as these tasks
         // only ever stop running when the underlying connection is closed.
-        if !self.is_healthy() {
-            return Err(Report::new(ConnectionPartiallyClosedError {
-                read: self.response_delegate_handle.is_finished(),
-                write: self.request_delegate_handle.is_finished(),
-            }));
-        }
+        let handle = self.healthy_handle().await?;
 
-        let (permit, response_rx) = self.transactions.acquire().await;
+        let (permit, response_rx) = handle.transactions.acquire().await;
 
         let (stream_tx, stream_rx) = mpsc::channel(1);
 
@@ -369,7 +515,7 @@ impl Connection {
             response_rx,
             response_tx: stream_tx,
             request_rx: payload,
-            request_tx: self.tx.clone(),
+            request_tx: handle.tx.clone(),
         };
 
         task.spawn(&self.tasks);
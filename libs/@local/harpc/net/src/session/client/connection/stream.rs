@@ -6,13 +6,16 @@ use core::{
 use futures::{prelude::stream::FusedStream, Stream};
 use tokio::sync::mpsc;
 
-use crate::session::client::{ErrorStream, ValueStream};
+use crate::session::client::{balance::OutstandingGuard, ErrorStream, ValueStream};
 
 #[derive(Debug)]
 pub struct ResponseStream {
     inner: mpsc::Receiver<Result<ValueStream, ErrorStream>>,
 
     terminated: bool,
+
+    // Marks the transaction as outstanding for load balancing until the stream is terminated
+    outstanding: Option<OutstandingGuard>,
 }
 
 impl ResponseStream {
@@ -20,6 +23,14 @@ impl ResponseStream {
         Self {
             inner,
             terminated: false,
+            outstanding: None,
+        }
+    }
+
+    pub(crate) fn with_outstanding(self, outstanding: OutstandingGuard) -> Self {
+        Self {
+            outstanding: Some(outstanding),
+            ..self
         }
     }
 }
@@ -36,6 +47,7 @@ impl Stream for ResponseStream {
 
         if value.is_none() {
             self.terminated = true;
+            self.outstanding = None;
         }
 
         Poll::Ready(value)
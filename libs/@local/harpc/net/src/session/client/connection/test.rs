@@ -53,6 +53,7 @@ use crate::{
             TransactionStream, ValueStream,
         },
         gc::ConnectionGarbageCollectorTask,
+        metrics::SessionMetrics,
         test::Descriptor,
     },
 };
@@ -474,6 +475,7 @@ async fn garbage_collect_ignore_active() {
     let task = ConnectionGarbageCollectorTask {
         every: Duration::from_millis(10),
         index: Arc::clone(collection.storage()),
+        metrics: SessionMetrics::default(),
     };
 
     let (_permit, _rx) = collection.acquire().await;
@@ -494,6 +496,7 @@ async fn garbage_collect_remove_inactive() {
     let task = ConnectionGarbageCollectorTask {
         every: Duration::from_millis(10),
         index: Arc::clone(collection.storage()),
+        metrics: SessionMetrics::default(),
     };
 
     let (permit, _rx) = collection.acquire().await;
@@ -517,6 +520,7 @@ async fn garbage_collect_cancel() {
     let task = ConnectionGarbageCollectorTask {
         every: Duration::from_millis(10),
         index: Arc::clone(collection.storage()),
+        metrics: SessionMetrics::default(),
     };
 
     let cancel = CancellationToken::new();
@@ -549,6 +553,7 @@ fn setup_connection_mapped<T>(
         config,
         tasks: &tasks,
         cancel: CancellationToken::new(),
+        metrics: SessionMetrics::default(),
     };
 
     let parts_value = with_parts(&parts);
@@ -787,7 +792,7 @@ async fn call_finished_removes_stale_entry() {
         .await
         .expect("should not be closed");
 
-    assert_eq!(connection.transactions.storage().len(), 1);
+    assert_eq!(connection.handle.borrow().transactions.storage().len(), 1);
 
     let mut value = stream
         .next()
@@ -796,7 +801,7 @@ async fn call_finished_removes_stale_entry() {
         .expect("should not error");
 
     assert_stream(&mut value, descriptor, &payload).await;
-    assert_eq!(connection.transactions.storage().len(), 0);
+    assert_eq!(connection.handle.borrow().transactions.storage().len(), 0);
 }
 
 #[tokio::test]
@@ -923,7 +928,7 @@ async fn connection_idle_does_not_terminate() {
         tokio::time::sleep(Duration::from_millis(100)).await;
 
         assert!(connection.is_healthy());
-        assert!(connection.transactions.storage().is_empty());
+        assert!(connection.handle.borrow().transactions.storage().is_empty());
     }
 }
 
@@ -0,0 +1,65 @@
+use alloc::sync::{Arc, Weak};
+
+use error_stack::{Report, Result, ResultExt};
+use libp2p::Multiaddr;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    session::error::SessionError,
+    transport::{connection::OutgoingConnection, TransportLayer},
+};
+
+/// Dials a peer at a known address.
+///
+/// Retained by connections which reconnect once the underlying connection has been closed. Only a
+/// weak reference to the transport layer is kept, so that an idle connection doesn't keep the
+/// transport layer alive once the session layer has been dropped.
+#[derive(Debug, Clone)]
+pub(crate) struct Dialer {
+    transport: Weak<TransportLayer>,
+    address: Multiaddr,
+
+    cancel: CancellationToken,
+}
+
+impl Dialer {
+    pub(crate) fn new(
+        transport: &Arc<TransportLayer>,
+        address: Multiaddr,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            transport: Arc::downgrade(transport),
+            address,
+            cancel,
+        }
+    }
+
+    pub(crate) const fn address(&self) -> &Multiaddr {
+        &self.address
+    }
+
+    /// Returns the transport layer, unless it has been shut down.
+    pub(crate) fn transport(&self) -> Option<Arc<TransportLayer>> {
+        self.transport.upgrade()
+    }
+
+    /// The cancellation token connections established by this dialer are derived from.
+    pub(crate) const fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    pub(crate) async fn dial(&self) -> Result<OutgoingConnection, SessionError> {
+        let Some(transport) = self.transport() else {
+            return Err(Report::new(SessionError)
+                .attach_printable("the transport layer has been shut down"));
+        };
+
+        let peer = transport
+            .lookup_peer(self.address.clone())
+            .await
+            .change_context(SessionError)?;
+
+        transport.dial(peer).await.change_context(SessionError)
+    }
+}
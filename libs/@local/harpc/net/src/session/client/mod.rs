@@ -1,17 +1,22 @@
+mod balance;
 mod config;
 mod connection;
+mod dialer;
 mod transaction;
 
-use error_stack::{Result, ResultExt};
+use alloc::sync::Arc;
+
+use error_stack::{Report, Result};
 use libp2p::Multiaddr;
 use tokio_util::sync::CancellationToken;
 
-use self::connection::ConnectionParts;
 pub use self::{
-    config::SessionConfig,
+    balance::{BalanceConfig, BalanceStrategy, BalancedConnection},
+    config::{ReconnectConfig, SessionConfig},
     connection::{Connection, ResponseStream},
     transaction::stream::{ErrorStream, TransactionStream, ValueStream},
 };
+use self::{connection::ConnectionParts, dialer::Dialer};
 use super::error::SessionError;
 use crate::transport::{connection::OutgoingConnection, TransportLayer};
 
@@ -20,7 +25,7 @@ pub struct SessionLayer {
 
     cancel: CancellationToken,
 
-    transport: TransportLayer,
+    transport: Arc<TransportLayer>,
 }
 
 impl SessionLayer {
@@ -30,43 +35,79 @@ impl SessionLayer {
             config,
 
             cancel: transport.cancellation_token(),
-            transport,
+            transport: Arc::new(transport),
         }
     }
 
     #[must_use]
-    pub const fn transport(&self) -> &TransportLayer {
+    pub fn transport(&self) -> &TransportLayer {
         &self.transport
     }
 
     /// Dial a peer.
     ///
+    /// If [`SessionConfig::reconnect`] is set, the returned connection will dial the peer again
+    /// once the underlying connection has been closed.
+    ///
     /// # Errors
     ///
     /// Returns an error if the dial fails.
     pub async fn dial(&self, address: Multiaddr) -> Result<Connection, SessionError> {
-        let peer = self
-            .transport
-            .lookup_peer(address)
-            .await
-            .change_context(SessionError)?;
+        let dialer = Dialer::new(&self.transport, address, self.cancel.clone());
 
-        let cancel = self.cancel.child_token();
+        let OutgoingConnection { sink, stream, .. } = dialer.dial().await?;
 
-        let OutgoingConnection { sink, stream, .. } = self
-            .transport
-            .dial(peer)
-            .await
-            .change_context(SessionError)?;
-
-        Ok(Connection::spawn(
+        let connection = Connection::spawn(
             ConnectionParts {
                 config: self.config,
                 tasks: self.transport.tasks(),
-                cancel,
+                cancel: self.cancel.child_token(),
+                metrics: self.transport.session_metrics().clone(),
             },
             sink,
             stream,
-        ))
+        );
+
+        Ok(match self.config.reconnect {
+            Some(config) => connection.with_reconnect(config, dialer),
+            None => connection,
+        })
+    }
+
+    /// Dial multiple peers and spread transactions across them.
+    ///
+    /// Peers which cannot be reached are dialed again in the background, see
+    /// [`BalancedConnection`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if none of the peers could be dialed.
+    pub async fn dial_balanced(
+        &self,
+        addresses: impl IntoIterator<Item = Multiaddr> + Send,
+        config: BalanceConfig,
+    ) -> Result<BalancedConnection, SessionError> {
+        let dialers = addresses
+            .into_iter()
+            .map(|address| Dialer::new(&self.transport, address, self.cancel.clone()))
+            .collect();
+
+        let connection = BalancedConnection::connect(
+            self.config,
+            config,
+            self.transport.tasks(),
+            self.transport.session_metrics(),
+            dialers,
+            self.cancel.child_token(),
+        )
+        .await;
+
+        if connection.healthy_peers() == 0 {
+            return Err(
+                Report::new(SessionError).attach_printable("unable to connect to any of the peers")
+            );
+        }
+
+        Ok(connection)
     }
 }
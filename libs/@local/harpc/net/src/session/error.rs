@@ -79,3 +79,9 @@ pub struct ConnectionPartiallyClosedError {
     pub read: bool,
     pub write: bool,
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+#[error("none of the {peers} peers is healthy, the transaction has been dropped")]
+pub struct NoHealthyPeerError {
+    pub peers: usize,
+}
@@ -6,6 +6,8 @@ use scc::HashIndex;
 use tokio::select;
 use tokio_util::sync::CancellationToken;
 
+use super::metrics::SessionMetrics;
+
 pub(crate) trait IsCancelled {
     fn is_cancelled(&self) -> bool;
 }
@@ -17,6 +19,7 @@ pub(crate) trait IsCancelled {
 pub(crate) struct ConnectionGarbageCollectorTask<C> {
     pub every: Duration,
     pub index: Arc<HashIndex<RequestId, C>>,
+    pub metrics: SessionMetrics,
 }
 
 impl<C> ConnectionGarbageCollectorTask<C>
@@ -50,6 +53,8 @@ where
                 })
                 .await;
 
+            self.metrics.sweep(removed);
+
             if removed > 0 {
                 // this should never really happen, but it's good to know if it does
                 tracing::warn!(removed, "garbage collector removed stale transactions");
@@ -0,0 +1,160 @@
+use prometheus_client::{
+    encoding::{EncodeLabelSet, EncodeLabelValue},
+    metrics::{counter::Counter, family::Family, gauge::Gauge},
+    registry::Registry,
+};
+
+fn to_u64(value: usize) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EncodeLabelValue)]
+pub(crate) enum Deadline {
+    /// [`SessionConfig::request_delivery_deadline`](crate::session::server::SessionConfig)
+    RequestDelivery,
+    /// [`SessionConfig::transaction_delivery_deadline`](crate::session::server::SessionConfig)
+    TransactionDelivery,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(crate) struct DeadlineLabels {
+    deadline: Deadline,
+}
+
+/// Metrics of the session layer.
+///
+/// The metrics are registered in the registry of the transport layer, which is shared by all
+/// session layers using the same transport layer. Metrics created through [`Default`] are not
+/// registered and therefore never exported.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SessionMetrics {
+    connections: Gauge,
+    connections_accepted: Counter,
+    connections_rejected: Counter,
+
+    transactions: Gauge,
+    transactions_started: Counter,
+
+    request_bytes: Counter,
+    response_bytes: Counter,
+
+    deadlines_exceeded: Family<DeadlineLabels, Counter>,
+
+    garbage_collector_sweeps: Counter,
+    garbage_collector_removed: Counter,
+}
+
+impl SessionMetrics {
+    pub(crate) fn register(registry: &mut Registry) -> Self {
+        let registry = registry.sub_registry_with_prefix("harpc_session");
+        let this = Self::default();
+
+        registry.register(
+            "connections",
+            "Number of currently open connections",
+            this.connections.clone(),
+        );
+        registry.register(
+            "connections_accepted",
+            "Number of accepted connections",
+            this.connections_accepted.clone(),
+        );
+        registry.register(
+            "connections_rejected",
+            "Number of connections rejected because the concurrent connection limit has been \
+             reached",
+            this.connections_rejected.clone(),
+        );
+        registry.register(
+            "transactions",
+            "Number of transactions currently in flight",
+            this.transactions.clone(),
+        );
+        registry.register(
+            "transactions_started",
+            "Number of transactions started",
+            this.transactions_started.clone(),
+        );
+        registry.register(
+            "request_bytes",
+            "Number of payload bytes received in requests",
+            this.request_bytes.clone(),
+        );
+        registry.register(
+            "response_bytes",
+            "Number of payload bytes sent in responses",
+            this.response_bytes.clone(),
+        );
+        registry.register(
+            "deadlines_exceeded",
+            "Number of requests and transactions dropped, because they couldn't be delivered in \
+             time",
+            this.deadlines_exceeded.clone(),
+        );
+        registry.register(
+            "garbage_collector_sweeps",
+            "Number of transaction garbage collector runs",
+            this.garbage_collector_sweeps.clone(),
+        );
+        registry.register(
+            "garbage_collector_removed",
+            "Number of stale transactions removed by the garbage collector",
+            this.garbage_collector_removed.clone(),
+        );
+
+        this
+    }
+
+    pub(crate) fn accept_connection(&self) -> ActiveGuard {
+        self.connections_accepted.inc();
+
+        ActiveGuard::new(&self.connections)
+    }
+
+    pub(crate) fn reject_connection(&self) {
+        self.connections_rejected.inc();
+    }
+
+    pub(crate) fn start_transaction(&self) -> ActiveGuard {
+        self.transactions_started.inc();
+
+        ActiveGuard::new(&self.transactions)
+    }
+
+    pub(crate) fn receive_request(&self, bytes: usize) {
+        self.request_bytes.inc_by(to_u64(bytes));
+    }
+
+    pub(crate) fn send_response(&self, bytes: usize) {
+        self.response_bytes.inc_by(to_u64(bytes));
+    }
+
+    pub(crate) fn exceed_deadline(&self, deadline: Deadline) {
+        self.deadlines_exceeded
+            .get_or_create(&DeadlineLabels { deadline })
+            .inc();
+    }
+
+    pub(crate) fn sweep(&self, removed: usize) {
+        self.garbage_collector_sweeps.inc();
+        self.garbage_collector_removed.inc_by(to_u64(removed));
+    }
+}
+
+/// Keeps a gauge incremented for as long as it is alive.
+#[derive(Debug)]
+pub(crate) struct ActiveGuard(Gauge);
+
+impl ActiveGuard {
+    fn new(gauge: &Gauge) -> Self {
+        gauge.inc();
+
+        Self(gauge.clone())
+    }
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
@@ -8,6 +8,7 @@ pub mod error;
 pub mod server;
 
 mod gc;
+pub(crate) mod metrics;
 mod writer;
 
 #[cfg(test)]
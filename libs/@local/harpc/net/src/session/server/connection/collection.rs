@@ -10,6 +10,7 @@ use tokio_util::sync::CancellationToken;
 use crate::session::{
     error::{ConnectionTransactionLimitReachedError, TransactionLaggingError},
     gc::IsCancelled,
+    metrics::{ActiveGuard, Deadline, SessionMetrics},
     server::{transaction::ServerTransactionPermit, SessionConfig},
 };
 
@@ -58,6 +59,7 @@ pub(crate) type TransactionStorage = Arc<HashIndex<RequestId, TransactionState>>
 
 pub(crate) struct TransactionCollection {
     config: SessionConfig,
+    metrics: SessionMetrics,
 
     generation: AtomicU64,
     notify: Arc<Notify>,
@@ -68,7 +70,11 @@ pub(crate) struct TransactionCollection {
 }
 
 impl TransactionCollection {
-    pub(crate) fn new(config: SessionConfig, cancel: CancellationToken) -> Self {
+    pub(crate) fn new(
+        config: SessionConfig,
+        metrics: SessionMetrics,
+        cancel: CancellationToken,
+    ) -> Self {
         let storage = Arc::new(HashIndex::new());
         let limit = ConcurrencyLimit::new(config.per_connection_concurrent_transaction_limit);
         let notify = Arc::new(Notify::new());
@@ -77,6 +83,7 @@ impl TransactionCollection {
             generation: AtomicU64::new(0),
             notify,
             config,
+            metrics,
             cancel,
             storage,
             limit,
@@ -213,6 +220,7 @@ impl TransactionCollection {
             }
             Err(SendTimeoutError::Timeout(_)) => {
                 tracing::warn!("transaction buffer is too slow, dropping transaction");
+                self.metrics.exceed_deadline(Deadline::RequestDelivery);
 
                 // we've missed the deadline, therefore we can no longer send data to the
                 // transaction without risking the integrity of the transaction.
@@ -273,6 +281,7 @@ pub(crate) struct TransactionPermit {
     notify: Arc<Notify>,
 
     _permit: ConcurrencyPermit,
+    _active: ActiveGuard,
 }
 
 impl TransactionPermit {
@@ -297,6 +306,7 @@ impl TransactionPermit {
             generation,
             notify,
             _permit: permit,
+            _active: collection.metrics.start_transaction(),
             cancel,
         })
     }
@@ -32,6 +32,7 @@ use crate::{
             ConnectionGracefulShutdownError, InstanceTransactionLimitReachedError, TransactionError,
         },
         gc::ConnectionGarbageCollectorTask,
+        metrics::{Deadline, SessionMetrics},
         writer::{ResponseContext, ResponseWriter, WriterOptions},
     },
 };
@@ -40,6 +41,7 @@ struct ConnectionDelegateTask<T> {
     rx: mpsc::Receiver<Response>,
 
     sink: T,
+    metrics: SessionMetrics,
 }
 
 impl<T> ConnectionDelegateTask<T>
@@ -54,7 +56,12 @@ where
         let sink = self.sink;
         pin!(sink);
 
-        let forward = ReceiverStream::new(self.rx).map(Ok).forward(sink).fuse();
+        let metrics = self.metrics;
+        let forward = ReceiverStream::new(self.rx)
+            .inspect(|response| metrics.send_response(response.body.payload().len()))
+            .map(Ok)
+            .forward(sink)
+            .fuse();
 
         // redirect the receiver stream to the sink, needs an extra task to drive both
         select! {
@@ -74,6 +81,7 @@ pub(crate) struct ConnectionTask<E> {
 
     pub config: SessionConfig,
     pub encoder: E,
+    pub metrics: SessionMetrics,
     pub _permit: OwnedSemaphorePermit,
 }
 
@@ -115,6 +123,8 @@ where
         // rogue request
         let request_id = request.header.request_id;
 
+        self.metrics.receive_request(request.body.payload().len());
+
         // these transactions then need to be propagated to the main session layer via an mpsc
         // channel, which drops a transaction if there's too many.
         match &request.body {
@@ -165,6 +175,7 @@ where
                         // transaction, because we can assume that the upper layer is unable to keep
                         // up with the incoming requests, it also helps us to prevent a DoS attack.
                         tracing::warn!("transaction delivery timed out, dropping transaction");
+                        self.metrics.exceed_deadline(Deadline::TransactionDelivery);
 
                         self.transactions.release(request_id).await;
 
@@ -223,6 +234,8 @@ where
         T: Sink<Response, Error: Debug + Send> + Send + 'static,
         U: Stream<Item = error_stack::Result<Request, io::Error>> + Send,
     {
+        let _active = self.metrics.accept_connection();
+
         let stream = stream.fuse();
         let stream = StreamNotifyClose::new(stream);
         let stream = Either::Left(stream);
@@ -239,6 +252,7 @@ where
                     .config
                     .per_connection_transaction_garbage_collect_interval,
                 index: Arc::clone(self.transactions.storage()),
+                metrics: self.metrics.clone(),
             }
             .run(cancel_gc.clone()),
         );
@@ -254,7 +268,14 @@ where
         // won't be able to create any more transactions.
         let (tx, rx) = mpsc::channel(self.config.per_connection_response_buffer_size.get());
         let mut connection_task_handle = tasks
-            .spawn(ConnectionDelegateTask { rx, sink }.run(cancel.clone()))
+            .spawn(
+                ConnectionDelegateTask {
+                    rx,
+                    sink,
+                    metrics: self.metrics.clone(),
+                }
+                .run(cancel.clone()),
+            )
             .fuse();
 
         let mut tx = Some(tx);
@@ -45,6 +45,7 @@ use crate::session::{
         ConnectionGracefulShutdownError, ConnectionTransactionLimitReachedError,
         InstanceTransactionLimitReachedError, TransactionLaggingError,
     },
+    metrics::SessionMetrics,
     server::{
         connection::{ConnectionDelegateTask, TransactionCollection},
         session_id::test_utils::mock_session_id,
@@ -63,7 +64,8 @@ pub(crate) async fn make_transaction_permit(
     tachyonix::Sender<Request>,
     tachyonix::Receiver<Request>,
 ) {
-    let collection = TransactionCollection::new(config, CancellationToken::new());
+    let collection =
+        TransactionCollection::new(config, SessionMetrics::default(), CancellationToken::new());
 
     collection
         .acquire(id)
@@ -103,11 +105,16 @@ impl Setup {
         let task = ConnectionTask {
             peer: PeerId::random(),
             session: Self::SESSION_ID,
-            transactions: TransactionCollection::new(config, cancel.clone()),
+            transactions: TransactionCollection::new(
+                config,
+                SessionMetrics::default(),
+                cancel.clone(),
+            ),
             output: output_tx,
             events: events_tx,
             config,
             encoder: StringEncoder,
+            metrics: SessionMetrics::default(),
             _permit: permit,
         };
 
@@ -1156,7 +1163,11 @@ async fn graceful_shutdown() {
 
 #[tokio::test]
 async fn transaction_collection_acquire() {
-    let collection = TransactionCollection::new(SessionConfig::default(), CancellationToken::new());
+    let collection = TransactionCollection::new(
+        SessionConfig::default(),
+        SessionMetrics::default(),
+        CancellationToken::new(),
+    );
 
     let (_permit, ..) = collection
         .acquire(mock_request_id(0x01))
@@ -1168,7 +1179,11 @@ async fn transaction_collection_acquire() {
 
 #[tokio::test]
 async fn transaction_collection_acquire_override() {
-    let collection = TransactionCollection::new(SessionConfig::default(), CancellationToken::new());
+    let collection = TransactionCollection::new(
+        SessionConfig::default(),
+        SessionMetrics::default(),
+        CancellationToken::new(),
+    );
 
     let (permit, ..) = collection
         .acquire(mock_request_id(0x01))
@@ -1195,6 +1210,7 @@ async fn transaction_collection_acquire_override_no_capacity() {
             per_connection_concurrent_transaction_limit: 1,
             ..SessionConfig::default()
         },
+        SessionMetrics::default(),
         CancellationToken::new(),
     );
 
@@ -1211,7 +1227,11 @@ async fn transaction_collection_acquire_override_no_capacity() {
 
 #[tokio::test]
 async fn transaction_collection_release() {
-    let collection = TransactionCollection::new(SessionConfig::default(), CancellationToken::new());
+    let collection = TransactionCollection::new(
+        SessionConfig::default(),
+        SessionMetrics::default(),
+        CancellationToken::new(),
+    );
 
     let (_permit, ..) = collection
         .acquire(mock_request_id(0x01))
@@ -1229,6 +1249,7 @@ async fn transaction_collection_acquire_full_no_insert() {
             per_connection_concurrent_transaction_limit: 0,
             ..SessionConfig::default()
         },
+        SessionMetrics::default(),
         CancellationToken::new(),
     );
 
@@ -1242,7 +1263,11 @@ async fn transaction_collection_acquire_full_no_insert() {
 
 #[tokio::test]
 async fn transaction_permit_reclaim() {
-    let collection = TransactionCollection::new(SessionConfig::default(), CancellationToken::new());
+    let collection = TransactionCollection::new(
+        SessionConfig::default(),
+        SessionMetrics::default(),
+        CancellationToken::new(),
+    );
 
     let (permit, ..) = collection
         .acquire(mock_request_id(0x01))
@@ -1261,7 +1286,11 @@ async fn transaction_permit_reclaim() {
 
 #[tokio::test]
 async fn transaction_permit_reclaim_override() {
-    let collection = TransactionCollection::new(SessionConfig::default(), CancellationToken::new());
+    let collection = TransactionCollection::new(
+        SessionConfig::default(),
+        SessionMetrics::default(),
+        CancellationToken::new(),
+    );
 
     let (permit_a, ..) = collection
         .acquire(mock_request_id(0x01))
@@ -1314,6 +1343,7 @@ async fn delegate() {
     let delegate = ConnectionDelegateTask {
         rx,
         sink: PollSender::new(sink),
+        metrics: SessionMetrics::default(),
     };
 
     let cancel = CancellationToken::new();
@@ -1345,6 +1375,7 @@ async fn delegate_drop_stream() {
     let delegate = ConnectionDelegateTask {
         rx,
         sink: PollSender::new(sink),
+        metrics: SessionMetrics::default(),
     };
 
     let handle = tokio::spawn(delegate.run(CancellationToken::new()));
@@ -1370,6 +1401,7 @@ async fn delegate_drop_tx() {
     let delegate = ConnectionDelegateTask {
         rx,
         sink: PollSender::new(sink),
+        metrics: SessionMetrics::default(),
     };
 
     let handle = tokio::spawn(delegate.run(CancellationToken::new()));
@@ -152,6 +152,7 @@ where
             output,
             events: self.events.clone(),
             encoder: self.encoder,
+            metrics: self.transport.session_metrics().clone(),
             _transport: self.transport,
         };
 
@@ -13,7 +13,7 @@ use super::{
 };
 use crate::{
     codec::ErrorEncoder,
-    session::server::connection::ConnectionTask,
+    session::{metrics::SessionMetrics, server::connection::ConnectionTask},
     transport::{
         connection::{IncomingConnection, IncomingConnections},
         TransportLayer,
@@ -30,6 +30,7 @@ pub(crate) struct Task<E> {
     pub output: mpsc::Sender<Transaction>,
     pub events: broadcast::Sender<SessionEvent>,
     pub encoder: E,
+    pub metrics: SessionMetrics,
 
     // significant because of the Drop, if dropped this will stop the task automatically
     pub _transport: TransportLayer,
@@ -93,6 +94,7 @@ where
                 Ok(permit) => permit,
                 Err(TryAcquireError::NoPermits) => {
                     // we have reached the connection limit, we can't accept new connections
+                    self.metrics.reject_connection();
                     continue;
                 }
                 Err(TryAcquireError::Closed) => {
@@ -107,10 +109,15 @@ where
                 peer: peer_id,
                 session: self.id.produce(),
                 config: self.config,
-                transactions: TransactionCollection::new(self.config, cancel.clone()),
+                transactions: TransactionCollection::new(
+                    self.config,
+                    self.metrics.clone(),
+                    cancel.clone(),
+                ),
                 output: self.output.clone(),
                 events: self.events.clone(),
                 encoder: self.encoder.clone(),
+                metrics: self.metrics.clone(),
                 _permit: permit,
             };
 
@@ -1,5 +1,11 @@
 use alloc::sync::Arc;
-use core::{future::ready, iter, net::Ipv4Addr, time::Duration};
+use core::{
+    future::ready,
+    iter,
+    net::{Ipv4Addr, SocketAddr},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
 use bytes::Bytes;
 use error_stack::{Report, ResultExt};
@@ -11,11 +17,17 @@ use harpc_wire_protocol::{
 };
 use humansize::ISizeFormatter;
 use libp2p::{multiaddr, Multiaddr};
-use tokio::{sync::Barrier, task::JoinSet, time::Instant};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::Barrier,
+    task::JoinSet,
+    time::Instant,
+};
 use tokio_util::sync::CancellationToken;
 
 use super::{
-    client::{self, Connection},
+    client::{self, BalanceConfig, BalanceStrategy, BalancedConnection, Connection},
     error::TransactionError,
     server::{
         self,
@@ -23,9 +35,14 @@ use super::{
         ListenStream,
     },
 };
+#[cfg(unix)]
+use crate::transport::{test::unix_address, UnixConfig};
 use crate::{
     codec::{ErrorEncoder, WireError},
-    transport::{test::memory_address, Transport, TransportConfig, TransportLayer},
+    transport::{
+        test::memory_address, MetricsExporterConfig, QuicConfig, Transport, TransportConfig,
+        TransportLayer,
+    },
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -164,12 +181,15 @@ fn client(
     (session_layer, cancel.drop_guard())
 }
 
-async fn echo<T>(mut transport: impl FnMut() -> T + Send, address: Multiaddr)
-where
+async fn echo<T>(
+    transport_config: fn() -> TransportConfig,
+    mut transport: impl FnMut() -> T + Send,
+    address: Multiaddr,
+) where
     T: Transport,
 {
     let (server, _server_guard) = server(
-        TransportConfig::default(),
+        transport_config(),
         server::SessionConfig::default(),
         transport(),
     );
@@ -200,7 +220,7 @@ where
     };
 
     let (client, _client_guard) = client(
-        TransportConfig::default(),
+        transport_config(),
         client::SessionConfig::default(),
         transport(),
     );
@@ -261,7 +281,12 @@ where
 async fn echo_memory() {
     let address = memory_address();
 
-    echo(libp2p::core::transport::MemoryTransport::default, address).await;
+    echo(
+        TransportConfig::default,
+        libp2p::core::transport::MemoryTransport::default,
+        address,
+    )
+    .await;
 }
 
 #[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
@@ -273,7 +298,60 @@ async fn echo_tcp() {
     .into_iter()
     .collect();
 
-    echo(libp2p::tcp::tokio::Transport::default, address).await;
+    echo(
+        TransportConfig::default,
+        libp2p::tcp::tokio::Transport::default,
+        address,
+    )
+    .await;
+}
+
+#[cfg(unix)]
+fn unix_transport_config() -> TransportConfig {
+    TransportConfig {
+        unix: Some(UnixConfig::default()),
+        ..TransportConfig::default()
+    }
+}
+
+fn quic_transport_config() -> TransportConfig {
+    TransportConfig {
+        quic: Some(QuicConfig::default()),
+        ..TransportConfig::default()
+    }
+}
+
+fn quic_address() -> Multiaddr {
+    [
+        multiaddr::Protocol::Ip4(Ipv4Addr::LOCALHOST),
+        multiaddr::Protocol::Udp(0),
+        multiaddr::Protocol::QuicV1,
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[cfg(unix)]
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn echo_unix() {
+    let address = unix_address();
+
+    echo(
+        unix_transport_config,
+        libp2p::core::transport::MemoryTransport::default,
+        address,
+    )
+    .await;
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn echo_quic() {
+    echo(
+        quic_transport_config,
+        libp2p::core::transport::MemoryTransport::default,
+        quic_address(),
+    )
+    .await;
 }
 
 struct ClientOptions {
@@ -282,7 +360,7 @@ struct ClientOptions {
 }
 
 async fn echo_client<const VERIFY: bool>(
-    connection: Connection,
+    connection: &Connection,
     ClientOptions { length, index }: ClientOptions,
 ) -> Duration {
     let descriptor = Descriptor::default();
@@ -325,6 +403,7 @@ async fn echo_client<const VERIFY: bool>(
 }
 
 async fn echo_concurrent<T>(
+    transport_config: fn() -> TransportConfig,
     mut transport: impl FnMut() -> T + Send,
     address: Multiaddr,
     clients: u8,
@@ -332,7 +411,7 @@ async fn echo_concurrent<T>(
     T: Transport,
 {
     let (server, _server_guard) = server(
-        TransportConfig::default(),
+        transport_config(),
         server::SessionConfig::default(),
         transport(),
     );
@@ -368,7 +447,7 @@ async fn echo_concurrent<T>(
     let barrier = Arc::new(Barrier::new(usize::from(clients)));
 
     let (client, _client_guard) = client(
-        TransportConfig::default(),
+        transport_config(),
         client::SessionConfig::default(),
         transport(),
     );
@@ -386,7 +465,7 @@ async fn echo_concurrent<T>(
         handles.spawn(async move {
             barrier.wait().await;
 
-            echo_client::<true>(connection, ClientOptions { length, index }).await
+            echo_client::<true>(&connection, ClientOptions { length, index }).await
         });
     }
 
@@ -414,6 +493,7 @@ async fn echo_memory_concurrent() {
     let address = memory_address();
 
     echo_concurrent(
+        TransportConfig::default,
         libp2p::core::transport::MemoryTransport::default,
         address,
         4,
@@ -430,5 +510,478 @@ async fn echo_tcp_concurrent() {
     .into_iter()
     .collect();
 
-    echo_concurrent(libp2p::tcp::tokio::Transport::default, address, 4).await;
+    echo_concurrent(
+        TransportConfig::default,
+        libp2p::tcp::tokio::Transport::default,
+        address,
+        4,
+    )
+    .await;
+}
+
+#[cfg(unix)]
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn echo_unix_concurrent() {
+    let address = unix_address();
+
+    echo_concurrent(
+        unix_transport_config,
+        libp2p::core::transport::MemoryTransport::default,
+        address,
+        4,
+    )
+    .await;
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn echo_quic_concurrent() {
+    echo_concurrent(
+        quic_transport_config,
+        libp2p::core::transport::MemoryTransport::default,
+        quic_address(),
+        4,
+    )
+    .await;
+}
+
+async fn echo_server(address: Multiaddr) -> (Multiaddr, impl Drop) {
+    // The address might still be occupied by a server which has just been shut down, as the
+    // listener is released asynchronously.
+    for _ in 0..50 {
+        let (server, guard) = server(
+            TransportConfig::default(),
+            server::SessionConfig::default(),
+            libp2p::tcp::tokio::Transport::default(),
+        );
+        let server_ipc = server.transport().ipc().clone();
+
+        let Ok(server_stream) = server.listen(address.clone()).await else {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            continue;
+        };
+
+        // Give the swarm some time to acquire the external address
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let address = server_ipc
+            .external_addresses()
+            .await
+            .expect("should have transport layer running")
+            .pop()
+            .expect("should have at least one external address");
+
+        SimpleEchoService::spawn(server_stream);
+
+        return (address, guard);
+    }
+
+    panic!("should be able to listen on {address}");
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn reconnect_tcp() {
+    let address: Multiaddr = [
+        multiaddr::Protocol::Ip4(Ipv4Addr::LOCALHOST),
+        multiaddr::Protocol::Tcp(0),
+    ]
+    .into_iter()
+    .collect();
+
+    let (address, server_guard) = echo_server(address).await;
+
+    let (client, _client_guard) = client(
+        TransportConfig::default(),
+        client::SessionConfig {
+            reconnect: Some(client::ReconnectConfig::default()),
+            ..client::SessionConfig::default()
+        },
+        libp2p::tcp::tokio::Transport::default(),
+    );
+
+    let connection = client
+        .dial(address.clone())
+        .await
+        .expect("should be able to dial server");
+
+    let options = || ClientOptions {
+        length: 1024,
+        index: 1,
+    };
+
+    echo_client::<true>(&connection, options()).await;
+
+    // restart the server, which closes the underlying connection
+    drop(server_guard);
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while connection.is_healthy() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("connection should become unhealthy once the server is gone");
+
+    let (_, _server_guard) = echo_server(address).await;
+
+    echo_client::<true>(&connection, options()).await;
+    assert!(connection.is_healthy());
+}
+
+/// Spawns an echo server, which counts the transactions it has accepted.
+async fn counting_server<T>(
+    mut transport: impl FnMut() -> T + Send,
+    address: Multiaddr,
+    counter: Arc<AtomicUsize>,
+) -> (Multiaddr, impl Drop)
+where
+    T: Transport,
+{
+    // The address might still be occupied by a server which has just been shut down, as the
+    // listener is released asynchronously.
+    for _ in 0..50 {
+        let (server, guard) = server(
+            TransportConfig::default(),
+            server::SessionConfig::default(),
+            transport(),
+        );
+        let server_ipc = server.transport().ipc().clone();
+
+        let Ok(mut server_stream) = server.listen(address.clone()).await else {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            continue;
+        };
+
+        // Give the swarm some time to acquire the external address
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let address = server_ipc
+            .external_addresses()
+            .await
+            .expect("should have transport layer running")
+            .pop()
+            .expect("should have at least one external address");
+
+        let counter = Arc::clone(&counter);
+        tokio::spawn(async move {
+            loop {
+                let Some(transaction) = server_stream.next().await else {
+                    break;
+                };
+
+                counter.fetch_add(1, Ordering::AcqRel);
+
+                let (_, sink, stream) = transaction.into_parts();
+                SimpleEchoService::accept(sink, stream);
+            }
+        });
+
+        return (address, guard);
+    }
+
+    panic!("should be able to listen on {address}");
+}
+
+async fn memory_servers(counters: &[Arc<AtomicUsize>]) -> (Vec<Multiaddr>, Vec<impl Drop>) {
+    let mut addresses = Vec::with_capacity(counters.len());
+    let mut guards = Vec::with_capacity(counters.len());
+
+    for counter in counters {
+        let (address, guard) = counting_server(
+            libp2p::core::transport::MemoryTransport::default,
+            memory_address(),
+            Arc::clone(counter),
+        )
+        .await;
+
+        addresses.push(address);
+        guards.push(guard);
+    }
+
+    (addresses, guards)
+}
+
+async fn balanced_echo(connection: &BalancedConnection) {
+    let descriptor = Descriptor::default();
+    let payload = Bytes::from_static(b"ping");
+
+    let mut stream = connection
+        .call(
+            descriptor.service,
+            descriptor.procedure,
+            stream::iter(iter::once(payload.clone())),
+        )
+        .await
+        .expect("should have a healthy peer");
+
+    let mut response = stream
+        .next()
+        .await
+        .expect("should receive a response")
+        .expect("value response");
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.next().await {
+        bytes.extend_from_slice(&chunk);
+    }
+
+    assert_eq!(bytes, payload);
+}
+
+async fn wait_for_healthy_peers(connection: &BalancedConnection, expected: usize) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while connection.healthy_peers() != expected {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("should have {expected} healthy peers"));
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn balance_round_robin_memory() {
+    let counters: [_; 3] = core::array::from_fn(|_| Arc::new(AtomicUsize::new(0)));
+
+    let (addresses, _guards) = memory_servers(&counters).await;
+
+    let (client, _client_guard) = client(
+        TransportConfig::default(),
+        client::SessionConfig::default(),
+        libp2p::core::transport::MemoryTransport::default(),
+    );
+
+    let connection = client
+        .dial_balanced(addresses, BalanceConfig::default())
+        .await
+        .expect("should be able to dial servers");
+    assert_eq!(connection.healthy_peers(), 3);
+
+    for _ in 0..6 {
+        balanced_echo(&connection).await;
+    }
+
+    for counter in &counters {
+        assert_eq!(counter.load(Ordering::Acquire), 2);
+    }
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn balance_least_outstanding_memory() {
+    let counters: [_; 2] = core::array::from_fn(|_| Arc::new(AtomicUsize::new(0)));
+
+    let (addresses, _guards) = memory_servers(&counters).await;
+
+    let (client, _client_guard) = client(
+        TransportConfig::default(),
+        client::SessionConfig::default(),
+        libp2p::core::transport::MemoryTransport::default(),
+    );
+
+    let connection = client
+        .dial_balanced(
+            addresses,
+            BalanceConfig {
+                strategy: BalanceStrategy::LeastOutstandingRequests,
+                ..BalanceConfig::default()
+            },
+        )
+        .await
+        .expect("should be able to dial servers");
+
+    let descriptor = Descriptor::default();
+
+    // keep a transaction open, so that one of the peers has an outstanding request
+    let _pending = connection
+        .call(descriptor.service, descriptor.procedure, stream::pending())
+        .await
+        .expect("should have a healthy peer");
+
+    for _ in 0..3 {
+        balanced_echo(&connection).await;
+    }
+
+    // the open transaction might not have reached the server yet, but all other transactions
+    // should have been sent to the other peer
+    let counts = counters
+        .iter()
+        .map(|counter| counter.load(Ordering::Acquire))
+        .collect::<Vec<_>>();
+
+    assert!(counts.contains(&3), "counts: {counts:?}");
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn balance_failover_tcp() {
+    let address: Multiaddr = [
+        multiaddr::Protocol::Ip4(Ipv4Addr::LOCALHOST),
+        multiaddr::Protocol::Tcp(0),
+    ]
+    .into_iter()
+    .collect();
+
+    let counters: [_; 2] = core::array::from_fn(|_| Arc::new(AtomicUsize::new(0)));
+
+    let (address_a, _guard_a) = counting_server(
+        libp2p::tcp::tokio::Transport::default,
+        address.clone(),
+        Arc::clone(&counters[0]),
+    )
+    .await;
+    let (address_b, guard_b) = counting_server(
+        libp2p::tcp::tokio::Transport::default,
+        address,
+        Arc::clone(&counters[1]),
+    )
+    .await;
+
+    let (client, _client_guard) = client(
+        TransportConfig::default(),
+        client::SessionConfig::default(),
+        libp2p::tcp::tokio::Transport::default(),
+    );
+
+    let connection = client
+        .dial_balanced(
+            [address_a, address_b.clone()],
+            BalanceConfig {
+                health_check_interval: Duration::from_millis(50),
+                ..BalanceConfig::default()
+            },
+        )
+        .await
+        .expect("should be able to dial servers");
+    assert_eq!(connection.healthy_peers(), 2);
+
+    // shut down one of the servers, all transactions should be sent to the remaining one
+    drop(guard_b);
+    wait_for_healthy_peers(&connection, 1).await;
+
+    let before = counters[0].load(Ordering::Acquire);
+    for _ in 0..4 {
+        balanced_echo(&connection).await;
+    }
+    assert_eq!(counters[0].load(Ordering::Acquire), before + 4);
+
+    // once the server is back, it should receive transactions again
+    let (_, _guard_b) = counting_server(
+        libp2p::tcp::tokio::Transport::default,
+        address_b,
+        Arc::clone(&counters[1]),
+    )
+    .await;
+    wait_for_healthy_peers(&connection, 2).await;
+
+    let before = counters[1].load(Ordering::Acquire);
+    for _ in 0..4 {
+        balanced_echo(&connection).await;
+    }
+    assert_eq!(counters[1].load(Ordering::Acquire), before + 2);
+}
+
+async fn scrape(address: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(address)
+        .await
+        .expect("should be able to connect to metrics exporter");
+
+    stream
+        .write_all(format!("GET {path} HTTP/1.0\r\nHost: {address}\r\n\r\n").as_bytes())
+        .await
+        .expect("should be able to send request");
+
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("should be able to read response");
+
+    response
+}
+
+#[test_log::test(tokio::test(flavor = "multi_thread", worker_threads = 8))]
+async fn metrics_exporter() {
+    let (server, _server_guard) = server(
+        TransportConfig::default(),
+        server::SessionConfig::default(),
+        libp2p::core::transport::MemoryTransport::default(),
+    );
+
+    let exporter = server
+        .transport()
+        .serve_metrics(MetricsExporterConfig {
+            address: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+            path: "/harpc/metrics".to_owned(),
+        })
+        .await
+        .expect("should be able to start metrics exporter");
+
+    let server_ipc = server.transport().ipc().clone();
+
+    let address = {
+        let server_stream = server
+            .listen(memory_address())
+            .await
+            .expect("should be able to listen");
+
+        // Give the swarm some time to acquire the external address
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let address = server_ipc
+            .external_addresses()
+            .await
+            .expect("should have transport layer running")
+            .pop()
+            .expect("should have at least one external address");
+
+        SimpleEchoService::spawn(server_stream);
+
+        address
+    };
+
+    let (client, _client_guard) = client(
+        TransportConfig::default(),
+        client::SessionConfig::default(),
+        libp2p::core::transport::MemoryTransport::default(),
+    );
+
+    let connection = client
+        .dial(address)
+        .await
+        .expect("should be able to dial server");
+
+    echo_client::<true>(
+        &connection,
+        ClientOptions {
+            length: 1024,
+            index: 1,
+        },
+    )
+    .await;
+
+    let response = scrape(exporter, "/harpc/metrics").await;
+
+    assert!(response.starts_with("HTTP/1.0 200 OK"), "{response}");
+    assert!(
+        response.contains("harpc_session_connections 1\n"),
+        "{response}"
+    );
+    assert!(
+        response.contains("harpc_session_connections_accepted_total 1\n"),
+        "{response}"
+    );
+    assert!(
+        response.contains("harpc_session_transactions_started_total 1\n"),
+        "{response}"
+    );
+    assert!(
+        response.contains("harpc_session_request_bytes_total 1024\n"),
+        "{response}"
+    );
+    assert!(
+        response.contains("harpc_session_response_bytes_total 1024\n"),
+        "{response}"
+    );
+    // the libp2p metrics are served alongside the metrics of the session layer
+    assert!(response.contains("libp2p_"), "{response}");
+
+    let response = scrape(exporter, "/metrics").await;
+    assert!(response.starts_with("HTTP/1.0 404"), "{response}");
 }
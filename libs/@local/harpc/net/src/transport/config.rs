@@ -2,7 +2,8 @@ use core::{num::NonZero, time::Duration};
 
 use libp2p::{
     core::upgrade,
-    ping, swarm,
+    identity::Keypair,
+    ping, quic, swarm,
     yamux::{self, WindowUpdateMode},
 };
 
@@ -40,6 +41,92 @@ impl Default for YamuxConfig {
     }
 }
 
+/// Configuration for the Unix domain socket transport.
+///
+/// Unix domain sockets are addressed using `/unix/<path>` and can only be used to communicate
+/// between processes on the same host.
+#[cfg(unix)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnixConfig {
+    /// Remove an existing socket file before listening on it, if no process accepts connections
+    /// on the socket anymore.
+    ///
+    /// The socket file of a listener is removed once the listener is closed, but is left behind if
+    /// the process is terminated abruptly.
+    ///
+    /// **Default:** `true`
+    pub remove_stale_socket: bool,
+}
+
+#[cfg(unix)]
+impl Default for UnixConfig {
+    fn default() -> Self {
+        Self {
+            remove_stale_socket: true,
+        }
+    }
+}
+
+/// Configuration for the QUIC transport.
+///
+/// QUIC connections are addressed using `/ip4/<address>/udp/<port>/quic-v1` (or `/ip6/...`) and
+/// are authenticated and multiplexed by QUIC itself, instead of noise and yamux.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuicConfig {
+    /// Timeout for the initial handshake when establishing a connection.
+    ///
+    /// **Default:** `5s`
+    pub handshake_timeout: Duration,
+
+    /// Maximum duration of inactivity to accept before timing out the connection.
+    ///
+    /// **Default:** `10s`
+    pub max_idle_timeout: Duration,
+
+    /// Period of inactivity before sending a keep-alive packet, must be lower than
+    /// [`Self::max_idle_timeout`].
+    ///
+    /// **Default:** `5s`
+    pub keep_alive_interval: Duration,
+
+    /// Maximum number of incoming bidirectional streams that may be open concurrently on a
+    /// connection.
+    ///
+    /// **Default:** `256`
+    pub max_concurrent_stream_limit: u32,
+}
+
+impl QuicConfig {
+    pub(crate) fn into_config(self, keypair: &Keypair) -> quic::Config {
+        let Self {
+            handshake_timeout,
+            max_idle_timeout,
+            keep_alive_interval,
+            max_concurrent_stream_limit,
+        } = self;
+
+        let mut config = quic::Config::new(keypair);
+
+        config.handshake_timeout = handshake_timeout;
+        config.max_idle_timeout = u32::try_from(max_idle_timeout.as_millis()).unwrap_or(u32::MAX);
+        config.keep_alive_interval = keep_alive_interval;
+        config.max_concurrent_stream_limit = max_concurrent_stream_limit;
+
+        config
+    }
+}
+
+impl Default for QuicConfig {
+    fn default() -> Self {
+        Self {
+            handshake_timeout: Duration::from_secs(5),
+            max_idle_timeout: Duration::from_secs(10),
+            keep_alive_interval: Duration::from_secs(5),
+            max_concurrent_stream_limit: 256,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SwarmConfig {
     /// Configures the number of events from the [`NetworkBehaviour`] in
@@ -175,6 +262,19 @@ pub struct TransportConfig {
     /// Configuration for the yamux protocol.
     pub yamux: YamuxConfig,
 
+    /// Enables the Unix domain socket transport in addition to the transport the transport layer
+    /// has been started with.
+    ///
+    /// **Default:** `None`
+    #[cfg(unix)]
+    pub unix: Option<UnixConfig>,
+
+    /// Enables the QUIC transport in addition to the transport the transport layer has been
+    /// started with.
+    ///
+    /// **Default:** `None`
+    pub quic: Option<QuicConfig>,
+
     /// Size of the buffer for IPC (Inter-Process Communication) messages to the transport task
     /// driving the swarm.
     ///
@@ -188,6 +288,9 @@ impl Default for TransportConfig {
             ping: ping::Config::default(),
             swarm: SwarmConfig::default(),
             yamux: YamuxConfig::default(),
+            #[cfg(unix)]
+            unix: None,
+            quic: None,
             ipc_buffer_size: non_zero!(16),
         }
     }
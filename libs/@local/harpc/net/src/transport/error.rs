@@ -48,4 +48,6 @@ pub enum TransportError {
     OpenStream { peer_id: PeerId },
     #[error("Unable to initialize underlying transport layer of swarm")]
     SetupSwarmTransport,
+    #[error("Unable to start the metrics exporter")]
+    MetricsExporter,
 }
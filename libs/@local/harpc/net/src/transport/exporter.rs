@@ -0,0 +1,72 @@
+use alloc::sync::Arc;
+use core::net::{Ipv4Addr, SocketAddr};
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use libp2p::metrics::Registry;
+use prometheus_client::encoding::text::encode;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Configuration of the HTTP endpoint serving metrics in the Prometheus text format.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MetricsExporterConfig {
+    /// Address the HTTP server binds to.
+    ///
+    /// **Default:** `127.0.0.1:9464`
+    pub address: SocketAddr,
+
+    /// Path under which the metrics are served.
+    ///
+    /// **Default:** `/metrics`
+    pub path: String,
+}
+
+impl Default for MetricsExporterConfig {
+    fn default() -> Self {
+        Self {
+            address: SocketAddr::from((Ipv4Addr::LOCALHOST, 9464)),
+            path: "/metrics".to_owned(),
+        }
+    }
+}
+
+async fn encode_metrics(State(registry): State<Arc<Registry>>) -> Response {
+    let mut body = String::new();
+
+    if let Err(error) = encode(&mut body, &registry) {
+        tracing::error!(?error, "unable to encode metrics");
+
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], body).into_response()
+}
+
+pub(crate) struct MetricsExporterTask {
+    pub listener: TcpListener,
+    pub path: String,
+    pub registry: Arc<Registry>,
+}
+
+impl MetricsExporterTask {
+    pub(crate) async fn run(self, cancel: CancellationToken) {
+        let router = Router::new()
+            .route(&self.path, get(encode_metrics))
+            .with_state(self.registry);
+
+        if let Err(error) = axum::serve(self.listener, router)
+            .with_graceful_shutdown(cancel.cancelled_owned())
+            .await
+        {
+            tracing::error!(?error, "metrics exporter stopped unexpectedly");
+        }
+    }
+}
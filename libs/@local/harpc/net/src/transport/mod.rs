@@ -3,32 +3,41 @@ pub mod client;
 mod config;
 pub mod connection;
 pub mod error;
+mod exporter;
 mod ipc;
 mod server;
 mod task;
 #[cfg(test)]
 pub(crate) mod test;
+#[cfg(unix)]
+mod unix;
 
 use alloc::sync::Arc;
+use core::net::SocketAddr;
 
 use error_stack::{Result, ResultExt};
 use futures::stream::StreamExt;
 use libp2p::{core::transport::ListenerId, metrics, Multiaddr, PeerId, StreamProtocol};
-use tokio::io::BufStream;
+use tokio::{io::BufStream, net::TcpListener};
 use tokio_util::{
     codec::Framed, compat::FuturesAsyncReadCompatExt, sync::CancellationToken, task::TaskTracker,
 };
 
+#[cfg(unix)]
+pub use self::config::UnixConfig;
 use self::{
     client::ClientCodec,
     connection::{IncomingConnections, OutgoingConnection},
     error::{OpenStreamError, TransportError},
-    task::TransportTask,
+    exporter::MetricsExporterTask,
+    task::{TransportTask, UnresponsivePeers},
 };
 pub use self::{
-    config::{SwarmConfig, TransportConfig, YamuxConfig},
+    config::{QuicConfig, SwarmConfig, TransportConfig, YamuxConfig},
+    exporter::MetricsExporterConfig,
     ipc::TransportLayerIpc,
 };
+use crate::session::metrics::SessionMetrics;
 
 const PROTOCOL_NAME: StreamProtocol = StreamProtocol::new("/harpc/1.0.0");
 
@@ -46,6 +55,8 @@ pub struct TransportLayer {
     ipc: TransportLayerIpc,
 
     registry: Arc<metrics::Registry>,
+    session_metrics: SessionMetrics,
+    unresponsive_peers: UnresponsivePeers,
 
     tasks: TaskTracker,
     cancel: CancellationToken,
@@ -65,10 +76,15 @@ impl TransportLayer {
         transport: impl Transport,
         cancel: CancellationToken,
     ) -> Result<Self, TransportError> {
-        let task = TransportTask::new(config, transport)?;
+        let mut registry = metrics::Registry::default();
+
+        let task = TransportTask::new(config, transport, &mut registry)?;
         let id = task.peer_id();
         let ipc = task.ipc();
-        let registry = task.registry();
+        let unresponsive_peers = task.unresponsive_peers();
+
+        let session_metrics = SessionMetrics::register(&mut registry);
+        let registry = Arc::new(registry);
 
         let cancel_task = cancel.child_token();
 
@@ -80,6 +96,8 @@ impl TransportLayer {
             ipc,
 
             registry,
+            session_metrics,
+            unresponsive_peers,
 
             tasks,
             cancel,
@@ -112,6 +130,14 @@ impl TransportLayer {
         self.id
     }
 
+    /// Check if a peer has responded to the last ping.
+    ///
+    /// Peers which haven't been pinged yet are considered responsive.
+    #[must_use]
+    pub fn is_responsive(&self, peer: PeerId) -> bool {
+        !self.unresponsive_peers.contains(&peer)
+    }
+
     /// Lookup a peer by address.
     ///
     /// If the peer has been dialed before, the peer won't be dialed again and the known peer id
@@ -145,6 +171,41 @@ impl TransportLayer {
         &self.registry
     }
 
+    pub(crate) const fn session_metrics(&self) -> &SessionMetrics {
+        &self.session_metrics
+    }
+
+    /// Serve the metrics of the registry in the Prometheus text format.
+    ///
+    /// The HTTP server is stopped once the transport layer is shut down. Returns the address the
+    /// server is listening on.
+    ///
+    /// # Errors
+    ///
+    /// If the HTTP server is unable to bind to the configured address.
+    pub async fn serve_metrics(
+        &self,
+        config: MetricsExporterConfig,
+    ) -> Result<SocketAddr, TransportError> {
+        let listener = TcpListener::bind(config.address)
+            .await
+            .change_context(TransportError::MetricsExporter)?;
+        let address = listener
+            .local_addr()
+            .change_context(TransportError::MetricsExporter)?;
+
+        self.tasks.spawn(
+            MetricsExporterTask {
+                listener,
+                path: config.path,
+                registry: Arc::clone(&self.registry),
+            }
+            .run(self.cancel_task.clone()),
+        );
+
+        Ok(address)
+    }
+
     /// Listen on an address.
     ///
     /// # Errors
@@ -6,12 +6,12 @@ use std::{
 };
 
 use error_stack::{Result, ResultExt};
-use futures::prelude::stream::StreamExt;
+use futures::{future::Either, prelude::stream::StreamExt};
 use libp2p::{
-    core::{transport::ListenerId, upgrade},
+    core::{muxing::StreamMuxerBox, transport::ListenerId, upgrade},
     identify,
     metrics::{self, Metrics, Recorder},
-    noise, ping,
+    noise, ping, quic,
     swarm::{dial_opts::DialOpts, ConnectionId, DialError, SwarmEvent},
     yamux, Multiaddr, PeerId, SwarmBuilder,
 };
@@ -23,6 +23,8 @@ use tokio::{
 };
 use tokio_util::sync::CancellationToken;
 
+#[cfg(unix)]
+use super::unix::UnixTransport;
 use super::{
     behaviour::{TransportBehaviour, TransportBehaviourEvent, TransportSwarm},
     error::TransportError,
@@ -30,6 +32,9 @@ use super::{
     Transport, TransportConfig, PROTOCOL_NAME,
 };
 
+/// Peers whose last ping has failed.
+pub(crate) type UnresponsivePeers = Arc<scc::HashSet<PeerId>>;
+
 type SenderPeerId = oneshot::Sender<core::result::Result<PeerId, DialError>>;
 type SenderListenerId =
     oneshot::Sender<core::result::Result<ListenerId, libp2p::TransportError<io::Error>>>;
@@ -55,7 +60,6 @@ pub(crate) struct TransportTask {
     peer_id: PeerId,
     swarm: TransportSwarm,
 
-    registry: Arc<metrics::Registry>,
     metrics: metrics::Metrics,
 
     rx: mpsc::Receiver<Command>,
@@ -65,15 +69,16 @@ pub(crate) struct TransportTask {
 
     peers_waiting: HashMap<Multiaddr, Vec<SenderPeerId>>,
     peers_address_lookup: HashMap<ConnectionId, Multiaddr>,
+
+    unresponsive_peers: UnresponsivePeers,
 }
 
 impl TransportTask {
     pub(crate) fn new(
         config: TransportConfig,
         transport: impl Transport,
+        registry: &mut metrics::Registry,
     ) -> Result<Self, TransportError> {
-        let mut registry = metrics::Registry::default();
-
         let (ipx_tx, rx) = mpsc::channel(config.ipc_buffer_size.get());
         let ipc = TransportLayerIpc::new(ipx_tx);
 
@@ -115,17 +120,50 @@ impl TransportTask {
                 // 16MiB.
                 let yamux: yamux::Config = config.yamux.into();
 
-                let transport = transport
+                let mut transport = transport
                     .upgrade(upgrade::Version::V1Lazy)
                     .authenticate(noise)
-                    .multiplex(yamux);
+                    .multiplex(yamux)
+                    .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+                    .boxed();
+
+                #[cfg(unix)]
+                if let Some(unix) = config.unix {
+                    let noise = noise::Config::new(keypair)?;
+                    let yamux: yamux::Config = config.yamux.into();
+
+                    transport = UnixTransport::new(unix)
+                        .upgrade(upgrade::Version::V1Lazy)
+                        .authenticate(noise)
+                        .multiplex(yamux)
+                        .or_transport(transport)
+                        .map(|output, _| match output {
+                            Either::Left((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+                            Either::Right(output) => output,
+                        })
+                        .boxed();
+                }
+
+                // QUIC brings its own encryption and multiplexing, so it doesn't need to be
+                // upgraded.
+                if let Some(quic) = config.quic {
+                    transport = quic::tokio::Transport::new(quic.into_config(keypair))
+                        .or_transport(transport)
+                        .map(|output, _| match output {
+                            Either::Left((peer_id, connection)) => {
+                                (peer_id, StreamMuxerBox::new(connection))
+                            }
+                            Either::Right(output) => output,
+                        })
+                        .boxed();
+                }
 
                 Ok(transport)
             })
             .change_context(TransportError::SetupSwarmTransport)?;
 
         let Ok(swarm) = swarm
-            .with_bandwidth_metrics(&mut registry)
+            .with_bandwidth_metrics(registry)
             .with_behaviour(|keys| TransportBehaviour {
                 stream: stream::Behaviour::new(),
                 identify: identify::Behaviour::new(identify::Config::new(
@@ -141,14 +179,12 @@ impl TransportTask {
 
         let peer_id = *swarm.local_peer_id();
 
-        let metrics = Metrics::new(&mut registry);
-        let registry = Arc::new(registry);
+        let metrics = Metrics::new(registry);
 
         Ok(Self {
             peer_id,
             swarm,
 
-            registry,
             metrics,
 
             rx,
@@ -157,6 +193,8 @@ impl TransportTask {
             peers: HashMap::new(),
             peers_waiting: HashMap::new(),
             peers_address_lookup: HashMap::new(),
+
+            unresponsive_peers: Arc::new(scc::HashSet::new()),
         })
     }
 
@@ -164,14 +202,14 @@ impl TransportTask {
         self.peer_id
     }
 
-    pub(crate) fn registry(&self) -> Arc<metrics::Registry> {
-        Arc::clone(&self.registry)
-    }
-
     pub(crate) fn ipc(&self) -> TransportLayerIpc {
         self.ipc.clone()
     }
 
+    pub(crate) fn unresponsive_peers(&self) -> UnresponsivePeers {
+        Arc::clone(&self.unresponsive_peers)
+    }
+
     fn send_ipc_response<T>(tx: oneshot::Sender<T>, value: T) {
         if tx.send(value).is_err() {
             tracing::error!("failed to send response to the IPC caller");
@@ -251,6 +289,13 @@ impl TransportTask {
 
         let mut error = error;
 
+        if let Some(peer_id) = peer_id {
+            // The peer might be reachable under a different identity (e.g. after a restart), so the
+            // next lookup of its address needs to dial the address again.
+            self.peers
+                .retain(|_, known_peer_id| *known_peer_id != peer_id);
+        }
+
         let Some(address) = self.peers_address_lookup.remove(&connection_id) else {
             return;
         };
@@ -268,6 +313,22 @@ impl TransportTask {
         }
     }
 
+    fn handle_ping(&self, event: &ping::Event) {
+        match &event.result {
+            Ok(_) => {
+                if self.unresponsive_peers.remove(&event.peer).is_some() {
+                    tracing::info!(peer_id = %event.peer, "peer is responsive again");
+                }
+            }
+            Err(error) => {
+                tracing::warn!(peer_id = %event.peer, %error, "peer did not respond to ping");
+
+                // the error is returned if the peer is already marked as unresponsive
+                _ = self.unresponsive_peers.insert(event.peer);
+            }
+        }
+    }
+
     fn handle_event(&mut self, event: SwarmEvent<TransportBehaviourEvent>) {
         tracing::debug!(?event, "received swarm event");
 
@@ -277,6 +338,7 @@ impl TransportTask {
             }
             SwarmEvent::Behaviour(TransportBehaviourEvent::Ping(event)) => {
                 self.metrics.record(event);
+                self.handle_ping(event);
             }
             event => self.metrics.record(event),
         }
@@ -295,6 +357,15 @@ impl TransportTask {
             } => {
                 self.handle_outgoing_connection_error(connection_id, peer_id, error);
             }
+            SwarmEvent::ConnectionClosed {
+                peer_id,
+                num_established: 0,
+                ..
+            } => {
+                // Without a connection there is nothing to ping, the health of a new connection
+                // is determined by its own pings.
+                self.unresponsive_peers.remove(&peer_id);
+            }
             _ => {}
         }
     }
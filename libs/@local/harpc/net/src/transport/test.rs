@@ -71,6 +71,19 @@ pub(crate) fn memory_address() -> libp2p::Multiaddr {
     iter::once(multiaddr::Protocol::Memory(id)).collect()
 }
 
+#[cfg(unix)]
+pub(crate) fn unix_address() -> libp2p::Multiaddr {
+    static SOCKET: AtomicU64 = AtomicU64::new(0);
+
+    let id = SOCKET.fetch_add(1, Ordering::SeqCst);
+
+    // the process id is included, as the socket files of multiple test processes share the same
+    // directory
+    let path = std::env::temp_dir().join(format!("harpc-{}-{id}.sock", std::process::id()));
+
+    iter::once(multiaddr::Protocol::Unix(path.to_string_lossy())).collect()
+}
+
 pub(crate) fn layer() -> (TransportLayer, impl Drop) {
     let transport = MemoryTransport::default();
     let config = TransportConfig::default();
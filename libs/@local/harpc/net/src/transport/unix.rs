@@ -0,0 +1,227 @@
+use alloc::collections::VecDeque;
+use core::{
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    fs, io,
+    os::unix::{fs::FileTypeExt, net::UnixStream as StdUnixStream},
+    path::{Path, PathBuf},
+};
+
+use futures::future::{self, BoxFuture, FutureExt, Ready};
+use libp2p::{
+    core::transport::{ListenerId, TransportError, TransportEvent},
+    multiaddr::Protocol,
+    Multiaddr,
+};
+use tokio::net::{UnixListener, UnixStream};
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+use super::config::UnixConfig;
+
+/// Extract the socket path from a `/unix/<path>` address.
+fn socket_path(address: &Multiaddr) -> Option<PathBuf> {
+    let mut protocols = address.iter();
+
+    let Some(Protocol::Unix(path)) = protocols.next() else {
+        return None;
+    };
+
+    if protocols.next().is_some() {
+        return None;
+    }
+
+    Some(PathBuf::from(path.as_ref()))
+}
+
+/// Remove the socket file at `path`, if it is no longer accepting connections.
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        // nothing to remove
+        return Ok(());
+    };
+
+    if !metadata.file_type().is_socket() {
+        // never remove anything that isn't a socket, binding will fail instead
+        return Ok(());
+    }
+
+    match StdUnixStream::connect(path) {
+        Err(error) if error.kind() == io::ErrorKind::ConnectionRefused => {
+            tracing::info!(path = %path.display(), "removing stale socket");
+
+            fs::remove_file(path)
+        }
+        // either the socket is still in use, in which case binding will fail, or we're unable to
+        // determine if it is stale
+        _ => Ok(()),
+    }
+}
+
+struct Listener {
+    id: ListenerId,
+    address: Multiaddr,
+    path: PathBuf,
+
+    inner: UnixListener,
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Err(error) = fs::remove_file(&self.path) {
+            tracing::warn!(path = %self.path.display(), %error, "unable to remove socket");
+        }
+    }
+}
+
+type Output = Compat<UnixStream>;
+
+/// Transport for Unix domain sockets.
+///
+/// Compared to TCP, there's no need to go through the network stack, which makes this transport
+/// preferable for processes on the same host.
+pub(crate) struct UnixTransport {
+    config: UnixConfig,
+
+    listeners: Vec<Listener>,
+    events: VecDeque<TransportEvent<Ready<io::Result<Output>>, io::Error>>,
+
+    waker: Option<Waker>,
+}
+
+impl UnixTransport {
+    pub(crate) const fn new(config: UnixConfig) -> Self {
+        Self {
+            config,
+            listeners: Vec::new(),
+            events: VecDeque::new(),
+            waker: None,
+        }
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl libp2p::Transport for UnixTransport {
+    type Dial = BoxFuture<'static, io::Result<Output>>;
+    type Error = io::Error;
+    type ListenerUpgrade = Ready<io::Result<Output>>;
+    type Output = Output;
+
+    fn listen_on(
+        &mut self,
+        id: ListenerId,
+        addr: Multiaddr,
+    ) -> Result<(), TransportError<Self::Error>> {
+        let Some(path) = socket_path(&addr) else {
+            return Err(TransportError::MultiaddrNotSupported(addr));
+        };
+
+        if self.config.remove_stale_socket {
+            remove_stale_socket(&path).map_err(TransportError::Other)?;
+        }
+
+        let listener = UnixListener::bind(&path).map_err(TransportError::Other)?;
+
+        tracing::debug!(%addr, "listening on unix socket");
+
+        self.events.push_back(TransportEvent::NewAddress {
+            listener_id: id,
+            listen_addr: addr.clone(),
+        });
+        self.listeners.push(Listener {
+            id,
+            address: addr,
+            path,
+            inner: listener,
+        });
+
+        self.wake();
+
+        Ok(())
+    }
+
+    fn remove_listener(&mut self, id: ListenerId) -> bool {
+        let Some(index) = self.listeners.iter().position(|listener| listener.id == id) else {
+            return false;
+        };
+
+        self.listeners.swap_remove(index);
+        self.events.push_back(TransportEvent::ListenerClosed {
+            listener_id: id,
+            reason: Ok(()),
+        });
+
+        self.wake();
+
+        true
+    }
+
+    fn dial(&mut self, addr: Multiaddr) -> Result<Self::Dial, TransportError<Self::Error>> {
+        let Some(path) = socket_path(&addr) else {
+            return Err(TransportError::MultiaddrNotSupported(addr));
+        };
+
+        tracing::debug!(%addr, "dialing unix socket");
+
+        Ok(async move {
+            UnixStream::connect(path)
+                .await
+                .map(TokioAsyncReadCompatExt::compat)
+        }
+        .boxed())
+    }
+
+    fn dial_as_listener(
+        &mut self,
+        addr: Multiaddr,
+    ) -> Result<Self::Dial, TransportError<Self::Error>> {
+        // there's no hole punching for unix sockets, dialing as listener is the same as dialing
+        self.dial(addr)
+    }
+
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<TransportEvent<Self::ListenerUpgrade, Self::Error>> {
+        let this = self.get_mut();
+
+        if let Some(event) = this.events.pop_front() {
+            return Poll::Ready(event);
+        }
+
+        for listener in &this.listeners {
+            match listener.inner.poll_accept(cx) {
+                Poll::Ready(Ok((stream, _))) => {
+                    // Unix sockets of clients are usually unnamed, therefore the only meaningful
+                    // address is the one of the listener
+                    return Poll::Ready(TransportEvent::Incoming {
+                        listener_id: listener.id,
+                        upgrade: future::ok(stream.compat()),
+                        local_addr: listener.address.clone(),
+                        send_back_addr: listener.address.clone(),
+                    });
+                }
+                Poll::Ready(Err(error)) => {
+                    return Poll::Ready(TransportEvent::ListenerError {
+                        listener_id: listener.id,
+                        error,
+                    });
+                }
+                Poll::Pending => {}
+            }
+        }
+
+        this.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    fn address_translation(&self, _: &Multiaddr, _: &Multiaddr) -> Option<Multiaddr> {
+        None
+    }
+}
@@ -96,7 +96,13 @@ where
 mod test {
     use bytes::Bytes;
     use error_stack::Report;
+    use futures::stream;
+    use harpc_net::codec::{
+        status::{ErrorDetail, StatusErrorDecoder, StatusErrorEncoder},
+        ErrorDecoder, ErrorEncoder,
+    };
     use harpc_wire_protocol::response::kind::{ErrorCode, ResponseKind};
+    use hash_status::StatusCode;
     use tokio_test::{assert_pending, assert_ready};
     use tower::{Layer, Service, ServiceExt};
     use tower_test::mock::{self, spawn_with};
@@ -117,7 +123,9 @@ mod test {
     };
 
     #[expect(clippy::type_complexity, reason = "test code")]
-    fn service() -> (
+    fn service<E>(
+        encoder: E,
+    ) -> (
         mock::Spawn<
             impl Service<
                 Request<Full<Bytes>>,
@@ -134,17 +142,20 @@ mod test {
             + 'static,
         >,
         mock::Handle<Request<Full<Bytes>>, Response<Controlled<ResponseKind, Full<Bytes>>>>,
-    ) {
-        spawn_with(|service| {
+    )
+    where
+        E: ErrorEncoder + Clone + Send + Sync + 'static,
+    {
+        spawn_with(move |service| {
             let service = service.map_err(|error| Report::from(BoxedError::from(error)));
 
-            HandleReportLayer::new(PlainErrorEncoder).layer(service)
+            HandleReportLayer::new(encoder.clone()).layer(service)
         })
     }
 
     #[tokio::test]
     async fn handle_error() {
-        let (mut service, mut handle) = service();
+        let (mut service, mut handle) = service(PlainErrorEncoder);
 
         assert_pending!(handle.poll_request());
         assert_ready!(service.poll_ready()).expect("should be ready");
@@ -187,7 +198,7 @@ mod test {
 
     #[tokio::test]
     async fn passthrough() {
-        let (mut service, mut handle) = service();
+        let (mut service, mut handle) = service(PlainErrorEncoder);
 
         assert_pending!(handle.poll_request());
         assert_ready!(service.poll_ready()).expect("should be ready");
@@ -235,4 +246,61 @@ mod test {
             .into_inner();
         assert_eq!(data, Bytes::from_static(b"response" as &[_]));
     }
+
+    #[tokio::test]
+    async fn handle_report_status() {
+        let (mut service, mut handle) = service(StatusErrorEncoder);
+
+        assert_pending!(handle.poll_request());
+        assert_ready!(service.poll_ready()).expect("should be ready");
+
+        let response = tokio::spawn(service.call(request()));
+
+        let Some((_, send_response)) = handle.next_request().await else {
+            panic!("expected a request, but non was received.");
+        };
+
+        send_response.send_error(
+            Report::from(GenericError::new(
+                ErrorCode::INSTANCE_TRANSACTION_LIMIT_REACHED,
+            ))
+            .attach(StatusCode::NotFound)
+            .attach_printable("entity: 0123"),
+        );
+
+        let mut response = response
+            .await
+            .expect("should be able to join")
+            .expect("response should be present");
+
+        let body = response.body_mut();
+        let Ok(frame) = body.frame().await.expect("frame should be present");
+        let control = frame
+            .into_control()
+            .expect("should be control frame")
+            .into_inner();
+        assert_eq!(
+            control,
+            ResponseKind::Err(ErrorCode::INSTANCE_TRANSACTION_LIMIT_REACHED)
+        );
+
+        let Ok(frame) = body.frame().await.expect("frame should be present");
+        let data = frame
+            .into_data()
+            .expect("should be data frame")
+            .into_inner();
+
+        let error = StatusErrorDecoder
+            .decode_report(stream::iter([data]))
+            .await
+            .expect("should be able to decode status");
+
+        assert_eq!(error.code(), StatusCode::NotFound);
+        assert_eq!(error.message(), Some("generic error"));
+        assert!(
+            error
+                .details()
+                .contains(&ErrorDetail::Context("generic error".to_owned()))
+        );
+    }
 }
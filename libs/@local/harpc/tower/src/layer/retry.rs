@@ -0,0 +1,260 @@
+use core::{
+    num::NonZero,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use harpc_wire_protocol::request::{procedure::ProcedureDescriptor, service::ServiceDescriptor};
+use tower::{Layer, Service, ServiceExt};
+
+use crate::{body::Body, request::Request};
+
+/// Decides which procedures are safe to be called multiple times.
+///
+/// Only requests to idempotent procedures are retried, as a failed request might still have been
+/// processed by the server.
+pub trait IdempotencyPolicy {
+    fn is_idempotent(&self, service: ServiceDescriptor, procedure: ProcedureDescriptor) -> bool;
+}
+
+impl<F> IdempotencyPolicy for F
+where
+    F: Fn(ServiceDescriptor, ProcedureDescriptor) -> bool,
+{
+    fn is_idempotent(&self, service: ServiceDescriptor, procedure: ProcedureDescriptor) -> bool {
+        self(service, procedure)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RetryConfig {
+    /// Maximum number of attempts, including the initial request.
+    ///
+    /// **Default:** `3`
+    pub max_attempts: NonZero<usize>,
+
+    /// Delay after the first failed attempt, which is doubled for every subsequent failed attempt.
+    ///
+    /// **Default:** `100ms`
+    pub initial_backoff: Duration,
+
+    /// Upper bound of the delay between two attempts.
+    ///
+    /// **Default:** `5s`
+    pub max_backoff: Duration,
+}
+
+impl RetryConfig {
+    fn backoff(&self, attempt: usize) -> Duration {
+        let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+
+        self.initial_backoff
+            .saturating_mul(2_u32.saturating_pow(exponent))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: NonZero::new(3).expect("3 is non-zero"),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Retries requests to idempotent procedures if the inner service fails.
+///
+/// Only errors returned by the inner service are retried, responses are passed through, even if
+/// they contain an error.
+pub struct RetryLayer<P> {
+    config: RetryConfig,
+    policy: P,
+}
+
+impl<P> RetryLayer<P> {
+    pub const fn new(config: RetryConfig, policy: P) -> Self {
+        Self { config, policy }
+    }
+}
+
+impl<S, P> Layer<S> for RetryLayer<P>
+where
+    P: Clone,
+{
+    type Service = Retry<S, P>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Retry {
+            inner,
+            config: self.config,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+pub struct Retry<S, P> {
+    inner: S,
+
+    config: RetryConfig,
+    policy: P,
+}
+
+impl<S, P, ReqBody> Service<Request<ReqBody>> for Retry<S, P>
+where
+    S: Service<Request<ReqBody>> + Clone + Send,
+    P: IdempotencyPolicy,
+    ReqBody: Body<Control = !> + Clone,
+{
+    type Error = S::Error;
+    type Response = S::Response;
+
+    type Future = impl Future<Output = Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // we're always ready because we clone the inner service for every attempt, therefore it is
+        // unused and always ready
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let clone = self.inner.clone();
+        let inner = core::mem::replace(&mut self.inner, clone);
+
+        let config = self.config;
+        let max_attempts = if self.policy.is_idempotent(req.service(), req.procedure()) {
+            config.max_attempts.get()
+        } else {
+            1
+        };
+
+        async move {
+            let mut attempt = 1;
+
+            loop {
+                if attempt >= max_attempts {
+                    return inner.oneshot(req).await;
+                }
+
+                if let Ok(response) = inner.clone().oneshot(req.clone()).await {
+                    return Ok(response);
+                }
+
+                tokio::time::sleep(config.backoff(attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{num::NonZero, time::Duration};
+
+    use bytes::Bytes;
+    use harpc_wire_protocol::{
+        request::{procedure::ProcedureDescriptor, service::ServiceDescriptor},
+        response::kind::{ErrorCode, ResponseKind},
+    };
+    use tokio_test::{assert_pending, assert_ready};
+    use tower_test::mock::spawn_layer;
+
+    use crate::{
+        body::{controlled::Controlled, full::Full},
+        layer::{
+            error::test::{request, GenericError},
+            retry::{RetryConfig, RetryLayer},
+        },
+        request::Request,
+        response::{self, Response},
+        Extensions,
+    };
+
+    type MockResponse = Response<Controlled<ResponseKind, Full<Bytes>>>;
+
+    fn config() -> RetryConfig {
+        RetryConfig {
+            max_attempts: NonZero::new(3).expect("3 is non-zero"),
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        }
+    }
+
+    fn ok_response(request: &Request<Full<Bytes>>) -> MockResponse {
+        Response::from_parts(
+            response::Parts {
+                session: request.session(),
+                extensions: Extensions::new(),
+            },
+            Controlled::new(ResponseKind::Ok, Full::new(Bytes::new())),
+        )
+    }
+
+    #[tokio::test]
+    async fn retry_idempotent() {
+        let (mut service, mut handle) = spawn_layer::<Request<Full<Bytes>>, MockResponse, _>(
+            RetryLayer::new(config(), |_: ServiceDescriptor, _: ProcedureDescriptor| {
+                true
+            }),
+        );
+
+        assert_pending!(handle.poll_request());
+        assert_ready!(service.poll_ready()).expect("should be ready");
+
+        let response = tokio::spawn(service.call(request()));
+
+        let (_, send_response) = handle.next_request().await.expect("should receive request");
+        send_response.send_error(GenericError::new(ErrorCode::INTERNAL_SERVER_ERROR));
+
+        let (actual, send_response) = handle.next_request().await.expect("should be retried");
+        send_response.send_response(ok_response(&actual));
+
+        response
+            .await
+            .expect("should be able to join")
+            .expect("retried request should succeed");
+    }
+
+    #[tokio::test]
+    async fn give_up_after_max_attempts() {
+        let (mut service, mut handle) = spawn_layer::<Request<Full<Bytes>>, MockResponse, _>(
+            RetryLayer::new(config(), |_: ServiceDescriptor, _: ProcedureDescriptor| {
+                true
+            }),
+        );
+
+        assert_ready!(service.poll_ready()).expect("should be ready");
+        let response = tokio::spawn(service.call(request()));
+
+        for _ in 0..config().max_attempts.get() {
+            let (_, send_response) = handle.next_request().await.expect("should receive request");
+            send_response.send_error(GenericError::new(ErrorCode::INTERNAL_SERVER_ERROR));
+        }
+
+        _ = response
+            .await
+            .expect("should be able to join")
+            .expect_err("request should fail after the last attempt");
+    }
+
+    #[tokio::test]
+    async fn no_retry_non_idempotent() {
+        let (mut service, mut handle) = spawn_layer::<Request<Full<Bytes>>, MockResponse, _>(
+            RetryLayer::new(config(), |_: ServiceDescriptor, _: ProcedureDescriptor| {
+                false
+            }),
+        );
+
+        assert_ready!(service.poll_ready()).expect("should be ready");
+        let response = tokio::spawn(service.call(request()));
+
+        let (_, send_response) = handle.next_request().await.expect("should receive request");
+        send_response.send_error(GenericError::new(ErrorCode::INTERNAL_SERVER_ERROR));
+
+        _ = response
+            .await
+            .expect("should be able to join")
+            .expect_err("non-idempotent request should not be retried");
+    }
+}
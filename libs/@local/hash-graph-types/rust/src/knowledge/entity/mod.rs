@@ -25,7 +25,7 @@ pub use self::{
 use crate::{
     knowledge::{
         link::LinkData,
-        property::{PatchError, PropertyMetadataObject},
+        property::{PatchError, PatchTestFailed, PropertyMetadataObject},
         Confidence, Property, PropertyMetadata, PropertyObject, PropertyPatchOperation,
         PropertyWithMetadata,
     },
@@ -158,6 +158,26 @@ impl Entity {
                         .replace(&path, property)
                         .change_context(PatchError)?;
                 }
+                PropertyPatchOperation::Test { path, value } => {
+                    if !properties_with_metadata
+                        .get(&path)
+                        .is_ok_and(|property| property.value_eq(&value))
+                    {
+                        return Err(Report::new(PatchTestFailed)
+                            .attach_printable(format!("path: {path:?}"))
+                            .change_context(PatchError));
+                    }
+                }
+                PropertyPatchOperation::Move { from, path } => {
+                    properties_with_metadata
+                        .move_property(&from, path)
+                        .change_context(PatchError)?;
+                }
+                PropertyPatchOperation::Copy { from, path } => {
+                    properties_with_metadata
+                        .copy_property(&from, path)
+                        .change_context(PatchError)?;
+                }
             }
         }
 
@@ -318,6 +338,7 @@ pub struct EntityEmbedding<'e> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::knowledge::{PropertyPath, PropertyPathElement};
 
     fn test_entity(json: &str) {
         let json_value: serde_json::Value = serde_json::from_str(json).expect("invalid JSON");
@@ -372,6 +393,70 @@ mod tests {
         test_entity(graph_test_data::entity::PAGE_V1);
     }
 
+    #[test]
+    fn patch_with_failing_test() {
+        let name = PropertyPath::from_iter([PropertyPathElement::from(
+            BaseUrl::new("https://example.com/@example/types/property-type/name/".to_owned())
+                .expect("invalid URL"),
+        )]);
+        let mut entity = Entity {
+            properties: serde_json::from_value(serde_json::json!({
+                "https://example.com/@example/types/property-type/name/": "Alice"
+            }))
+            .expect("invalid properties"),
+            link_data: None,
+            metadata: serde_json::from_value(serde_json::json!({
+                "recordId": {
+                    "entityId": "2af6e3c4-3b64-4a2e-b5b5-7a1bfb0a2c37~1f4bc7e4-d0c3-4a3e-97c7-8f3b1c5a4f1e",
+                    "editionId": "5c4b0fa1-3c3e-4d8f-9d3a-6c2e9b0a1f2d"
+                },
+                "temporalVersioning": {
+                    "decisionTime": {
+                        "start": { "kind": "inclusive", "limit": "2024-01-01T00:00:00Z" },
+                        "end": { "kind": "unbounded" }
+                    },
+                    "transactionTime": {
+                        "start": { "kind": "inclusive", "limit": "2024-01-01T00:00:00Z" },
+                        "end": { "kind": "unbounded" }
+                    }
+                },
+                "entityTypeIds": ["https://example.com/@example/types/entity-type/person/v/1"],
+                "archived": false,
+                "provenance": {
+                    "createdById": "2af6e3c4-3b64-4a2e-b5b5-7a1bfb0a2c37",
+                    "createdAtTransactionTime": "2024-01-01T00:00:00Z",
+                    "createdAtDecisionTime": "2024-01-01T00:00:00Z",
+                    "edition": {
+                        "createdById": "2af6e3c4-3b64-4a2e-b5b5-7a1bfb0a2c37"
+                    }
+                }
+            }))
+            .expect("invalid metadata"),
+        };
+        let original = entity.clone();
+
+        let report = entity
+            .patch([
+                PropertyPatchOperation::Replace {
+                    path: name.clone(),
+                    property: PropertyWithMetadata::from_parts(
+                        Property::Value(serde_json::json!("Bob")),
+                        None,
+                    )
+                    .expect("invalid property"),
+                },
+                PropertyPatchOperation::Test {
+                    path: name,
+                    value: Property::Value(serde_json::json!("Alice")),
+                },
+            ])
+            .expect_err("the test operation should fail");
+        assert!(report.contains::<PatchTestFailed>());
+
+        // The operations before the failing test are not applied
+        assert_eq!(entity, original);
+    }
+
     mod diff {
         use alloc::borrow::Cow;
         use core::iter::once;
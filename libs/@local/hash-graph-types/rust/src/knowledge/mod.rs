@@ -5,10 +5,10 @@ pub use self::{
     confidence::Confidence,
     entity::EntityTypeIdDiff,
     property::{
-        ArrayMetadata, ObjectMetadata, PatchError, Property, PropertyDiff, PropertyMetadata,
-        PropertyMetadataObject, PropertyObject, PropertyPatchOperation, PropertyPath,
-        PropertyPathElement, PropertyProvenance, PropertyWithMetadata, PropertyWithMetadataObject,
-        ValueMetadata, ValueWithMetadata,
+        ArrayMetadata, ObjectMetadata, PatchError, PatchTestFailed, Property, PropertyDiff,
+        PropertyMetadata, PropertyMetadataObject, PropertyObject, PropertyPatchOperation,
+        PropertyPath, PropertyPathElement, PropertyProvenance, PropertyWithMetadata,
+        PropertyWithMetadataObject, ValueMetadata, ValueWithMetadata,
     },
 };
 
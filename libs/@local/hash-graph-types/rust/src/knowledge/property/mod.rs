@@ -23,7 +23,7 @@ pub use self::{
         PropertyProvenance, ValueMetadata, ValueWithMetadata,
     },
     object::{PropertyObject, PropertyWithMetadataObject},
-    patch::{PatchError, PropertyPatchOperation},
+    patch::{PatchError, PatchTestFailed, PropertyPatchOperation},
     path::{PropertyPath, PropertyPathElement},
 };
 
@@ -73,6 +73,8 @@ pub enum PropertyPathError {
     UnexpectedValue,
     #[error("Properties and metadata do not match")]
     PropertyMetadataMismatch,
+    #[error("Cannot move a property into one of its children")]
+    MoveIntoChild,
 }
 
 impl PropertyWithMetadata {
@@ -94,6 +96,82 @@ impl PropertyWithMetadata {
         }
     }
 
+    /// Returns the property at the given path.
+    ///
+    /// # Errors
+    ///
+    /// - If the path does not point to a property.
+    pub fn get(&self, path: &PropertyPath<'_>) -> Result<&Self, Report<PropertyPathError>> {
+        let mut value = self;
+        for path_element in path.as_ref() {
+            match (value, path_element) {
+                (
+                    Self::Array {
+                        value: elements, ..
+                    },
+                    PropertyPathElement::Index(index),
+                ) => {
+                    let len = elements.len();
+                    value = elements
+                        .get(*index)
+                        .ok_or(PropertyPathError::IndexOutOfBounds { index: *index, len })?;
+                }
+                (Self::Array { .. }, PropertyPathElement::Property(key)) => {
+                    return Err(Report::new(PropertyPathError::UnexpectedKey {
+                        key: key.clone().into_owned(),
+                    }));
+                }
+                (
+                    Self::Object {
+                        value: properties, ..
+                    },
+                    PropertyPathElement::Property(key),
+                ) => {
+                    value = properties.get(key.as_ref()).ok_or_else(|| {
+                        PropertyPathError::InvalidKey {
+                            key: key.clone().into_owned(),
+                        }
+                    })?;
+                }
+                (Self::Object { .. }, PropertyPathElement::Index(index)) => {
+                    return Err(Report::new(PropertyPathError::UnexpectedIndex {
+                        index: *index,
+                    }));
+                }
+                (Self::Value { .. }, _) => {
+                    return Err(Report::new(PropertyPathError::UnexpectedValue));
+                }
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Returns if the values of this property are equal to `property`, ignoring the metadata.
+    #[must_use]
+    pub fn value_eq(&self, property: &Property) -> bool {
+        match (self, property) {
+            (Self::Array { value, .. }, Property::Array(elements)) => {
+                value.len() == elements.len()
+                    && value
+                        .iter()
+                        .zip(elements)
+                        .all(|(lhs, rhs)| lhs.value_eq(rhs))
+            }
+            (Self::Object { value, .. }, Property::Object(object)) => {
+                value.len() == object.len()
+                    && value.iter().all(|(key, lhs)| {
+                        object
+                            .properties()
+                            .get(key)
+                            .is_some_and(|rhs| lhs.value_eq(rhs))
+                    })
+            }
+            (Self::Value(lhs), Property::Value(rhs)) => lhs.value == *rhs,
+            _ => false,
+        }
+    }
+
     fn get_mut(
         &mut self,
         path: &[PropertyPathElement<'_>],
@@ -214,15 +292,16 @@ impl PropertyWithMetadata {
         Ok(mem::replace(self.get_mut(path.as_ref())?, value))
     }
 
-    /// Removes the property at the given path.
+    /// Removes the property at the given path and returns it.
     ///
     /// # Errors
     ///
     /// - If the path is empty.
     /// - If the value cannot be removed from the parent, e.g. when attempting to remove an index
     ///   from an object or the index is out of bounds.
+    /// - If the object at the parent path does not contain the property.
     /// - The path to the last element is not valid.
-    pub fn remove(&mut self, path: &PropertyPath<'_>) -> Result<(), Report<PropertyPathError>> {
+    pub fn remove(&mut self, path: &PropertyPath<'_>) -> Result<Self, Report<PropertyPathError>> {
         let [path @ .., last] = path.as_ref() else {
             return Err(Report::new(PropertyPathError::EmptyPath));
         };
@@ -234,9 +313,8 @@ impl PropertyWithMetadata {
                 },
                 PropertyPathElement::Index(index),
             ) => {
-                if *index <= elements.len() {
-                    elements.remove(*index);
-                    Ok(())
+                if *index < elements.len() {
+                    Ok(elements.remove(*index))
                 } else {
                     Err(Report::new(PropertyPathError::IndexOutOfBounds {
                         index: *index,
@@ -254,10 +332,11 @@ impl PropertyWithMetadata {
                     value: properties, ..
                 },
                 PropertyPathElement::Property(key),
-            ) => {
-                properties.remove(key);
-                Ok(())
-            }
+            ) => properties.remove(key).ok_or_else(|| {
+                Report::new(PropertyPathError::InvalidKey {
+                    key: key.clone().into_owned(),
+                })
+            }),
             (Self::Object { .. }, PropertyPathElement::Index(index)) => {
                 Err(Report::new(PropertyPathError::UnexpectedIndex {
                     index: *index,
@@ -267,6 +346,45 @@ impl PropertyWithMetadata {
         }
     }
 
+    /// Moves the property at `from`, including its metadata, to `path`.
+    ///
+    /// # Errors
+    ///
+    /// - If `from` is a proper prefix of `path`.
+    /// - If there is no property at `from`.
+    /// - If the property cannot be added at `path`, see [`add`].
+    ///
+    /// [`add`]: Self::add
+    pub fn move_property(
+        &mut self,
+        from: &PropertyPath<'_>,
+        path: PropertyPath<'_>,
+    ) -> Result<(), Report<PropertyPathError>> {
+        if path.len() > from.len() && path.starts_with(from) {
+            return Err(Report::new(PropertyPathError::MoveIntoChild));
+        }
+
+        let property = self.remove(from)?;
+        self.add(path, property)
+    }
+
+    /// Adds a copy of the property at `from`, including its metadata, at `path`.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no property at `from`.
+    /// - If the property cannot be added at `path`, see [`add`].
+    ///
+    /// [`add`]: Self::add
+    pub fn copy_property(
+        &mut self,
+        from: &PropertyPath<'_>,
+        path: PropertyPath<'_>,
+    ) -> Result<(), Report<PropertyPathError>> {
+        let property = self.get(from)?.clone();
+        self.add(path, property)
+    }
+
     /// Creates a unified representation of the property and its metadata.
     ///
     /// # Errors
@@ -588,3 +706,144 @@ impl PartialEq<JsonValue> for Property {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use type_system::url::BaseUrl;
+
+    use super::{
+        Property, PropertyPath, PropertyPathElement, PropertyPathError, PropertyWithMetadata,
+    };
+
+    fn create_path(properties: impl IntoIterator<Item = usize>) -> PropertyPath<'static> {
+        properties
+            .into_iter()
+            .map(|property| {
+                PropertyPathElement::from(
+                    BaseUrl::new(format!("http://example.com/property-{property}/"))
+                        .expect("invalid URL"),
+                )
+            })
+            .collect()
+    }
+
+    fn properties() -> PropertyWithMetadata {
+        serde_json::from_value(serde_json::json!({
+            "value": {
+                "http://example.com/property-1/": {
+                    "value": "foo",
+                    "metadata": {
+                        "dataTypeId": "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1"
+                    }
+                },
+                "http://example.com/property-2/": {
+                    "value": {
+                        "http://example.com/property-3/": {
+                            "value": "bar",
+                            "metadata": {}
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("invalid JSON")
+    }
+
+    #[test]
+    fn test_value() {
+        let properties = properties();
+        let property = properties
+            .get(&create_path([1]))
+            .expect("property should exist");
+        assert!(property.value_eq(&Property::Value(serde_json::json!("foo"))));
+        assert!(!property.value_eq(&Property::Value(serde_json::json!("bar"))));
+        _ = properties
+            .get(&create_path([4]))
+            .expect_err("property should not exist");
+    }
+
+    #[test]
+    fn remove() {
+        let mut properties = properties();
+        let removed = properties
+            .get(&create_path([2, 3]))
+            .expect("property should exist")
+            .clone();
+
+        assert_eq!(
+            properties
+                .remove(&create_path([2, 3]))
+                .expect("property should be removable"),
+            removed
+        );
+        _ = properties
+            .get(&create_path([2, 3]))
+            .expect_err("property should be removed");
+
+        // Removing a missing key is an error, see RFC 6902, section 4.2
+        let report = properties
+            .remove(&create_path([2, 3]))
+            .expect_err("removed property should not be removable");
+        assert!(matches!(
+            report.current_context(),
+            PropertyPathError::InvalidKey { .. }
+        ));
+        _ = properties
+            .remove(&create_path([4, 3]))
+            .expect_err("property of missing parent should not be removable");
+    }
+
+    #[test]
+    fn move_property() {
+        let mut properties = properties();
+        let moved = properties
+            .get(&create_path([1]))
+            .expect("property should exist")
+            .clone();
+
+        properties
+            .move_property(&create_path([1]), create_path([2, 4]))
+            .expect("property should be movable");
+        _ = properties
+            .get(&create_path([1]))
+            .expect_err("property should be removed");
+        assert_eq!(
+            properties
+                .get(&create_path([2, 4]))
+                .expect("property should be moved"),
+            &moved
+        );
+
+        _ = properties
+            .move_property(&create_path([2]), create_path([2, 5]))
+            .expect_err("property should not be movable into itself");
+        _ = properties
+            .move_property(&create_path([1]), create_path([5]))
+            .expect_err("removed property should not be movable");
+    }
+
+    #[test]
+    fn copy_property() {
+        let mut properties = properties();
+        let copied = properties
+            .get(&create_path([2]))
+            .expect("property should exist")
+            .clone();
+
+        properties
+            .copy_property(&create_path([2]), create_path([4]))
+            .expect("property should be copyable");
+        assert_eq!(
+            properties
+                .get(&create_path([2]))
+                .expect("property should still exist"),
+            &copied
+        );
+        assert_eq!(
+            properties
+                .get(&create_path([4]))
+                .expect("property should be copied"),
+            &copied
+        );
+    }
+}
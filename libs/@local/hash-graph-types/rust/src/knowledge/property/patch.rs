@@ -1,7 +1,7 @@
 use serde::Deserialize;
 use thiserror::Error;
 
-use crate::knowledge::{PropertyPath, PropertyWithMetadata};
+use crate::knowledge::{Property, PropertyPath, PropertyWithMetadata};
 
 #[derive(Debug, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -18,8 +18,29 @@ pub enum PropertyPatchOperation {
         path: PropertyPath<'static>,
         property: PropertyWithMetadata,
     },
+    /// Fails the patch if the property at `path` does not equal `value`.
+    ///
+    /// Only the values are compared, the metadata of the property is ignored.
+    Test {
+        path: PropertyPath<'static>,
+        value: Property,
+    },
+    /// Removes the property at `from` and adds it, including its metadata, at `path`.
+    Move {
+        from: PropertyPath<'static>,
+        path: PropertyPath<'static>,
+    },
+    /// Adds a copy of the property at `from`, including its metadata, at `path`.
+    Copy {
+        from: PropertyPath<'static>,
+        path: PropertyPath<'static>,
+    },
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
 #[error("Failed to apply patch")]
 pub struct PatchError;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+#[error("The tested property does not match the expected value")]
+pub struct PatchTestFailed;
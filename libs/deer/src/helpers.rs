@@ -123,6 +123,30 @@ impl<'de> Deserialize<'de> for ExpectNone {
     }
 }
 
+/// Schema of a variant of an externally tagged enum.
+///
+/// The variant is an object with the name of the variant as its only property, the value of the
+/// property is described by the reference.
+pub struct ExternallyTaggedVariant(pub &'static str, pub Reference);
+
+impl Serialize for ExternallyTaggedVariant {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Self(name, reference) = *self;
+
+        let mut map = serializer.serialize_map(Some(4))?;
+
+        map.serialize_entry("additionalProperties", &false)?;
+        map.serialize_entry("properties", &Properties([(name, reference)]))?;
+        map.serialize_entry("required", &[name])?;
+        map.serialize_entry("type", "object")?;
+
+        map.end()
+    }
+}
+
 // TODO: consider adding an error attachment marker type for "short-circuit"
 
 pub struct Properties<const N: usize>(pub [(&'static str, Reference); N]);
@@ -0,0 +1,19 @@
+use alloc::borrow::{Cow, ToOwned};
+
+use error_stack::Result;
+
+use crate::{error::DeserializeError, Deserialize, Deserializer};
+
+// We're unable to know if the deserializer is able to provide borrowed data, therefore we always
+// deserialize into the owned variant.
+impl<'de, T> Deserialize<'de> for Cow<'_, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: Deserialize<'de>,
+{
+    type Reflection = <T::Owned as Deserialize<'de>>::Reflection;
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, DeserializeError> {
+        T::Owned::deserialize(deserializer).map(Cow::Owned)
+    }
+}
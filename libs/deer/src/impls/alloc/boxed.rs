@@ -0,0 +1,47 @@
+use alloc::boxed::Box;
+
+macro_rules! impl_owned {
+    ($ptr:ident) => {
+        impl<'de, T> $crate::Deserialize<'de> for $ptr<T>
+        where
+            T: $crate::Deserialize<'de>,
+        {
+            type Reflection = T::Reflection;
+
+            fn deserialize<D: $crate::Deserializer<'de>>(
+                deserializer: D,
+            ) -> error_stack::Result<Self, $crate::error::DeserializeError> {
+                T::deserialize(deserializer).map($ptr::new)
+            }
+        }
+
+        impl<'de> $crate::Deserialize<'de> for $ptr<str> {
+            type Reflection = str;
+
+            fn deserialize<D: $crate::Deserializer<'de>>(
+                deserializer: D,
+            ) -> error_stack::Result<Self, $crate::error::DeserializeError> {
+                <::alloc::string::String as $crate::Deserialize<'de>>::deserialize(deserializer)
+                    .map($ptr::from)
+            }
+        }
+
+        impl<'de, T> $crate::Deserialize<'de> for $ptr<[T]>
+        where
+            T: $crate::Deserialize<'de>,
+        {
+            type Reflection = <::alloc::vec::Vec<T> as $crate::Deserialize<'de>>::Reflection;
+
+            fn deserialize<D: $crate::Deserializer<'de>>(
+                deserializer: D,
+            ) -> error_stack::Result<Self, $crate::error::DeserializeError> {
+                <::alloc::vec::Vec<T> as $crate::Deserialize<'de>>::deserialize(deserializer)
+                    .map($ptr::from)
+            }
+        }
+    };
+}
+
+pub(super) use impl_owned;
+
+impl_owned!(Box);
@@ -0,0 +1,24 @@
+use alloc::collections::BTreeMap;
+use core::fmt::Display;
+
+use error_stack::{Result, ResultExt};
+
+use crate::{
+    error::DeserializeError,
+    impls::alloc::collections::{MapReflection, MapVisitor},
+    Deserialize, Deserializer,
+};
+
+impl<'de, K, V> Deserialize<'de> for BTreeMap<K, V>
+where
+    K: Deserialize<'de> + Display + Ord,
+    V: Deserialize<'de>,
+{
+    type Reflection = MapReflection<K::Reflection, V::Reflection>;
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_object(MapVisitor::<K, V, Self>::new())
+            .change_context(DeserializeError)
+    }
+}
@@ -0,0 +1,198 @@
+use alloc::{collections::BTreeMap, string::ToString};
+use core::{fmt::Display, marker::PhantomData};
+
+use error_stack::{Report, Result, ResultExt};
+
+use crate::{
+    error::{
+        ArrayAccessError, DuplicateKey, DuplicateKeyError, Location, ObjectAccessError,
+        ResultExtPrivate, Variant, VisitorError,
+    },
+    ArrayAccess, Deserialize, Deserializer, Document, FieldVisitor, ObjectAccess, Reflection,
+    Schema, Visitor,
+};
+
+mod binary_heap;
+mod btree_map;
+mod btree_set;
+mod linked_list;
+mod vec_deque;
+
+/// Visitor used by every collection that is deserialized from an array, like [`Vec`] or
+/// [`BTreeSet`].
+///
+/// Errors of individual items are collected (including their index) and reported together once
+/// the array has been fully consumed.
+///
+/// [`Vec`]: alloc::vec::Vec
+/// [`BTreeSet`]: alloc::collections::BTreeSet
+pub(crate) struct SequenceVisitor<T, C>(PhantomData<fn() -> *const (T, C)>);
+
+impl<T, C> SequenceVisitor<T, C> {
+    pub(crate) const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'de, T, C> Visitor<'de> for SequenceVisitor<T, C>
+where
+    T: Deserialize<'de>,
+    C: Deserialize<'de> + Default + Extend<T>,
+{
+    type Value = C;
+
+    fn expecting(&self) -> Document {
+        C::reflection()
+    }
+
+    fn visit_array<A>(self, mut array: A) -> Result<Self::Value, VisitorError>
+    where
+        A: ArrayAccess<'de>,
+    {
+        let mut collection = C::default();
+        let mut errors: Result<(), ArrayAccessError> = Ok(());
+
+        let mut index = 0;
+        while let Some(item) = array.next::<T>() {
+            match item {
+                Ok(item) if errors.is_ok() => collection.extend(core::iter::once(item)),
+                // We have already failed, there's no need to keep the item around.
+                Ok(_) => {}
+                Err(error) => errors.extend_one(error.attach(Location::Array(index))),
+            }
+
+            index += 1;
+        }
+
+        if let Err(error) = array.end() {
+            errors.extend_one(error);
+        }
+
+        errors.map(|()| collection).change_context(VisitorError)
+    }
+}
+
+/// Reflection of every collection that is deserialized from an array.
+///
+/// Sets do not declare `uniqueItems`, as (like `serde`) duplicate items are accepted and
+/// deduplicated instead of being rejected.
+pub struct SequenceReflection<T: ?Sized>(PhantomData<fn() -> *const T>);
+
+impl<T: Reflection + ?Sized> Reflection for SequenceReflection<T> {
+    fn schema(doc: &mut Document) -> Schema {
+        Schema::new("array").with("items", doc.add::<T>())
+    }
+}
+
+/// Minimal interface over all map types, used to detect duplicate keys during deserialization.
+pub(crate) trait Map<K, V>: Default {
+    /// Insert the key-value pair, returns the key if it was already present.
+    fn try_insert(&mut self, key: K, value: V) -> Option<K>;
+}
+
+impl<K: Ord, V> Map<K, V> for BTreeMap<K, V> {
+    fn try_insert(&mut self, key: K, value: V) -> Option<K> {
+        if self.contains_key(&key) {
+            return Some(key);
+        }
+
+        self.insert(key, value);
+        None
+    }
+}
+
+struct MapFieldVisitor<'a, K, V, M> {
+    map: &'a mut M,
+    _marker: PhantomData<fn() -> *const (K, V)>,
+}
+
+impl<'a, 'de, K, V, M> FieldVisitor<'de> for MapFieldVisitor<'a, K, V, M>
+where
+    K: Deserialize<'de> + Display,
+    V: Deserialize<'de>,
+    M: Map<K, V>,
+{
+    type Key = K;
+    type Value = ();
+
+    fn visit_value<D>(self, key: Self::Key, deserializer: D) -> Result<Self::Value, VisitorError>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = V::deserialize(deserializer)
+            .attach_lazy(|| Location::Entry(key.to_string()))
+            .change_context(VisitorError)?;
+
+        if let Some(key) = self.map.try_insert(key, value) {
+            let key = key.to_string();
+
+            return Err(Report::new(DuplicateKeyError.into_error())
+                .attach(DuplicateKey::new(key.clone()))
+                .attach(Location::Entry(key))
+                .change_context(VisitorError));
+        }
+
+        Ok(())
+    }
+}
+
+/// Visitor used by every map, like [`BTreeMap`].
+///
+/// Unlike most other deserializers a key that is present multiple times is considered an error
+/// and will not silently overwrite the previous value.
+pub(crate) struct MapVisitor<K, V, M>(
+    PhantomData<fn() -> *const (K, V)>,
+    PhantomData<fn() -> *const M>,
+);
+
+impl<K, V, M> MapVisitor<K, V, M> {
+    pub(crate) const fn new() -> Self {
+        Self(PhantomData, PhantomData)
+    }
+}
+
+impl<'de, K, V, M> Visitor<'de> for MapVisitor<K, V, M>
+where
+    K: Deserialize<'de> + Display,
+    V: Deserialize<'de>,
+    M: Deserialize<'de> + Map<K, V>,
+{
+    type Value = M;
+
+    fn expecting(&self) -> Document {
+        M::reflection()
+    }
+
+    fn visit_object<A>(self, mut object: A) -> Result<Self::Value, VisitorError>
+    where
+        A: ObjectAccess<'de>,
+    {
+        let mut map = M::default();
+        let mut errors: Result<(), ObjectAccessError> = Ok(());
+
+        while let Some(field) = object.field(MapFieldVisitor::<K, V, M> {
+            map: &mut map,
+            _marker: PhantomData,
+        }) {
+            if let Err(error) = field {
+                errors.extend_one(error);
+            }
+        }
+
+        if let Err(error) = object.end() {
+            errors.extend_one(error);
+        }
+
+        errors.map(|()| map).change_context(VisitorError)
+    }
+}
+
+pub struct MapReflection<K: ?Sized, V: ?Sized>(PhantomData<fn() -> *const (*const K, *const V)>);
+
+impl<K: Reflection + ?Sized, V: Reflection + ?Sized> Reflection for MapReflection<K, V> {
+    fn schema(doc: &mut Document) -> Schema {
+        Schema::new("object")
+            .with("propertyNames", doc.add::<K>())
+            .with("additionalProperties", doc.add::<V>())
+    }
+}
@@ -0,0 +1,8 @@
+mod borrow;
+mod boxed;
+pub(super) mod collections;
+mod rc;
+mod string;
+#[cfg(target_has_atomic = "ptr")]
+mod sync;
+mod vec;
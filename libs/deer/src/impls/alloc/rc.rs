@@ -0,0 +1,5 @@
+use alloc::rc::Rc;
+
+use crate::impls::alloc::boxed::impl_owned;
+
+impl_owned!(Rc);
@@ -0,0 +1,36 @@
+use alloc::{borrow::ToOwned, string::String};
+
+use error_stack::{Result, ResultExt};
+
+use crate::{
+    error::{DeserializeError, VisitorError},
+    Deserialize, Deserializer, Document, Visitor,
+};
+
+struct StringVisitor;
+
+impl<'de> Visitor<'de> for StringVisitor {
+    type Value = String;
+
+    fn expecting(&self) -> Document {
+        Self::Value::reflection()
+    }
+
+    fn visit_str(self, value: &str) -> Result<Self::Value, VisitorError> {
+        Ok(value.to_owned())
+    }
+
+    fn visit_string(self, value: String) -> Result<Self::Value, VisitorError> {
+        Ok(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for String {
+    type Reflection = str;
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_string(StringVisitor)
+            .change_context(DeserializeError)
+    }
+}
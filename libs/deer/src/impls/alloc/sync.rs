@@ -0,0 +1,5 @@
+use alloc::sync::Arc;
+
+use crate::impls::alloc::boxed::impl_owned;
+
+impl_owned!(Arc);
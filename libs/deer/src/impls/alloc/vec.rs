@@ -0,0 +1,22 @@
+use alloc::vec::Vec;
+
+use error_stack::{Result, ResultExt};
+
+use crate::{
+    error::DeserializeError,
+    impls::alloc::collections::{SequenceReflection, SequenceVisitor},
+    Deserialize, Deserializer,
+};
+
+impl<'de, T> Deserialize<'de> for Vec<T>
+where
+    T: Deserialize<'de>,
+{
+    type Reflection = SequenceReflection<T::Reflection>;
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_array(SequenceVisitor::<T, Self>::new())
+            .change_context(DeserializeError)
+    }
+}
@@ -64,7 +64,7 @@ impl<'de> Visitor<'de> for OrderingVisitor {
 
 impl Reflection for Ordering {
     fn schema(_: &mut Document) -> Schema {
-        Schema::new("string").with("oneOf", ["Less", "Equal", "Greater"])
+        Schema::new("string").with("enum", ["Less", "Equal", "Greater"])
     }
 }
 
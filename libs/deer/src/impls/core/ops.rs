@@ -11,10 +11,9 @@ use crate::{
         ObjectAccessError, ResultExtPrivate, Variant, VisitorError,
     },
     ext::TupleExt,
-    helpers::Properties,
+    helpers::{ExternallyTaggedVariant, Properties},
     identifier,
     impls::UnitVariantVisitor,
-    schema::Reference,
     value::NoneDeserializer,
     ArrayAccess, Deserialize, Deserializer, Document, EnumVisitor, FieldVisitor, ObjectAccess,
     Reflection, Schema, StructVisitor,
@@ -74,21 +73,14 @@ where
     T: Reflection + ?Sized,
 {
     fn schema(doc: &mut Document) -> Schema {
-        #[derive(serde::Serialize)]
-        enum BoundOneOf {
-            Included(Reference),
-            Excluded(Reference),
-            Unbounded(Reference),
-        }
-
         // TODO: the case where "Unbounded" as a single value is possible cannot be
         //  represented right now with deer Schema capabilities
         Schema::new("object").with(
             "oneOf",
             [
-                BoundOneOf::Included(doc.add::<T>()),
-                BoundOneOf::Excluded(doc.add::<T>()),
-                BoundOneOf::Unbounded(doc.add::<<() as Deserialize>::Reflection>()),
+                ExternallyTaggedVariant("Included", doc.add::<T>()),
+                ExternallyTaggedVariant("Excluded", doc.add::<T>()),
+                ExternallyTaggedVariant("Unbounded", doc.add::<<() as Deserialize>::Reflection>()),
             ],
         )
     }
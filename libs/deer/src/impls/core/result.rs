@@ -7,7 +7,7 @@ use crate::{
         DeserializeError, ExpectedVariant, Location, ReceivedVariant, UnknownVariantError, Variant,
         VisitorError,
     },
-    schema::Reference,
+    helpers::ExternallyTaggedVariant,
     Deserialize, Deserializer, Document, EnumVisitor, Reflection, Schema, Visitor,
 };
 
@@ -119,29 +119,30 @@ where
     /// ```json
     /// {
     ///     "type": "object",
-    ///     "additionalProperties": false,
     ///     "oneOf": [
-    ///         {"properties": {"Ok": <ref>}}
-    ///         {"properties": {"Err": <ref>}}
+    ///         {
+    ///             "type": "object",
+    ///             "properties": {"Ok": <ref>},
+    ///             "required": ["Ok"],
+    ///             "additionalProperties": false
+    ///         },
+    ///         {
+    ///             "type": "object",
+    ///             "properties": {"Err": <ref>},
+    ///             "required": ["Err"],
+    ///             "additionalProperties": false
+    ///         }
     ///     ]
     /// }
     /// ```
     fn schema(doc: &mut Document) -> Schema {
-        #[derive(serde::Serialize)]
-        enum Properties {
-            Ok(Reference),
-            Err(Reference),
-        }
-
-        Schema::new("object")
-            .with(
-                "oneOf",
-                [
-                    Properties::Ok(doc.add::<T>()),
-                    Properties::Err(doc.add::<E>()),
-                ],
-            )
-            .with("additionalProperties", false)
+        Schema::new("object").with(
+            "oneOf",
+            [
+                ExternallyTaggedVariant("Ok", doc.add::<T>()),
+                ExternallyTaggedVariant("Err", doc.add::<E>()),
+            ],
+        )
     }
 }
 
@@ -2,7 +2,11 @@ use error_stack::Result;
 
 use crate::{error::VisitorError, Deserialize, Document, OptionalVisitor};
 
+#[cfg(feature = "alloc")]
+mod alloc;
 mod core;
+#[cfg(feature = "std")]
+mod std;
 
 pub(crate) struct UnitVariantVisitor;
 
@@ -0,0 +1,59 @@
+use core::{
+    fmt::Display,
+    hash::{BuildHasher, Hash},
+};
+use std::collections::{HashMap, HashSet};
+
+use error_stack::{Result, ResultExt};
+
+use crate::{
+    error::DeserializeError,
+    impls::alloc::collections::{
+        Map, MapReflection, MapVisitor, SequenceReflection, SequenceVisitor,
+    },
+    Deserialize, Deserializer,
+};
+
+impl<K, V, S> Map<K, V> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn try_insert(&mut self, key: K, value: V) -> Option<K> {
+        if self.contains_key(&key) {
+            return Some(key);
+        }
+
+        self.insert(key, value);
+        None
+    }
+}
+
+impl<'de, K, V, S> Deserialize<'de> for HashMap<K, V, S>
+where
+    K: Deserialize<'de> + Display + Eq + Hash,
+    V: Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    type Reflection = MapReflection<K::Reflection, V::Reflection>;
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_object(MapVisitor::<K, V, Self>::new())
+            .change_context(DeserializeError)
+    }
+}
+
+impl<'de, T, S> Deserialize<'de> for HashSet<T, S>
+where
+    T: Deserialize<'de> + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Reflection = SequenceReflection<T::Reflection>;
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_array(SequenceVisitor::<T, Self>::new())
+            .change_context(DeserializeError)
+    }
+}
@@ -0,0 +1,3 @@
+mod collections;
+mod net;
+mod path;
@@ -0,0 +1,68 @@
+use alloc::borrow::ToOwned;
+use core::{marker::PhantomData, str::FromStr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use error_stack::{Report, Result, ResultExt};
+
+use crate::{
+    error::{DeserializeError, ExpectedType, ReceivedValue, ValueError, Variant, VisitorError},
+    Deserialize, Deserializer, Document, Reflection, Schema, Visitor,
+};
+
+/// Visitor for all types, which are represented as a string and parsed through [`FromStr`].
+struct FromStrVisitor<T>(PhantomData<fn() -> *const T>);
+
+impl<'de, T> Visitor<'de> for FromStrVisitor<T>
+where
+    T: Deserialize<'de> + FromStr,
+{
+    type Value = T;
+
+    fn expecting(&self) -> Document {
+        T::reflection()
+    }
+
+    fn visit_str(self, value: &str) -> Result<Self::Value, VisitorError> {
+        value.parse().map_err(|_error| {
+            Report::new(ValueError.into_error())
+                .attach(ExpectedType::new(self.expecting()))
+                .attach(ReceivedValue::new(value.to_owned()))
+                .change_context(VisitorError)
+        })
+    }
+}
+
+macro_rules! impl_from_str {
+    ($($ty:ident => $format:literal),* $(,)?) => {
+        $(
+            impl Reflection for $ty {
+                fn schema(_: &mut Document) -> Schema {
+                    Schema::new("string").with("format", $format)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                type Reflection = Self;
+
+                fn deserialize<D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, DeserializeError> {
+                    deserializer
+                        .deserialize_str(FromStrVisitor(PhantomData))
+                        .change_context(DeserializeError)
+                }
+            }
+        )*
+    };
+}
+
+// `ip` and `socket-address` are not formats defined by JSON-Schema, they are used to
+// distinguish the type from a plain string.
+impl_from_str![
+    IpAddr => "ip",
+    Ipv4Addr => "ipv4",
+    Ipv6Addr => "ipv6",
+    SocketAddr => "socket-address",
+    SocketAddrV4 => "socket-address-v4",
+    SocketAddrV6 => "socket-address-v6",
+];
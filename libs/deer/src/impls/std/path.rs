@@ -0,0 +1,27 @@
+use std::path::{Path, PathBuf};
+
+use error_stack::Result;
+
+use crate::{error::DeserializeError, Deserialize, Deserializer, Document, Reflection, Schema};
+
+impl Reflection for Path {
+    fn schema(_: &mut Document) -> Schema {
+        Schema::new("string")
+    }
+}
+
+impl<'de> Deserialize<'de> for PathBuf {
+    type Reflection = Path;
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, DeserializeError> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<Path> {
+    type Reflection = Path;
+
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, DeserializeError> {
+        PathBuf::deserialize(deserializer).map(PathBuf::into_boxed_path)
+    }
+}
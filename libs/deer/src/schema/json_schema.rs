@@ -0,0 +1,329 @@
+//! Export of a [`Document`] as [JSON Schema (draft 2020-12)].
+//!
+//! The schema graph of a [`Document`] is mostly JSON Schema already, the exporter takes care of the
+//! parts which are not:
+//!
+//! * the document is annotated with the `$schema` dialect,
+//! * every definition is placed into `$defs` and every `$ref` is a valid URI-fragment encoded JSON
+//!   pointer to it,
+//! * `deer` specific types, which have no equivalent in JSON Schema, are translated: `none` (the
+//!   absence of a value) becomes the `false` schema, while `bytes` and `binary` are represented as
+//!   an array of octets.
+//!
+//! [JSON Schema (draft 2020-12)]: https://json-schema.org/draft/2020-12/json-schema-core
+
+#[cfg_attr(feature = "std", allow(unused_imports))]
+use alloc::string::String;
+use core::fmt::Write as _;
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use crate::{schema::SerializeSchema, Document, Schema};
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Escape a definition name, so that it can be used as segment of a JSON pointer inside of an URI
+/// fragment.
+///
+/// This first escapes according to [RFC 6901] (`~` and `/`) and then percent-encodes every
+/// character which is not allowed in an URI fragment according to [RFC 3986].
+///
+/// [RFC 6901]: https://datatracker.ietf.org/doc/html/rfc6901#section-3
+/// [RFC 3986]: https://datatracker.ietf.org/doc/html/rfc3986#section-3.5
+pub(crate) fn escape_pointer(name: &str) -> String {
+    let mut output = String::with_capacity(name.len());
+
+    for char in name.chars() {
+        match char {
+            '~' => output.push_str("~0"),
+            '/' => output.push_str("~1"),
+            'a'..='z'
+            | 'A'..='Z'
+            | '0'..='9'
+            | '-'
+            | '.'
+            | '_'
+            | '!'
+            | '$'
+            | '&'
+            | '\''
+            | '('
+            | ')'
+            | '*'
+            | '+'
+            | ','
+            | ';'
+            | '='
+            | ':'
+            | '@' => output.push(char),
+            char => {
+                let mut buffer = [0; 4];
+
+                for byte in char.encode_utf8(&mut buffer).bytes() {
+                    // writing to a `String` is infallible
+                    let _ = write!(output, "%{byte:02X}");
+                }
+            }
+        }
+    }
+
+    output
+}
+
+struct SerializeOctets;
+
+impl Serialize for SerializeOctets {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(3))?;
+
+        map.serialize_entry("type", "integer")?;
+        map.serialize_entry("minimum", &u8::MIN)?;
+        map.serialize_entry("maximum", &u8::MAX)?;
+
+        map.end()
+    }
+}
+
+struct SerializeJsonSchema<'a>(&'a Schema);
+
+impl Serialize for SerializeJsonSchema<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let schema = self.0;
+
+        match schema.ty() {
+            // there's no way to describe the absence of a value as type, the closest is the
+            // `false` schema, which fails for any value that is present.
+            "none" => serializer.serialize_bool(false),
+            "bytes" | "binary" => {
+                let mut map = serializer.serialize_map(Some(schema.other.len() + 2))?;
+
+                for (key, value) in &schema.other {
+                    map.serialize_entry(key, value)?;
+                }
+
+                map.serialize_entry("items", &SerializeOctets)?;
+                map.serialize_entry("type", "array")?;
+
+                map.end()
+            }
+            _ => SerializeSchema(schema).serialize(serializer),
+        }
+    }
+}
+
+struct SerializeDefinitions<'a>(&'a Document);
+
+impl Serialize for SerializeDefinitions<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Document {
+            schemas,
+            references,
+            ..
+        } = self.0;
+
+        let defs = references.iter().filter_map(|(key, reference)| {
+            schemas
+                .get(key)
+                .map(|schema| (reference.as_bare(), SerializeJsonSchema(schema)))
+        });
+
+        serializer.collect_map(defs)
+    }
+}
+
+/// A [`Document`] represented as [JSON Schema (draft 2020-12)].
+///
+/// Created through [`Document::json_schema`], use any [`Serializer`] (like `serde_json`) to
+/// retrieve the actual schema.
+///
+/// [JSON Schema (draft 2020-12)]: https://json-schema.org/draft/2020-12/json-schema-core
+pub struct JsonSchema<'a> {
+    document: &'a Document,
+}
+
+impl<'a> JsonSchema<'a> {
+    #[must_use]
+    pub const fn new(document: &'a Document) -> Self {
+        Self { document }
+    }
+}
+
+impl Serialize for JsonSchema<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(3))?;
+
+        let id = self
+            .document
+            .references
+            .get(&self.document.id)
+            .expect("`new()` should have created a schema for the main schema");
+
+        map.serialize_entry("$schema", DIALECT)?;
+        map.serialize_entry("$ref", &id.as_path())?;
+        map.serialize_entry("$defs", &SerializeDefinitions(self.document))?;
+
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use core::ops::Bound;
+
+    use jsonschema::{Draft, JSONSchema};
+    use serde_json::{json, to_value, Value};
+    use similar_asserts::assert_serde_eq;
+
+    use crate::{
+        helpers::ExpectNone, schema::json_schema::escape_pointer, Deserialize, Document,
+        Reflection, Schema,
+    };
+
+    #[test]
+    fn escape() {
+        assert_eq!(escape_pointer("0000-u8"), "0000-u8");
+        assert_eq!(
+            escape_pointer("0000-deer::schema::Node"),
+            "0000-deer::schema::Node"
+        );
+        assert_eq!(escape_pointer("0001-[u8; 8]"), "0001-%5Bu8;%208%5D");
+        assert_eq!(
+            escape_pointer("0002-alloc::vec::Vec<u8>"),
+            "0002-alloc::vec::Vec%3Cu8%3E"
+        );
+        assert_eq!(escape_pointer("a/b~c"), "a~1b~0c");
+        assert_eq!(escape_pointer("\u{fc}"), "%C3%BC");
+    }
+
+    struct Wrapper;
+
+    impl Reflection for Wrapper {
+        fn schema(doc: &mut Document) -> Schema {
+            let mut properties = BTreeMap::new();
+            properties.insert("array", doc.add::<<[u8; 2] as Deserialize>::Reflection>());
+            properties.insert("bytes", doc.add::<[u8]>());
+            properties.insert("none", doc.add::<ExpectNone>());
+
+            Schema::new("object")
+                .with("additionalProperties", false)
+                .with("properties", properties)
+        }
+    }
+
+    #[test]
+    fn export() {
+        let document = Wrapper::document();
+        let schema = to_value(document.json_schema()).expect("should be valid json");
+
+        assert_serde_eq!(
+            schema,
+            json!({
+              "$schema": "https://json-schema.org/draft/2020-12/schema",
+              "$ref": "#/$defs/0000-deer::schema::json_schema::tests::Wrapper",
+              "$defs": {
+                "0000-deer::schema::json_schema::tests::Wrapper": {
+                  "additionalProperties": false,
+                  "properties": {
+                    "array": {
+                      "$ref": "#/$defs/0001-deer::impls::core::array::ArrayReflection%3Cu8,%202%3E"
+                    },
+                    "bytes": {
+                      "$ref": "#/$defs/0003-%5Bu8%5D"
+                    },
+                    "none": {
+                      "$ref": "#/$defs/0004-deer::helpers::ExpectNone"
+                    }
+                  },
+                  "type": "object"
+                },
+                "0001-deer::impls::core::array::ArrayReflection<u8, 2>": {
+                  "items": {
+                    "$ref": "#/$defs/0002-u8"
+                  },
+                  "maxItems": 2,
+                  "minItems": 2,
+                  "type": "array"
+                },
+                "0002-u8": {
+                  "maximum": u8::MAX,
+                  "minimum": 0,
+                  "type": "integer"
+                },
+                "0003-[u8]": {
+                  "items": {
+                    "maximum": u8::MAX,
+                    "minimum": 0,
+                    "type": "integer"
+                  },
+                  "type": "array"
+                },
+                "0004-deer::helpers::ExpectNone": false
+              }
+            })
+        );
+    }
+
+    fn compile<T: Reflection + ?Sized>() -> JSONSchema {
+        let schema = to_value(T::document().json_schema()).expect("should be valid json");
+
+        JSONSchema::options()
+            .with_draft(Draft::Draft202012)
+            .compile(&schema)
+            .expect("should be a valid JSON Schema")
+    }
+
+    #[test]
+    fn validate_result() {
+        let schema = compile::<<Result<u8, bool> as Deserialize>::Reflection>();
+
+        for value in [Ok(12), Err(true)] {
+            let value: Result<u8, bool> = value;
+            let instance = to_value(value).expect("should be valid json");
+            assert!(schema.is_valid(&instance), "{instance} should be valid");
+        }
+
+        for instance in [
+            json!({}),
+            json!({"Ok": 12, "Err": true}),
+            json!({"Ok": true}),
+            json!({"Other": 12}),
+        ] {
+            assert!(!schema.is_valid(&instance), "{instance} should be invalid");
+        }
+    }
+
+    #[test]
+    fn validate_bound() {
+        let schema = compile::<<Bound<u8> as Deserialize>::Reflection>();
+
+        for instance in [
+            to_value(Bound::Included(12_u8)).expect("should be valid json"),
+            to_value(Bound::Excluded(12_u8)).expect("should be valid json"),
+            json!({"Unbounded": null}),
+        ] {
+            assert!(schema.is_valid(&instance), "{instance} should be valid");
+        }
+
+        for instance in [
+            json!({}),
+            json!({"Included": 12, "Excluded": 12}),
+            json!({"Included": "12"}),
+            Value::Null,
+        ] {
+            assert!(!schema.is_valid(&instance), "{instance} should be invalid");
+        }
+    }
+}
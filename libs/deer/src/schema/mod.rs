@@ -8,6 +8,10 @@ use core::any::{type_name, TypeId};
 
 use serde::{ser::SerializeMap, Serialize, Serializer};
 
+pub use self::json_schema::JsonSchema;
+
+mod json_schema;
+
 pub trait Reflection: 'static {
     fn schema(doc: &mut Document) -> Schema;
 
@@ -98,9 +102,14 @@ pub struct Reference {
 }
 
 impl Reference {
+    /// The reference as URI-fragment encoded JSON pointer into `$defs`.
+    ///
+    /// Type names may contain characters (like `<` or spaces) that are not allowed in an URI
+    /// fragment, these are percent-encoded, while the key in `$defs` stays unescaped.
     fn as_path(&self) -> String {
         let bare = self.as_bare();
-        format!("#/$defs/{bare}")
+        let pointer = json_schema::escape_pointer(&bare);
+        format!("#/$defs/{pointer}")
     }
 
     fn as_bare(&self) -> String {
@@ -218,6 +227,14 @@ impl Document {
         self.schemas.insert(type_id, schema);
     }
 
+    /// Export the document as a standalone [JSON Schema (draft 2020-12)].
+    ///
+    /// [JSON Schema (draft 2020-12)]: https://json-schema.org/draft/2020-12/json-schema-core
+    #[must_use]
+    pub const fn json_schema(&self) -> JsonSchema<'_> {
+        JsonSchema::new(self)
+    }
+
     pub fn add<T: Reflection + ?Sized>(&mut self) -> Reference {
         let type_id = TypeId::of::<T>();
 
@@ -306,6 +323,7 @@ mod tests {
     use alloc::collections::BTreeMap;
     #[cfg_attr(feature = "std", allow(unused_imports))]
     use alloc::{boxed::Box, vec::Vec};
+    use core::marker::PhantomData;
 
     use serde_json::{json, to_value};
     use similar_asserts::assert_serde_eq;
@@ -433,6 +451,35 @@ mod tests {
         );
     }
 
+    struct Generic<T>(PhantomData<T>);
+
+    impl<T: 'static> Reflection for Generic<T> {
+        fn schema(doc: &mut Document) -> Schema {
+            Schema::new("array").with("items", doc.add::<Self>())
+        }
+    }
+
+    #[test]
+    fn escaped_reference() {
+        let document = Generic::<u8>::document();
+        let document = to_value(document).expect("should be valid json");
+
+        assert_eq!(
+            document,
+            json!({
+              "$ref": "#/$defs/0000-deer::schema::tests::Generic%3Cu8%3E",
+              "$defs": {
+                "0000-deer::schema::tests::Generic<u8>": {
+                  "items": {
+                    "$ref": "#/$defs/0000-deer::schema::tests::Generic%3Cu8%3E"
+                  },
+                  "type": "array"
+                }
+              }
+            })
+        );
+    }
+
     // TODO: once `Describe` is implemented for `core` types replace this temporary type
     struct VecVertex;
 
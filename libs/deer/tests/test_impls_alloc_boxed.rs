@@ -0,0 +1,56 @@
+extern crate alloc;
+
+use alloc::{rc::Rc, sync::Arc};
+
+use deer::{Deserialize, Number};
+use deer_desert::{assert_tokens, assert_tokens_error, error, Token};
+use proptest::prelude::*;
+use serde_json::json;
+
+#[cfg(not(miri))]
+proptest! {
+    #[test]
+    fn box_ok(value in any::<u8>()) {
+        assert_tokens(&Box::new(value), &[Token::Number(Number::from(value))]);
+    }
+
+    #[test]
+    fn rc_ok(value in any::<u8>()) {
+        assert_tokens(&Rc::new(value), &[Token::Number(Number::from(value))]);
+    }
+
+    #[test]
+    fn arc_ok(value in any::<u8>()) {
+        assert_tokens(&Arc::new(value), &[Token::Number(Number::from(value))]);
+    }
+}
+
+#[test]
+fn boxed_slice_ok() {
+    let tokens = [
+        Token::Array { length: Some(2) },
+        Token::Number(Number::from(1)),
+        Token::Number(Number::from(2)),
+        Token::ArrayEnd,
+    ];
+
+    assert_tokens(&Box::<[u8]>::from([1, 2]), &tokens);
+    assert_tokens(&Rc::<[u8]>::from([1, 2]), &tokens);
+    assert_tokens(&Arc::<[u8]>::from([1, 2]), &tokens);
+}
+
+#[test]
+fn box_err() {
+    assert_tokens_error::<Box<u8>>(
+        &error!([{
+            ns: "deer",
+            id: ["value"],
+            properties: {
+                "expected": u8::reflection(),
+                "received": 256,
+                "location": []
+            }
+        }]),
+        &[Token::Number(Number::from(256))],
+    );
+}
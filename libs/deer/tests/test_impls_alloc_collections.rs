@@ -0,0 +1,162 @@
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+
+use deer::{Deserialize, Number};
+use deer_desert::{assert_tokens, assert_tokens_error, assert_tokens_with_assertion, error, Token};
+use serde_json::json;
+
+fn array() -> [Token; 5] {
+    [
+        Token::Array { length: Some(3) },
+        Token::Number(Number::from(2)),
+        Token::Number(Number::from(1)),
+        Token::Number(Number::from(3)),
+        Token::ArrayEnd,
+    ]
+}
+
+#[test]
+fn vec_ok() {
+    assert_tokens(&vec![2_u8, 1, 3], &array());
+}
+
+#[test]
+fn vec_empty_ok() {
+    assert_tokens(
+        &Vec::<u8>::new(),
+        &[Token::Array { length: Some(0) }, Token::ArrayEnd],
+    );
+}
+
+#[test]
+fn vec_err_items() {
+    assert_tokens_error::<Vec<u8>>(
+        &error!([
+            {
+                ns: "deer",
+                id: ["value"],
+                properties: {
+                    "expected": u8::reflection(),
+                    "received": 256,
+                    "location": [{"type": "array", "value": 1}]
+                }
+            },
+            {
+                ns: "deer",
+                id: ["type"],
+                properties: {
+                    "expected": u8::reflection(),
+                    "received": bool::reflection(),
+                    "location": [{"type": "array", "value": 2}]
+                }
+            }
+        ]),
+        &[
+            Token::Array { length: Some(3) },
+            Token::Number(Number::from(0)),
+            Token::Number(Number::from(256)),
+            Token::Bool(true),
+            Token::ArrayEnd,
+        ],
+    );
+}
+
+#[test]
+fn vec_deque_ok() {
+    assert_tokens(&VecDeque::from([2_u8, 1, 3]), &array());
+}
+
+#[test]
+fn linked_list_ok() {
+    assert_tokens(&LinkedList::from([2_u8, 1, 3]), &array());
+}
+
+#[test]
+fn binary_heap_ok() {
+    assert_tokens_with_assertion(
+        |heap: BinaryHeap<u8>| {
+            assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
+        },
+        &array(),
+    );
+}
+
+#[test]
+fn btree_set_ok() {
+    assert_tokens(&BTreeSet::from([1_u8, 2, 3]), &array());
+}
+
+#[test]
+fn btree_set_duplicate_ok() {
+    assert_tokens(
+        &BTreeSet::from([1_u8, 2]),
+        &[
+            Token::Array { length: Some(3) },
+            Token::Number(Number::from(1)),
+            Token::Number(Number::from(2)),
+            Token::Number(Number::from(1)),
+            Token::ArrayEnd,
+        ],
+    );
+}
+
+#[test]
+fn btree_map_ok() {
+    assert_tokens(
+        &BTreeMap::from([(String::from("a"), 1_u8), (String::from("b"), 2)]),
+        &[
+            Token::Object { length: Some(2) },
+            Token::Str("a"),
+            Token::Number(Number::from(1)),
+            Token::Str("b"),
+            Token::Number(Number::from(2)),
+            Token::ObjectEnd,
+        ],
+    );
+}
+
+#[test]
+fn btree_map_err_value() {
+    assert_tokens_error::<BTreeMap<String, u8>>(
+        &error!([{
+            ns: "deer",
+            id: ["value"],
+            properties: {
+                "expected": u8::reflection(),
+                "received": 256,
+                "location": [{"type": "entry", "value": "b"}]
+            }
+        }]),
+        &[
+            Token::Object { length: Some(2) },
+            Token::Str("a"),
+            Token::Number(Number::from(1)),
+            Token::Str("b"),
+            Token::Number(Number::from(256)),
+            Token::ObjectEnd,
+        ],
+    );
+}
+
+#[test]
+fn btree_map_err_duplicate() {
+    assert_tokens_error::<BTreeMap<String, u8>>(
+        &error!([{
+            ns: "deer",
+            id: ["duplicate", "key"],
+            properties: {
+                "key": "a",
+                "location": [{"type": "entry", "value": "a"}]
+            }
+        }]),
+        &[
+            Token::Object { length: Some(2) },
+            Token::Str("a"),
+            Token::Number(Number::from(1)),
+            Token::Str("a"),
+            Token::Number(Number::from(2)),
+            Token::ObjectEnd,
+        ],
+    );
+}
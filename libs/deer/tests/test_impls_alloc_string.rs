@@ -0,0 +1,58 @@
+extern crate alloc;
+
+use alloc::{borrow::Cow, rc::Rc, sync::Arc};
+
+use deer::{Deserialize, Number};
+use deer_desert::{assert_tokens, assert_tokens_error, error, Token};
+use serde_json::json;
+
+#[test]
+fn string_ok() {
+    let expected = String::from("example");
+
+    assert_tokens(&expected, &[Token::Str("example")]);
+    assert_tokens(&expected, &[Token::BorrowedStr("example")]);
+    assert_tokens(&expected, &[Token::String("example")]);
+}
+
+#[test]
+fn string_from_char_ok() {
+    assert_tokens(&String::from("A"), &[Token::Char('A')]);
+}
+
+#[test]
+fn string_err() {
+    assert_tokens_error::<String>(
+        &error!([{
+            ns: "deer",
+            id: ["type"],
+            properties: {
+                "expected": String::reflection(),
+                "received": Number::reflection(),
+                "location": []
+            }
+        }]),
+        &[Token::Number(Number::from(1))],
+    );
+}
+
+#[test]
+fn boxed_str_ok() {
+    assert_tokens(&Box::<str>::from("example"), &[Token::Str("example")]);
+    assert_tokens(&Rc::<str>::from("example"), &[Token::Str("example")]);
+    assert_tokens(&Arc::<str>::from("example"), &[Token::Str("example")]);
+}
+
+#[test]
+fn cow_ok() {
+    assert_tokens(&Cow::<str>::Borrowed("example"), &[Token::Str("example")]);
+    assert_tokens(
+        &Cow::<[u8]>::Borrowed(&[1, 2]),
+        &[
+            Token::Array { length: Some(2) },
+            Token::Number(Number::from(1)),
+            Token::Number(Number::from(2)),
+            Token::ArrayEnd,
+        ],
+    );
+}
@@ -44,3 +44,17 @@ fn reverse_reflection_same() {
 
     assert_json(lhs, rhs);
 }
+
+#[test]
+fn ordering_reflection() {
+    let document = serde_json::to_value(Ordering::reflection()).expect("should be valid json");
+    let schema = &document["$defs"]["0000-core::cmp::Ordering"];
+
+    assert_eq!(
+        *schema,
+        serde_json::json!({
+            "type": "string",
+            "enum": ["Less", "Equal", "Greater"]
+        })
+    );
+}
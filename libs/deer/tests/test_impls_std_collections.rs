@@ -0,0 +1,68 @@
+use std::collections::{HashMap, HashSet};
+
+use deer::Number;
+use deer_desert::{assert_tokens, assert_tokens_error, error, Token};
+use serde_json::json;
+
+#[test]
+fn hash_set_ok() {
+    assert_tokens(
+        &HashSet::from([1_u8, 2, 3]),
+        &[
+            Token::Array { length: Some(3) },
+            Token::Number(Number::from(1)),
+            Token::Number(Number::from(2)),
+            Token::Number(Number::from(3)),
+            Token::ArrayEnd,
+        ],
+    );
+}
+
+#[test]
+fn hash_map_ok() {
+    assert_tokens(
+        &HashMap::from([(String::from("a"), 1_u8), (String::from("b"), 2)]),
+        &[
+            Token::Object { length: Some(2) },
+            Token::Str("a"),
+            Token::Number(Number::from(1)),
+            Token::Str("b"),
+            Token::Number(Number::from(2)),
+            Token::ObjectEnd,
+        ],
+    );
+}
+
+#[test]
+fn hash_map_err_duplicate() {
+    assert_tokens_error::<HashMap<String, u8>>(
+        &error!([
+            {
+                ns: "deer",
+                id: ["duplicate", "key"],
+                properties: {
+                    "key": "a",
+                    "location": [{"type": "entry", "value": "a"}]
+                }
+            },
+            {
+                ns: "deer",
+                id: ["duplicate", "key"],
+                properties: {
+                    "key": "a",
+                    "location": [{"type": "entry", "value": "a"}]
+                }
+            }
+        ]),
+        &[
+            Token::Object { length: Some(3) },
+            Token::Str("a"),
+            Token::Number(Number::from(1)),
+            Token::Str("a"),
+            Token::Number(Number::from(2)),
+            Token::Str("a"),
+            Token::Number(Number::from(3)),
+            Token::ObjectEnd,
+        ],
+    );
+}
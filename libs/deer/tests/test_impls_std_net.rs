@@ -0,0 +1,48 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use deer::Deserialize;
+use deer_desert::{assert_tokens, assert_tokens_error, error, Token};
+use serde_json::json;
+
+#[test]
+fn ip_addr_ok() {
+    assert_tokens(
+        &IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+        &[Token::Str("127.0.0.1")],
+    );
+    assert_tokens(&IpAddr::V6(Ipv6Addr::LOCALHOST), &[Token::Str("::1")]);
+    assert_tokens(&Ipv4Addr::new(10, 0, 0, 1), &[Token::Str("10.0.0.1")]);
+    assert_tokens(&Ipv6Addr::UNSPECIFIED, &[Token::Str("::")]);
+}
+
+#[test]
+fn socket_addr_ok() {
+    assert_tokens(
+        &SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080)),
+        &[Token::Str("127.0.0.1:8080")],
+    );
+    assert_tokens(
+        &SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080),
+        &[Token::Str("127.0.0.1:8080")],
+    );
+    assert_tokens(
+        &SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0),
+        &[Token::Str("[::1]:8080")],
+    );
+}
+
+#[test]
+fn ip_addr_err() {
+    assert_tokens_error::<Ipv4Addr>(
+        &error!([{
+            ns: "deer",
+            id: ["value"],
+            properties: {
+                "expected": Ipv4Addr::reflection(),
+                "received": "256.0.0.1",
+                "location": []
+            }
+        }]),
+        &[Token::Str("256.0.0.1")],
+    );
+}
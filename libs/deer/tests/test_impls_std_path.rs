@@ -0,0 +1,16 @@
+use std::path::{Path, PathBuf};
+
+use deer_desert::{assert_tokens, Token};
+
+#[test]
+fn path_buf_ok() {
+    assert_tokens(&PathBuf::from("/etc/hosts"), &[Token::Str("/etc/hosts")]);
+}
+
+#[test]
+fn boxed_path_ok() {
+    assert_tokens(
+        &Box::<Path>::from(Path::new("config.toml")),
+        &[Token::String("config.toml")],
+    );
+}
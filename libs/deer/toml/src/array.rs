@@ -0,0 +1,74 @@
+use deer::{
+    error::{ArrayAccessError, ArrayLengthError, DeserializerError},
+    Context, Deserialize, Deserializer as _,
+};
+use error_stack::{Result, ResultExt};
+
+use crate::{deserializer::Deserializer, skip::skip_tokens, token::Token};
+
+pub(crate) struct ArrayAccess<'a, 'b> {
+    deserializer: &'a mut Deserializer<'b>,
+
+    dirty: bool,
+    expected: usize,
+}
+
+impl<'a, 'b> ArrayAccess<'a, 'b> {
+    pub(crate) fn new(deserializer: &'a mut Deserializer<'b>) -> Result<Self, DeserializerError> {
+        deserializer.try_stack_push(&Token::Array)?;
+
+        Ok(Self {
+            deserializer,
+            dirty: false,
+            expected: 0,
+        })
+    }
+}
+
+impl<'de> deer::ArrayAccess<'de> for ArrayAccess<'_, '_> {
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn context(&self) -> &Context {
+        self.deserializer.context()
+    }
+
+    fn next<T>(&mut self) -> Option<Result<T, ArrayAccessError>>
+    where
+        T: Deserialize<'de>,
+    {
+        self.dirty = true;
+
+        // we check for `None` here because we could be EOF, in that case we're "done", we will
+        // error out at `.end()`
+        match self.deserializer.peek() {
+            None | Some(Token::ArrayEnd) => return None,
+            Some(_) => {}
+        }
+
+        self.expected += 1;
+
+        let value = T::deserialize(&mut *self.deserializer);
+
+        Some(value.change_context(ArrayAccessError))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+
+    fn end(self) -> Result<(), ArrayAccessError> {
+        self.deserializer.stack.pop();
+
+        let result = match self.deserializer.peek() {
+            None => Err(self.deserializer.tape.error_eof()),
+            Some(Token::ArrayEnd) => Ok(()),
+            Some(_) => Err(ArrayLengthError::new(&self, self.expected)),
+        };
+
+        skip_tokens(&mut self.deserializer.tape, &Token::Array);
+
+        result.change_context(ArrayAccessError)
+    }
+}
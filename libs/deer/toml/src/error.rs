@@ -0,0 +1,143 @@
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
+
+use deer::{
+    error::{ErrorProperties, ErrorProperty, Id, Location, Namespace, Variant},
+    id,
+};
+
+const NAMESPACE: Namespace = Namespace::new("deer-toml");
+
+#[derive(Debug)]
+pub(crate) struct RecursionLimitError;
+
+impl Display for RecursionLimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // This message is vague by design to not encourage abuse from the consumers
+        f.write_str("Recursion limit has been exceeded")
+    }
+}
+
+impl Variant for RecursionLimitError {
+    type Properties = (Location,);
+
+    const ID: Id = id!["recursion"];
+    const NAMESPACE: Namespace = NAMESPACE;
+
+    fn message(
+        &self,
+        fmt: &mut Formatter,
+        _: &<Self::Properties as ErrorProperties>::Value<'_>,
+    ) -> core::fmt::Result {
+        Display::fmt(self, fmt)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct BytesUnsupportedError;
+
+impl Display for BytesUnsupportedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("TOML does not support bytes")
+    }
+}
+
+impl Variant for BytesUnsupportedError {
+    type Properties = (Location,);
+
+    const ID: Id = id!["bytes"];
+    const NAMESPACE: Namespace = NAMESPACE;
+
+    fn message(
+        &self,
+        fmt: &mut Formatter,
+        _: &<Self::Properties as ErrorProperties>::Value<'_>,
+    ) -> core::fmt::Result {
+        fmt.write_str("deer-toml does not support deserialization of bytes")
+    }
+}
+
+/// Location of a token in the source document, both `line` and `column` are 1-indexed.
+///
+/// Attached to syntax errors, as well as to every error of a value, like a type mismatch or a
+/// number which is out of range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    pub(crate) const fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    #[must_use]
+    pub const fn line(self) -> usize {
+        self.line
+    }
+
+    #[must_use]
+    pub const fn column(self) -> usize {
+        self.column
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let Self { line, column } = self;
+
+        f.write_fmt(format_args!("line {line}, column {column}"))
+    }
+}
+
+impl ErrorProperty for Position {
+    type Value<'a> = Option<&'a Self> where Self: 'a ;
+
+    fn key() -> &'static str {
+        "position"
+    }
+
+    fn value<'a>(mut stack: impl Iterator<Item = &'a Self>) -> Self::Value<'a> {
+        stack.next()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum SyntaxError {
+    UnexpectedEof,
+    UnexpectedEnd,
+    Parse(String),
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnexpectedEof => f.write_str("unexpected end of file"),
+            Self::UnexpectedEnd => f.write_str("unexpected end of collection"),
+            Self::Parse(message) => f.write_str(message),
+        }
+    }
+}
+
+impl Variant for SyntaxError {
+    type Properties = (Location, Position);
+
+    const ID: Id = id!["syntax"];
+    const NAMESPACE: Namespace = NAMESPACE;
+
+    fn message(
+        &self,
+        fmt: &mut Formatter,
+        properties: &<Self::Properties as ErrorProperties>::Value<'_>,
+    ) -> core::fmt::Result {
+        let position = properties.1;
+
+        // TODO: context via codespan -> Property (if fancy)
+        if let Some(position) = position {
+            fmt.write_fmt(format_args!("{self} at {position}"))
+        } else {
+            Display::fmt(&self, fmt)
+        }
+    }
+}
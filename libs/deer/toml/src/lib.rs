@@ -0,0 +1,34 @@
+// TODO: once more stable introduce: warning missing_docs, clippy::missing_errors_doc
+#![deny(unsafe_code)]
+#![expect(clippy::missing_errors_doc)]
+
+mod array;
+mod deserializer;
+mod error;
+mod object;
+mod skip;
+mod tape;
+mod token;
+
+extern crate alloc;
+
+use deer::{error::DeserializeError, Context, Deserialize};
+use error_stack::Result;
+
+pub use crate::{
+    deserializer::{Deserializer, StackLimit},
+    error::Position,
+};
+
+/// Deserialize a TOML document.
+///
+/// The document itself is always a table, date-times are deserialized as their string
+/// representation.
+pub fn from_str<'de, T>(value: &str, context: &Context) -> Result<T, DeserializeError>
+where
+    T: Deserialize<'de>,
+{
+    let mut deserializer = Deserializer::new(value, context);
+
+    T::deserialize(&mut deserializer)
+}
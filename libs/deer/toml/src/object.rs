@@ -0,0 +1,90 @@
+use deer::{
+    error::{DeserializerError, ObjectAccessError, ObjectLengthError},
+    Context, Deserializer as _, FieldVisitor,
+};
+use error_stack::{Result, ResultExt};
+
+use crate::{deserializer::Deserializer, skip::skip_tokens, token::Token};
+
+pub(crate) struct ObjectAccess<'a, 'b> {
+    deserializer: &'a mut Deserializer<'b>,
+
+    dirty: bool,
+    expected: usize,
+}
+
+impl<'a, 'b> ObjectAccess<'a, 'b> {
+    pub(crate) fn new(deserializer: &'a mut Deserializer<'b>) -> Result<Self, DeserializerError> {
+        deserializer.try_stack_push(&Token::Object)?;
+
+        Ok(Self {
+            deserializer,
+
+            dirty: false,
+            expected: 0,
+        })
+    }
+}
+
+impl<'de> deer::ObjectAccess<'de> for ObjectAccess<'_, '_> {
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn context(&self) -> &Context {
+        self.deserializer.context()
+    }
+
+    fn try_field<F>(
+        &mut self,
+        visitor: F,
+    ) -> core::result::Result<Result<F::Value, ObjectAccessError>, F>
+    where
+        F: FieldVisitor<'de>,
+    {
+        self.dirty = true;
+
+        // we check for `None` here because we could be EOF, in that case we still error out with
+        // the visitor as we're "done".
+        match self.deserializer.peek() {
+            None | Some(Token::ObjectEnd) => return Err(visitor),
+            Some(_) => {}
+        }
+
+        self.expected += 1;
+
+        // keys in TOML are always strings
+        let key = visitor.visit_key(&mut *self.deserializer);
+
+        let result = match key {
+            Ok(key) => visitor
+                .visit_value(key, &mut *self.deserializer)
+                .change_context(ObjectAccessError),
+            Err(error) => {
+                self.deserializer.skip(); // skip value
+
+                Err(error.change_context(ObjectAccessError))
+            }
+        };
+
+        Ok(result)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+
+    fn end(self) -> Result<(), ObjectAccessError> {
+        self.deserializer.stack.pop();
+
+        let result = match self.deserializer.peek() {
+            None => Err(self.deserializer.tape.error_eof()),
+            Some(Token::ObjectEnd) => Ok(()),
+            Some(_) => Err(ObjectLengthError::new(&self, self.expected)),
+        };
+
+        skip_tokens(&mut self.deserializer.tape, &Token::Object);
+
+        result.change_context(ObjectAccessError)
+    }
+}
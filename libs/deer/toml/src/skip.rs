@@ -0,0 +1,38 @@
+use crate::{tape::Tape, token::Token};
+
+fn skip_nested(tape: &mut Tape, is_stop: fn(&Token) -> bool) {
+    let mut objects: usize = 0;
+    let mut arrays: usize = 0;
+
+    loop {
+        let Some(token) = tape.next() else {
+            // we're at the end
+            return;
+        };
+
+        if is_stop(&token) && arrays == 0 && objects == 0 {
+            // we're at the outer layer, we have already consumed the token and can safely return
+            return;
+        }
+
+        // the parser guarantees that every collection is closed, but the tape might end early
+        // because of a syntax error, in that case we simply consume everything that is left.
+        match token {
+            Token::Array => arrays += 1,
+            Token::ArrayEnd => arrays = arrays.saturating_sub(1),
+            Token::Object => objects += 1,
+            Token::ObjectEnd => objects = objects.saturating_sub(1),
+            _ => {}
+        }
+    }
+}
+
+/// Skips all tokens required for the start token, be aware that the token should already be
+/// consumed.
+pub(crate) fn skip_tokens(tape: &mut Tape, start: &Token) {
+    match start {
+        Token::Array => skip_nested(tape, |token| matches!(token, Token::ArrayEnd)),
+        Token::Object => skip_nested(tape, |token| matches!(token, Token::ObjectEnd)),
+        _ => {}
+    };
+}
@@ -0,0 +1,245 @@
+use alloc::{borrow::ToOwned, string::ToString, vec::Vec};
+use core::{mem, ops::Range};
+
+use deer::{
+    error::{Error, Variant},
+    Number,
+};
+use error_stack::Report;
+use toml_edit::{ArrayOfTables, ImDocument, InlineTable, Item, Key, Table, Value};
+
+use crate::{
+    error::{Position, SyntaxError},
+    token::Token,
+};
+
+/// Byte offset of the start of every line, used to translate spans into positions.
+struct Lines<'a> {
+    input: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(input: &'a str) -> Self {
+        let starts = core::iter::once(0)
+            .chain(input.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Self { input, starts }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let line = self
+            .starts
+            .partition_point(|start| *start <= offset)
+            .saturating_sub(1);
+        let start = self.starts.get(line).copied().unwrap_or(0);
+
+        // columns are counted in characters, not bytes
+        let column = self
+            .input
+            .get(start..offset)
+            .map_or(0, |prefix| prefix.chars().count());
+
+        Position::new(line + 1, column + 1)
+    }
+
+    fn start(&self, span: Option<Range<usize>>, fallback: Position) -> Position {
+        span.map_or(fallback, |span| self.position(span.start))
+    }
+
+    fn end(&self, span: Option<Range<usize>>, fallback: Position) -> Position {
+        span.map_or(fallback, |span| self.position(span.end))
+    }
+}
+
+/// Flattens the parsed document into a stream of tokens.
+///
+/// Implicit tables (like `a` in `[a.b]`) have no span, these use the position of their key
+/// instead.
+struct Builder<'a> {
+    lines: Lines<'a>,
+    tokens: Vec<(Token, Position)>,
+}
+
+impl Builder<'_> {
+    fn key(&mut self, key: &str, span: Option<&Key>, fallback: Position) -> Position {
+        let position = self.lines.start(span.and_then(Key::span), fallback);
+        self.tokens.push((Token::String(key.to_owned()), position));
+
+        position
+    }
+
+    fn table(&mut self, table: &Table, fallback: Position) {
+        let position = self.lines.start(table.span(), fallback);
+        self.tokens.push((Token::Object, position));
+
+        for (key, item) in table {
+            let position = self.key(key, table.key(key), position);
+            self.item(item, position);
+        }
+
+        let end = self.lines.end(table.span(), position);
+        self.tokens.push((Token::ObjectEnd, end));
+    }
+
+    fn inline_table(&mut self, table: &InlineTable, fallback: Position) {
+        let position = self.lines.start(table.span(), fallback);
+        self.tokens.push((Token::Object, position));
+
+        for (key, value) in table {
+            let position = self.key(key, table.key(key), position);
+            self.value(value, position);
+        }
+
+        let end = self.lines.end(table.span(), position);
+        self.tokens.push((Token::ObjectEnd, end));
+    }
+
+    fn array_of_tables(&mut self, array: &ArrayOfTables, fallback: Position) {
+        let position = self.lines.start(array.span(), fallback);
+        self.tokens.push((Token::Array, position));
+
+        for table in array {
+            self.table(table, position);
+        }
+
+        let end = self.lines.end(array.span(), position);
+        self.tokens.push((Token::ArrayEnd, end));
+    }
+
+    fn item(&mut self, item: &Item, fallback: Position) {
+        match item {
+            // removed items are never yielded during iteration
+            Item::None => {}
+            Item::Value(value) => self.value(value, fallback),
+            Item::Table(table) => self.table(table, fallback),
+            Item::ArrayOfTables(array) => self.array_of_tables(array, fallback),
+        }
+    }
+
+    fn value(&mut self, value: &Value, fallback: Position) {
+        let position = self.lines.start(value.span(), fallback);
+
+        let token = match value {
+            Value::String(value) => Token::String(value.value().clone()),
+            Value::Integer(value) => Token::Number(Number::from(*value.value())),
+            Value::Float(value) => Token::Number(Number::from(*value.value())),
+            Value::Boolean(value) => Token::Bool(*value.value()),
+            Value::Datetime(value) => Token::String(value.value().to_string()),
+            Value::Array(array) => {
+                self.tokens.push((Token::Array, position));
+
+                for value in array {
+                    self.value(value, position);
+                }
+
+                let end = self.lines.end(array.span(), position);
+                self.tokens.push((Token::ArrayEnd, end));
+                return;
+            }
+            Value::InlineTable(table) => {
+                self.inline_table(table, position);
+                return;
+            }
+        };
+
+        self.tokens.push((token, position));
+    }
+}
+
+/// The token stream of a TOML document.
+///
+/// The whole document is parsed upfront, as keys of a table can be spread across the whole
+/// document (`[a]`, `[b]`, `[a.c]`). If the document is invalid the tape is empty and the parsing
+/// error is reported once a value is requested.
+pub(crate) struct Tape {
+    tokens: Vec<(Token, Position)>,
+    cursor: usize,
+
+    end: Position,
+    error: Option<Report<Error>>,
+}
+
+impl Tape {
+    pub(crate) fn parse(input: &str) -> Self {
+        let mut builder = Builder {
+            lines: Lines::new(input),
+            tokens: Vec::new(),
+        };
+
+        let end = builder.lines.position(input.len());
+
+        let error = match ImDocument::parse(input) {
+            Ok(document) => {
+                builder.table(document.as_table(), Position::new(1, 1));
+                None
+            }
+            Err(error) => {
+                let position = error
+                    .span()
+                    .map_or(end, |span| builder.lines.position(span.start));
+
+                Some(
+                    Report::new(SyntaxError::Parse(error.message().to_owned()).into_error())
+                        .attach(position),
+                )
+            }
+        };
+
+        Self {
+            tokens: builder.tokens,
+            cursor: 0,
+            end,
+            error,
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.cursor).map(|(token, _)| token)
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Token> {
+        let (token, _) = self.tokens.get_mut(self.cursor)?;
+        self.cursor += 1;
+
+        // every token is only consumed once, therefore we can take it out of the tape
+        Some(mem::replace(token, Token::Bool(false)))
+    }
+
+    /// Position of the next token, if the tape has been exhausted the position of the end of the
+    /// document.
+    pub(crate) fn position(&self) -> Position {
+        self.tokens
+            .get(self.cursor)
+            .map_or(self.end, |(_, position)| *position)
+    }
+
+    /// Error for the exhausted tape.
+    ///
+    /// The first time this is called the error of the parser (if any) is returned, any subsequent
+    /// call will only return an unexpected end of file.
+    pub(crate) fn error_eof(&mut self) -> Report<Error> {
+        self.error.take().unwrap_or_else(|| {
+            Report::new(SyntaxError::UnexpectedEof.into_error()).attach(self.end)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lines;
+    use crate::error::Position;
+
+    #[test]
+    fn position() {
+        let lines = Lines::new("a = 1\n\u{e4} = 2\n");
+
+        assert_eq!(lines.position(0), Position::new(1, 1));
+        assert_eq!(lines.position(4), Position::new(1, 5));
+        assert_eq!(lines.position(6), Position::new(2, 1));
+        // `ä` is two bytes long, but only a single character
+        assert_eq!(lines.position(9), Position::new(2, 3));
+        assert_eq!(lines.position(13), Position::new(3, 1));
+    }
+}
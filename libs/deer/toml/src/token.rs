@@ -0,0 +1,49 @@
+use alloc::string::String;
+
+use deer::{Deserialize, Document, Number, Reflection, Schema};
+
+/// A single token of a TOML document.
+///
+/// TOML has no notion of `null`, tables (inline or not) are represented as objects, while arrays
+/// of tables are represented as arrays of objects. Date-times are represented as their string
+/// representation according to [RFC 3339].
+///
+/// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+#[derive(Debug, Clone)]
+pub(crate) enum Token {
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Object,
+    ObjectEnd,
+    Array,
+    ArrayEnd,
+}
+
+struct AnyObject;
+
+impl Reflection for AnyObject {
+    fn schema(_: &mut Document) -> Schema {
+        Schema::new("object")
+    }
+}
+
+struct AnyArray;
+
+impl Reflection for AnyArray {
+    fn schema(_: &mut Document) -> Schema {
+        Schema::new("array")
+    }
+}
+
+impl Token {
+    pub(crate) fn schema(&self) -> Document {
+        match self {
+            Self::Bool(_) => bool::reflection(),
+            Self::String(_) => str::document(),
+            Self::Number(_) => Number::reflection(),
+            Self::Object | Self::ObjectEnd => AnyObject::document(),
+            Self::Array | Self::ArrayEnd => AnyArray::document(),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use deer::{error::ReportExt, Context, Deserialize, Number};
+use deer_toml::{from_str, Position, StackLimit};
+use serde_json::{json, to_value, Value};
+use similar_asserts::assert_serde_eq;
+
+fn deserialize<'de, T: Deserialize<'de>>(input: &str) -> T {
+    from_str(input, &Context::new()).expect("input should be valid")
+}
+
+fn deserialize_error<'de, T: Deserialize<'de> + core::fmt::Debug>(
+    input: &str,
+    context: &Context,
+) -> Value {
+    let error = from_str::<T>(input, context).expect_err("input should be invalid");
+
+    to_value(error.export()).expect("error should be serializable")
+}
+
+#[test]
+fn tables() {
+    let value: BTreeMap<String, BTreeMap<String, BTreeMap<String, Number>>> =
+        deserialize("[a.b]\nc = 1\n\n[a.d]\ne = 2.5\n");
+
+    assert_eq!(
+        value,
+        BTreeMap::from([(
+            "a".to_owned(),
+            BTreeMap::from([
+                (
+                    "b".to_owned(),
+                    BTreeMap::from([("c".to_owned(), Number::from(1))])
+                ),
+                (
+                    "d".to_owned(),
+                    BTreeMap::from([("e".to_owned(), Number::from(2.5))])
+                ),
+            ])
+        )])
+    );
+}
+
+#[test]
+fn datetime() {
+    let value: BTreeMap<String, String> = deserialize("dob = 1979-05-27T07:32:00-08:00");
+
+    assert_eq!(value["dob"], "1979-05-27T07:32:00-08:00");
+}
+
+#[test]
+fn arrays() {
+    let values: BTreeMap<String, (u8, u8, u8)> = deserialize("values = [1, 2, 3]");
+    assert_eq!(values["values"], (1, 2, 3));
+
+    let servers: BTreeMap<String, Vec<BTreeMap<String, String>>> = deserialize(
+        r#"
+[[servers]]
+name = "alpha"
+
+[[servers]]
+name = "beta"
+"#,
+    );
+    assert_eq!(servers["servers"].len(), 2);
+    assert_eq!(servers["servers"][1]["name"], "beta");
+}
+
+#[test]
+fn accumulate_errors() {
+    let input = r#"
+a = [1, 2]
+b = "text"
+c = [1, true]
+"#;
+
+    let error = deserialize_error::<BTreeMap<String, Vec<u8>>>(input, &Context::new());
+    let error = error.as_array().expect("errors should be an array");
+
+    assert_eq!(error.len(), 2);
+}
+
+#[test]
+fn syntax_error_position() {
+    let input = "a = 1\nb = [1,\n";
+
+    let error = deserialize_error::<BTreeMap<String, u8>>(input, &Context::new());
+
+    assert_eq!(error[0]["namespace"], "deer-toml");
+    assert_eq!(error[0]["id"], json!(["syntax"]));
+    assert_serde_eq!(
+        error[0]["properties"]["position"],
+        json!({"line": 3, "column": 1})
+    );
+}
+
+#[test]
+fn type_error_position() {
+    let input = "a = \"x\"\nb = 1\nc = \"xy\"\n";
+
+    let error = from_str::<BTreeMap<String, char>>(input, &Context::new())
+        .expect_err("input should be invalid");
+    let positions: Vec<_> = error
+        .frames()
+        .filter_map(|frame| frame.downcast_ref::<Position>())
+        .map(|position| (position.line(), position.column()))
+        .collect();
+
+    // `1` is of the wrong type and `"xy"` is more than a single character
+    assert_eq!(positions, [(2, 5), (3, 5)]);
+}
+
+#[test]
+fn stack_limit() {
+    let mut context = Context::new();
+    context.insert(StackLimit::new(2));
+
+    let error = deserialize_error::<BTreeMap<String, Vec<Vec<u8>>>>("a = [[1]]", &context);
+
+    assert_eq!(error[0]["id"], json!(["recursion"]));
+}
@@ -0,0 +1,421 @@
+use deer::{
+    error::{
+        DeserializerError, ExpectedLength, ExpectedType, ObjectLengthError, ReceivedType,
+        TypeError, Variant,
+    },
+    schema::Document,
+    value::NoneDeserializer,
+    Context, Deserialize, EnumVisitor, IdentifierVisitor, Number, OptionalVisitor, Reflection,
+    StructVisitor, Visitor,
+};
+use error_stack::{Report, Result, ResultExt};
+
+use crate::{
+    array::ArrayAccess,
+    error::{BytesUnsupportedError, Position, RecursionLimitError, SyntaxError},
+    number::try_convert_number,
+    object::ObjectAccess,
+    skip::skip_tokens,
+    tape::Tape,
+    token::Token,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StackLimit(usize);
+
+impl StackLimit {
+    #[must_use]
+    pub const fn new(limit: usize) -> Self {
+        Self(limit)
+    }
+
+    #[must_use]
+    pub const fn limit(self) -> usize {
+        self.0
+    }
+}
+
+pub(crate) struct Stack {
+    limit: usize,
+    depth: usize,
+}
+
+impl Stack {
+    const fn new(limit: usize) -> Self {
+        Self { limit, depth: 0 }
+    }
+
+    pub(crate) fn push(&mut self) -> Result<(), DeserializerError> {
+        self.depth += 1;
+
+        if self.depth >= self.limit {
+            Err(Report::new(RecursionLimitError.into_error()).change_context(DeserializerError))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+#[expect(clippy::field_scoped_visibility_modifiers)]
+pub struct Deserializer<'a> {
+    pub(crate) tape: Tape,
+
+    context: &'a Context,
+    pub(crate) stack: Stack,
+    // set while deserializing the key of a mapping, in that case numbers are also accepted as
+    // strings, as keys like `404:` are most likely meant to be strings.
+    pub(crate) key: bool,
+}
+
+impl<'a> Deserializer<'a> {
+    #[must_use]
+    pub fn new(input: &str, context: &'a Context) -> Self {
+        let limit = context
+            .request_ref::<StackLimit>()
+            .map_or(usize::MAX, |limit| limit.limit());
+
+        Self {
+            tape: Tape::parse(input),
+            context,
+            stack: Stack::new(limit),
+            key: false,
+        }
+    }
+
+    /// Consume the next value token, together with its position in the source document.
+    fn next(&mut self) -> Result<(Token, Position), DeserializerError> {
+        match self.peek() {
+            None => Err(self.tape.error_eof().change_context(DeserializerError)),
+            // the end of a collection is never a value, we do not consume it, so that the
+            // collection itself can still be closed
+            Some(Token::ObjectEnd | Token::ArrayEnd) => {
+                Err(Report::new(SyntaxError::UnexpectedEnd.into_error())
+                    .attach(self.position())
+                    .change_context(DeserializerError))
+            }
+            Some(_) => {
+                let position = self.position();
+
+                Ok((self.tape.next().expect("token should be present"), position))
+            }
+        }
+    }
+
+    pub(crate) fn recover(&mut self, token: &Token) {
+        skip_tokens(&mut self.tape, token);
+    }
+
+    pub(crate) fn skip(&mut self) {
+        if let Some(token) = self.tape.next() {
+            skip_tokens(&mut self.tape, &token);
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<&Token> {
+        self.tape.peek()
+    }
+
+    pub(crate) fn position(&self) -> Position {
+        self.tape.position()
+    }
+
+    pub(crate) fn try_stack_push(&mut self, token: &Token) -> Result<(), DeserializerError> {
+        if let Err(error) = self.stack.push() {
+            // we can still recover, we pop us again from the stack as we stopped before and do not
+            // commit. We still show the error, but we could continue, so we skip all tokens.
+            self.stack.pop();
+            skip_tokens(&mut self.tape, token);
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    fn error_invalid_type(
+        &mut self,
+        received: &Token,
+        position: Position,
+        expected: Document,
+    ) -> Report<DeserializerError> {
+        self.recover(received);
+
+        Report::new(TypeError.into_error())
+            .attach(ExpectedType::new(expected))
+            .attach(ReceivedType::new(received.schema()))
+            .attach(position)
+            .change_context(DeserializerError)
+    }
+}
+
+impl<'de> deer::Deserializer<'de> for &mut Deserializer<'_> {
+    fn context(&self) -> &Context {
+        self.context
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        match token {
+            Token::Null => visitor.visit_null().attach(position),
+            Token::Bool(value) => visitor.visit_bool(value).attach(position),
+            Token::String(value) => visitor.visit_string(value).attach(position),
+            Token::Number(value) => {
+                let value = try_convert_number(&value)
+                    .attach(position)
+                    .change_context(DeserializerError)?;
+
+                visitor.visit_number(value).attach(position)
+            }
+            Token::Object => visitor.visit_object(ObjectAccess::new(self)?),
+            Token::Array => visitor.visit_array(ArrayAccess::new(self)?),
+            Token::ObjectEnd | Token::ArrayEnd => unreachable!("`next()` never returns the end"),
+        }
+        .change_context(DeserializerError)
+    }
+
+    fn deserialize_null<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        match token {
+            Token::Null => visitor
+                .visit_null()
+                .attach(position)
+                .change_context(DeserializerError),
+            token => Err(self.error_invalid_type(&token, position, <()>::reflection())),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        match token {
+            Token::Bool(value) => visitor
+                .visit_bool(value)
+                .attach(position)
+                .change_context(DeserializerError),
+            token => Err(self.error_invalid_type(&token, position, bool::reflection())),
+        }
+    }
+
+    fn deserialize_number<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        match token {
+            Token::Number(value) => visitor
+                .visit_number(
+                    try_convert_number(&value)
+                        .attach(position)
+                        .change_context(DeserializerError)?,
+                )
+                .attach(position)
+                .change_context(DeserializerError),
+            token => Err(self.error_invalid_type(&token, position, Number::reflection())),
+        }
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        match token {
+            Token::String(value) => visitor
+                .visit_string(value)
+                .attach(position)
+                .change_context(DeserializerError),
+            Token::Number(value) if self.key => visitor
+                .visit_string(value)
+                .attach(position)
+                .change_context(DeserializerError),
+            token => Err(self.error_invalid_type(&token, position, str::document())),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, _: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Report::new(BytesUnsupportedError.into_error()).change_context(DeserializerError))
+    }
+
+    fn deserialize_bytes_buffer<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_array<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        match token {
+            Token::Array => visitor
+                .visit_array(ArrayAccess::new(self)?)
+                .change_context(DeserializerError),
+            token => Err(self.error_invalid_type(&token, position, Token::Array.schema())),
+        }
+    }
+
+    fn deserialize_object<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: Visitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        match token {
+            Token::Object => visitor
+                .visit_object(ObjectAccess::new(self)?)
+                .change_context(DeserializerError),
+            token => Err(self.error_invalid_type(&token, position, Token::Object.schema())),
+        }
+    }
+
+    fn deserialize_optional<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: OptionalVisitor<'de>,
+    {
+        match self.peek() {
+            None => Err(self.tape.error_eof().change_context(DeserializerError)),
+            Some(Token::Null) => {
+                // we know the value will be `null`, therefore we can just discard the next token
+                _ = self.tape.next();
+
+                visitor.visit_null().change_context(DeserializerError)
+            }
+            _ => visitor.visit_some(self).change_context(DeserializerError),
+        }
+    }
+
+    fn deserialize_enum<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: EnumVisitor<'de>,
+    {
+        let is_map = match self.peek() {
+            Some(Token::Object) => {
+                // eat the token, so that we're at the key (that we need)
+                _ = self.tape.next();
+                true
+            }
+            Some(_) => false,
+            None => return Err(self.tape.error_eof().change_context(DeserializerError)),
+        };
+
+        let discriminant = visitor
+            .visit_discriminant(&mut *self)
+            .change_context(DeserializerError);
+
+        let discriminant = match discriminant {
+            Ok(discriminant) => discriminant,
+            Err(error) => {
+                if is_map {
+                    // the key is an error, we need to swallow the value and close the mapping
+                    self.recover(&Token::Object);
+                }
+
+                return Err(error);
+            }
+        };
+
+        let mut value = if is_map {
+            visitor
+                .visit_value(discriminant, &mut *self)
+                .change_context(DeserializerError)
+        } else {
+            visitor
+                .visit_value(discriminant, NoneDeserializer::new(self.context))
+                .change_context(DeserializerError)
+        };
+
+        if is_map {
+            if matches!(self.peek(), Some(Token::ObjectEnd)) {
+                // we can safely continue
+                _ = self.tape.next();
+            } else {
+                // we have received multiple keys, error out
+                // make sure we close the mapping
+                self.recover(&Token::Object);
+
+                let error = Report::new(ObjectLengthError.into_error())
+                    .attach(ExpectedLength::new(1))
+                    .change_context(DeserializerError);
+
+                match &mut value {
+                    Err(value) => value.extend_one(error),
+                    value => *value = Err(error),
+                }
+            }
+        }
+
+        value
+    }
+
+    fn deserialize_struct<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: StructVisitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        // like JSON we only deserialize mappings, not sequences
+        match token {
+            Token::Object => visitor
+                .visit_object(ObjectAccess::new(self)?)
+                .change_context(DeserializerError),
+
+            token => Err(self.error_invalid_type(&token, position, Token::Object.schema())),
+        }
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, DeserializerError>
+    where
+        V: IdentifierVisitor<'de>,
+    {
+        let (token, position) = self.next()?;
+
+        match token {
+            Token::String(value) => visitor
+                .visit_str(&value)
+                .attach(position)
+                .change_context(DeserializerError),
+            Token::Number(value) if self.key => visitor
+                .visit_str(&value)
+                .attach(position)
+                .change_context(DeserializerError),
+            token => Err(self.error_invalid_type(&token, position, str::document())),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
+
+use deer::{
+    error::{ErrorProperties, ErrorProperty, Id, Location, Namespace, ReceivedValue, Variant},
+    id,
+};
+use yaml_rust2::scanner::Marker;
+
+const NAMESPACE: Namespace = Namespace::new("deer-yaml");
+
+#[derive(Debug)]
+pub(crate) struct RecursionLimitError;
+
+impl Display for RecursionLimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // This message is vague by design to not encourage abuse from the consumers
+        f.write_str("Recursion limit has been exceeded")
+    }
+}
+
+impl Variant for RecursionLimitError {
+    type Properties = (Location,);
+
+    const ID: Id = id!["recursion"];
+    const NAMESPACE: Namespace = NAMESPACE;
+
+    fn message(
+        &self,
+        fmt: &mut Formatter,
+        _: &<Self::Properties as ErrorProperties>::Value<'_>,
+    ) -> core::fmt::Result {
+        Display::fmt(self, fmt)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct BytesUnsupportedError;
+
+impl Display for BytesUnsupportedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("YAML does not support bytes")
+    }
+}
+
+impl Variant for BytesUnsupportedError {
+    type Properties = (Location,);
+
+    const ID: Id = id!["bytes"];
+    const NAMESPACE: Namespace = NAMESPACE;
+
+    fn message(
+        &self,
+        fmt: &mut Formatter,
+        _: &<Self::Properties as ErrorProperties>::Value<'_>,
+    ) -> core::fmt::Result {
+        fmt.write_str("deer-yaml does not support deserialization of bytes")
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum NumberError {
+    Overflow,
+    Underflow,
+    Unknown,
+}
+
+impl Display for NumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Overflow => f.write_str("number too large"),
+            Self::Underflow => f.write_str("number too small"),
+            Self::Unknown => f.write_str("unable to parse number"),
+        }
+    }
+}
+
+impl Variant for NumberError {
+    type Properties = (Location, ReceivedValue);
+
+    const ID: Id = id!["number"];
+    const NAMESPACE: Namespace = NAMESPACE;
+
+    fn message(
+        &self,
+        fmt: &mut Formatter,
+        _: &<Self::Properties as ErrorProperties>::Value<'_>,
+    ) -> core::fmt::Result {
+        Display::fmt(&self, fmt)
+    }
+}
+
+/// Location of a token in the source document, both `line` and `column` are 1-indexed.
+///
+/// Attached to syntax errors, as well as to every error of a value, like a type mismatch or a
+/// number which is out of range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    pub(crate) const fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    #[must_use]
+    pub const fn line(self) -> usize {
+        self.line
+    }
+
+    #[must_use]
+    pub const fn column(self) -> usize {
+        self.column
+    }
+}
+
+impl From<Marker> for Position {
+    fn from(value: Marker) -> Self {
+        // lines are 1-indexed, but columns (despite what the documentation says) are 0-indexed
+        Self::new(value.line(), value.col() + 1)
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let Self { line, column } = self;
+
+        f.write_fmt(format_args!("line {line}, column {column}"))
+    }
+}
+
+impl ErrorProperty for Position {
+    type Value<'a> = Option<&'a Self> where Self: 'a ;
+
+    fn key() -> &'static str {
+        "position"
+    }
+
+    fn value<'a>(mut stack: impl Iterator<Item = &'a Self>) -> Self::Value<'a> {
+        stack.next()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum SyntaxError {
+    UnexpectedEof,
+    UnexpectedEnd,
+    ObjectKeyMustBeScalar,
+    AliasLimit,
+    Scan(String),
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnexpectedEof => f.write_str("unexpected end of file"),
+            Self::UnexpectedEnd => f.write_str("unexpected end of collection"),
+            Self::ObjectKeyMustBeScalar => f.write_str("mapping keys must be scalars"),
+            // This message is vague by design to not encourage abuse from the consumers
+            Self::AliasLimit => f.write_str("alias expansion limit has been exceeded"),
+            Self::Scan(info) => f.write_str(info),
+        }
+    }
+}
+
+impl Variant for SyntaxError {
+    type Properties = (Location, Position);
+
+    const ID: Id = id!["syntax"];
+    const NAMESPACE: Namespace = NAMESPACE;
+
+    fn message(
+        &self,
+        fmt: &mut Formatter,
+        properties: &<Self::Properties as ErrorProperties>::Value<'_>,
+    ) -> core::fmt::Result {
+        let position = properties.1;
+
+        // TODO: context via codespan -> Property (if fancy)
+        if let Some(position) = position {
+            fmt.write_fmt(format_args!("{self} at {position}"))
+        } else {
+            Display::fmt(&self, fmt)
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// TODO: once more stable introduce: warning missing_docs, clippy::missing_errors_doc
+#![deny(unsafe_code)]
+#![expect(clippy::missing_errors_doc)]
+
+mod array;
+mod deserializer;
+mod error;
+mod number;
+mod object;
+mod skip;
+mod tape;
+mod token;
+
+extern crate alloc;
+
+use deer::{error::DeserializeError, Context, Deserialize};
+use error_stack::Result;
+
+pub use crate::{
+    deserializer::{Deserializer, StackLimit},
+    error::Position,
+};
+
+/// Deserialize the first document of the YAML stream.
+///
+/// Scalars are resolved according to the YAML 1.2 core schema, aliases are expanded and any
+/// subsequent document is ignored.
+pub fn from_str<'de, T>(value: &str, context: &Context) -> Result<T, DeserializeError>
+where
+    T: Deserialize<'de>,
+{
+    let mut deserializer = Deserializer::new(value, context);
+
+    T::deserialize(&mut deserializer)
+}
@@ -0,0 +1,66 @@
+use alloc::borrow::ToOwned;
+use core::num::{IntErrorKind, ParseIntError};
+
+use deer::{
+    error::{Error, ReceivedValue, Variant},
+    Number,
+};
+use error_stack::{Report, Result};
+
+use crate::error::NumberError;
+
+fn convert_integer_error(error: &ParseIntError, source: &str) -> Report<Error> {
+    let error = match error.kind() {
+        IntErrorKind::PosOverflow => NumberError::Overflow,
+        IntErrorKind::NegOverflow => NumberError::Underflow,
+        _ => NumberError::Unknown,
+    };
+
+    Report::new(error.into_error()).attach(ReceivedValue::new(source.to_owned()))
+}
+
+/// Converts a number, which has been resolved according to the YAML 1.2 core schema.
+pub(crate) fn try_convert_number(source: &str) -> Result<Number, Error> {
+    if let Some(octal) = source.strip_prefix("0o") {
+        return u64::from_str_radix(octal, 8)
+            .map(Number::from)
+            .map_err(|error| convert_integer_error(&error, source));
+    }
+
+    if let Some(hexadecimal) = source.strip_prefix("0x") {
+        return u64::from_str_radix(hexadecimal, 16)
+            .map(Number::from)
+            .map_err(|error| convert_integer_error(&error, source));
+    }
+
+    let unsigned = source.strip_prefix(['-', '+']).unwrap_or(source);
+
+    match unsigned {
+        ".nan" | ".NaN" | ".NAN" => return Ok(Number::from(f64::NAN)),
+        ".inf" | ".Inf" | ".INF" if source.starts_with('-') => {
+            return Ok(Number::from(f64::NEG_INFINITY));
+        }
+        ".inf" | ".Inf" | ".INF" => return Ok(Number::from(f64::INFINITY)),
+        _ => {}
+    }
+
+    if unsigned.contains(['.', 'e', 'E']) {
+        source.parse::<f64>().map(Number::from).map_err(
+            // TODO: once stabilized use `Error` as base
+            |_error| {
+                Report::new(NumberError::Unknown.into_error())
+                    .attach(ReceivedValue::new(source.to_owned()))
+            },
+        )
+    } else if source.starts_with('-') {
+        source
+            .parse::<i64>()
+            .map(Number::from)
+            .map_err(|error| convert_integer_error(&error, source))
+    } else {
+        unsigned
+            .parse::<u64>()
+            .map(Number::from)
+            .map_err(|error| convert_integer_error(&error, source))
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::vec::Vec;
+use core::{mem, ops::Range};
+use std::collections::HashMap;
+
+use deer::error::{Error, Variant};
+use error_stack::Report;
+use yaml_rust2::parser::{Event, Parser};
+
+use crate::{
+    error::{Position, SyntaxError},
+    token::Token,
+};
+
+/// Maximum number of tokens, which are allowed to be created through the expansion of aliases.
+///
+/// Aliases are expanded eagerly, without a limit a small document that references the same anchor
+/// over and over again ("billion laughs") would exhaust all memory.
+const ALIAS_EXPANSION_LIMIT: usize = 1 << 20;
+
+/// The (fully expanded) token stream of the first document in the input.
+///
+/// YAML is not a streaming friendly format, as aliases can reference any previously anchored node,
+/// therefore the whole document is parsed upfront. If the parser encounters an error, all tokens
+/// up until that point are retained and the error is reported once the tape has been exhausted.
+pub(crate) struct Tape {
+    tokens: Vec<(Token, Position)>,
+    cursor: usize,
+
+    end: Position,
+    error: Option<Report<Error>>,
+}
+
+impl Tape {
+    pub(crate) fn parse(input: &str) -> Self {
+        let mut parser = Parser::new_from_str(input);
+
+        let mut tokens: Vec<(Token, Position)> = Vec::new();
+        let mut anchors: HashMap<usize, Range<usize>> = HashMap::new();
+        // collections that are currently open, together with their anchor and first token
+        let mut open: Vec<(usize, usize)> = Vec::new();
+        let mut expanded = 0_usize;
+
+        let mut end;
+        let mut error = None;
+
+        loop {
+            let (event, marker) = match parser.next_token() {
+                Ok(event) => event,
+                Err(scan) => {
+                    end = Position::from(*scan.marker());
+                    error = Some(SyntaxError::Scan(scan.info().into()));
+                    break;
+                }
+            };
+
+            let position = Position::from(marker);
+            end = position;
+
+            match event {
+                Event::Nothing | Event::StreamStart | Event::DocumentStart => {}
+                // we only ever deserialize the first document
+                Event::DocumentEnd | Event::StreamEnd => break,
+                Event::Alias(id) => {
+                    // the parser will already error out on unknown anchors
+                    let Some(range) = anchors.get(&id).cloned() else {
+                        continue;
+                    };
+
+                    expanded += range.len();
+                    if expanded > ALIAS_EXPANSION_LIMIT {
+                        error = Some(SyntaxError::AliasLimit);
+                        break;
+                    }
+
+                    tokens.extend_from_within(range);
+                }
+                Event::Scalar(value, style, anchor, tag) => {
+                    tokens.push((Token::scalar(value, style, tag.as_ref()), position));
+
+                    if anchor > 0 {
+                        anchors.insert(anchor, tokens.len() - 1..tokens.len());
+                    }
+                }
+                Event::SequenceStart(anchor, _) => {
+                    open.push((anchor, tokens.len()));
+                    tokens.push((Token::Array, position));
+                }
+                Event::MappingStart(anchor, _) => {
+                    open.push((anchor, tokens.len()));
+                    tokens.push((Token::Object, position));
+                }
+                Event::SequenceEnd | Event::MappingEnd => {
+                    let token = if matches!(event, Event::SequenceEnd) {
+                        Token::ArrayEnd
+                    } else {
+                        Token::ObjectEnd
+                    };
+                    tokens.push((token, position));
+
+                    if let Some((anchor, start)) = open.pop() {
+                        if anchor > 0 {
+                            anchors.insert(anchor, start..tokens.len());
+                        }
+                    }
+                }
+            }
+        }
+
+        if tokens.is_empty() && error.is_none() {
+            // an empty document is equivalent to `null`
+            tokens.push((Token::Null, end));
+        }
+
+        Self {
+            tokens,
+            cursor: 0,
+            end,
+            error: error.map(|error| Report::new(error.into_error()).attach(end)),
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.cursor).map(|(token, _)| token)
+    }
+
+    pub(crate) fn next(&mut self) -> Option<Token> {
+        let (token, _) = self.tokens.get_mut(self.cursor)?;
+        self.cursor += 1;
+
+        // every token is only consumed once, therefore we can take it out of the tape
+        Some(mem::replace(token, Token::Null))
+    }
+
+    /// Position of the next token, if the tape has been exhausted the position of the end of the
+    /// document.
+    pub(crate) fn position(&self) -> Position {
+        self.tokens
+            .get(self.cursor)
+            .map_or(self.end, |(_, position)| *position)
+    }
+
+    /// Error for the exhausted tape.
+    ///
+    /// The first time this is called the error of the parser (if any) is returned, any subsequent
+    /// call will only return an unexpected end of file.
+    pub(crate) fn error_eof(&mut self) -> Report<Error> {
+        self.error.take().unwrap_or_else(|| {
+            Report::new(SyntaxError::UnexpectedEof.into_error()).attach(self.end)
+        })
+    }
+}
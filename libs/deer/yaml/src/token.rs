@@ -0,0 +1,144 @@
+use alloc::string::String;
+
+use deer::{Deserialize, Document, Number, Reflection, Schema};
+use yaml_rust2::{parser::Tag, scanner::TScalarStyle};
+
+/// Handle of all tags defined in the YAML core schema (`!!`).
+const CORE_TAG_HANDLE: &str = "tag:yaml.org,2002:";
+
+/// A single node event of the YAML event stream.
+///
+/// Unlike the events emitted by the parser, aliases have already been expanded and scalars have
+/// been resolved according to the YAML 1.2 core schema.
+#[derive(Debug, Clone)]
+pub(crate) enum Token {
+    Null,
+    Bool(bool),
+    // the source of the number, conversion is delayed until it is requested
+    Number(String),
+    String(String),
+    Object,
+    ObjectEnd,
+    Array,
+    ArrayEnd,
+}
+
+impl Token {
+    pub(crate) fn scalar(value: String, style: TScalarStyle, tag: Option<&Tag>) -> Self {
+        let tag = tag
+            .filter(|tag| tag.handle == CORE_TAG_HANDLE)
+            .map(|tag| tag.suffix.as_str());
+
+        match tag {
+            Some("str") => Self::String(value),
+            Some("null" | "bool" | "int" | "float") => Self::plain(value),
+            _ if style == TScalarStyle::Plain => Self::plain(value),
+            _ => Self::String(value),
+        }
+    }
+
+    fn plain(value: String) -> Self {
+        match value.as_str() {
+            "" | "~" | "null" | "Null" | "NULL" => Self::Null,
+            "true" | "True" | "TRUE" => Self::Bool(true),
+            "false" | "False" | "FALSE" => Self::Bool(false),
+            source if is_number(source) => Self::Number(value),
+            _ => Self::String(value),
+        }
+    }
+}
+
+fn is_digits(value: &str, radix: u32) -> bool {
+    !value.is_empty() && value.chars().all(|char| char.is_digit(radix))
+}
+
+/// Checks if a plain scalar is either an integer or float, as defined in the YAML 1.2 core schema.
+fn is_number(value: &str) -> bool {
+    if let Some(octal) = value.strip_prefix("0o") {
+        return is_digits(octal, 8);
+    }
+
+    if let Some(hexadecimal) = value.strip_prefix("0x") {
+        return is_digits(hexadecimal, 16);
+    }
+
+    if matches!(value, ".nan" | ".NaN" | ".NAN") {
+        return true;
+    }
+
+    let unsigned = value.strip_prefix(['-', '+']).unwrap_or(value);
+
+    if matches!(unsigned, ".inf" | ".Inf" | ".INF") {
+        return true;
+    }
+
+    let (mantissa, exponent) = unsigned
+        .split_once(['e', 'E'])
+        .map_or((unsigned, None), |(mantissa, exponent)| {
+            (mantissa, Some(exponent))
+        });
+
+    let mantissa = match mantissa.split_once('.') {
+        Some(("", fraction)) => is_digits(fraction, 10),
+        Some((integer, fraction)) => {
+            is_digits(integer, 10) && (fraction.is_empty() || is_digits(fraction, 10))
+        }
+        None => is_digits(mantissa, 10),
+    };
+
+    let exponent = exponent.map_or(true, |exponent| {
+        is_digits(exponent.strip_prefix(['-', '+']).unwrap_or(exponent), 10)
+    });
+
+    mantissa && exponent
+}
+
+struct AnyObject;
+
+impl Reflection for AnyObject {
+    fn schema(_: &mut Document) -> Schema {
+        Schema::new("object")
+    }
+}
+
+struct AnyArray;
+
+impl Reflection for AnyArray {
+    fn schema(_: &mut Document) -> Schema {
+        Schema::new("array")
+    }
+}
+
+impl Token {
+    pub(crate) fn schema(&self) -> Document {
+        match self {
+            Self::Null => <() as Deserialize>::reflection(),
+            Self::Bool(_) => bool::reflection(),
+            Self::String(_) => str::document(),
+            Self::Number(_) => Number::reflection(),
+            Self::Object | Self::ObjectEnd => AnyObject::document(),
+            Self::Array | Self::ArrayEnd => AnyArray::document(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_number;
+
+    #[test]
+    fn core_schema_numbers() {
+        for number in [
+            "0", "-12", "+12", "0o14", "0xC", "1.", "1.5", "-.5", "1e3", "2.5E-3", ".inf", "-.Inf",
+            ".NaN",
+        ] {
+            assert!(is_number(number), "{number} should be a number");
+        }
+
+        for string in [
+            "", "-", ".", "0o", "0x", "0xG", "1_000", "e3", "1e", "inf", "-.nan",
+        ] {
+            assert!(!is_number(string), "{string} should not be a number");
+        }
+    }
+}
@@ -0,0 +1,125 @@
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use deer::{error::ReportExt, Context, Deserialize, Number};
+use deer_yaml::{from_str, Position, StackLimit};
+use serde_json::{json, to_value, Value};
+use similar_asserts::assert_serde_eq;
+
+fn deserialize<'de, T: Deserialize<'de>>(input: &str) -> T {
+    from_str(input, &Context::new()).expect("input should be valid")
+}
+
+fn deserialize_error<'de, T: Deserialize<'de> + core::fmt::Debug>(
+    input: &str,
+    context: &Context,
+) -> Value {
+    let error = from_str::<T>(input, context).expect_err("input should be invalid");
+
+    to_value(error.export()).expect("error should be serializable")
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(deserialize::<Option<u8>>("~"), None);
+    assert_eq!(deserialize::<Option<u8>>(""), None);
+    assert!(deserialize::<bool>("True"));
+    assert_eq!(deserialize::<u16>("0x1F"), 0x1F);
+    assert_eq!(deserialize::<u16>("0o17"), 0o17);
+    assert_eq!(deserialize::<i8>("-12"), -12);
+    assert_eq!(deserialize::<Number>("2.5e3"), Number::from(2500.0));
+    assert_eq!(deserialize::<String>("'12'"), "12");
+    assert_eq!(deserialize::<String>("!!str 12"), "12");
+    assert_eq!(deserialize::<String>("plain text"), "plain text");
+}
+
+#[test]
+fn collections() {
+    let input = "
+base: &base
+  - 1
+  - 2
+copy: *base
+404: [4, 0, 4]
+";
+
+    let value: BTreeMap<String, Vec<u8>> = deserialize(input);
+
+    assert_eq!(
+        value,
+        BTreeMap::from([
+            ("404".to_owned(), vec![4, 0, 4]),
+            ("base".to_owned(), vec![1, 2]),
+            ("copy".to_owned(), vec![1, 2]),
+        ])
+    );
+}
+
+#[test]
+fn accumulate_errors() {
+    let input = "
+a: 1
+b: text
+c: [1, true]
+";
+
+    let error = deserialize_error::<BTreeMap<String, Vec<u8>>>(input, &Context::new());
+    let error = error.as_array().expect("errors should be an array");
+
+    assert_eq!(error.len(), 3);
+}
+
+#[test]
+fn syntax_error_position() {
+    let input = "
+a:
+  - 1
+  - [2
+";
+
+    let error = deserialize_error::<BTreeMap<String, Vec<Vec<u8>>>>(input, &Context::new());
+    let error = error
+        .as_array()
+        .and_then(|errors| {
+            errors
+                .iter()
+                .find(|error| error["namespace"] == "deer-yaml" && error["id"] == json!(["syntax"]))
+        })
+        .expect("syntax error should be present");
+
+    assert_serde_eq!(
+        error["properties"]["position"],
+        json!({"line": 5, "column": 1})
+    );
+}
+
+#[test]
+fn type_error_position() {
+    let input = "
+a: x
+b: 1
+c: xy
+";
+
+    let error = from_str::<BTreeMap<String, char>>(input, &Context::new())
+        .expect_err("input should be invalid");
+    let positions: Vec<_> = error
+        .frames()
+        .filter_map(|frame| frame.downcast_ref::<Position>())
+        .map(|position| (position.line(), position.column()))
+        .collect();
+
+    // `1` is of the wrong type and `xy` is more than a single character
+    assert_eq!(positions, [(3, 4), (4, 4)]);
+}
+
+#[test]
+fn stack_limit() {
+    let mut context = Context::new();
+    context.insert(StackLimit::new(2));
+
+    let error = deserialize_error::<Vec<Vec<Vec<u8>>>>("[[[1]]]", &context);
+
+    assert_eq!(error[0]["id"], json!(["recursion"]));
+}
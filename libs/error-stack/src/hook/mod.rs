@@ -3,6 +3,8 @@ pub(crate) mod context;
 #[cfg_attr(feature = "std", allow(unused_imports))]
 use alloc::vec::Vec;
 
+#[cfg(feature = "serde")]
+use crate::serde::SerdeHooks;
 use crate::{
     fmt::{install_builtin_hooks, Hooks},
     Report,
@@ -17,6 +19,8 @@ type RwLock<T> = std::sync::RwLock<T>;
 type RwLock<T> = spin::rwlock::RwLock<T>;
 
 static FMT_HOOK: RwLock<Hooks> = RwLock::new(Hooks { inner: Vec::new() });
+#[cfg(feature = "serde")]
+static SERDE_HOOK: RwLock<SerdeHooks> = RwLock::new(SerdeHooks { inner: Vec::new() });
 
 impl Report<()> {
     /// Can be used to globally set a [`Debug`] format hook, for a specific type `T`.
@@ -236,4 +240,83 @@ impl Report<()> {
 
         closure(&hook)
     }
+
+    /// Can be used to globally set a serialization hook, for a specific type `T`.
+    ///
+    /// By default, only the [`Display`] representation of contexts and printable attachments is
+    /// serialized. Once a hook has been installed for `T`, every context or attachment of type `T`
+    /// additionally serializes its type name and its value, using the [`Serialize`] implementation
+    /// of `T`. Opaque attachments, which do not have a hook installed, are omitted.
+    ///
+    /// [`Display`]: core::fmt::Display
+    /// [`Serialize`]: serde::Serialize
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::fmt;
+    ///
+    /// use error_stack::{Context, Report};
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Debug, Serialize)]
+    /// struct HttpError {
+    ///     status: u16,
+    /// }
+    ///
+    /// impl fmt::Display for HttpError {
+    ///     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(fmt, "request failed with status {}", self.status)
+    ///     }
+    /// }
+    ///
+    /// impl Context for HttpError {}
+    ///
+    /// Report::install_serde_hook::<HttpError>();
+    ///
+    /// let report = Report::new(HttpError { status: 404 });
+    /// let value = serde_json::to_value(&report)?;
+    ///
+    /// assert_eq!(value[0]["context"], "request failed with status 404");
+    /// assert_eq!(value[0]["value"]["status"], 404);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn install_serde_hook<T: serde::Serialize + Send + Sync + 'static>() {
+        // TODO: Use `let ... else` when MSRV is 1.65
+        #[cfg(feature = "std")]
+        let mut lock = SERDE_HOOK.write().unwrap_or_else(|_| {
+            unreachable!(
+                "Hook is poisoned. This is considered a bug and should be reported to \
+                https://github.com/hashintel/hash/issues/new/choose"
+            )
+        });
+
+        // The spin RwLock cannot panic
+        #[cfg(all(not(feature = "std"), feature = "hooks"))]
+        let mut lock = SERDE_HOOK.write();
+
+        lock.insert::<T>();
+    }
+
+    /// Returns the hooks that were previously set by [`install_serde_hook`]
+    ///
+    /// [`install_serde_hook`]: Self::install_serde_hook
+    #[cfg(feature = "serde")]
+    pub(crate) fn invoke_serde_hook<T>(closure: impl FnOnce(&SerdeHooks) -> T) -> T {
+        // TODO: Use `let ... else` when MSRV is 1.65
+        #[cfg(feature = "std")]
+        let hook = SERDE_HOOK.read().unwrap_or_else(|_| {
+            unreachable!(
+                "Hook is poisoned. This is considered a bug and should be reported to \
+                https://github.com/hashintel/hash/issues/new/choose"
+            )
+        });
+
+        // The spin RwLock cannot panic
+        #[cfg(all(not(feature = "std"), feature = "hooks"))]
+        let hook = SERDE_HOOK.read();
+
+        closure(&hook)
+    }
 }
@@ -457,7 +457,7 @@
 //! `backtrace`    | Enables automatic capturing of [`Backtrace`]s (requires Rust 1.65+) | enabled
 //! `spantrace`    | Enables automatic capturing of [`SpanTrace`]s                       | disabled
 //! `hooks`        | Enables hooks on `no-std` platforms using spin locks                | disabled
-//! `serde`        | Enables serialization support for [`Report`]                        | disabled
+//! `serde`        | Enables serialization and deserialization support for [`Report`]    | disabled
 //! `anyhow`       | Provides `into_report` to convert [`anyhow::Error`] to [`Report`]   | disabled
 //! `eyre`         | Provides `into_report` to convert [`eyre::Report`] to [`Report`]    | disabled
 //!
@@ -505,6 +505,8 @@ mod hook;
 #[cfg(feature = "serde")]
 mod serde;
 
+#[cfg(feature = "serde")]
+pub use self::serde::{RemoteAttachment, RemoteContext, RemoteData, RemoteError, RemoteReport};
 pub use self::{
     compat::IntoReportCompat,
     context::Context,
@@ -0,0 +1,49 @@
+#[cfg_attr(feature = "std", allow(unused_imports))]
+use alloc::vec::Vec;
+use core::any::{type_name, TypeId};
+
+use serde::Serialize;
+
+use crate::Frame;
+
+type SerializeHook = for<'a> fn(&'a Frame) -> Option<&'a dyn erased_serde::Serialize>;
+
+fn serialize_frame<T: Serialize + Send + Sync + 'static>(
+    frame: &Frame,
+) -> Option<&dyn erased_serde::Serialize> {
+    frame
+        .downcast_ref::<T>()
+        .map(|value| value as &dyn erased_serde::Serialize)
+}
+
+/// Registry of all types, which are able to be serialized if they are a context or attachment of a
+/// [`Frame`].
+#[allow(clippy::field_scoped_visibility_modifiers)]
+pub(crate) struct SerdeHooks {
+    // We use `Vec`, instead of `HashMap` or `BTreeMap`, so that ordering is consistent with the
+    // insertion order of types.
+    pub(crate) inner: Vec<(TypeId, &'static str, SerializeHook)>,
+}
+
+impl SerdeHooks {
+    pub(crate) fn insert<T: Serialize + Send + Sync + 'static>(&mut self) {
+        let type_id = TypeId::of::<T>();
+
+        // make sure that previous hooks of the same TypeId are deleted.
+        self.inner.retain(|(id, ..)| *id != type_id);
+        // push new hook onto the stack
+        self.inner
+            .push((type_id, type_name::<T>(), serialize_frame::<T>));
+    }
+
+    /// Returns the name of the type and the function used to serialize the frame, if a hook for
+    /// the type of the frame has been installed.
+    pub(crate) fn find(&self, frame: &Frame) -> Option<(&'static str, SerializeHook)> {
+        let type_id = frame.type_id();
+
+        self.inner
+            .iter()
+            .find(|(id, ..)| *id == type_id)
+            .map(|(_, name, hook)| (*name, *hook))
+    }
+}
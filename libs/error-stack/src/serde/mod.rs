@@ -0,0 +1,227 @@
+//! Implementation of general [`Report`] serialization and deserialization.
+//!
+//! By default only the [`Display`] output of contexts and printable attachments is serialized.
+//! Typed values can be serialized by installing a hook for their type through
+//! [`Report::install_serde_hook`]. Opaque attachments without a hook are skipped.
+//!
+//! The serialized [`Report`] is a list of all current sources with the following output:
+//!
+//! ```json
+//! {
+//!     "context": "context display output",
+//!     "type": "type name of the context", // only if a hook is installed
+//!     "value": {}, // serialized value of the context, only if a hook is installed
+//!     "attachments": ["all", "attachments", "leading", "up", "to", "this", "context"],
+//!     "sources": [] // recursive render using `frame.sources()`
+//! }
+//! ```
+//!
+//! Attachments, for which a hook is installed, are serialized as a map, consisting of `"message"`
+//! (only for printable attachments), `"type"` and `"value"`.
+//!
+//! The output can be deserialized into a [`RemoteReport`], which can be converted back into a
+//! [`Report`] of [`RemoteError`]s, retaining the frame tree.
+//!
+//! [`Display`]: core::fmt::Display
+
+#[cfg(any(feature = "std", feature = "hooks"))]
+mod hook;
+mod remote;
+
+#[cfg_attr(feature = "std", allow(unused_imports))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+#[cfg(any(feature = "std", feature = "hooks"))]
+pub(crate) use self::hook::SerdeHooks;
+pub use self::remote::{RemoteAttachment, RemoteContext, RemoteData, RemoteError, RemoteReport};
+use crate::{AttachmentKind, Context, Frame, FrameKind, Report};
+
+/// Returns the type name and the serializable value of the frame, if available.
+///
+/// Frames, which were created from a [`RemoteReport`], retain their type and value, every other
+/// frame requires a hook installed through [`Report::install_serde_hook`].
+fn frame_value(frame: &Frame) -> Option<(&str, &dyn erased_serde::Serialize)> {
+    if let Some(error) = frame.downcast_ref::<RemoteError>() {
+        return error.serialize_value();
+    }
+
+    if let Some(data) = frame.downcast_ref::<RemoteData>() {
+        return data.serialize_value();
+    }
+
+    #[cfg(any(feature = "std", feature = "hooks"))]
+    if let Some((type_name, hook)) = Report::invoke_serde_hook(|hooks| hooks.find(frame)) {
+        return hook(frame).map(|value| (type_name, value));
+    }
+
+    None
+}
+
+enum SerializeAttachment<'a> {
+    Printable(String),
+    Value {
+        message: Option<String>,
+        type_name: &'a str,
+        value: &'a dyn erased_serde::Serialize,
+    },
+}
+
+impl<'a> SerializeAttachment<'a> {
+    /// Returns `None` if the attachment is opaque and no value could be retrieved.
+    fn new(frame: &'a Frame) -> Option<Self> {
+        let message = match frame.kind() {
+            FrameKind::Context(_) => {
+                // `find_next` ensures that no context is ever serialized as an attachment
+                return None;
+            }
+            FrameKind::Attachment(AttachmentKind::Opaque(_)) => None,
+            FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+                Some(attachment.to_string())
+            }
+        };
+
+        match (frame_value(frame), message) {
+            (Some((type_name, value)), message) => Some(Self::Value {
+                message,
+                type_name,
+                value,
+            }),
+            (None, Some(message)) => Some(Self::Printable(message)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl<'a> Serialize for SerializeAttachment<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Printable(message) => message.serialize(serializer),
+            Self::Value {
+                message,
+                type_name,
+                value,
+            } => {
+                let mut map = serializer.serialize_map(Some(2 + usize::from(message.is_some())))?;
+                if let Some(message) = message {
+                    map.serialize_entry("message", message)?;
+                }
+                map.serialize_entry("type", type_name)?;
+                map.serialize_entry("value", value)?;
+
+                map.end()
+            }
+        }
+    }
+}
+
+struct SerializeAttachmentList<'a, 'b>(&'a [&'b Frame]);
+
+impl<'a, 'b> Serialize for SerializeAttachmentList<'a, 'b> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // opaque attachments without a hook are ignored
+        let attachments = self.0.iter().copied().filter_map(SerializeAttachment::new);
+
+        serializer.collect_seq(attachments)
+    }
+}
+
+struct SerializeContext<'a> {
+    attachments: Vec<&'a Frame>,
+    frame: &'a Frame,
+    context: &'a dyn Context,
+}
+
+impl<'a> Serialize for SerializeContext<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Self {
+            attachments,
+            frame,
+            context,
+        } = self;
+
+        let value = frame_value(frame);
+
+        let mut map = serializer.serialize_map(Some(if value.is_some() { 5 } else { 3 }))?;
+        map.serialize_entry("context", &format!("{context}").as_str())?;
+        if let Some((type_name, value)) = value {
+            map.serialize_entry("type", type_name)?;
+            map.serialize_entry("value", value)?;
+        }
+        map.serialize_entry("attachments", &SerializeAttachmentList(attachments))?;
+        map.serialize_entry("sources", &SerializeSources(frame.sources()))?;
+
+        map.end()
+    }
+}
+
+struct SerializeSources<'a>(&'a [Frame]);
+
+impl<'a> Serialize for SerializeSources<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.0.iter().flat_map(|source| find_next(&[], source)))
+    }
+}
+
+// find the next applicable context and return the serializer
+fn find_next<'a>(head: &[&'a Frame], mut current: &'a Frame) -> Vec<SerializeContext<'a>> {
+    let mut attachments = vec![];
+    attachments.extend(head);
+
+    loop {
+        if let FrameKind::Context(context) = current.kind() {
+            // found the context, return all attachments (reversed)
+            attachments.reverse();
+
+            return vec![SerializeContext {
+                attachments,
+                frame: current,
+                context,
+            }];
+        } else if current.sources().len() > 1 {
+            // current is an attachment, add to attachments and recursively probe
+            attachments.push(current);
+
+            return current
+                .sources()
+                .iter()
+                .flat_map(|source| find_next(&attachments, source))
+                .collect();
+        } else if current.sources().len() == 1 {
+            attachments.push(current);
+
+            current = &current.sources()[0];
+        } else {
+            // there are no more frames, therefore we need to abandon
+            // this is theoretically impossible (the bottom is always a context), but not enforced
+            return vec![];
+        }
+    }
+}
+
+impl<C: Context> Serialize for Report<C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializeSources(self.current_frames()).serialize(serializer)
+    }
+}
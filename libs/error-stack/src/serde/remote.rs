@@ -0,0 +1,414 @@
+#[cfg_attr(feature = "std", allow(unused_imports))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{any::Any, fmt};
+
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+
+use crate::{Context, Report};
+
+/// Value of a [`RemoteError`] or [`RemoteData`], which is able to be serialized again.
+trait RemoteValue: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_serialize(&self) -> &dyn erased_serde::Serialize;
+}
+
+impl<T: Serialize + Send + Sync + 'static> RemoteValue for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_serialize(&self) -> &dyn erased_serde::Serialize {
+        self
+    }
+}
+
+fn serialize_value<'a>(
+    type_name: Option<&'a String>,
+    value: Option<&'a dyn RemoteValue>,
+) -> Option<(&'a str, &'a dyn erased_serde::Serialize)> {
+    Some((type_name.map_or("", String::as_str), value?.as_serialize()))
+}
+
+/// A serialized [`Report`], which has been received from a remote source.
+///
+/// The report keeps the frame tree of the original [`Report`], but all contexts and attachments
+/// are reduced to their [`Display`] output and, if a hook has been installed via
+/// [`Report::install_serde_hook`], their type name and value.
+///
+/// Values are deserialized into `V`, which is usually a self-describing value type, like
+/// `serde_json::Value`.
+///
+/// [`Display`]: core::fmt::Display
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io;
+///
+/// use error_stack::{RemoteError, RemoteReport, Report};
+///
+/// let report = Report::new(io::Error::from(io::ErrorKind::NotFound))
+///     .attach_printable("config.toml could not be read");
+///
+/// let serialized = serde_json::to_string(&report)?;
+/// let remote: RemoteReport<serde_json::Value> = serde_json::from_str(&serialized)?;
+/// assert_eq!(remote.contexts()[0].context(), "entity not found");
+///
+/// let report: Report<RemoteError> = remote.into_report().expect("report is not empty");
+/// assert_eq!(report.current_context().message(), "entity not found");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+#[serde(bound(deserialize = "V: Deserialize<'de>"))]
+pub struct RemoteReport<V> {
+    contexts: Vec<RemoteContext<V>>,
+}
+
+impl<V> RemoteReport<V> {
+    /// Returns the current contexts of the report.
+    #[must_use]
+    pub fn contexts(&self) -> &[RemoteContext<V>] {
+        &self.contexts
+    }
+
+    /// Converts the remote report into a [`Report`] of [`RemoteError`]s.
+    ///
+    /// Every context is converted into a [`RemoteError`] and every attachment into a
+    /// [`RemoteData`], which are attached as printable if they had a message. Returns `None` if the
+    /// report does not contain any context.
+    #[track_caller]
+    #[must_use]
+    pub fn into_report(self) -> Option<Report<RemoteError>>
+    where
+        V: Serialize + Send + Sync + 'static,
+    {
+        let mut contexts = self.contexts.into_iter().map(RemoteContext::into_report);
+
+        let mut report = contexts.next()?;
+        for context in contexts {
+            report.extend_one(context);
+        }
+
+        Some(report)
+    }
+}
+
+/// A single context of a [`RemoteReport`], together with the attachments leading up to it and its
+/// sources.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "V: Deserialize<'de>"))]
+pub struct RemoteContext<V> {
+    context: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    type_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<V>,
+    #[serde(default)]
+    attachments: Vec<RemoteAttachment<V>>,
+    #[serde(default)]
+    sources: Vec<RemoteContext<V>>,
+}
+
+impl<V> RemoteContext<V> {
+    /// Returns the [`Display`] output of the context.
+    ///
+    /// [`Display`]: core::fmt::Display
+    #[must_use]
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+
+    /// Returns the name of the type of the context, if it has been serialized.
+    #[must_use]
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    /// Returns the value of the context, if it has been serialized.
+    #[must_use]
+    pub const fn value(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    /// Returns all attachments, from the innermost to the outermost attachment.
+    #[must_use]
+    pub fn attachments(&self) -> &[RemoteAttachment<V>] {
+        &self.attachments
+    }
+
+    /// Returns the sources of the context.
+    #[must_use]
+    pub fn sources(&self) -> &[Self] {
+        &self.sources
+    }
+
+    #[track_caller]
+    fn into_report(self) -> Report<RemoteError>
+    where
+        V: Serialize + Send + Sync + 'static,
+    {
+        let Self {
+            context,
+            type_name,
+            value,
+            attachments,
+            sources,
+        } = self;
+
+        let error = RemoteError {
+            message: context,
+            type_name,
+            value: value.map(|value| Box::new(value) as Box<dyn RemoteValue>),
+        };
+
+        let mut sources = sources.into_iter().map(Self::into_report);
+        let mut report = match sources.next() {
+            Some(mut report) => {
+                for source in sources {
+                    report.extend_one(source);
+                }
+
+                report.change_context(error)
+            }
+            None => Report::new(error),
+        };
+
+        for attachment in attachments {
+            report = attachment.attach_to(report);
+        }
+
+        report
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RemoteAttachmentRepr<V> {
+    Printable(String),
+    Value {
+        message: Option<String>,
+        #[serde(rename = "type")]
+        type_name: Option<String>,
+        value: Option<V>,
+    },
+}
+
+/// A single attachment of a [`RemoteContext`].
+///
+/// Printable attachments without a value are serialized as plain string, all other attachments
+/// are serialized as map.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "RemoteAttachmentRepr<V>")]
+pub struct RemoteAttachment<V> {
+    message: Option<String>,
+    type_name: Option<String>,
+    value: Option<V>,
+}
+
+impl<V> From<RemoteAttachmentRepr<V>> for RemoteAttachment<V> {
+    fn from(repr: RemoteAttachmentRepr<V>) -> Self {
+        match repr {
+            RemoteAttachmentRepr::Printable(message) => Self {
+                message: Some(message),
+                type_name: None,
+                value: None,
+            },
+            RemoteAttachmentRepr::Value {
+                message,
+                type_name,
+                value,
+            } => Self {
+                message,
+                type_name,
+                value,
+            },
+        }
+    }
+}
+
+impl<V> RemoteAttachment<V> {
+    /// Returns the [`Display`] output of the attachment, if it was printable.
+    ///
+    /// [`Display`]: core::fmt::Display
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Returns the name of the type of the attachment, if it has been serialized.
+    #[must_use]
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    /// Returns the value of the attachment, if it has been serialized.
+    #[must_use]
+    pub const fn value(&self) -> Option<&V> {
+        self.value.as_ref()
+    }
+
+    #[track_caller]
+    fn attach_to(self, report: Report<RemoteError>) -> Report<RemoteError>
+    where
+        V: Serialize + Send + Sync + 'static,
+    {
+        let Self {
+            message,
+            type_name,
+            value,
+        } = self;
+
+        let printable = message.is_some();
+        let data = RemoteData {
+            message,
+            type_name,
+            value: value.map(|value| Box::new(value) as Box<dyn RemoteValue>),
+        };
+
+        if printable {
+            report.attach_printable(data)
+        } else if data.value.is_some() {
+            report.attach(data)
+        } else {
+            // neither printable nor a value, there's nothing to retain
+            report
+        }
+    }
+}
+
+impl<V: Serialize> Serialize for RemoteAttachment<V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if let (Some(message), None) = (&self.message, &self.value) {
+            return message.serialize(serializer);
+        }
+
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(message) = &self.message {
+            map.serialize_entry("message", message)?;
+        }
+        if let Some(type_name) = &self.type_name {
+            map.serialize_entry("type", type_name)?;
+        }
+        if let Some(value) = &self.value {
+            map.serialize_entry("value", value)?;
+        }
+
+        map.end()
+    }
+}
+
+/// Context of a [`Report`], which has been created from a [`RemoteReport`].
+///
+/// The [`Display`] output is the same as the one of the original context.
+///
+/// [`Display`]: core::fmt::Display
+pub struct RemoteError {
+    message: String,
+    type_name: Option<String>,
+    value: Option<Box<dyn RemoteValue>>,
+}
+
+impl RemoteError {
+    /// Returns the [`Display`] output of the original context.
+    ///
+    /// [`Display`]: core::fmt::Display
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the name of the type of the original context, if it has been serialized.
+    #[must_use]
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    /// Returns the value of the original context, if it has been serialized and is of type `T`.
+    ///
+    /// `T` is the value type the [`RemoteReport`] has been deserialized with.
+    #[must_use]
+    pub fn value<T: 'static>(&self) -> Option<&T> {
+        self.value.as_ref()?.as_any().downcast_ref()
+    }
+
+    pub(super) fn serialize_value(&self) -> Option<(&str, &dyn erased_serde::Serialize)> {
+        serialize_value(self.type_name.as_ref(), self.value.as_deref())
+    }
+}
+
+impl fmt::Debug for RemoteError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("RemoteError")
+            .field("message", &self.message)
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(&self.message)
+    }
+}
+
+impl Context for RemoteError {}
+
+/// Attachment of a [`Report`], which has been created from a [`RemoteReport`].
+///
+/// If the original attachment was printable, the [`Display`] output is the same as the one of the
+/// original attachment.
+///
+/// [`Display`]: core::fmt::Display
+pub struct RemoteData {
+    message: Option<String>,
+    type_name: Option<String>,
+    value: Option<Box<dyn RemoteValue>>,
+}
+
+impl RemoteData {
+    /// Returns the [`Display`] output of the original attachment, if it was printable.
+    ///
+    /// [`Display`]: core::fmt::Display
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Returns the name of the type of the original attachment, if it has been serialized.
+    #[must_use]
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    /// Returns the value of the original attachment, if it has been serialized and is of type `T`.
+    ///
+    /// `T` is the value type the [`RemoteReport`] has been deserialized with.
+    #[must_use]
+    pub fn value<T: 'static>(&self) -> Option<&T> {
+        self.value.as_ref()?.as_any().downcast_ref()
+    }
+
+    pub(super) fn serialize_value(&self) -> Option<(&str, &dyn erased_serde::Serialize)> {
+        serialize_value(self.type_name.as_ref(), self.value.as_deref())
+    }
+}
+
+impl fmt::Debug for RemoteData {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("RemoteData")
+            .field("message", &self.message)
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for RemoteData {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.message.as_deref().unwrap_or_default())
+    }
+}
@@ -0,0 +1,178 @@
+//! Tests for serialization hooks and the deserialization into [`RemoteReport`].
+//!
+//! These are kept in a separate binary from `test_serialize`, as hooks are installed globally.
+#![cfg(all(feature = "std", feature = "serde"))]
+
+use core::fmt;
+
+use error_stack::{Context, RemoteData, RemoteError, RemoteReport, Report};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Serialize)]
+struct HttpError {
+    status: u16,
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "request failed with status {}", self.status)
+    }
+}
+
+impl Context for HttpError {}
+
+#[derive(Debug)]
+struct FetchError;
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("unable to fetch resource")
+    }
+}
+
+impl Context for FetchError {}
+
+#[derive(Serialize)]
+struct RequestId(u64);
+
+#[derive(Debug, Serialize)]
+struct Url(&'static str);
+
+impl fmt::Display for Url {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.0)
+    }
+}
+
+struct Unregistered;
+
+fn install_hooks() {
+    Report::install_serde_hook::<HttpError>();
+    Report::install_serde_hook::<RequestId>();
+    Report::install_serde_hook::<Url>();
+}
+
+fn create_report() -> Report<FetchError> {
+    Report::new(HttpError { status: 404 })
+        .attach(RequestId(42))
+        .attach(Unregistered)
+        .attach_printable(Url("https://example.com"))
+        .attach_printable("no retries left")
+        .change_context(FetchError)
+}
+
+#[test]
+fn hooks() {
+    install_hooks();
+
+    let value = serde_json::to_value(create_report()).expect("report should be serializable");
+
+    assert_eq!(
+        value,
+        json!([{
+            "context": "unable to fetch resource",
+            "attachments": [],
+            "sources": [{
+                "context": "request failed with status 404",
+                "type": core::any::type_name::<HttpError>(),
+                "value": {"status": 404},
+                "attachments": [
+                    {"type": core::any::type_name::<RequestId>(), "value": 42},
+                    {
+                        "message": "https://example.com",
+                        "type": core::any::type_name::<Url>(),
+                        "value": "https://example.com"
+                    },
+                    "no retries left"
+                ],
+                "sources": []
+            }]
+        }])
+    );
+}
+
+#[test]
+fn round_trip() {
+    install_hooks();
+
+    let mut report = create_report();
+    report.extend_one(Report::new(FetchError));
+
+    let serialized = serde_json::to_value(&report).expect("report should be serializable");
+    let remote: RemoteReport<Value> =
+        serde_json::from_value(serialized.clone()).expect("report should be deserializable");
+
+    assert_eq!(remote.contexts().len(), 2);
+    let source = &remote.contexts()[0].sources()[0];
+    assert_eq!(
+        source.type_name(),
+        Some(core::any::type_name::<HttpError>())
+    );
+    assert_eq!(source.value(), Some(&json!({"status": 404})));
+    assert_eq!(source.attachments()[2].message(), Some("no retries left"));
+
+    let report = remote.into_report().expect("report should not be empty");
+    assert_eq!(report.current_frames().len(), 2);
+    assert_eq!(
+        report.current_context().message(),
+        "unable to fetch resource"
+    );
+
+    let error = report
+        .frames()
+        .filter_map(|frame| frame.downcast_ref::<RemoteError>())
+        .find(|error| error.type_name().is_some())
+        .expect("source context should be present");
+    assert_eq!(error.to_string(), "request failed with status 404");
+    assert_eq!(error.value::<Value>(), Some(&json!({"status": 404})));
+
+    let request_id = report
+        .frames()
+        .filter_map(|frame| frame.downcast_ref::<RemoteData>())
+        .find(|data| data.message().is_none())
+        .expect("opaque attachment should be present");
+    assert_eq!(request_id.value::<Value>(), Some(&json!(42)));
+
+    // serializing the remote report again yields the same output
+    assert_eq!(
+        serde_json::to_value(&report).expect("report should be serializable"),
+        serialized
+    );
+}
+
+#[test]
+fn deserialize_without_values() {
+    let remote: RemoteReport<Value> = serde_json::from_value(json!([{
+        "context": "unable to fetch resource",
+        "sources": [{"context": "request failed", "attachments": ["no retries left"]}]
+    }]))
+    .expect("report should be deserializable");
+
+    let report = remote.into_report().expect("report should not be empty");
+
+    assert_eq!(
+        report
+            .frames()
+            .filter_map(|frame| frame.downcast_ref::<RemoteError>())
+            .map(RemoteError::message)
+            .collect::<Vec<_>>(),
+        ["unable to fetch resource", "request failed"]
+    );
+    assert_eq!(
+        report
+            .frames()
+            .filter_map(|frame| frame.downcast_ref::<RemoteData>())
+            .filter_map(RemoteData::message)
+            .collect::<Vec<_>>(),
+        ["no retries left"]
+    );
+}
+
+#[test]
+fn empty_report() {
+    let remote: RemoteReport<Value> =
+        serde_json::from_value(json!([])).expect("report should be deserializable");
+
+    assert!(remote.into_report().is_none());
+}
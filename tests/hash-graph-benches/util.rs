@@ -29,7 +29,6 @@ use graph_types::{
 };
 use repo_chores::benches::generate_path;
 use tokio::runtime::Runtime;
-use tokio_postgres::NoTls;
 use tracing_flame::FlameLayer;
 use tracing_subscriber::{prelude::*, registry::Registry};
 use type_system::schema::{DataType, EntityType, PropertyType};
@@ -122,13 +121,10 @@ where
             bench_db_name.to_owned(),
         );
 
-        let source_db_pool = PostgresStorePool::new(
-            &source_db_connection_info,
-            &DatabasePoolConfig::default(),
-            NoTls,
-        )
-        .await
-        .expect("could not connect to database");
+        let source_db_pool =
+            PostgresStorePool::new(&source_db_connection_info, &DatabasePoolConfig::default())
+                .await
+                .expect("could not connect to database");
 
         // Create a new connection to the source database, copy the database, drop the connection
         {
@@ -198,7 +194,6 @@ where
                     bench_db_name.to_owned(),
                 ),
                 &DatabasePoolConfig::default(),
-                NoTls,
             )
             .await
             .expect("could not connect to database");
@@ -212,13 +207,10 @@ where
                 .expect("could not run migrations");
         }
 
-        let pool = PostgresStorePool::new(
-            &bench_db_connection_info,
-            &DatabasePoolConfig::default(),
-            NoTls,
-        )
-        .await
-        .expect("could not connect to database");
+        let pool =
+            PostgresStorePool::new(&bench_db_connection_info, &DatabasePoolConfig::default())
+                .await
+                .expect("could not connect to database");
 
         // _owned is necessary as otherwise we have a self-referential struct
         let store = pool
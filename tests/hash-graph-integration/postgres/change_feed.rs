@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+
+use graph::store::{
+    change_feed::{ChangeFeedStore, ChangeKind, ChangedRecord, ReadChangesParams},
+    knowledge::{CreateEntityParams, PatchEntityParams},
+    EntityStore,
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    knowledge::{
+        entity::ProvidedEntityEditionProvenance, Property, PropertyObject, PropertyPatchOperation,
+        PropertyPath, PropertyWithMetadata, PropertyWithMetadataObject,
+    },
+    owned_by_id::OwnedById,
+};
+use type_system::url::{BaseUrl, OntologyTypeVersion, VersionedUrl};
+
+use crate::DatabaseTestWrapper;
+
+#[tokio::test]
+#[expect(clippy::too_many_lines)]
+async fn entity_changes() {
+    let page_v1: PropertyObject =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: PropertyObject =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::TEXT_V1],
+            [entity_type::PAGE_V1],
+        )
+        .await
+        .expect("could not seed database:");
+
+    let seeded_changes = api
+        .read_changes(
+            api.account_id,
+            ReadChangesParams {
+                after: None,
+                limit: None,
+            },
+        )
+        .await
+        .expect("could not read changes");
+    assert!(seeded_changes.changes.iter().any(|change| matches!(
+        &change.record,
+        ChangedRecord::EntityType(record_id) if record_id.base_url.as_str()
+            == "https://blockprotocol.org/@alice/types/entity-type/page/"
+    )));
+
+    let v1_entity = api
+        .create_entity(
+            api.account_id,
+            CreateEntityParams {
+                owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+                entity_uuid: None,
+                decision_time: None,
+                entity_type_ids: HashSet::from([VersionedUrl {
+                    base_url: BaseUrl::new(
+                        "https://blockprotocol.org/@alice/types/entity-type/page/".to_owned(),
+                    )
+                    .expect("couldn't construct Base URL"),
+                    version: OntologyTypeVersion::new(1),
+                }]),
+                properties: PropertyWithMetadataObject::from_parts(page_v1, None)
+                    .expect("could not create property with metadata object"),
+                confidence: None,
+                link_data: None,
+                draft: false,
+                relationships: [],
+                provenance: ProvidedEntityEditionProvenance::default(),
+            },
+        )
+        .await
+        .expect("could not create entity");
+
+    let patch = |properties, archived| PatchEntityParams {
+        entity_id: v1_entity.metadata.record_id.entity_id,
+        expected_edition_id: None,
+        properties,
+        entity_type_ids: HashSet::new(),
+        archived,
+        draft: None,
+        decision_time: None,
+        confidence: None,
+        provenance: ProvidedEntityEditionProvenance::default(),
+    };
+
+    let v2_entity = api
+        .patch_entity(
+            api.account_id,
+            patch(
+                vec![PropertyPatchOperation::Replace {
+                    path: PropertyPath::default(),
+                    property: PropertyWithMetadata::from_parts(Property::Object(page_v2), None)
+                        .expect("could not create property with metadata"),
+                }],
+                None,
+            ),
+        )
+        .await
+        .expect("could not update entity");
+    let v3_entity = api
+        .patch_entity(api.account_id, patch(Vec::new(), Some(true)))
+        .await
+        .expect("could not archive entity");
+
+    let response = api
+        .read_changes(
+            api.account_id,
+            ReadChangesParams {
+                after: seeded_changes.cursor,
+                limit: None,
+            },
+        )
+        .await
+        .expect("could not read changes");
+
+    let changes = response
+        .changes
+        .iter()
+        .map(|change| (change.kind, change.record.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        [
+            (
+                ChangeKind::Create,
+                ChangedRecord::Entity(v1_entity.metadata.record_id)
+            ),
+            (
+                ChangeKind::Update,
+                ChangedRecord::Entity(v2_entity.metadata.record_id)
+            ),
+            (
+                ChangeKind::Archive,
+                ChangedRecord::Entity(v3_entity.metadata.record_id)
+            ),
+        ]
+    );
+    assert_eq!(
+        response.cursor,
+        response.changes.last().map(|change| change.sequence_id)
+    );
+
+    let response = api
+        .read_changes(
+            api.account_id,
+            ReadChangesParams {
+                after: response.cursor,
+                limit: None,
+            },
+        )
+        .await
+        .expect("could not read changes");
+    assert!(response.changes.is_empty());
+}
@@ -5,7 +5,10 @@ use graph::{
     store::{
         error::{OntologyTypeIsNotOwned, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
         knowledge::CreateEntityParams,
-        ontology::{CreateDataTypeParams, GetDataTypesParams, UpdateDataTypesParams},
+        ontology::{
+            CheckDataTypeUpdateParams, CreateDataTypeParams, GetDataTypesParams,
+            UpdateDataTypesParams,
+        },
         query::Filter,
         BaseUrlAlreadyExists, ConflictBehavior, DataTypeStore, EntityStore,
     },
@@ -28,7 +31,7 @@ use serde_json::json;
 use temporal_versioning::TemporalBound;
 use time::OffsetDateTime;
 use type_system::{
-    schema::DataType,
+    schema::{DataType, DataTypeIncompatibility},
     url::{BaseUrl, VersionedUrl},
 };
 
@@ -756,3 +759,77 @@ async fn update_external_with_owned() {
         "wrong error, expected `OntologyTypeIsNotOwned`, got {report:?}"
     );
 }
+
+#[tokio::test]
+async fn check_update() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [graph_test_data::data_type::TEXT_V1],
+            [graph_test_data::property_type::NAME_V1],
+            [graph_test_data::entity_type::ORGANIZATION_V1],
+        )
+        .await
+        .expect("could not seed database");
+
+    let name =
+        BaseUrl::new("https://blockprotocol.org/@alice/types/property-type/name/".to_owned())
+            .expect("couldn't construct Base URL");
+    for organization in ["HASH, Ltd", "ACME"] {
+        api.create_entity(
+            api.account_id,
+            CreateEntityParams {
+                owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+                entity_uuid: None,
+                decision_time: None,
+                entity_type_ids: HashSet::from([VersionedUrl::from_str(
+                    "https://blockprotocol.org/@alice/types/entity-type/organization/v/1",
+                )
+                .expect("couldn't construct Versioned URL")]),
+                properties: PropertyWithMetadataObject {
+                    value: HashMap::from([(
+                        name.clone(),
+                        PropertyWithMetadata::Value(ValueWithMetadata {
+                            value: json!(organization),
+                            metadata: ValueMetadata {
+                                provenance: PropertyProvenance::default(),
+                                confidence: None,
+                                data_type_id: None,
+                            },
+                        }),
+                    )]),
+                    metadata: ObjectMetadata::default(),
+                },
+                confidence: None,
+                link_data: None,
+                draft: false,
+                relationships: [],
+                provenance: ProvidedEntityEditionProvenance::default(),
+            },
+        )
+        .await
+        .expect("could not create entity");
+    }
+
+    let mut text_v2: DataType = serde_json::from_str(graph_test_data::data_type::TEXT_V1)
+        .expect("could not parse data type representation");
+    text_v2.id =
+        VersionedUrl::from_str("https://blockprotocol.org/@blockprotocol/types/data-type/text/v/2")
+            .expect("couldn't construct Versioned URL");
+    text_v2.max_length = Some(5);
+
+    let response = api
+        .check_data_type_update(
+            api.account_id,
+            CheckDataTypeUpdateParams { schema: text_v2 },
+        )
+        .await
+        .expect("could not check data type update");
+
+    assert_eq!(
+        response.incompatibilities,
+        [DataTypeIncompatibility::MaxLengthLowered]
+    );
+    assert_eq!(response.checked_entities, 2);
+    assert_eq!(response.invalid_entities, 1);
+}
@@ -126,6 +126,7 @@ async fn initial_draft() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: vec![PropertyPatchOperation::Replace {
                     path: PropertyPath::default(),
                     property: PropertyWithMetadata::from_parts(Property::Object(bob()), None)
@@ -177,6 +178,7 @@ async fn initial_draft() {
             api.account_id,
             PatchEntityParams {
                 entity_id: updated_entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: vec![PropertyPatchOperation::Replace {
                     path: PropertyPath::default(),
                     property: PropertyWithMetadata::from_parts(Property::Object(charles()), None)
@@ -304,6 +306,7 @@ async fn no_initial_draft() {
                 api.account_id,
                 PatchEntityParams {
                     entity_id: entity.metadata.record_id.entity_id,
+                    expected_edition_id: None,
                     properties: vec![PropertyPatchOperation::Replace {
                         path: PropertyPath::default(),
                         property: PropertyWithMetadata::from_parts(Property::Object(bob()), None)
@@ -360,6 +363,7 @@ async fn no_initial_draft() {
                 api.account_id,
                 PatchEntityParams {
                     entity_id: updated_entity.metadata.record_id.entity_id,
+                    expected_edition_id: None,
                     properties: vec![PropertyPatchOperation::Replace {
                         path: PropertyPath::default(),
                         property: PropertyWithMetadata::from_parts(
@@ -467,6 +471,7 @@ async fn multiple_drafts() {
                 api.account_id,
                 PatchEntityParams {
                     entity_id: entity.metadata.record_id.entity_id,
+                    expected_edition_id: None,
                     properties: vec![PropertyPatchOperation::Replace {
                         path: PropertyPath::default(),
                         property: PropertyWithMetadata::from_parts(Property::Object(bob()), None)
@@ -526,6 +531,7 @@ async fn multiple_drafts() {
                 api.account_id,
                 PatchEntityParams {
                     entity_id: draft,
+                    expected_edition_id: None,
                     properties: vec![PropertyPatchOperation::Replace {
                         path: PropertyPath::default(),
                         property: PropertyWithMetadata::from_parts(
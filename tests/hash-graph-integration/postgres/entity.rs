@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use graph::{
     store::{
+        error::EntityEditionConflict,
         knowledge::{
             CountEntitiesParams, CreateEntityParams, GetEntitiesParams, PatchEntityParams,
         },
@@ -227,6 +228,7 @@ async fn update() {
             api.account_id,
             PatchEntityParams {
                 entity_id: v1_entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: vec![PropertyPatchOperation::Replace {
                     path: PropertyPath::default(),
                     property: PropertyWithMetadata::from_parts(
@@ -350,3 +352,87 @@ async fn update() {
     let entity_v2 = response_v2.entities.pop().expect("no entity found");
     assert_eq!(entity_v2.properties.properties(), page_v2.properties());
 }
+
+#[tokio::test]
+async fn update_with_expected_edition() {
+    let page_v1: PropertyObject =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: PropertyObject =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::TEXT_V1],
+            [entity_type::PAGE_V1],
+        )
+        .await
+        .expect("could not seed database:");
+
+    let v1_entity = api
+        .create_entity(
+            api.account_id,
+            CreateEntityParams {
+                owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+                entity_uuid: None,
+                decision_time: None,
+                entity_type_ids: HashSet::from([VersionedUrl {
+                    base_url: BaseUrl::new(
+                        "https://blockprotocol.org/@alice/types/entity-type/page/".to_owned(),
+                    )
+                    .expect("couldn't construct Base URL"),
+                    version: OntologyTypeVersion::new(1),
+                }]),
+                properties: PropertyWithMetadataObject::from_parts(page_v1, None)
+                    .expect("could not create property with metadata object"),
+                confidence: None,
+                link_data: None,
+                draft: false,
+                relationships: [],
+                provenance: ProvidedEntityEditionProvenance::default(),
+            },
+        )
+        .await
+        .expect("could not create entity");
+
+    let patch = |expected_edition_id| PatchEntityParams {
+        entity_id: v1_entity.metadata.record_id.entity_id,
+        expected_edition_id: Some(expected_edition_id),
+        properties: vec![PropertyPatchOperation::Replace {
+            path: PropertyPath::default(),
+            property: PropertyWithMetadata::from_parts(Property::Object(page_v2.clone()), None)
+                .expect("could not create property with metadata"),
+        }],
+        entity_type_ids: HashSet::new(),
+        archived: None,
+        draft: None,
+        decision_time: None,
+        confidence: None,
+        provenance: ProvidedEntityEditionProvenance::default(),
+    };
+
+    let v2_entity = api
+        .patch_entity(
+            api.account_id,
+            patch(v1_entity.metadata.record_id.edition_id),
+        )
+        .await
+        .expect("could not update entity");
+
+    let report = api
+        .patch_entity(
+            api.account_id,
+            patch(v1_entity.metadata.record_id.edition_id),
+        )
+        .await
+        .expect_err("outdated edition should be rejected");
+    assert!(report.contains::<EntityEditionConflict>());
+
+    api.patch_entity(
+        api.account_id,
+        patch(v2_entity.metadata.record_id.edition_id),
+    )
+    .await
+    .expect("could not update entity");
+}
@@ -1,20 +1,31 @@
+use std::collections::HashSet;
+
 use graph::{
     store::{
-        ontology::{CreateEntityTypeParams, GetEntityTypesParams, UpdateEntityTypesParams},
+        knowledge::CreateEntityParams,
+        ontology::{
+            CheckEntityTypeUpdateParams, CreateEntityTypeParams, GetEntityTypesParams,
+            UpdateEntityTypesParams,
+        },
         query::Filter,
-        ConflictBehavior, EntityTypeStore,
+        ConflictBehavior, EntityStore, EntityTypeStore,
     },
     subgraph::temporal_axes::{
         PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
     },
 };
-use graph_test_data::{data_type, entity_type, property_type};
+use graph_test_data::{data_type, entity, entity_type, property_type};
 use graph_types::{
+    knowledge::{entity::ProvidedEntityEditionProvenance, PropertyWithMetadataObject},
     ontology::{OntologyTypeClassificationMetadata, ProvidedOntologyEditionProvenance},
     owned_by_id::OwnedById,
 };
+use serde_json::json;
 use temporal_versioning::TemporalBound;
-use type_system::schema::EntityType;
+use type_system::{
+    schema::{EntityType, EntityTypeIncompatibility},
+    url::BaseUrl,
+};
 
 use crate::{entity_type_relationships, DatabaseTestWrapper};
 
@@ -233,3 +244,78 @@ async fn update() {
     assert_eq!(page_et_v1.id, returned_page_et_v1.schema.id);
     assert_eq!(page_et_v2.id, returned_page_et_v2.schema.id);
 }
+
+#[tokio::test]
+async fn check_update() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1, data_type::NUMBER_V1],
+            [
+                property_type::NAME_V1,
+                property_type::AGE_V1,
+                property_type::INTERESTS_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::FAVORITE_FILM_V1,
+                property_type::HOBBY_V1,
+            ],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let person_v1: EntityType = serde_json::from_str(entity_type::PERSON_V1)
+        .expect("could not parse entity type representation");
+    for person in [entity::PERSON_ALICE_V1, entity::PERSON_BOB_V1] {
+        api.create_entity(
+            api.account_id,
+            CreateEntityParams {
+                owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+                entity_uuid: None,
+                decision_time: None,
+                entity_type_ids: HashSet::from([person_v1.id.clone()]),
+                properties: PropertyWithMetadataObject::from_parts(
+                    serde_json::from_str(person).expect("could not parse entity"),
+                    None,
+                )
+                .expect("could not create property with metadata object"),
+                confidence: None,
+                link_data: None,
+                draft: false,
+                relationships: [],
+                provenance: ProvidedEntityEditionProvenance::default(),
+            },
+        )
+        .await
+        .expect("could not create entity");
+    }
+
+    // Only Bob has an age, so requiring it invalidates Alice
+    let age = BaseUrl::new("https://blockprotocol.org/@alice/types/property-type/age/".to_owned())
+        .expect("couldn't construct Base URL");
+    let mut person_v2 = serde_json::to_value(&person_v1).expect("could not serialize entity type");
+    person_v2["$id"] = json!("https://blockprotocol.org/@alice/types/entity-type/person/v/2");
+    person_v2["required"] = json!([age]);
+    let person_v2: EntityType =
+        serde_json::from_value(person_v2).expect("could not parse entity type representation");
+
+    let response = api
+        .check_entity_type_update(
+            api.account_id,
+            CheckEntityTypeUpdateParams { schema: person_v2 },
+        )
+        .await
+        .expect("could not check entity type update");
+
+    assert_eq!(
+        response.incompatibilities,
+        [EntityTypeIncompatibility::PropertyRequired { property: age }]
+    );
+    assert_eq!(response.checked_entities, 2);
+    assert_eq!(response.invalid_entities, 1);
+}
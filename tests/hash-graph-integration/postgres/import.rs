@@ -0,0 +1,244 @@
+use alloc::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
+
+use authorization::NoAuthorization;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use error_stack::Report;
+use graph::{
+    knowledge::import::{
+        import_entities, EntityImportMapping, ImportFormat, ImportReport, ImportRow,
+        ImportRowDecoder, ImportRowError,
+    },
+    ontology::domain_validator::DomainValidator,
+    store::FetchingPool,
+};
+use graph_api::rest::{
+    authentication::AuthenticationConfig, rest_api_router, RestRouterDependencies,
+};
+use graph_test_data::{data_type, entity_type, property_type};
+use graph_types::owned_by_id::OwnedById;
+use regex::Regex;
+use serde_json::json;
+use tokio_util::codec::FramedRead;
+use tower::ServiceExt as _;
+use type_system::url::BaseUrl;
+
+use crate::{
+    migration::{count_entities_of_type, create_person_types},
+    subscription::seed_committed_page_type,
+    DatabaseApi, DatabaseTestWrapper,
+};
+
+fn base_url(url: &str) -> BaseUrl {
+    BaseUrl::new(url.to_owned()).expect("could not parse base url")
+}
+
+async fn seed_persons(
+    database: &mut DatabaseTestWrapper<NoAuthorization>,
+) -> (DatabaseApi<'_, &mut NoAuthorization>, EntityImportMapping) {
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1, data_type::NUMBER_V1],
+            [
+                property_type::NAME_V1,
+                property_type::AGE_V1,
+                property_type::INTERESTS_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::FAVORITE_FILM_V1,
+                property_type::HOBBY_V1,
+            ],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    // The second version requires an age, so rows without an age cannot be inserted
+    let (_, person_v2) = create_person_types(&mut api).await;
+
+    let mapping = EntityImportMapping {
+        owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+        entity_type_ids: HashSet::from([person_v2.id]),
+        columns: HashMap::from([
+            (
+                "name".to_owned(),
+                base_url("https://blockprotocol.org/@alice/types/property-type/name/"),
+            ),
+            (
+                "age".to_owned(),
+                base_url("https://blockprotocol.org/@alice/types/property-type/age/"),
+            ),
+        ]),
+        array_delimiter: ';',
+        draft: false,
+        relationships: Vec::new(),
+    };
+
+    (api, mapping)
+}
+
+#[tokio::test]
+async fn import_in_batches() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let (mut api, mapping) = seed_persons(&mut database).await;
+    let account_id = api.account_id;
+
+    let input = "name,age\nAlice,30\nBob,thirty\nCharles,\nDave,27\nEve,35\n";
+    let rows = FramedRead::new(input.as_bytes(), ImportRowDecoder::new(ImportFormat::Csv));
+
+    // Alice and Charles form the first batch, which is inserted one by one as Charles has no age
+    let report = import_entities(&mut api, account_id, &mapping, rows, 2)
+        .await
+        .expect("could not import entities");
+
+    assert_eq!(report.created.len(), 3);
+    assert_eq!(
+        report
+            .errors
+            .iter()
+            .map(|error| error.row)
+            .collect::<Vec<_>>(),
+        [3, 4]
+    );
+    assert!(report.errors.iter().all(|error| !error.message.is_empty()));
+    assert_eq!(report.aborted, None);
+
+    let entity_type_id = mapping
+        .entity_type_ids
+        .iter()
+        .next()
+        .expect("mapping should have an entity type");
+    assert_eq!(count_entities_of_type(&api, entity_type_id).await, 3);
+}
+
+#[tokio::test]
+async fn import_until_read_error() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let (mut api, mapping) = seed_persons(&mut database).await;
+    let account_id = api.account_id;
+
+    let row = |row, name: &str, age: u8| -> Result<Result<ImportRow, ImportRowError>, _> {
+        let serde_json::Value::Object(values) = json!({ "name": name, "age": age }) else {
+            unreachable!("row values are an object")
+        };
+        Ok(Ok(ImportRow { row, values }))
+    };
+    let rows = futures::stream::iter([
+        row(1, "Alice", 30),
+        row(2, "Bob", 42),
+        Err(Report::new(io::Error::other("connection reset"))),
+        row(4, "Eve", 35),
+    ]);
+
+    let report = import_entities(&mut api, account_id, &mapping, rows, 10)
+        .await
+        .expect("could not import entities");
+
+    // The rows read before the error are still imported
+    assert_eq!(report.created.len(), 2);
+    assert!(report.errors.is_empty());
+    assert!(
+        report
+            .aborted
+            .as_deref()
+            .is_some_and(|reason| reason.contains("connection reset")),
+        "unexpected abort reason: {:?}",
+        report.aborted
+    );
+
+    let entity_type_id = mapping
+        .entity_type_ids
+        .iter()
+        .next()
+        .expect("mapping should have an entity type");
+    assert_eq!(count_entities_of_type(&api, entity_type_id).await, 2);
+}
+
+#[tokio::test]
+async fn import_over_rest() {
+    let DatabaseTestWrapper::<NoAuthorization> {
+        pool,
+        mut connection,
+    } = DatabaseTestWrapper::new().await;
+
+    // The router acquires its own connections, so the records are committed
+    let (account_id, page_type_id) = seed_committed_page_type(&mut connection).await;
+
+    let router = rest_api_router(RestRouterDependencies {
+        store: Arc::new(FetchingPool::<_, (String, u16)>::new_offline(pool, None)),
+        authorization_api: Arc::new(NoAuthorization),
+        temporal_client: None,
+        domain_regex: DomainValidator::new(
+            Regex::new(r"http://localhost:3000/@(?P<shortname>[\w-]+)/types/")
+                .expect("could not parse domain regex"),
+        ),
+        authentication: AuthenticationConfig::default(),
+        subscription_secret_key: None,
+    });
+
+    let mapping = EntityImportMapping {
+        owned_by_id: OwnedById::new(account_id.into_uuid()),
+        entity_type_ids: HashSet::from([page_type_id]),
+        columns: HashMap::from([(
+            "text".to_owned(),
+            base_url("https://blockprotocol.org/@alice/types/property-type/text/"),
+        )]),
+        array_delimiter: ';',
+        draft: false,
+        relationships: Vec::new(),
+    };
+    let query = serde_urlencoded::to_string([
+        (
+            "mapping",
+            serde_json::to_string(&mapping).expect("could not serialize mapping"),
+        ),
+        ("batchSize", "2".to_owned()),
+    ])
+    .expect("could not encode query");
+
+    let response = router
+        .oneshot(
+            Request::post(format!("/entities/import?{query}"))
+                .header("X-Authenticated-User-Actor-Id", account_id.to_string())
+                .header(header::CONTENT_TYPE, "text/csv")
+                .body(Body::from(
+                    &b"text\nfirst\nsecond,extra\n\xffx\nfourth\n"[..],
+                ))
+                .expect("could not build request"),
+        )
+        .await
+        .expect("could not send request");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let report: ImportReport = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("could not read response"),
+    )
+    .expect("could not parse import report");
+
+    assert_eq!(report.created.len(), 2);
+    assert_eq!(
+        report.errors,
+        [
+            ImportRowError {
+                row: 3,
+                message: "expected 1 columns, found 2".to_owned(),
+            },
+            ImportRowError {
+                row: 4,
+                message: "the row is not valid UTF-8".to_owned(),
+            },
+        ]
+    );
+    assert_eq!(report.aborted, None);
+}
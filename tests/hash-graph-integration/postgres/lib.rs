@@ -11,17 +11,22 @@
 extern crate alloc;
 extern crate core;
 
+mod change_feed;
 mod data_type;
 mod drafts;
 mod entity;
 mod entity_type;
+mod import;
 mod interconnected_graph;
 mod links;
+mod migration;
 mod multi_type;
 mod partial_updates;
 mod property_metadata;
 mod property_type;
+mod snapshot;
 mod sorting;
+mod subscription;
 
 use authorization::{
     schema::{
@@ -39,6 +44,8 @@ use graph::{
     load_env,
     store::{
         account::{InsertAccountIdParams, InsertWebIdParams},
+        change_feed::{ChangeFeedStore, ReadChangesParams, ReadChangesResponse},
+        error::DeletionError,
         knowledge::{
             CountEntitiesParams, CreateEntityParams, GetEntitiesParams, GetEntitiesResponse,
             GetEntitySubgraphParams, GetEntitySubgraphResponse, PatchEntityParams,
@@ -46,19 +53,27 @@ use graph::{
         },
         ontology::{
             ArchiveDataTypeParams, ArchiveEntityTypeParams, ArchivePropertyTypeParams,
-            CountDataTypesParams, CountEntityTypesParams, CountPropertyTypesParams,
-            CreateDataTypeParams, CreateEntityTypeParams, CreatePropertyTypeParams,
-            GetDataTypeSubgraphParams, GetDataTypeSubgraphResponse, GetDataTypesParams,
-            GetDataTypesResponse, GetEntityTypeSubgraphParams, GetEntityTypeSubgraphResponse,
-            GetEntityTypesParams, GetEntityTypesResponse, GetPropertyTypeSubgraphParams,
-            GetPropertyTypeSubgraphResponse, GetPropertyTypesParams, GetPropertyTypesResponse,
-            UnarchiveDataTypeParams, UnarchiveEntityTypeParams, UnarchivePropertyTypeParams,
-            UpdateDataTypeEmbeddingParams, UpdateDataTypesParams, UpdateEntityTypeEmbeddingParams,
-            UpdateEntityTypesParams, UpdatePropertyTypeEmbeddingParams, UpdatePropertyTypesParams,
+            CheckDataTypeUpdateParams, CheckDataTypeUpdateResponse, CheckEntityTypeUpdateParams,
+            CheckEntityTypeUpdateResponse, CheckPropertyTypeUpdateParams,
+            CheckPropertyTypeUpdateResponse, CountDataTypesParams, CountEntityTypesParams,
+            CountPropertyTypesParams, CreateDataTypeParams, CreateEntityTypeParams,
+            CreatePropertyTypeParams, GetDataTypeSubgraphParams, GetDataTypeSubgraphResponse,
+            GetDataTypesParams, GetDataTypesResponse, GetEntityTypeSubgraphParams,
+            GetEntityTypeSubgraphResponse, GetEntityTypesParams, GetEntityTypesResponse,
+            GetPropertyTypeSubgraphParams, GetPropertyTypeSubgraphResponse, GetPropertyTypesParams,
+            GetPropertyTypesResponse, UnarchiveDataTypeParams, UnarchiveEntityTypeParams,
+            UnarchivePropertyTypeParams, UpdateDataTypeEmbeddingParams, UpdateDataTypesParams,
+            UpdateEntityTypeEmbeddingParams, UpdateEntityTypesParams,
+            UpdatePropertyTypeEmbeddingParams, UpdatePropertyTypesParams,
+        },
+        subscription::{
+            CreateEntitySubscriptionParams, EntitySubscription, ParkedChange, SubscriptionId,
+            SubscriptionSecretKey, SubscriptionStore, SubscriptionTarget,
         },
         AccountStore, ConflictBehavior, DataTypeStore, DatabaseConnectionInfo, DatabasePoolConfig,
-        DatabaseType, EntityStore, EntityTypeStore, InsertionError, PostgresStore,
-        PostgresStorePool, PropertyTypeStore, QueryError, StorePool, UpdateError,
+        DatabaseTlsConfig, DatabaseTlsMode, DatabaseType, EntityStore, EntityTypeStore,
+        InsertionError, PostgresStore, PostgresStorePool, PropertyTypeStore, QueryError, StorePool,
+        UpdateError,
     },
     Environment,
 };
@@ -74,12 +89,12 @@ use graph_types::{
 };
 use hash_tracing::logging::env_filter;
 use temporal_versioning::{DecisionTime, Timestamp, TransactionTime};
-use tokio_postgres::{NoTls, Transaction};
+use tokio_postgres::Transaction;
 use type_system::schema::{DataType, EntityType, PropertyType};
 use uuid::Uuid;
 
 pub struct DatabaseTestWrapper<A: AuthorizationApi> {
-    _pool: PostgresStorePool,
+    pool: PostgresStorePool,
     connection: <PostgresStorePool as StorePool>::Store<'static, A>,
 }
 
@@ -154,6 +169,16 @@ impl DatabaseTestWrapper<NoAuthorization> {
         let database =
             std::env::var("HASH_GRAPH_PG_DATABASE").unwrap_or_else(|_| "graph".to_owned());
 
+        // Allows running the tests against a database which requires TLS, e.g. with a self-signed
+        // certificate passed as `HASH_GRAPH_PG_SSL_ROOT_CERT`.
+        let tls_mode = match std::env::var("HASH_GRAPH_PG_SSL_MODE").as_deref() {
+            Err(_) | Ok("disable") => DatabaseTlsMode::Disable,
+            Ok("prefer") => DatabaseTlsMode::Prefer,
+            Ok("require") => DatabaseTlsMode::Require,
+            Ok("verify-full") => DatabaseTlsMode::VerifyFull,
+            Ok(mode) => panic!("{mode} is not a valid TLS mode"),
+        };
+
         let connection_info = DatabaseConnectionInfo::new(
             DatabaseType::Postgres,
             user,
@@ -161,9 +186,15 @@ impl DatabaseTestWrapper<NoAuthorization> {
             host,
             port,
             database,
-        );
-
-        let pool = PostgresStorePool::new(&connection_info, &DatabasePoolConfig::default(), NoTls)
+        )
+        .with_tls(DatabaseTlsConfig {
+            mode: tls_mode,
+            root_certificates: std::env::var_os("HASH_GRAPH_PG_SSL_ROOT_CERT").map(Into::into),
+            client_certificate: std::env::var_os("HASH_GRAPH_PG_SSL_CERT").map(Into::into),
+            client_key: std::env::var_os("HASH_GRAPH_PG_SSL_KEY").map(Into::into),
+        });
+
+        let pool = PostgresStorePool::new(&connection_info, &DatabasePoolConfig::default())
             .await
             .expect("could not connect to database");
 
@@ -172,10 +203,7 @@ impl DatabaseTestWrapper<NoAuthorization> {
             .await
             .expect("could not acquire a database connection");
 
-        Self {
-            _pool: pool,
-            connection,
-        }
+        Self { pool, connection }
     }
 }
 
@@ -276,6 +304,80 @@ impl<A: AuthorizationApi> DatabaseTestWrapper<A> {
     }
 }
 
+impl<A: AuthorizationApi> ChangeFeedStore for DatabaseApi<'_, A> {
+    async fn read_changes(
+        &self,
+        actor_id: AccountId,
+        params: ReadChangesParams,
+    ) -> Result<ReadChangesResponse, QueryError> {
+        self.store.read_changes(actor_id, params).await
+    }
+}
+
+impl<A: AuthorizationApi> SubscriptionStore for DatabaseApi<'_, A> {
+    async fn create_entity_subscription(
+        &mut self,
+        actor_id: AccountId,
+        params: CreateEntitySubscriptionParams,
+        secret_key: &SubscriptionSecretKey,
+    ) -> Result<EntitySubscription, InsertionError> {
+        self.store
+            .create_entity_subscription(actor_id, params, secret_key)
+            .await
+    }
+
+    async fn get_entity_subscriptions(
+        &self,
+        actor_id: AccountId,
+    ) -> Result<Vec<EntitySubscription>, QueryError> {
+        self.store.get_entity_subscriptions(actor_id).await
+    }
+
+    async fn delete_entity_subscription(
+        &mut self,
+        actor_id: AccountId,
+        subscription_id: SubscriptionId,
+    ) -> Result<(), DeletionError> {
+        self.store
+            .delete_entity_subscription(actor_id, subscription_id)
+            .await
+    }
+
+    async fn get_subscription_targets(&self) -> Result<Vec<SubscriptionTarget>, QueryError> {
+        self.store.get_subscription_targets().await
+    }
+
+    async fn advance_subscription_cursor(
+        &mut self,
+        subscription_id: SubscriptionId,
+        cursor: i64,
+    ) -> Result<(), UpdateError> {
+        self.store
+            .advance_subscription_cursor(subscription_id, cursor)
+            .await
+    }
+
+    async fn park_subscription_change(
+        &mut self,
+        subscription_id: SubscriptionId,
+        change: ParkedChange,
+    ) -> Result<(), UpdateError> {
+        self.store
+            .park_subscription_change(subscription_id, change)
+            .await
+    }
+
+    async fn get_parked_changes(
+        &self,
+        actor_id: AccountId,
+        subscription_id: SubscriptionId,
+    ) -> Result<Vec<ParkedChange>, QueryError> {
+        self.store
+            .get_parked_changes(actor_id, subscription_id)
+            .await
+    }
+}
+
 impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
     async fn create_data_types<P, R>(
         &mut self,
@@ -378,6 +480,14 @@ impl<A: AuthorizationApi> DataTypeStore for DatabaseApi<'_, A> {
         self.store.update_data_type(actor_id, params).await
     }
 
+    async fn check_data_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckDataTypeUpdateParams,
+    ) -> Result<CheckDataTypeUpdateResponse, QueryError> {
+        self.store.check_data_type_update(actor_id, params).await
+    }
+
     async fn archive_data_type(
         &mut self,
         actor_id: AccountId,
@@ -513,6 +623,16 @@ impl<A: AuthorizationApi> PropertyTypeStore for DatabaseApi<'_, A> {
         self.store.update_property_type(actor_id, params).await
     }
 
+    async fn check_property_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckPropertyTypeUpdateParams,
+    ) -> Result<CheckPropertyTypeUpdateResponse, QueryError> {
+        self.store
+            .check_property_type_update(actor_id, params)
+            .await
+    }
+
     async fn archive_property_type(
         &mut self,
         actor_id: AccountId,
@@ -646,6 +766,14 @@ impl<A: AuthorizationApi> EntityTypeStore for DatabaseApi<'_, A> {
         self.store.update_entity_type(actor_id, params).await
     }
 
+    async fn check_entity_type_update(
+        &self,
+        actor_id: AccountId,
+        params: CheckEntityTypeUpdateParams,
+    ) -> Result<CheckEntityTypeUpdateResponse, QueryError> {
+        self.store.check_entity_type_update(actor_id, params).await
+    }
+
     async fn archive_entity_type(
         &mut self,
         actor_id: AccountId,
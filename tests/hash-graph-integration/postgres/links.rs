@@ -621,6 +621,7 @@ async fn remove_link() {
         api.account_id,
         PatchEntityParams {
             entity_id: link_entity_metadata.metadata.record_id.entity_id,
+            expected_edition_id: None,
             decision_time: None,
             archived: Some(true),
             draft: None,
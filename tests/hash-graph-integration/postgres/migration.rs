@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+
+use graph::{
+    store::{
+        knowledge::{CountEntitiesParams, CreateEntityParams, MigrateEntitiesParams},
+        ontology::{CreateEntityTypeParams, UpdateEntityTypesParams},
+        query::Filter,
+        ConflictBehavior, EntityStore, EntityTypeStore,
+    },
+    subgraph::temporal_axes::{
+        PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved, VariableTemporalAxisUnresolved,
+    },
+};
+use graph_test_data::{data_type, entity_type, property_type};
+use graph_types::{
+    knowledge::{entity::ProvidedEntityEditionProvenance, PropertyWithMetadataObject},
+    ontology::{OntologyTypeClassificationMetadata, ProvidedOntologyEditionProvenance},
+    owned_by_id::OwnedById,
+};
+use serde_json::json;
+use type_system::{schema::EntityType, url::VersionedUrl};
+
+use crate::{entity_type_relationships, DatabaseApi, DatabaseTestWrapper};
+
+pub(crate) async fn count_entities_of_type<A: authorization::AuthorizationApi>(
+    api: &DatabaseApi<'_, A>,
+    entity_type_id: &VersionedUrl,
+) -> usize {
+    api.count_entities(
+        api.account_id,
+        CountEntitiesParams {
+            filter: Filter::for_entity_by_type_id(entity_type_id),
+            temporal_axes: QueryTemporalAxesUnresolved::DecisionTime {
+                pinned: PinnedTemporalAxisUnresolved::new(None),
+                variable: VariableTemporalAxisUnresolved::new(None, None),
+            },
+            include_drafts: false,
+        },
+    )
+    .await
+    .expect("could not count entities")
+}
+
+/// Creates the person entity type and a second version of it, which requires an age.
+pub(crate) async fn create_person_types<A: authorization::AuthorizationApi>(
+    api: &mut DatabaseApi<'_, A>,
+) -> (EntityType, EntityType) {
+    let person_v1: EntityType = serde_json::from_str(entity_type::PERSON_V1)
+        .expect("could not parse entity type representation");
+    api.create_entity_type(
+        api.account_id,
+        CreateEntityTypeParams {
+            schema: person_v1.clone(),
+            classification: OntologyTypeClassificationMetadata::Owned {
+                owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+            },
+            label_property: None,
+            icon: None,
+            relationships: entity_type_relationships(),
+            conflict_behavior: ConflictBehavior::Fail,
+            provenance: ProvidedOntologyEditionProvenance::default(),
+        },
+    )
+    .await
+    .expect("could not create entity type");
+
+    let mut person_v2 = serde_json::to_value(&person_v1).expect("could not serialize entity type");
+    person_v2["$id"] = json!("https://blockprotocol.org/@alice/types/entity-type/person/v/2");
+    person_v2["required"] = json!(["https://blockprotocol.org/@alice/types/property-type/age/"]);
+    let person_v2: EntityType =
+        serde_json::from_value(person_v2).expect("could not parse entity type representation");
+    api.update_entity_type(
+        api.account_id,
+        UpdateEntityTypesParams {
+            schema: person_v2.clone(),
+            label_property: None,
+            icon: None,
+            relationships: entity_type_relationships(),
+            provenance: ProvidedOntologyEditionProvenance::default(),
+        },
+    )
+    .await
+    .expect("could not update entity type");
+
+    (person_v1, person_v2)
+}
+
+#[tokio::test]
+async fn migrate_in_batches() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1, data_type::NUMBER_V1],
+            [
+                property_type::NAME_V1,
+                property_type::AGE_V1,
+                property_type::INTERESTS_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::FAVORITE_FILM_V1,
+                property_type::HOBBY_V1,
+            ],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::link::ACQUAINTANCE_OF_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+
+    let (person_v1, person_v2) = create_person_types(&mut api).await;
+
+    let mut ageless = None;
+    for (name, age) in [
+        ("Alice", Some(30)),
+        ("Bob", Some(42)),
+        ("Charles", None),
+        ("Dave", Some(27)),
+        ("Eve", Some(35)),
+    ] {
+        let mut properties = json!({
+            "https://blockprotocol.org/@alice/types/property-type/name/": name,
+        });
+        if let Some(age) = age {
+            properties["https://blockprotocol.org/@alice/types/property-type/age/"] = json!(age);
+        }
+        let entity = api
+            .create_entity(
+                api.account_id,
+                CreateEntityParams {
+                    owned_by_id: OwnedById::new(api.account_id.into_uuid()),
+                    entity_uuid: None,
+                    decision_time: None,
+                    entity_type_ids: HashSet::from([person_v1.id.clone()]),
+                    properties: PropertyWithMetadataObject::from_parts(
+                        serde_json::from_value(properties).expect("could not parse entity"),
+                        None,
+                    )
+                    .expect("could not create property with metadata object"),
+                    confidence: None,
+                    link_data: None,
+                    draft: false,
+                    relationships: [],
+                    provenance: ProvidedEntityEditionProvenance::default(),
+                },
+            )
+            .await
+            .expect("could not create entity");
+        if age.is_none() {
+            ageless = Some(entity.metadata.record_id.entity_id);
+        }
+    }
+
+    // Five entities in batches of two require three batches
+    let response = api
+        .migrate_entities(
+            api.account_id,
+            MigrateEntitiesParams {
+                source_entity_type_id: person_v1.id.clone(),
+                target_entity_type_id: person_v2.id.clone(),
+                mappings: Vec::new(),
+                batch_size: Some(2),
+                provenance: ProvidedEntityEditionProvenance::default(),
+            },
+        )
+        .await
+        .expect("could not migrate entities");
+
+    assert_eq!(response.migrated_entities, 4);
+    assert_eq!(response.failed_entities.len(), 1);
+    assert_eq!(Some(response.failed_entities[0].entity_id), ageless);
+    assert!(!response.failed_entities[0].reason.is_empty());
+
+    // The entity failing validation keeps its previous type
+    assert_eq!(count_entities_of_type(&api, &person_v1.id).await, 1);
+    assert_eq!(count_entities_of_type(&api, &person_v2.id).await, 4);
+}
@@ -155,6 +155,7 @@ async fn initial_person() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 decision_time: None,
                 entity_type_ids: HashSet::from([person_entity_type_id(), org_entity_type_id()]),
                 properties: vec![],
@@ -326,6 +327,7 @@ async fn create_multi() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 decision_time: None,
                 entity_type_ids: HashSet::from([person_entity_type_id()]),
                 properties: vec![],
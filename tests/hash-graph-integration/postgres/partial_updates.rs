@@ -106,6 +106,7 @@ async fn properties_add() {
         api.account_id,
         PatchEntityParams {
             entity_id,
+            expected_edition_id: None,
             decision_time: None,
             entity_type_ids: HashSet::new(),
             properties: vec![
@@ -201,6 +202,7 @@ async fn properties_remove() {
         api.account_id,
         PatchEntityParams {
             entity_id,
+            expected_edition_id: None,
             decision_time: None,
             entity_type_ids: HashSet::new(),
             properties: vec![PropertyPatchOperation::Remove {
@@ -273,6 +275,7 @@ async fn properties_replace() {
         api.account_id,
         PatchEntityParams {
             entity_id,
+            expected_edition_id: None,
             decision_time: None,
             entity_type_ids: HashSet::new(),
             properties: vec![PropertyPatchOperation::Replace {
@@ -355,6 +358,7 @@ async fn type_ids() {
         api.account_id,
         PatchEntityParams {
             entity_id,
+            expected_edition_id: None,
             decision_time: None,
             entity_type_ids: HashSet::new(),
             properties: vec![],
@@ -402,6 +406,7 @@ async fn type_ids() {
         api.account_id,
         PatchEntityParams {
             entity_id,
+            expected_edition_id: None,
             decision_time: None,
             entity_type_ids: HashSet::from([person_entity_type_id(), org_entity_type_id()]),
             properties: vec![],
@@ -451,6 +456,7 @@ async fn type_ids() {
         api.account_id,
         PatchEntityParams {
             entity_id,
+            expected_edition_id: None,
             decision_time: None,
             entity_type_ids: HashSet::from([person_entity_type_id()]),
             properties: vec![],
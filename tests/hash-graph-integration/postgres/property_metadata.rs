@@ -191,6 +191,7 @@ async fn initial_metadata() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: vec![PropertyPatchOperation::Replace {
                     path: once(PropertyPathElement::Property(Cow::Owned(
                         name_property_type_id(),
@@ -233,6 +234,7 @@ async fn initial_metadata() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: Vec::new(),
                 entity_type_ids: HashSet::new(),
                 archived: None,
@@ -301,6 +303,7 @@ async fn no_initial_metadata() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: Vec::new(),
                 entity_type_ids: HashSet::new(),
                 archived: None,
@@ -320,6 +323,7 @@ async fn no_initial_metadata() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: Vec::new(),
                 entity_type_ids: HashSet::new(),
                 archived: None,
@@ -355,6 +359,7 @@ async fn no_initial_metadata() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: vec![PropertyPatchOperation::Replace {
                     path: once(PropertyPathElement::from(name_property_type_id())).collect(),
                     property: PropertyWithMetadata::Value(ValueWithMetadata {
@@ -400,6 +405,7 @@ async fn no_initial_metadata() {
             api.account_id,
             PatchEntityParams {
                 entity_id: entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
                 properties: Vec::new(),
                 entity_type_ids: HashSet::new(),
                 archived: None,
@@ -467,6 +473,7 @@ async fn properties_add() {
             api.account_id,
             PatchEntityParams {
                 entity_id,
+                expected_edition_id: None,
                 decision_time: None,
                 entity_type_ids: HashSet::new(),
                 properties: vec![PropertyPatchOperation::Add {
@@ -560,6 +567,7 @@ async fn properties_remove() {
             api.account_id,
             PatchEntityParams {
                 entity_id,
+                expected_edition_id: None,
                 decision_time: None,
                 entity_type_ids: HashSet::new(),
                 properties: vec![
@@ -638,6 +646,7 @@ async fn properties_remove() {
             api.account_id,
             PatchEntityParams {
                 entity_id,
+                expected_edition_id: None,
                 decision_time: None,
                 entity_type_ids: HashSet::new(),
                 properties: vec![PropertyPatchOperation::Remove {
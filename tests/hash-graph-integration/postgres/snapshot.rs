@@ -0,0 +1,749 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::{collections::HashSet, io};
+
+use authorization::{
+    backend::{
+        BulkCheckItem, BulkCheckResponse, CheckError, CheckResponse, DeleteRelationshipError,
+        DeleteRelationshipResponse, ExportSchemaError, ExportSchemaResponse, ImportSchemaError,
+        ImportSchemaResponse, ModifyRelationshipError, ModifyRelationshipOperation,
+        ModifyRelationshipResponse, ReadError, ZanzibarBackend,
+    },
+    schema::{EntityOwnerSubject, EntityRelationAndSubject, WebOwnerSubject},
+    zanzibar::{
+        types::{Relationship, RelationshipFilter, Resource, Subject},
+        Consistency, Permission, ZanzibarClient,
+    },
+    NoAuthorization,
+};
+use error_stack::Report;
+use futures::{channel::mpsc, stream, SinkExt, Stream, StreamExt};
+use graph::{
+    snapshot::{
+        entity::EntitySnapshotRecord, AuthorizationRelation, SnapshotDecoder, SnapshotDumpSettings,
+        SnapshotEntry, SnapshotManifestBuilder, SnapshotRestoreError, SnapshotScope,
+        SnapshotSection, SnapshotStore,
+    },
+    store::{
+        account::{InsertAccountIdParams, InsertWebIdParams},
+        knowledge::{CreateEntityParams, PatchEntityParams},
+        ontology::{CreateDataTypeParams, CreateEntityTypeParams, CreatePropertyTypeParams},
+        AccountStore, AsClient, ConflictBehavior, DataTypeStore, EntityStore, EntityTypeStore,
+        PropertyTypeStore, StorePool,
+    },
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    account::AccountId,
+    knowledge::{
+        entity::{Entity, ProvidedEntityEditionProvenance},
+        Property, PropertyObject, PropertyPatchOperation, PropertyPath, PropertyWithMetadata,
+        PropertyWithMetadataObject,
+    },
+    ontology::{OntologyTypeClassificationMetadata, ProvidedOntologyEditionProvenance},
+    owned_by_id::OwnedById,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use temporal_versioning::{ClosedTemporalBound, OpenTemporalBound};
+use tokio_util::codec::FramedRead;
+use type_system::{
+    schema::{DataType, EntityType, PropertyType},
+    url::{BaseUrl, OntologyTypeVersion, VersionedUrl},
+};
+use uuid::Uuid;
+
+use crate::{
+    data_type_relationships, entity_type_relationships, property_type_relationships,
+    DatabaseTestWrapper,
+};
+
+/// Creates a page entity in a new web and updates it once.
+///
+/// In contrast to [`DatabaseTestWrapper::seed`] the records are committed, so they can be read
+/// by a snapshot dump.
+#[expect(clippy::too_many_lines)]
+async fn create_updated_page(database: &mut DatabaseTestWrapper<NoAuthorization>) -> [Entity; 2] {
+    let store = &mut database.connection;
+
+    let account_id = AccountId::new(Uuid::new_v4());
+    let owned_by_id = OwnedById::new(account_id.into_uuid());
+    store
+        .insert_account_id(account_id, InsertAccountIdParams { account_id })
+        .await
+        .expect("could not insert account id");
+    store
+        .insert_web_id(
+            account_id,
+            InsertWebIdParams {
+                owned_by_id,
+                owner: WebOwnerSubject::Account { id: account_id },
+            },
+        )
+        .await
+        .expect("could not create web id");
+
+    store
+        .create_data_types(
+            account_id,
+            [CreateDataTypeParams {
+                schema: serde_json::from_str::<DataType>(data_type::TEXT_V1)
+                    .expect("could not parse data type"),
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                relationships: data_type_relationships(),
+                conflict_behavior: ConflictBehavior::Skip,
+                provenance: ProvidedOntologyEditionProvenance::default(),
+            }],
+        )
+        .await
+        .expect("could not create data type");
+    store
+        .create_property_types(
+            account_id,
+            [CreatePropertyTypeParams {
+                schema: serde_json::from_str::<PropertyType>(property_type::TEXT_V1)
+                    .expect("could not parse property type"),
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                relationships: property_type_relationships(),
+                conflict_behavior: ConflictBehavior::Skip,
+                provenance: ProvidedOntologyEditionProvenance::default(),
+            }],
+        )
+        .await
+        .expect("could not create property type");
+    store
+        .create_entity_types(
+            account_id,
+            [CreateEntityTypeParams {
+                schema: serde_json::from_str::<EntityType>(entity_type::PAGE_V1)
+                    .expect("could not parse entity type"),
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                label_property: None,
+                icon: None,
+                relationships: entity_type_relationships(),
+                conflict_behavior: ConflictBehavior::Skip,
+                provenance: ProvidedOntologyEditionProvenance::default(),
+            }],
+        )
+        .await
+        .expect("could not create entity type");
+
+    let page_v1: PropertyObject =
+        serde_json::from_str(entity::PAGE_V1).expect("could not parse entity");
+    let page_v2: PropertyObject =
+        serde_json::from_str(entity::PAGE_V2).expect("could not parse entity");
+
+    let v1_entity = store
+        .create_entity(
+            account_id,
+            CreateEntityParams {
+                owned_by_id,
+                entity_uuid: None,
+                decision_time: None,
+                entity_type_ids: HashSet::from([VersionedUrl {
+                    base_url: BaseUrl::new(
+                        "https://blockprotocol.org/@alice/types/entity-type/page/".to_owned(),
+                    )
+                    .expect("couldn't construct Base URL"),
+                    version: OntologyTypeVersion::new(1),
+                }]),
+                properties: PropertyWithMetadataObject::from_parts(page_v1, None)
+                    .expect("could not create property with metadata object"),
+                confidence: None,
+                link_data: None,
+                draft: false,
+                relationships: [],
+                provenance: ProvidedEntityEditionProvenance::default(),
+            },
+        )
+        .await
+        .expect("could not create entity");
+
+    let v2_entity = store
+        .patch_entity(
+            account_id,
+            PatchEntityParams {
+                entity_id: v1_entity.metadata.record_id.entity_id,
+                expected_edition_id: None,
+                properties: vec![PropertyPatchOperation::Replace {
+                    path: PropertyPath::default(),
+                    property: PropertyWithMetadata::from_parts(Property::Object(page_v2), None)
+                        .expect("could not create property with metadata"),
+                }],
+                entity_type_ids: HashSet::new(),
+                archived: None,
+                draft: None,
+                decision_time: None,
+                confidence: None,
+                provenance: ProvidedEntityEditionProvenance::default(),
+            },
+        )
+        .await
+        .expect("could not update entity");
+
+    [v1_entity, v2_entity]
+}
+
+/// Dumps the records of the scope into memory.
+async fn dump(
+    database: &DatabaseTestWrapper<NoAuthorization>,
+    scope: SnapshotScope,
+) -> Vec<SnapshotEntry> {
+    let (tx, rx) = mpsc::unbounded();
+    database
+        .pool
+        .dump_snapshot(
+            tx.sink_map_err(Report::new),
+            &NoAuthorization,
+            &SnapshotDumpSettings {
+                chunk_size: 100,
+                dump_webs: true,
+                dump_accounts: true,
+                dump_account_groups: false,
+                dump_entities: true,
+                dump_entity_types: true,
+                dump_property_types: true,
+                dump_data_types: true,
+                dump_embeddings: false,
+                dump_relations: true,
+                scope,
+            },
+        )
+        .expect("could not dump snapshot");
+
+    rx.collect().await
+}
+
+fn dumped_entities(entries: &[SnapshotEntry]) -> Vec<&EntitySnapshotRecord> {
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            SnapshotEntry::Entity(record) => Some(&**record),
+            _ => None,
+        })
+        .collect()
+}
+
+async fn count_entity_editions(
+    database: &DatabaseTestWrapper<NoAuthorization>,
+    entity: &Entity,
+) -> i64 {
+    let entity_id = entity.metadata.record_id.entity_id;
+    database
+        .connection
+        .as_client()
+        .query_one(
+            "
+                SELECT count(*)
+                FROM entity_temporal_metadata
+                WHERE web_id = $1 AND entity_uuid = $2
+            ",
+            &[&entity_id.owned_by_id, &entity_id.entity_uuid],
+        )
+        .await
+        .expect("could not count entity editions")
+        .get(0)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dump_since() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let [_, v2_entity] = create_updated_page(&mut database).await;
+    let owned_by_id = v2_entity.metadata.record_id.entity_id.owned_by_id;
+
+    let entries = dump(
+        &database,
+        SnapshotScope {
+            webs: vec![owned_by_id],
+            transaction_time_since: None,
+        },
+    )
+    .await;
+    let entities = dumped_entities(&entries);
+    assert_eq!(
+        i64::try_from(entities.len()).expect("too many entities"),
+        count_entity_editions(&database, &v2_entity).await
+    );
+
+    // The first edition was created before the update, so it is not contained anymore.
+    let ClosedTemporalBound::Inclusive(since) = *v2_entity
+        .metadata
+        .temporal_versioning
+        .transaction_time
+        .start();
+    let scoped_entries = dump(
+        &database,
+        SnapshotScope {
+            webs: vec![owned_by_id],
+            transaction_time_since: Some(since),
+        },
+    )
+    .await;
+
+    let Some(SnapshotEntry::Snapshot(metadata)) = scoped_entries.first() else {
+        panic!("snapshot should start with its metadata");
+    };
+    assert_eq!(metadata.scope.transaction_time_since, Some(since));
+    assert!(matches!(
+        scoped_entries.last(),
+        Some(SnapshotEntry::Manifest(_))
+    ));
+
+    let scoped_entities = dumped_entities(&scoped_entries);
+    assert_eq!(scoped_entities.len(), entities.len() - 1);
+    assert!(scoped_entities.iter().all(|record| matches!(
+        record.metadata.temporal_versioning.transaction_time.end(),
+        OpenTemporalBound::Unbounded
+    )));
+    assert!(
+        scoped_entities
+            .iter()
+            .any(|record| record.properties == v2_entity.properties)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dump_since_skips_unchanged_records() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let [_, v2_entity] = create_updated_page(&mut database).await;
+    let owned_by_id = v2_entity.metadata.record_id.entity_id.owned_by_id;
+
+    let created_entity = database
+        .connection
+        .create_entity(
+            AccountId::new(owned_by_id.into_uuid()),
+            CreateEntityParams {
+                owned_by_id,
+                entity_uuid: None,
+                decision_time: None,
+                entity_type_ids: v2_entity.metadata.entity_type_ids.clone(),
+                properties: PropertyWithMetadataObject::from_parts(
+                    serde_json::from_str(entity::PAGE_V1).expect("could not parse entity"),
+                    None,
+                )
+                .expect("could not create property with metadata object"),
+                confidence: None,
+                link_data: None,
+                draft: false,
+                relationships: [],
+                provenance: ProvidedEntityEditionProvenance::default(),
+            },
+        )
+        .await
+        .expect("could not create entity");
+
+    // The updated page is still current but was created before `since`, so neither the page nor
+    // its types are contained.
+    let ClosedTemporalBound::Inclusive(since) = *created_entity
+        .metadata
+        .temporal_versioning
+        .transaction_time
+        .start();
+    let entries = dump(
+        &database,
+        SnapshotScope {
+            webs: vec![owned_by_id],
+            transaction_time_since: Some(since),
+        },
+    )
+    .await;
+
+    let entities = dumped_entities(&entries);
+    assert_eq!(entities.len(), 1);
+    assert_eq!(
+        entities[0].metadata.record_id,
+        created_entity.metadata.record_id
+    );
+    assert!(!entries.iter().any(|entry| matches!(
+        entry,
+        SnapshotEntry::DataType(_) | SnapshotEntry::PropertyType(_) | SnapshotEntry::EntityType(_)
+    )));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn restore_scoped_snapshot_into_non_empty_store() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let [_, v2_entity] = create_updated_page(&mut database).await;
+    let editions = count_entity_editions(&database, &v2_entity).await;
+
+    let ClosedTemporalBound::Inclusive(since) = *v2_entity
+        .metadata
+        .temporal_versioning
+        .transaction_time
+        .start();
+    let entries = dump(
+        &database,
+        SnapshotScope {
+            webs: vec![v2_entity.metadata.record_id.entity_id.owned_by_id],
+            transaction_time_since: Some(since),
+        },
+    )
+    .await;
+
+    // All records of the snapshot already exist in the store, so they have to be merged
+    SnapshotStore::new(
+        database
+            .pool
+            .acquire(NoAuthorization, None)
+            .await
+            .expect("could not acquire a database connection"),
+    )
+    .restore_snapshot(
+        stream::iter(entries.into_iter().map(Ok::<_, Report<std::io::Error>>)),
+        100,
+        false,
+    )
+    .await
+    .expect("could not restore snapshot");
+
+    assert_eq!(count_entity_editions(&database, &v2_entity).await, editions);
+}
+
+/// Encodes the entries as JSON lines and decodes them again, which verifies the manifest.
+fn decode(
+    entries: &[SnapshotEntry],
+) -> impl Stream<Item = Result<SnapshotEntry, Report<io::Error>>> {
+    let mut lines = Vec::new();
+    for entry in entries {
+        serde_json::to_writer(&mut lines, entry).expect("could not serialize snapshot entry");
+        lines.push(b'\n');
+    }
+    FramedRead::new(io::Cursor::new(lines), SnapshotDecoder::new())
+}
+
+async fn checkpoint_exists(database: &DatabaseTestWrapper<NoAuthorization>, name: &str) -> bool {
+    database
+        .connection
+        .as_client()
+        .query_one(
+            "SELECT EXISTS (SELECT 1 FROM pg_namespace WHERE nspname = $1)",
+            &[&name],
+        )
+        .await
+        .expect("could not query schemas")
+        .get(0)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resume_restore_from_checkpoint() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let [_, v2_entity] = create_updated_page(&mut database).await;
+    let editions = count_entity_editions(&database, &v2_entity).await;
+    let checkpoint = format!("snapshot_checkpoint_{}", Uuid::new_v4().simple());
+
+    let mut entries = dump(
+        &database,
+        SnapshotScope {
+            webs: vec![v2_entity.metadata.record_id.entity_id.owned_by_id],
+            transaction_time_since: None,
+        },
+    )
+    .await;
+    assert!(
+        entries.len() > 6,
+        "the snapshot should contain multiple batches"
+    );
+
+    let mut store = SnapshotStore::new(
+        database
+            .pool
+            .acquire(NoAuthorization, None)
+            .await
+            .expect("could not acquire a database connection"),
+    );
+
+    // The restore is killed after the metadata and two batches of two entries have been read
+    let report = store
+        .restore_snapshot_with_checkpoint(
+            decode(&entries).take(5).chain(stream::once(async {
+                Err(Report::new(io::Error::other("restore killed")))
+            })),
+            2,
+            false,
+            &checkpoint,
+        )
+        .await
+        .expect_err("restore should be aborted");
+    assert!(matches!(
+        report.current_context(),
+        SnapshotRestoreError::Read
+    ));
+    assert!(checkpoint_exists(&database, &checkpoint).await);
+
+    let staged: i64 = database
+        .connection
+        .as_client()
+        .query_one(
+            &format!(
+                "SELECT (checkpoint->>'records')::BIGINT FROM {checkpoint}.snapshot_checkpoint"
+            ),
+            &[],
+        )
+        .await
+        .expect("could not read checkpoint")
+        .get(0);
+    assert_eq!(staged, 4);
+
+    // A snapshot, which does not match the checkpoint, is rejected
+    entries.swap(1, 2);
+    let report = store
+        .restore_snapshot_with_checkpoint(decode(&entries), 2, false, &checkpoint)
+        .await
+        .expect_err("the checkpoint should belong to a different snapshot");
+    assert!(matches!(
+        report.current_context(),
+        SnapshotRestoreError::Checkpoint
+    ));
+    entries.swap(1, 2);
+
+    store
+        .restore_snapshot_with_checkpoint(decode(&entries), 2, false, &checkpoint)
+        .await
+        .expect("could not resume snapshot restore");
+    assert!(!checkpoint_exists(&database, &checkpoint).await);
+    assert_eq!(count_entity_editions(&database, &v2_entity).await, editions);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reject_snapshot_with_wrong_checksum() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let [_, v2_entity] = create_updated_page(&mut database).await;
+    let editions = count_entity_editions(&database, &v2_entity).await;
+    let checkpoint = format!("snapshot_checkpoint_{}", Uuid::new_v4().simple());
+
+    let mut entries = dump(
+        &database,
+        SnapshotScope {
+            webs: vec![v2_entity.metadata.record_id.entity_id.owned_by_id],
+            transaction_time_since: None,
+        },
+    )
+    .await;
+    let Some(SnapshotEntry::Manifest(manifest)) = entries.last_mut() else {
+        panic!("snapshot should end with its manifest");
+    };
+    manifest
+        .sections
+        .get_mut(&SnapshotSection::Entity)
+        .expect("snapshot should contain entities")
+        .checksum = "0".repeat(64);
+
+    let mut store = SnapshotStore::new(
+        database
+            .pool
+            .acquire(NoAuthorization, None)
+            .await
+            .expect("could not acquire a database connection"),
+    );
+    let report = store
+        .restore_snapshot_with_checkpoint(decode(&entries), 2, false, &checkpoint)
+        .await
+        .expect_err("the manifest should not match the snapshot");
+    assert!(matches!(
+        report.current_context(),
+        SnapshotRestoreError::Read
+    ));
+
+    // Nothing has been committed, the staged records are kept until the checkpoint is discarded
+    assert!(checkpoint_exists(&database, &checkpoint).await);
+    assert_eq!(count_entity_editions(&database, &v2_entity).await, editions);
+    database
+        .connection
+        .as_client()
+        .batch_execute(&format!("DROP SCHEMA {checkpoint} CASCADE"))
+        .await
+        .expect("could not discard checkpoint");
+}
+
+/// Authorization backend, which counts the written relations and otherwise behaves like
+/// [`NoAuthorization`].
+#[derive(Debug, Clone, Default)]
+struct CountingBackend(Arc<AtomicUsize>);
+
+impl ZanzibarBackend for CountingBackend {
+    async fn import_schema(
+        &mut self,
+        schema: &str,
+    ) -> Result<ImportSchemaResponse, Report<ImportSchemaError>> {
+        NoAuthorization.import_schema(schema).await
+    }
+
+    async fn export_schema(&self) -> Result<ExportSchemaResponse, Report<ExportSchemaError>> {
+        NoAuthorization.export_schema().await
+    }
+
+    async fn modify_relationships<R>(
+        &mut self,
+        relationships: impl IntoIterator<Item = (ModifyRelationshipOperation, R), IntoIter: Send> + Send,
+    ) -> Result<ModifyRelationshipResponse, Report<ModifyRelationshipError>>
+    where
+        R: Relationship<
+                Resource: Resource<Kind: Serialize, Id: Serialize>,
+                Relation: Serialize,
+                Subject: Resource<Kind: Serialize, Id: Serialize>,
+                SubjectSet: Serialize,
+            > + Send
+            + Sync,
+    {
+        let relationships = relationships.into_iter().collect::<Vec<_>>();
+        self.0.fetch_add(relationships.len(), Ordering::Relaxed);
+        NoAuthorization.modify_relationships(relationships).await
+    }
+
+    async fn check_permission<O, R, S>(
+        &self,
+        resource: &O,
+        permission: &R,
+        subject: &S,
+        consistency: Consistency<'_>,
+    ) -> Result<CheckResponse, Report<CheckError>>
+    where
+        O: Resource<Kind: Serialize, Id: Serialize> + Sync,
+        R: Serialize + Permission<O> + Sync,
+        S: Subject<Resource: Resource<Kind: Serialize, Id: Serialize>, Relation: Serialize> + Sync,
+    {
+        NoAuthorization
+            .check_permission(resource, permission, subject, consistency)
+            .await
+    }
+
+    async fn check_permissions<O, R, S>(
+        &self,
+        relationships: impl IntoIterator<Item = (O, R, S)> + Send,
+        consistency: Consistency<'_>,
+    ) -> Result<
+        BulkCheckResponse<impl IntoIterator<Item = BulkCheckItem<O, R, S>>>,
+        Report<CheckError>,
+    >
+    where
+        O: Resource<Kind: Serialize + DeserializeOwned, Id: Serialize + DeserializeOwned>
+            + Send
+            + Sync,
+        R: Serialize + DeserializeOwned + Permission<O> + Send + Sync,
+        S: Subject<
+                Resource: Resource<
+                    Kind: Serialize + DeserializeOwned,
+                    Id: Serialize + DeserializeOwned,
+                >,
+                Relation: Serialize + DeserializeOwned,
+            > + Send
+            + Sync,
+    {
+        NoAuthorization
+            .check_permissions(relationships, consistency)
+            .await
+    }
+
+    async fn read_relations<R>(
+        &self,
+        _: RelationshipFilter<
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+        >,
+        _: Consistency<'_>,
+    ) -> Result<impl Stream<Item = Result<R, Report<ReadError>>>, Report<ReadError>> {
+        Ok(stream::empty())
+    }
+
+    async fn delete_relations(
+        &mut self,
+        filter: RelationshipFilter<
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+            impl Serialize + Send + Sync,
+        >,
+    ) -> Result<DeleteRelationshipResponse, Report<DeleteRelationshipError>> {
+        NoAuthorization.delete_relations(filter).await
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn write_relations_after_verifying_snapshot() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let [_, v2_entity] = create_updated_page(&mut database).await;
+    let entity_id = v2_entity.metadata.record_id.entity_id;
+    let checkpoint = format!("snapshot_checkpoint_{}", Uuid::new_v4().simple());
+
+    let mut entries = dump(
+        &database,
+        SnapshotScope {
+            webs: vec![entity_id.owned_by_id],
+            transaction_time_since: None,
+        },
+    )
+    .await;
+
+    // The test store does not dump any relations, so one is added in front of the manifest
+    entries.insert(
+        entries.len() - 1,
+        SnapshotEntry::Relation(AuthorizationRelation::Entity {
+            object: entity_id.entity_uuid,
+            relationship: EntityRelationAndSubject::Owner {
+                subject: EntityOwnerSubject::Web {
+                    id: entity_id.owned_by_id,
+                },
+                level: 0,
+            },
+        }),
+    );
+    let mut manifest = SnapshotManifestBuilder::default();
+    for entry in &entries {
+        if let Some(section) = entry.section() {
+            manifest.update(
+                section,
+                &serde_json::to_vec(entry).expect("could not serialize snapshot entry"),
+            );
+        }
+    }
+    let Some(SnapshotEntry::Manifest(snapshot_manifest)) = entries.last_mut() else {
+        panic!("snapshot should end with its manifest");
+    };
+    *snapshot_manifest = manifest.finish();
+    let section = snapshot_manifest
+        .sections
+        .get_mut(&SnapshotSection::Relation)
+        .expect("snapshot should contain relations");
+    let checksum = core::mem::replace(&mut section.checksum, "0".repeat(64));
+
+    let relations = CountingBackend::default();
+    let mut store = SnapshotStore::new(
+        database
+            .pool
+            .acquire(ZanzibarClient::new(relations.clone()), None)
+            .await
+            .expect("could not acquire a database connection"),
+    );
+
+    // Reading the manifest fails, so the relation must not have been written
+    let report = store
+        .restore_snapshot_with_checkpoint(decode(&entries), 2, false, &checkpoint)
+        .await
+        .expect_err("the manifest should not match the snapshot");
+    assert!(matches!(
+        report.current_context(),
+        SnapshotRestoreError::Read
+    ));
+    assert_eq!(relations.0.load(Ordering::Relaxed), 0);
+
+    // The relation is only written after the snapshot has been verified
+    let Some(SnapshotEntry::Manifest(snapshot_manifest)) = entries.last_mut() else {
+        panic!("snapshot should end with its manifest");
+    };
+    snapshot_manifest
+        .sections
+        .get_mut(&SnapshotSection::Relation)
+        .expect("snapshot should contain relations")
+        .checksum = checksum;
+    store
+        .restore_snapshot_with_checkpoint(decode(&entries), 2, false, &checkpoint)
+        .await
+        .expect("could not restore snapshot");
+    assert!(!checkpoint_exists(&database, &checkpoint).await);
+    assert_eq!(relations.0.load(Ordering::Relaxed), 1);
+}
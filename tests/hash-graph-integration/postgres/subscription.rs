@@ -0,0 +1,375 @@
+use alloc::sync::Arc;
+use std::{collections::HashSet, sync::Mutex};
+
+use authorization::{schema::WebOwnerSubject, NoAuthorization};
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use graph::{
+    store::{
+        account::{InsertAccountIdParams, InsertWebIdParams},
+        error::DeletionError,
+        knowledge::CreateEntityParams,
+        ontology::{CreateDataTypeParams, CreateEntityTypeParams, CreatePropertyTypeParams},
+        subscription::{CreateEntitySubscriptionParams, SubscriptionSecretKey, SubscriptionStore},
+        AccountStore, ConflictBehavior, DataTypeStore, EntityStore, EntityTypeStore,
+        InsertionError, PostgresStorePool, PropertyTypeStore, StorePool,
+    },
+    webhook::{
+        sign_payload, WebhookConfig, WebhookDispatcher, WebhookTargetPolicy, SIGNATURE_HEADER,
+    },
+};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    account::AccountId,
+    knowledge::{
+        entity::{EntityUuid, ProvidedEntityEditionProvenance},
+        PropertyWithMetadataObject,
+    },
+    ontology::{OntologyTypeClassificationMetadata, ProvidedOntologyEditionProvenance},
+    owned_by_id::OwnedById,
+};
+use serde_json::json;
+use tokio::net::TcpListener;
+use type_system::{
+    schema::{DataType, EntityType, PropertyType},
+    url::VersionedUrl,
+};
+use uuid::Uuid;
+
+use crate::{
+    data_type_relationships, entity_type_relationships, property_type_relationships,
+    DatabaseTestWrapper,
+};
+
+const fn secret_key() -> SubscriptionSecretKey {
+    SubscriptionSecretKey::new([7; 32])
+}
+
+#[tokio::test]
+async fn create_and_delete() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [property_type::TEXT_V1],
+            [entity_type::PAGE_V1],
+        )
+        .await
+        .expect("could not seed database:");
+
+    let filter = json!({
+        "equal": [
+            { "path": ["type", "baseUrl"] },
+            { "parameter": "https://blockprotocol.org/@alice/types/entity-type/page/" }
+        ]
+    });
+    let subscription = api
+        .create_entity_subscription(
+            api.account_id,
+            CreateEntitySubscriptionParams {
+                url: "http://localhost:8080/hook".to_owned(),
+                filter: filter.clone(),
+                secret: "secret".to_owned(),
+            },
+            &secret_key(),
+        )
+        .await
+        .expect("could not create subscription");
+    assert_eq!(subscription.filter, filter);
+
+    let subscriptions = api
+        .get_entity_subscriptions(api.account_id)
+        .await
+        .expect("could not read subscriptions");
+    assert_eq!(subscriptions, [subscription.clone()]);
+
+    let targets = api
+        .get_subscription_targets()
+        .await
+        .expect("could not read subscription targets");
+    let target = targets
+        .iter()
+        .find(|target| target.subscription_id == subscription.subscription_id)
+        .expect("subscription should be a target");
+    assert_eq!(target.actor_id, api.account_id);
+    assert_ne!(target.sealed_secret, b"secret");
+    assert_eq!(
+        secret_key()
+            .open(&target.sealed_secret)
+            .expect("could not open secret"),
+        "secret"
+    );
+
+    api.delete_entity_subscription(api.account_id, subscription.subscription_id)
+        .await
+        .expect("could not delete subscription");
+    assert!(
+        api.get_entity_subscriptions(api.account_id)
+            .await
+            .expect("could not read subscriptions")
+            .is_empty()
+    );
+
+    let report = api
+        .delete_entity_subscription(api.account_id, subscription.subscription_id)
+        .await
+        .expect_err("subscription should not exist anymore");
+    assert!(report.contains::<DeletionError>());
+}
+
+#[tokio::test]
+async fn reject_invalid_subscription() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database:");
+
+    let report = api
+        .create_entity_subscription(
+            api.account_id,
+            CreateEntitySubscriptionParams {
+                url: "ftp://localhost/hook".to_owned(),
+                filter: json!({ "all": [] }),
+                secret: "secret".to_owned(),
+            },
+            &secret_key(),
+        )
+        .await
+        .expect_err("non-HTTP URL should be rejected");
+    assert!(report.contains::<InsertionError>());
+
+    _ = api
+        .create_entity_subscription(
+            api.account_id,
+            CreateEntitySubscriptionParams {
+                url: "http://localhost/hook".to_owned(),
+                filter: json!({ "unknown": [] }),
+                secret: "secret".to_owned(),
+            },
+            &secret_key(),
+        )
+        .await
+        .expect_err("invalid filter should be rejected");
+}
+
+type Requests = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+/// Starts a webhook receiver recording all requests.
+async fn receiver() -> (String, Requests) {
+    let requests = Requests::default();
+    let router = Router::new().route(
+        "/hook",
+        post({
+            let requests = Arc::clone(&requests);
+            move |headers: HeaderMap, body: Bytes| async move {
+                requests
+                    .lock()
+                    .expect("lock should not be poisoned")
+                    .push((headers, body));
+                StatusCode::OK
+            }
+        }),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("could not bind receiver");
+    let address = listener
+        .local_addr()
+        .expect("receiver should have an address");
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    (format!("http://{address}/hook"), requests)
+}
+
+/// Creates an account and its web together with the page type outside of a test transaction.
+///
+/// This is required if the records are read with other connections of the pool.
+pub(crate) async fn seed_committed_page_type(
+    connection: &mut <PostgresStorePool as StorePool>::Store<'static, NoAuthorization>,
+) -> (AccountId, VersionedUrl) {
+    let account_id = AccountId::new(Uuid::new_v4());
+    let owned_by_id = OwnedById::new(account_id.into_uuid());
+    connection
+        .insert_account_id(account_id, InsertAccountIdParams { account_id })
+        .await
+        .expect("could not insert account id");
+    connection
+        .insert_web_id(
+            account_id,
+            InsertWebIdParams {
+                owned_by_id,
+                owner: WebOwnerSubject::Account { id: account_id },
+            },
+        )
+        .await
+        .expect("could not create web id");
+    connection
+        .create_data_types(
+            account_id,
+            [CreateDataTypeParams {
+                schema: serde_json::from_str::<DataType>(data_type::TEXT_V1)
+                    .expect("could not parse data type"),
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                relationships: data_type_relationships(),
+                conflict_behavior: ConflictBehavior::Skip,
+                provenance: ProvidedOntologyEditionProvenance::default(),
+            }],
+        )
+        .await
+        .expect("could not create data type");
+    connection
+        .create_property_types(
+            account_id,
+            [CreatePropertyTypeParams {
+                schema: serde_json::from_str::<PropertyType>(property_type::TEXT_V1)
+                    .expect("could not parse property type"),
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                relationships: property_type_relationships(),
+                conflict_behavior: ConflictBehavior::Skip,
+                provenance: ProvidedOntologyEditionProvenance::default(),
+            }],
+        )
+        .await
+        .expect("could not create property type");
+    let page_type = serde_json::from_str::<EntityType>(entity_type::PAGE_V1)
+        .expect("could not parse entity type");
+    let page_type_id: VersionedUrl = page_type.id.clone();
+    connection
+        .create_entity_types(
+            account_id,
+            [CreateEntityTypeParams {
+                schema: page_type,
+                classification: OntologyTypeClassificationMetadata::Owned { owned_by_id },
+                label_property: None,
+                icon: None,
+                relationships: entity_type_relationships(),
+                conflict_behavior: ConflictBehavior::Skip,
+                provenance: ProvidedOntologyEditionProvenance::default(),
+            }],
+        )
+        .await
+        .expect("could not create entity type");
+
+    (account_id, page_type_id)
+}
+
+#[tokio::test]
+#[expect(clippy::too_many_lines)]
+async fn deliver_matching_changes() {
+    let DatabaseTestWrapper::<NoAuthorization> {
+        pool,
+        mut connection,
+    } = DatabaseTestWrapper::new().await;
+    let (url, requests) = receiver().await;
+
+    // The dispatcher reads the changes with its own connections, so the records are committed
+    let (account_id, page_type_id) = seed_committed_page_type(&mut connection).await;
+    let owned_by_id = OwnedById::new(account_id.into_uuid());
+
+    // Only the first of the two pages matches the filter
+    let matching_uuid = Uuid::new_v4();
+    let subscription = connection
+        .create_entity_subscription(
+            account_id,
+            CreateEntitySubscriptionParams {
+                url,
+                filter: json!({
+                    "all": [
+                        { "equal": [{ "path": ["ownedById"] }, { "parameter": owned_by_id }] },
+                        { "equal": [{ "path": ["uuid"] }, { "parameter": matching_uuid }] },
+                    ]
+                }),
+                secret: "secret".to_owned(),
+            },
+            &secret_key(),
+        )
+        .await
+        .expect("could not create subscription");
+
+    let mut entities = Vec::new();
+    for entity_uuid in [matching_uuid, Uuid::new_v4()] {
+        entities.push(
+            connection
+                .create_entity(
+                    account_id,
+                    CreateEntityParams {
+                        owned_by_id,
+                        entity_uuid: Some(EntityUuid::new(entity_uuid)),
+                        decision_time: None,
+                        entity_type_ids: HashSet::from([page_type_id.clone()]),
+                        properties: PropertyWithMetadataObject::from_parts(
+                            serde_json::from_str(entity::PAGE_V1).expect("could not parse entity"),
+                            None,
+                        )
+                        .expect("could not create property with metadata object"),
+                        confidence: None,
+                        link_data: None,
+                        draft: false,
+                        relationships: [],
+                        provenance: ProvidedEntityEditionProvenance::default(),
+                    },
+                )
+                .await
+                .expect("could not create entity"),
+        );
+    }
+
+    WebhookDispatcher::new(
+        Arc::new(pool),
+        Arc::new(NoAuthorization),
+        WebhookConfig {
+            max_attempts: 1,
+            target_policy: WebhookTargetPolicy {
+                allowed_hosts: vec!["127.0.0.1".to_owned()],
+                denied_hosts: Vec::new(),
+                allow_private_networks: true,
+            },
+            ..WebhookConfig::default()
+        },
+        secret_key(),
+    )
+    .expect("could not create dispatcher")
+    .dispatch()
+    .await
+    .expect("could not dispatch changes");
+
+    {
+        let requests = requests.lock().expect("lock should not be poisoned");
+        assert_eq!(requests.len(), 1);
+        let (headers, body) = &requests[0];
+        assert_eq!(
+            headers[SIGNATURE_HEADER],
+            sign_payload("secret", body).as_str()
+        );
+
+        let payload: serde_json::Value =
+            serde_json::from_slice(body).expect("payload should be JSON");
+        assert_eq!(
+            payload["subscriptionId"],
+            json!(subscription.subscription_id)
+        );
+        assert_eq!(
+            payload["entity"]["metadata"]["recordId"]["entityId"],
+            json!(entities[0].metadata.record_id.entity_id)
+        );
+    }
+
+    // The change was delivered, so it is not parked
+    assert!(
+        connection
+            .get_parked_changes(account_id, subscription.subscription_id)
+            .await
+            .expect("could not read parked changes")
+            .is_empty()
+    );
+    connection
+        .delete_entity_subscription(account_id, subscription.subscription_id)
+        .await
+        .expect("could not delete subscription");
+}